@@ -1,18 +1,75 @@
+use bit_operations::bitvec::BitVec;
+use bit_operations::export::{GridImage, Raster};
+use bit_operations::net_repr;
+use bit_operations::script::BitOp;
+use bit_operations::search::{self, BitPattern};
+use bit_operations::session::Session;
+use bit_operations::settings::Settings;
+use bit_operations::snippet::{self, Language, Snippet};
 use bit_operations::BitArray;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
 
-/// Toggles a specific bit in the given 64-bit integer at the specified position.
+/// Largest window `read_file_window` returns, in bytes.
+const MAX_WINDOW: usize = 4096;
+
+/// A window of bytes read from a file.
+#[derive(serde::Serialize)]
+struct FileWindow {
+    /// Offset of the first byte in the file.
+    offset: u64,
+    /// The bytes read; shorter than requested at the end of the file.
+    bytes: Vec<u8>,
+    /// Total file size in bytes.
+    file_len: u64,
+}
+
+/// Most hits `search_file` returns.
+const MAX_HITS: usize = 10_000;
+
+/// Bit offsets where a pattern occurs in a file.
+#[derive(serde::Serialize)]
+struct SearchHits {
+    /// Offsets in bits from the top bit of the first byte, ascending.
+    hits: Vec<u64>,
+    /// Whether the search stopped at `MAX_HITS`.
+    truncated: bool,
+    /// Total file size in bytes.
+    file_len: u64,
+}
+
+/// File name of the session used when no path is given, in the app's data
+/// directory.
+const DEFAULT_SESSION: &str = "session.json";
+
+/// Session written to the default session file when the app exits, set
+/// while the frontend has autosave turned on.
+#[derive(Default)]
+struct Autosave(Mutex<Option<Session>>);
+
+/// File name of the viewer settings in the app's config directory.
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Toggles a specific bit in the low `bit_size` bits of a value.
 ///
 /// # Arguments
 /// * `bit_array` - The original 64-bit integer representing the bit array.
 /// * `position` - The position of the bit to toggle (0-based index).
+/// * `bit_size` - The width of the value: 8, 16, 32 or 64.
 ///
 /// # Returns
-/// A new 64-bit integer with the bit at `position` toggled.
+/// A new 64-bit integer with the bit at `position` toggled, or an error if
+/// the position is outside the width or the value does not fit in it.
 #[tauri::command]
-fn toggle_bit(bit_array: u64, position: u8) -> u64 {
-    let mut ba = BitArray(bit_array);
-    ba.toggle_bit(position);
-    ba.0
+fn toggle_bit(bit_array: u64, position: u32, bit_size: u32) -> Result<u64, String> {
+    let mut sized = BitArray(bit_array)
+        .with_width(bit_size)
+        .map_err(|e| e.to_string())?;
+    sized.toggle_bit(position).map_err(|e| e.to_string())?;
+    Ok(sized.value().0)
 }
 
 /// Retrieves all bits from a 64-bit integer as a vector of booleans.
@@ -53,15 +110,402 @@ fn process_bits(bits: u64) -> u64 {
     bits.reverse_bits()
 }
 
+/// Interprets the low `bit_size` bits of a value as a two's complement integer.
+///
+/// # Arguments
+/// * `state` - The 64-bit integer holding the bits.
+/// * `bit_size` - The width of the value: 8, 16, 32 or 64.
+///
+/// # Returns
+/// The signed value, or an error for an unsupported bit size or a value
+/// with bits set above it.
+#[tauri::command]
+fn as_signed(state: u64, bit_size: u32) -> Result<i64, String> {
+    let sized = BitArray(state)
+        .with_width(bit_size)
+        .map_err(|e| e.to_string())?;
+    Ok(sized.as_signed())
+}
+
+/// Encodes a signed integer as `bit_size` bits of two's complement.
+///
+/// # Arguments
+/// * `value` - The signed integer.
+/// * `bit_size` - The width of the value: 8, 16, 32 or 64.
+///
+/// # Returns
+/// The encoded bits, or an error if the value does not fit in `bit_size` bits.
+#[tauri::command]
+fn from_signed(value: i64, bit_size: u32) -> Result<u64, String> {
+    let mut sized = BitArray::new()
+        .with_width(bit_size)
+        .map_err(|e| e.to_string())?;
+    sized.set_signed(value).map_err(|e| e.to_string())?;
+    Ok(sized.value().0)
+}
+
+/// Shifts or rotates the low `bit_size` bits of a value.
+///
+/// # Arguments
+/// * `state` - The 64-bit integer holding the bits.
+/// * `operation` - `"shl"`, `"shr"`, `"sar"`, `"rotl"` or `"rotr"`.
+/// * `amount` - The shift or rotate count.
+/// * `bit_size` - The width of the value: 8, 16, 32 or 64.
+///
+/// # Returns
+/// The shifted bits, or an error for an unknown operation or bit size, or a
+/// value with bits set above the bit size.
+#[tauri::command]
+fn shift_bits(state: u64, operation: &str, amount: u32, bit_size: u32) -> Result<u64, String> {
+    let sized = BitArray(state)
+        .with_width(bit_size)
+        .map_err(|e| e.to_string())?;
+    let (mut bits, width) = (sized.value(), sized.width());
+    match operation {
+        "shl" => bits.shl(amount, width),
+        "shr" => bits.shr(amount, width),
+        "sar" => bits.sar(amount, width),
+        "rotl" => bits.rotl(amount, width),
+        "rotr" => bits.rotr(amount, width),
+        _ => return Err(format!("unknown shift operation `{}`", operation)),
+    }
+    Ok(bits.0)
+}
+
+/// Applies a sequence of bit operations to a value in one call.
+///
+/// # Arguments
+/// * `state` - The 64-bit integer holding the bits.
+/// * `bit_size` - The width of the value: 8, 16, 32 or 64.
+/// * `ops` - The operations to apply in order, e.g.
+///   `[{"Set": 3}, {"Shl": 1}, {"Xor": 255}, "ReverseBytes"]`.
+///
+/// # Returns
+/// The transformed bits, or an error naming the first operation that
+/// failed, such as a bit position or mask outside the bit size.
+#[tauri::command]
+fn apply_ops(state: u64, bit_size: u32, ops: Vec<BitOp>) -> Result<u64, String> {
+    let mut sized = BitArray(state)
+        .with_width(bit_size)
+        .map_err(|e| e.to_string())?;
+    sized
+        .apply_all(&ops)
+        .map_err(|(i, e)| format!("operation {} (`{}`): {}", i + 1, ops[i], e))?;
+    Ok(sized.value().0)
+}
+
+/// Formats a value as a network address.
+///
+/// # Arguments
+/// * `words` - The value as 64-bit words, least significant first.
+/// * `kind` - `"ipv4"` (low 32 bits), `"mac"` (low 48 bits) or `"ipv6"`
+///   (low 128 bits).
+///
+/// # Returns
+/// The formatted address, or an error for an unknown address kind.
+#[tauri::command]
+fn format_address(words: Vec<u64>, kind: &str) -> Result<String, String> {
+    let word = |i: usize| words.get(i).copied().unwrap_or(0);
+    match kind {
+        "ipv4" => Ok(net_repr::ipv4(word(0)).to_string()),
+        "mac" => Ok(net_repr::mac(word(0)).to_string()),
+        "ipv6" => Ok(net_repr::ipv6(u128::from(word(1)) << 64 | u128::from(word(0))).to_string()),
+        _ => Err(format!("unknown address kind `{}`", kind)),
+    }
+}
+
+/// Parses a network address.
+///
+/// # Arguments
+/// * `text` - The address, e.g. `192.168.0.1`, `00:1a:2b:3c:4d:5e` or
+///   `2001:db8::1`.
+/// * `kind` - `"ipv4"`, `"mac"` or `"ipv6"`.
+///
+/// # Returns
+/// The address as 64-bit words, least significant first, or an error if
+/// the text is not an address of that kind.
+#[tauri::command]
+fn parse_address(text: &str, kind: &str) -> Result<Vec<u64>, String> {
+    let invalid = || format!("`{}` is not a valid {} address", text, kind);
+    match kind {
+        "ipv4" => net_repr::parse_ipv4(text)
+            .map(|v| vec![v])
+            .ok_or_else(invalid),
+        "mac" => net_repr::parse_mac(text)
+            .map(|m| vec![m.to_u64()])
+            .ok_or_else(invalid),
+        "ipv6" => net_repr::parse_ipv6(text)
+            .map(|v| vec![v as u64, (v >> 64) as u64])
+            .ok_or_else(invalid),
+        _ => Err(format!("unknown address kind `{}`", kind)),
+    }
+}
+
+/// Renders a bit grid image to a file in the user's download directory.
+///
+/// # Arguments
+/// * `image` - The value, bit size, title, fields and annotations to draw.
+/// * `format` - `"svg"` or `"png"`.
+/// * `scale` - Pixel zoom factor for PNG output; ignored for SVG.
+/// * `file_name` - Name of the file to create; any directory part is dropped.
+///
+/// # Returns
+/// The full path of the written file, or an error message.
+#[tauri::command]
+fn export_grid(
+    app: tauri::AppHandle,
+    image: GridImage,
+    format: String,
+    scale: u32,
+    file_name: String,
+) -> Result<String, String> {
+    let name = Path::new(&file_name)
+        .file_name()
+        .ok_or_else(|| format!("invalid file name `{}`", file_name))?;
+    let dir = app.path().download_dir().map_err(|e| e.to_string())?;
+    let path = dir.join(name).with_extension(&format);
+
+    match format.as_str() {
+        "svg" => fs::write(&path, image.to_svg()),
+        "png" => write_png(&path, &image.to_raster(scale)),
+        _ => return Err(format!("unsupported image format `{}`", format)),
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// Generates C, Rust or Python source snippets declaring a value.
+///
+/// # Arguments
+/// * `value` - The value; its length selects the integer and float types.
+/// * `language` - `"C"`, `"Rust"` or `"Python"`.
+/// * `name` - Identifier used for the declared constants.
+///
+/// # Returns
+/// The integer, float (where the language has one) and byte array snippets.
+#[tauri::command]
+fn code_snippets(value: BitVec, language: Language, name: String) -> Vec<Snippet> {
+    snippet::snippets(language, &value, &name)
+}
+
+/// Resolves a session file path: empty means the default session file, and
+/// relative paths are taken from the app's data directory.
+fn session_path(app: &tauri::AppHandle, path: Option<&str>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let path = path.map(str::trim).filter(|p| !p.is_empty());
+    Ok(dir.join(path.unwrap_or(DEFAULT_SESSION)))
+}
+
+/// Writes a session as pretty-printed JSON, creating missing directories.
+fn write_session(path: &Path, state: &Session) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Saves the workspace, register map and history to a JSON file.
+///
+/// # Arguments
+/// * `path` - The file to write, usually picked in the save dialog; empty
+///   for the default session file.
+///   Relative paths are taken from the app's data directory.
+/// * `state` - The session to save.
+///
+/// # Returns
+/// The full path of the written file, or an error message.
+#[tauri::command]
+fn save_session(
+    app: tauri::AppHandle,
+    path: Option<String>,
+    state: Session,
+) -> Result<String, String> {
+    let path = session_path(&app, path.as_deref())?;
+    write_session(&path, &state)?;
+    Ok(path.display().to_string())
+}
+
+/// Loads a session saved by `save_session`.
+///
+/// # Arguments
+/// * `path` - The file to read, usually picked in the open dialog; empty
+///   for the default session file.
+///   Relative paths are taken from the app's data directory.
+///
+/// # Returns
+/// The session, or an error message if the file is missing or invalid.
+#[tauri::command]
+fn load_session(app: tauri::AppHandle, path: Option<String>) -> Result<Session, String> {
+    let path = session_path(&app, path.as_deref())?;
+    let json = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let state: Session = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    if state.version > Session::VERSION {
+        return Err(format!(
+            "session version {} is newer than this app supports",
+            state.version
+        ));
+    }
+    Ok(state)
+}
+
+/// Sets the session saved to the default session file on exit.
+///
+/// # Arguments
+/// * `state` - The session to save, or `None` to turn autosave off.
+#[tauri::command]
+fn set_autosave(autosave: tauri::State<Autosave>, state: Option<Session>) {
+    *autosave.0.lock().unwrap_or_else(|e| e.into_inner()) = state;
+}
+
+/// Returns the path of the settings file in the app's config directory.
+fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+/// Loads the viewer settings saved by `save_settings`.
+///
+/// # Returns
+/// The settings, the defaults if none were saved yet, or an error message
+/// if the file is invalid.
+#[tauri::command]
+fn load_settings(app: tauri::AppHandle) -> Result<Settings, String> {
+    let path = settings_path(&app)?;
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// Saves the viewer settings as JSON in the app's config directory.
+///
+/// # Arguments
+/// * `settings` - The settings to save.
+///
+/// # Returns
+/// An error message if the file cannot be written.
+#[tauri::command]
+fn save_settings(app: tauri::AppHandle, settings: Settings) -> Result<(), String> {
+    let path = settings_path(&app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// Reads a window of bytes from a file for browsing it in the grid.
+///
+/// # Arguments
+/// * `path` - The file to read.
+/// * `offset` - Offset of the first byte to read.
+/// * `len` - Number of bytes to read, at most 4096.
+///
+/// # Returns
+/// The bytes with the file size, or an error message if the file cannot
+/// be read or `offset` is past its end.
+#[tauri::command]
+fn read_file_window(path: String, offset: u64, len: usize) -> Result<FileWindow, String> {
+    let mut file = File::open(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+    if offset > file_len {
+        return Err(format!(
+            "offset {} is past the end of the {}-byte file",
+            offset, file_len
+        ));
+    }
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| e.to_string())?;
+    let mut bytes = Vec::with_capacity(len.min(MAX_WINDOW));
+    file.take(len.min(MAX_WINDOW) as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    Ok(FileWindow {
+        offset,
+        bytes,
+        file_len,
+    })
+}
+
+/// Finds the bit offsets where a pattern occurs in a file, at any bit
+/// alignment.
+///
+/// # Arguments
+/// * `path` - The file to search.
+/// * `pattern` - The bits to find, with a mask of the bits that must match.
+///
+/// # Returns
+/// Up to 10000 offsets with the file size, or an error message if the file
+/// cannot be read.
+#[tauri::command]
+fn search_file(path: String, pattern: BitPattern) -> Result<SearchHits, String> {
+    let data = fs::read(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let mut hits: Vec<u64> = search::find(&data, pattern).take(MAX_HITS + 1).collect();
+    let truncated = hits.len() > MAX_HITS;
+    hits.truncate(MAX_HITS);
+    Ok(SearchHits {
+        hits,
+        truncated,
+        file_len: data.len() as u64,
+    })
+}
+
+/// Encodes an RGBA raster as a PNG file.
+fn write_png(path: &Path, raster: &Raster) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, raster.width, raster.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&raster.pixels)?;
+    Ok(())
+}
+
 /// The main function initializes and runs the Tauri application.
 fn main() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             toggle_bit,
             get_bits,
             get_raw,
-            process_bits
+            process_bits,
+            as_signed,
+            from_signed,
+            shift_bits,
+            apply_ops,
+            format_address,
+            parse_address,
+            export_grid,
+            code_snippets,
+            save_session,
+            load_session,
+            set_autosave,
+            load_settings,
+            save_settings,
+            read_file_window,
+            search_file
         ])
-        .run(tauri::generate_context!())
-        .expect("error running tauri application");
+        .manage(Autosave::default())
+        .build(tauri::generate_context!())
+        .expect("error building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                let state = app
+                    .state::<Autosave>()
+                    .0
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .take();
+                if let Some(state) = state {
+                    if let Err(e) =
+                        session_path(app, None).and_then(|path| write_session(&path, &state))
+                    {
+                        eprintln!("autosave failed: {}", e);
+                    }
+                }
+            }
+        });
 }
@@ -0,0 +1,175 @@
+//! Accumulation Error Explorer Module
+
+use bit_operations::accumulate::{accumulate, AccumulationStep};
+use bit_operations::parse::parse_f64;
+use bit_operations::rounding::{FloatFormat, RoundingMode};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Width and height of the drift plot in SVG user units
+const PLOT_WIDTH: f64 = 400.0;
+const PLOT_HEIGHT: f64 = 120.0;
+
+/// Builds SVG polyline points for the drift of each step from the exact sum.
+fn drift_points(
+    steps: &[AccumulationStep],
+    value: impl Fn(&AccumulationStep) -> f64,
+    scale: f64,
+) -> String {
+    let last = (steps.len().max(2) - 1) as f64;
+    steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let drift = value(step) - step.exact;
+            let x = i as f64 / last * PLOT_WIDTH;
+            let y = PLOT_HEIGHT / 2.0 - drift / scale * (PLOT_HEIGHT / 2.0);
+            format!("{:.2},{:.2}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Accumulation Error Component
+///
+/// Adds a comma-separated sequence N times in the float format matching the
+/// current bit size, rounding after every step, and plots how far the running
+/// sum drifts from the exact sum of the stored terms. Kahan summation can be
+/// overlaid for comparison.
+#[component]
+pub fn AccumulationExplorer(
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (terms_input, set_terms_input) = signal("0.1".to_string());
+    let (repeat, set_repeat) = signal(100usize);
+    let (show_kahan, set_show_kahan) = signal(true);
+
+    let format = move || match bit_size.get() {
+        Width::W16 => Some(FloatFormat::HALF),
+        Width::W32 => Some(FloatFormat::SINGLE),
+        Width::W64 => Some(FloatFormat::DOUBLE),
+        _ => None,
+    };
+
+    let steps = Memo::new(move |_| {
+        let format = format()?;
+        let terms = terms_input
+            .get()
+            .split(',')
+            .map(|s| {
+                parse_f64(s)
+                    .ok()
+                    .map(|x| format.encode(x, RoundingMode::NearestEven))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((format, accumulate(format, &terms, repeat.get())))
+    });
+
+    view! {
+        <div class="accumulation-panel">
+            <label>
+                <span class="input-label">Accumulation error</span>
+            </label>
+            <div class="accumulation-inputs">
+                <label>
+                    "terms "
+                    <input
+                        type="text"
+                        prop:value=terms_input
+                        on:input=move |ev| set_terms_input.set(event_target_value(&ev))
+                    />
+                </label>
+                <label>
+                    "× "
+                    <input
+                        type="number"
+                        min="1"
+                        max="10000"
+                        prop:value=move || repeat.get().to_string()
+                        on:input=move |ev| {
+                            if let Ok(n) = event_target_value(&ev).parse::<usize>() {
+                                set_repeat.set(n.clamp(1, 10_000));
+                            }
+                        }
+                    />
+                </label>
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=show_kahan
+                        on:change=move |ev| set_show_kahan.set(event_target_checked(&ev))
+                    />
+                    " Kahan"
+                </label>
+            </div>
+            {move || match steps.get() {
+                None => view! {
+                    <div class="accumulation-row">
+                        "Select 16, 32 or 64 bit size and enter comma-separated numbers"
+                    </div>
+                }.into_any(),
+                Some((_, steps)) if steps.is_empty() => view! {
+                    <div class="accumulation-row">"Nothing to sum"</div>
+                }.into_any(),
+                Some((format, steps)) => {
+                    let kahan = show_kahan.get();
+                    let naive_value = |s: &AccumulationStep| format.decode(s.naive);
+                    let kahan_value = |s: &AccumulationStep| format.decode(s.kahan);
+                    let scale = steps
+                        .iter()
+                        .flat_map(|s| {
+                            let k = if kahan { kahan_value(s) - s.exact } else { 0.0 };
+                            [naive_value(s) - s.exact, k]
+                        })
+                        .map(f64::abs)
+                        .filter(|d| d.is_finite())
+                        .fold(0.0, f64::max);
+                    let scale = if scale > 0.0 { scale } else { 1.0 };
+                    let last = *steps.last().unwrap();
+                    view! {
+                        <svg
+                            class="accumulation-plot"
+                            viewBox=format!("0 0 {} {}", PLOT_WIDTH, PLOT_HEIGHT)
+                            preserveAspectRatio="none"
+                        >
+                            <line
+                                class="axis"
+                                x1="0"
+                                y1=PLOT_HEIGHT / 2.0
+                                x2=PLOT_WIDTH
+                                y2=PLOT_HEIGHT / 2.0
+                            />
+                            <polyline class="naive" points=drift_points(&steps, naive_value, scale) />
+                            {kahan.then(|| view! {
+                                <polyline class="kahan" points=drift_points(&steps, kahan_value, scale) />
+                            })}
+                        </svg>
+                        <div class="accumulation-row">{format!("Plot range: ±{:e}", scale)}</div>
+                        <div class="accumulation-row">
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(last.naive))>
+                                "Naive"
+                            </button>
+                            {format!(
+                                "{:e} (error {:e})",
+                                format.decode(last.naive),
+                                format.decode(last.naive) - last.exact
+                            )}
+                        </div>
+                        <div class="accumulation-row">
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(last.kahan))>
+                                "Kahan"
+                            </button>
+                            {format!(
+                                "{:e} (error {:e})",
+                                format.decode(last.kahan),
+                                format.decode(last.kahan) - last.exact
+                            )}
+                        </div>
+                        <div class="accumulation-row">{format!("Exact: {:e}", last.exact)}</div>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
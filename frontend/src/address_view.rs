@@ -0,0 +1,91 @@
+//! Network Address Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::net_repr::{self, AddressKind};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Address Component
+///
+/// Number panel row reading the current value as a network address: a
+/// dotted-quad IPv4 address at 32 bits, a MAC address from the low 48 bits
+/// at 64 bits and an IPv6 address at 128 bits. Typing an address writes its
+/// bits; the row is hidden at other sizes.
+#[component]
+pub fn AddressRow(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+    let (editing, set_editing) = signal(false);
+
+    let kind = move || AddressKind::for_width(bit_size.get());
+
+    // Effect to show the current address unless the field is being edited
+    Effect::new(move |_| {
+        let value = bit_array.get().0;
+        let address = match kind() {
+            Some(AddressKind::Ipv4) => net_repr::ipv4(value).to_string(),
+            Some(AddressKind::Mac) => net_repr::mac(value).to_string(),
+            Some(AddressKind::Ipv6) => {
+                let high = bit_vec.with(|v| if v.len() >= 128 { v.word(1) } else { 0 });
+                net_repr::ipv6(u128::from(high) << 64 | u128::from(value)).to_string()
+            }
+            None => String::new(),
+        };
+        if !editing.get() {
+            set_text.set(address);
+        }
+    });
+
+    let input = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_text.set(input.clone());
+        match kind() {
+            Some(AddressKind::Ipv4) => {
+                if let Some(value) = net_repr::parse_ipv4(&input) {
+                    set_bit_array.set(BitArray(value));
+                }
+            }
+            // Keep the high 16 bits, which a MAC address does not cover
+            Some(AddressKind::Mac) => {
+                if let Some(mac) = net_repr::parse_mac(&input) {
+                    let high = bit_array.get_untracked().0 & !((1 << 48) - 1);
+                    set_bit_array.set(BitArray(high | mac.to_u64()));
+                }
+            }
+            Some(AddressKind::Ipv6) => {
+                if let Some(value) = net_repr::parse_ipv6(&input) {
+                    set_bit_vec.update(|v| {
+                        v.set_word(0, value as u64);
+                        v.set_word(1, (value >> 64) as u64);
+                    });
+                }
+            }
+            None => {}
+        }
+    };
+
+    view! {
+        <Show when=move || kind().is_some()>
+            <label>
+                <span class="input-label">{move || kind().map(|k| k.to_string())}</span>
+                <input
+                    type="text"
+                    title=move || match kind() {
+                        Some(AddressKind::Ipv4) => "IPv4 address, e.g. 192.168.0.1",
+                        Some(AddressKind::Mac) => "MAC address in the low 48 bits, e.g. 00:1a:2b:3c:4d:5e",
+                        _ => "IPv6 address, e.g. 2001:db8::1",
+                    }
+                    prop:value=text
+                    on:input=input
+                    on:focus=move |_| set_editing.set(true)
+                    on:blur=move |_| set_editing.set(false)
+                />
+            </label>
+        </Show>
+    }
+}
@@ -0,0 +1,111 @@
+//! Integer Arithmetic Module
+
+use bit_operations::arith::{self, ArithMode, ArithOp, Overflow};
+use bit_operations::parse::{parse_i64, parse_u64};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Arithmetic Operations Component
+///
+/// Applies unsigned or, with Signed ticked, two's complement arithmetic to
+/// the current value with the globally selected [`ArithMode`] and reports
+/// what happened at the boundary, e.g. `INT_MAX + 1` saturating at MAX.
+#[component]
+pub fn ArithmeticOperations(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    arith_mode: ReadSignal<ArithMode>,
+) -> impl IntoView {
+    let (operand, set_operand) = signal("1".to_string());
+    let (signed, set_signed) = signal(false);
+    let (status, set_status) = signal(String::new());
+
+    let run = move |op: ArithOp, b: u64| {
+        let (a, width, mode) = (bit_array.get().0, bit_size.get(), arith_mode.get());
+        let signed = signed.get();
+        // Values are shown as they are read: signed or unsigned
+        let show = move |value: u64| {
+            if signed {
+                BitArray(value).as_signed(width).to_string()
+            } else {
+                width.truncate(value).to_string()
+            }
+        };
+        let (a_text, b_text) = (show(a), show(b));
+        let result = if signed {
+            arith::apply_signed(op, a, b, width, mode)
+        } else {
+            arith::apply(op, a, b, width, mode)
+        };
+        match result {
+            Ok(result) => {
+                set_bit_array.set(BitArray(result.value));
+                let value = show(result.value);
+                set_status.set(match (result.overflow, mode) {
+                    (None, _) => format!("{} {} {} = {}", a_text, op, b_text, value),
+                    (Some(Overflow::Above), ArithMode::Saturating) => {
+                        format!("{} {} {}: saturated at MAX ({})", a_text, op, b_text, value)
+                    }
+                    (Some(Overflow::Below), ArithMode::Saturating) => {
+                        format!("{} {} {}: saturated at MIN ({})", a_text, op, b_text, value)
+                    }
+                    (Some(_), _) => {
+                        format!("{} {} {}: wrapped around to {}", a_text, op, b_text, value)
+                    }
+                });
+            }
+            Err(err) => set_status.set(format!(
+                "{} {} {}: {} (value unchanged)",
+                a_text, op, b_text, err
+            )),
+        }
+    };
+
+    let run_with_operand = move |op: ArithOp| {
+        let parsed = if signed.get() {
+            parse_i64(&operand.get(), 10).map(|b| b as u64)
+        } else {
+            parse_u64(&operand.get(), 10)
+        };
+        match parsed {
+            Ok(b) => run(op, b),
+            Err(err) => set_status.set(format!("Invalid operand: {}", err)),
+        }
+    };
+
+    view! {
+        <div class="arithmetic-panel">
+            <label>
+                <span class="input-label">Arithmetic</span>
+                <span class="arithmetic-mode">{move || arith_mode.get().to_string()}</span>
+            </label>
+            <label title="Treat the value and operand as two's complement, so the boundaries are INT_MIN and INT_MAX">
+                <input
+                    type="checkbox"
+                    prop:checked=signed
+                    on:change=move |ev| set_signed.set(event_target_checked(&ev))
+                />
+                "Signed"
+            </label>
+            <div class="bit-operations">
+                <button class="bit-btn" on:click=move |_| run(ArithOp::Add, 1)>"Inc"</button>
+                <button class="bit-btn" on:click=move |_| run(ArithOp::Sub, 1)>"Dec"</button>
+                <input
+                    type="text"
+                    prop:value=operand
+                    on:input=move |ev| set_operand.set(event_target_value(&ev))
+                />
+                {[ArithOp::Add, ArithOp::Sub, ArithOp::Mul, ArithOp::Div]
+                    .into_iter()
+                    .map(|op| view! {
+                        <button class="bit-btn" on:click=move |_| run_with_operand(op)>
+                            {op.to_string()}
+                        </button>
+                    })
+                    .collect_view()}
+            </div>
+            <div class="arithmetic-status">{status}</div>
+        </div>
+    }
+}
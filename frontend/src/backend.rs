@@ -0,0 +1,87 @@
+//! Desktop Backend Module
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "core"], catch)]
+    async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// Invokes a backend command, turning its error into a message
+pub async fn call(cmd: &str, args: serde_json::Value) -> Result<JsValue, String> {
+    let args = js_sys::JSON::parse(&args.to_string()).unwrap_or(JsValue::NULL);
+    invoke(cmd, args)
+        .await
+        .map_err(|err| err.as_string().unwrap_or_else(|| format!("{:?}", err)))
+}
+
+/// Invokes a backend command and deserializes its result
+pub async fn call_json<T: DeserializeOwned>(
+    cmd: &str,
+    args: serde_json::Value,
+) -> Result<T, String> {
+    let value = call(cmd, args).await?;
+    let json = js_sys::JSON::stringify(&value)
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_default();
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+/// A file type offered in the native file dialogs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DialogFilter {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+}
+
+/// Builds the options of a dialog plugin call
+fn dialog_options(
+    title: &str,
+    filter: Option<DialogFilter>,
+    default_path: Option<&str>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "options": {
+            "title": title,
+            "filters": filter.into_iter().collect::<Vec<_>>(),
+            "defaultPath": default_path,
+        }
+    })
+}
+
+/// Shows the native open dialog for a single file
+///
+/// # Returns
+/// - The chosen path, or `None` if the dialog was cancelled
+pub async fn open_dialog(
+    title: &str,
+    filter: Option<DialogFilter>,
+) -> Result<Option<String>, String> {
+    let mut args = dialog_options(title, filter, None);
+    args["options"]["multiple"] = false.into();
+    args["options"]["directory"] = false.into();
+    Ok(call("plugin:dialog|open", args).await?.as_string())
+}
+
+/// Shows the native save dialog, starting at `default_path`
+///
+/// # Returns
+/// - The chosen path, or `None` if the dialog was cancelled
+pub async fn save_dialog(
+    title: &str,
+    filter: Option<DialogFilter>,
+    default_path: &str,
+) -> Result<Option<String>, String> {
+    let args = dialog_options(title, filter, Some(default_path));
+    Ok(call("plugin:dialog|save", args).await?.as_string())
+}
+
+/// Returns whether the viewer runs in the desktop app, where the backend
+/// commands are available
+pub fn is_desktop() -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("__TAURI__")).unwrap_or(false)
+}
@@ -0,0 +1,112 @@
+//! Base64/Base32 Module
+
+use bit_operations::base_encoding::BaseEncoding;
+use bit_operations::bitvec::BitVec;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Base64/Base32 Component
+///
+/// Number panel rows encoding the active bytes of the value as Base64 and
+/// Base32, in big- or little-endian byte order. Pasting an encoded string
+/// loads its bytes once it decodes to exactly the bytes of the current size.
+#[component]
+pub fn BaseEncodingRows(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (big_endian, set_big_endian) = signal(true);
+
+    let bytes = move || {
+        let width = bit_size.get();
+        match (width.word_count() > 1, big_endian.get()) {
+            (true, true) => bit_vec.get().to_be_bytes(),
+            (true, false) => bit_vec.get().to_le_bytes(),
+            (false, true) => width.be_bytes(bit_array.get().0),
+            (false, false) => width.le_bytes(bit_array.get().0),
+        }
+    };
+
+    let load = move |encoding: BaseEncoding, text: &str| {
+        let Some(bytes) = encoding.decode(text) else {
+            return;
+        };
+        let width = bit_size.get();
+        if width.word_count() > 1 {
+            let len = width.bits() as usize;
+            let value = if big_endian.get() {
+                BitVec::from_be_bytes(&bytes, len)
+            } else {
+                BitVec::from_le_bytes(&bytes, len)
+            };
+            if let Some(value) = value {
+                set_bit_vec.set(value);
+            }
+        } else {
+            let value = if big_endian.get() {
+                width.from_be_bytes(&bytes)
+            } else {
+                width.from_le_bytes(&bytes)
+            };
+            if let Some(value) = value {
+                set_bit_array.set(BitArray(value));
+            }
+        }
+    };
+
+    BaseEncoding::ALL
+        .into_iter()
+        .enumerate()
+        .map(|(i, encoding)| {
+            let (text, set_text) = signal(String::new());
+            let (editing, set_editing) = signal(false);
+
+            // Effect to encode the current bytes unless the field is being edited
+            Effect::new(move |_| {
+                let encoded = encoding.encode(&bytes());
+                if !editing.get() {
+                    set_text.set(encoded);
+                }
+            });
+
+            view! {
+                <label>
+                    <span class="input-label">{encoding.to_string().to_uppercase()}</span>
+                    <input
+                        type="text"
+                        prop:value=text
+                        on:input=move |ev| {
+                            let input = event_target_value(&ev);
+                            load(encoding, &input);
+                            set_text.set(input);
+                        }
+                        on:focus=move |_| set_editing.set(true)
+                        on:blur=move |_| set_editing.set(false)
+                    />
+                    {(i == 0)
+                        .then(|| {
+                            view! {
+                                <select
+                                    class="byte-order"
+                                    title="Byte order of the encoded bytes"
+                                    on:change=move |ev| {
+                                        set_big_endian.set(event_target_value(&ev) == "be")
+                                    }
+                                >
+                                    <option value="be" selected=move || big_endian.get()>
+                                        "BE"
+                                    </option>
+                                    <option value="le" selected=move || !big_endian.get()>
+                                        "LE"
+                                    </option>
+                                </select>
+                            }
+                        })}
+                </label>
+            }
+        })
+        .collect_view()
+}
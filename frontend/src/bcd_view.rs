@@ -0,0 +1,99 @@
+//! Binary-Coded Decimal Module
+
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// BCD Component
+///
+/// Number panel row reading the value as packed BCD, one decimal digit per
+/// nibble. Nibbles above 9 are shown as their hex digit and listed as
+/// invalid. Typing a decimal number writes its packed BCD encoding.
+#[component]
+pub fn BcdRow(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+    let (editing, set_editing) = signal(false);
+
+    // Nibbles of the current value, most significant first; none beyond 64 bits
+    let nibbles = move || {
+        let width = bit_size.get();
+        let value = bit_array.get().0;
+        let count = if width.word_count() == 1 {
+            width.bits() / 4
+        } else {
+            0
+        };
+        (0..count)
+            .rev()
+            .map(|n| (n, (value >> (n * 4)) & 0xf))
+            .collect::<Vec<_>>()
+    };
+
+    // Effect to show the digits unless the field is being edited
+    Effect::new(move |_| {
+        if bit_size.get().word_count() != 1 {
+            set_text.set("n/a beyond 64 bits".to_string());
+            return;
+        }
+        let digits: String = nibbles()
+            .into_iter()
+            .map(|(_, d)| {
+                char::from_digit(d as u32, 16)
+                    .unwrap_or('?')
+                    .to_ascii_uppercase()
+            })
+            .collect();
+        let digits = digits.trim_start_matches('0');
+        if !editing.get() {
+            set_text.set(if digits.is_empty() { "0" } else { digits }.to_string());
+        }
+    });
+
+    let input = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_text.set(input.clone());
+        let width = bit_size.get();
+        let bcd = input
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .and_then(|v| BitArray(v).to_bcd());
+        if let Some(bcd) = bcd {
+            if width.truncate(bcd.0) == bcd.0 {
+                set_bit_array.set(bcd);
+            }
+        }
+    };
+
+    let status = move || {
+        let invalid: Vec<String> = nibbles()
+            .into_iter()
+            .filter(|&(_, d)| d > 9)
+            .map(|(n, d)| format!("nibble {} ({:X})", n, d))
+            .collect();
+        if invalid.is_empty() {
+            String::new()
+        } else {
+            format!("invalid: {}", invalid.join(", "))
+        }
+    };
+
+    view! {
+        <label>
+            <span class="input-label">BCD</span>
+            <input
+                type="text"
+                title="Packed BCD digits; type a decimal number to encode it"
+                prop:value=text
+                prop:disabled=move || bit_size.get().word_count() != 1
+                on:input=input
+                on:focus=move |_| set_editing.set(true)
+                on:blur=move |_| set_editing.set(false)
+            />
+            <span class="repr-status">{status}</span>
+        </label>
+    }
+}
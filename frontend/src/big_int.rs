@@ -0,0 +1,213 @@
+//! Arbitrary-Precision Integer Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::parse::normalize_digits;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Input fields of the big integer panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BigField {
+    Dec,
+    Hex,
+    Bin,
+}
+
+impl BigField {
+    /// All fields in display order
+    const ALL: [BigField; 3] = [BigField::Dec, BigField::Hex, BigField::Bin];
+
+    /// Returns the radix digits are read in without a prefix
+    fn radix(self) -> u32 {
+        match self {
+            BigField::Dec => 10,
+            BigField::Hex => 16,
+            BigField::Bin => 2,
+        }
+    }
+
+    /// Returns the field's label
+    fn label(self) -> &'static str {
+        match self {
+            BigField::Dec => "DEC",
+            BigField::Hex => "HEX",
+            BigField::Bin => "BIN",
+        }
+    }
+
+    /// Formats `value` in the field's notation
+    fn format(self, value: &BitVec) -> String {
+        match self {
+            BigField::Dec => value.to_str_radix(10),
+            BigField::Hex => format!("0x{}", value.to_str_radix(16).to_uppercase()),
+            BigField::Bin => format!("0b{}", value.to_str_radix(2)),
+        }
+    }
+}
+
+/// Returns the number of significant bits of `value`
+fn bit_len(value: &BitVec) -> usize {
+    value.len() - value.leading_zeros() as usize
+}
+
+/// Big Integer Component
+///
+/// Holds a value of unbounded width, edited through DEC/HEX/BIN fields that
+/// keep the text as typed and only take it over once it parses. The value is
+/// paged through the main view at its bit size: page N covers bits
+/// `N × size` up to `(N + 1) × size`, is loaded into the main grid and
+/// number fields whenever the page or the big value changes, and "Main →
+/// page" writes edits made there back.
+#[component]
+pub fn BigIntPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (big, set_big) = signal(BitVec::new(64));
+    let (page, set_page) = signal(0usize);
+    let (dec_input, set_dec_input) = signal(String::new());
+    let (hex_input, set_hex_input) = signal(String::new());
+    let (bin_input, set_bin_input) = signal(String::new());
+    let (editing, set_editing) = signal(None::<BigField>);
+    // Field whose text does not parse
+    let (invalid, set_invalid) = signal(None::<BigField>);
+
+    let text = move |field: BigField| match field {
+        BigField::Dec => (dec_input, set_dec_input),
+        BigField::Hex => (hex_input, set_hex_input),
+        BigField::Bin => (bin_input, set_bin_input),
+    };
+
+    // Effect to refresh every field but the one being typed in
+    Effect::new(move |_| {
+        let editing = editing.get();
+        big.with(|value| {
+            for field in BigField::ALL {
+                if Some(field) != editing {
+                    text(field).1.set(field.format(value));
+                }
+            }
+        });
+    });
+
+    let page_bits = move || bit_size.get().bits() as usize;
+    let page_count = move || big.with(bit_len).div_ceil(page_bits()).max(1);
+
+    // Effect to show the current page in the main view once the big value
+    // or the page changes; mounting the panel leaves the main value alone
+    Effect::new(move |first: Option<()>| {
+        let width = bit_size.get_untracked();
+        let bits = width.bits() as usize;
+        let window = big.with(|b| b.range(page.get() * bits, bits));
+        if first.is_none() {
+            return;
+        }
+        if width.word_count() > 1 {
+            set_bit_vec.set(window);
+        } else {
+            set_bit_array.set(BitArray(window.word(0)));
+        }
+    });
+
+    // Input handler: keep the raw text, update the value only once it parses
+    let input = move |field: BigField, ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        text(field).1.set(input.clone());
+        let parsed = normalize_digits(&input, field.radix())
+            .ok()
+            .and_then(|(radix, digits)| BitVec::from_str_radix_fit(&digits, radix));
+        match parsed {
+            Some(value) => {
+                set_invalid.set(None);
+                set_big.set(value);
+            }
+            None => set_invalid.set(Some(field)),
+        }
+    };
+
+    let store_page = move |_| {
+        let width = bit_size.get();
+        let bits = width.bits() as usize;
+        let current = if width.word_count() > 1 {
+            bit_vec.get()
+        } else {
+            BitVec::from_words(&[bit_array.get().0], bits)
+        };
+        let offset = page.get() * bits;
+        set_big.update(|b| {
+            let needed = (offset + bits).div_ceil(64) * 64;
+            if b.len() < needed {
+                b.resize(needed);
+            }
+            b.set_range(offset, &current);
+        });
+    };
+
+    view! {
+        <div class="big-int-panel">
+            <label>
+                <span class="input-label">Big integer</span>
+                <span class="big-int-info">
+                    {move || format!("{} bits", big.with(bit_len))}
+                </span>
+            </label>
+            <div class="number-repr">
+                {BigField::ALL
+                    .into_iter()
+                    .map(|field| {
+                        view! {
+                            <label>
+                                <span class="input-label">{field.label()}</span>
+                                <input
+                                    type="text"
+                                    prop:value=text(field).0
+                                    class:invalid=move || invalid.get() == Some(field)
+                                    on:input=move |ev| input(field, ev)
+                                    on:focus=move |_| set_editing.set(Some(field))
+                                    on:blur=move |_| {
+                                        set_editing.set(None);
+                                        set_invalid.set(None);
+                                    }
+                                />
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div class="bit-operations">
+                <button
+                    class="bit-btn"
+                    on:click=move |_| set_page.update(|p| *p = p.saturating_sub(1))
+                    disabled=move || page.get() == 0
+                >
+                    "Prev page"
+                </button>
+                <span class="big-int-info">
+                    {move || {
+                        let start = page.get() * page_bits();
+                        format!(
+                            "Page {} of {}, bits {}–{}",
+                            page.get() + 1,
+                            page_count().max(page.get() + 1),
+                            start,
+                            start + page_bits() - 1,
+                        )
+                    }}
+                </span>
+                <button class="bit-btn" on:click=move |_| set_page.update(|p| *p += 1)>
+                    "Next page"
+                </button>
+                <button
+                    class="bit-btn"
+                    title="Write the main view's value into the current page"
+                    on:click=store_page
+                >
+                    "Main → page"
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -1,51 +1,312 @@
 //! Bit Grid Visualization Module
 
-use bit_operations::BitArray;
+use bit_operations::bitvec::BitVec;
+use bit_operations::ieee754::IEEEFormat;
+use bit_operations::register_map::RegisterMap;
+use bit_operations::{BitArray, Width};
 use leptos::prelude::CustomAttribute;
 use leptos::prelude::Update;
 use leptos::prelude::{
-    ClassAttribute, CollectView, ElementChild, Get, OnAttribute, ReadSignal, WriteSignal,
+    event_target_checked, event_target_value, on_cleanup, signal, window_event_listener,
+    ClassAttribute, CollectView, Effect, ElementChild, Get, GetUntracked, GlobalAttributes,
+    OnAttribute, PropAttribute, ReadSignal, Set, Signal, Track, With, WriteSignal,
 };
 use leptos::*;
 
+/// Cells per grid row, as laid out by the stylesheet
+const ROW_BITS: u32 = 16;
+
+/// Cursor and selected bit range on the grid
+///
+/// The selection runs from `anchor` to `cursor` inclusive; without an
+/// anchor only the cursor bit is selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub cursor: u32,
+    pub anchor: Option<u32>,
+}
+
+impl Selection {
+    /// Selection of the single bit `cursor`
+    pub fn at(cursor: u32) -> Self {
+        Self {
+            cursor,
+            anchor: None,
+        }
+    }
+
+    /// Selection of the bits `low..=high`, with the cursor on `high`
+    pub fn span(low: u32, high: u32) -> Self {
+        Self {
+            cursor: high,
+            anchor: Some(low),
+        }
+    }
+
+    /// Moves the cursor to `bit`, extending the selection from the old
+    /// cursor when `extend` is set and collapsing it otherwise
+    pub fn move_to(self, bit: u32, extend: bool) -> Self {
+        Self {
+            cursor: bit,
+            anchor: extend.then(|| self.anchor.unwrap_or(self.cursor)),
+        }
+    }
+
+    /// Returns the lowest and highest selected bit
+    pub fn range(self) -> (u32, u32) {
+        let anchor = self.anchor.unwrap_or(self.cursor);
+        (anchor.min(self.cursor), anchor.max(self.cursor))
+    }
+
+    pub fn contains(self, bit: u32) -> bool {
+        let (low, high) = self.range();
+        (low..=high).contains(&bit)
+    }
+}
+
+/// How bits are numbered in labels, tooltips and typed ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Numbering {
+    /// Bit 0 is the least significant bit
+    Lsb0,
+    /// Bit 0 is the most significant bit, as in IBM and PowerPC manuals
+    Msb0,
+}
+
+impl Numbering {
+    /// Returns the number shown for `bit` of a `bits`-wide value; `None`
+    /// for bits outside the value under MSB-0, which has no number for them
+    pub fn label(self, bit: u32, bits: u32) -> Option<u32> {
+        match self {
+            Numbering::Lsb0 => Some(bit),
+            Numbering::Msb0 => (bit < bits).then(|| bits - 1 - bit),
+        }
+    }
+
+    /// Returns the bit named by `label` in a `bits`-wide value
+    pub fn bit(self, label: u32, bits: u32) -> Option<u32> {
+        (label < bits).then(|| match self {
+            Numbering::Lsb0 => label,
+            Numbering::Msb0 => bits - 1 - label,
+        })
+    }
+}
+
+/// Display options for the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridOptions {
+    /// Bits per visual group: 0 (no separators), 4 or 8
+    pub group: u32,
+    /// Whether each cell shows its bit number underneath
+    pub labels: bool,
+    pub numbering: Numbering,
+}
+
+impl Default for GridOptions {
+    fn default() -> Self {
+        Self {
+            group: 8,
+            labels: true,
+            numbering: Numbering::Lsb0,
+        }
+    }
+}
+
+/// What a key press does to the selected bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Toggle,
+    Set,
+    Clear,
+}
+
 /// Bit Grid Component
+///
+/// Bits set in `highlight` are outlined, letting decoders point at fields,
+/// and bits set in `diff` are underlined to mark where two values differ.
+/// Sizes beyond 64 bits show one cell per bit of `bit_vec`. With a register
+/// map loaded, cells are grouped by field and name it on hover. `options`
+/// sets the nibble or byte grouping, the bit labels and the numbering
+/// shown in labels and tooltips. While `float_fields` names a format, the
+/// sign, exponent and mantissa cells are coloured apart.
+///
+/// The grid takes keyboard focus: arrow keys move a cursor (left and up
+/// towards the MSB), Home/End jump to the MSB/LSB and Shift extends a
+/// selection. Space toggles the selected bits, `1` sets and `0` clears
+/// them, and Escape collapses the selection to the cursor. Dragging across
+/// cells selects a range with the mouse.
 #[component]
 pub fn BitGrid(
     bit_array: ReadSignal<BitArray>,
     set_bit_array: WriteSignal<BitArray>,
-    bit_size: ReadSignal<u64>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    highlight: ReadSignal<u64>,
+    diff: ReadSignal<Option<BitVec>>,
+    register_map: ReadSignal<Option<RegisterMap>>,
+    selection: ReadSignal<Option<Selection>>,
+    set_selection: WriteSignal<Option<Selection>>,
+    options: ReadSignal<GridOptions>,
+    float_fields: Signal<Option<IEEEFormat>>,
 ) -> impl IntoView {
-    let mask = move || match bit_size.get() {
-        8 => 0xFFu64,
-        16 => 0xFFFFu64,
-        32 => 0xFFFFFFFFu64,
-        _ => u64::MAX,
+    let mask = move || bit_size.get().mask();
+    let wide = move || bit_size.get().word_count() > 1;
+    let (focused, set_focused) = signal(false);
+    let (dragging, set_dragging) = signal(false);
+
+    // Effect to drop the selection when the bit size changes
+    Effect::new(move |_| {
+        bit_size.track();
+        set_selection.set(None);
+    });
+
+    let mouseup = window_event_listener(ev::mouseup, move |_| set_dragging.set(false));
+    on_cleanup(move || mouseup.remove());
+
+    // Applies `edit` to the selected bits
+    let apply = move |edit: Edit, selection: Selection| {
+        let (low, high) = selection.range();
+        if wide() {
+            set_bit_vec.update(|v| {
+                for bit in low..=high {
+                    let bit = bit as usize;
+                    match edit {
+                        Edit::Toggle => v.toggle_bit(bit),
+                        Edit::Set => v.set_bit(bit),
+                        Edit::Clear => v.clear_bit(bit),
+                    }
+                }
+            });
+        } else {
+            let bits = (low..=high).fold(0u64, |acc, bit| acc | 1 << bit);
+            set_bit_array.update(|ba| {
+                ba.0 = match edit {
+                    Edit::Toggle => ba.0 ^ bits,
+                    Edit::Set => ba.0 | bits,
+                    Edit::Clear => ba.0 & !bits,
+                } & mask();
+            });
+        }
+    };
+
+    let keydown = move |ev: web_sys::KeyboardEvent| {
+        let msb = bit_size.get_untracked().bits() - 1;
+        let current = selection.get_untracked().unwrap_or(Selection::at(msb));
+        let cursor = current.cursor.min(msb);
+        let extend = ev.shift_key();
+        let step = |delta: i64| (i64::from(cursor) + delta).clamp(0, i64::from(msb)) as u32;
+        let next = match ev.key().as_str() {
+            "ArrowLeft" => current.move_to(step(1), extend),
+            "ArrowRight" => current.move_to(step(-1), extend),
+            "ArrowUp" => current.move_to(step(i64::from(ROW_BITS)), extend),
+            "ArrowDown" => current.move_to(step(-i64::from(ROW_BITS)), extend),
+            "Home" => current.move_to(msb, extend),
+            "End" => current.move_to(0, extend),
+            "Escape" => Selection::at(cursor),
+            key => {
+                let edit = match key {
+                    " " => Edit::Toggle,
+                    "1" => Edit::Set,
+                    "0" => Edit::Clear,
+                    _ => return,
+                };
+                apply(edit, current);
+                current
+            }
+        };
+        ev.prevent_default();
+        set_selection.set(Some(next));
+    };
+
+    // Marks the cursor while the grid has focus, and a selected range
+    let is_cursor =
+        move |bit: u32| focused.get() && selection.get().is_some_and(|s| s.cursor == bit);
+    let is_selected = move |bit: u32| {
+        selection
+            .get()
+            .is_some_and(|s| s.anchor.is_some() && s.contains(bit))
     };
 
     view! {
-        <div class="bit-grid">
-            {(0..64).rev().map(|bit_index| {
-                let is_active = move || bit_index < bit_size.get();
-                let current_mask = mask();
+        <div
+            class="bit-grid"
+            class:group-4=move || options.get().group == 4
+            class:group-8=move || options.get().group == 8
+            class:no-labels=move || !options.get().labels
+            tabindex="0"
+            on:keydown=keydown
+            on:focus=move |_| set_focused.set(true)
+            on:blur=move |_| set_focused.set(false)
+        >
+            {move || (0..bit_size.get().bits().max(64)).rev().map(|bit_index| {
+                let is_active = move || bit_index < bit_size.get().bits();
                 let bit_value = move || {
-                    let current_bits = bit_array.get().0 & current_mask;
+                    if wide() {
+                        return bit_vec.with(|v| v.get_bit(bit_index as usize));
+                    }
+                    let current_bits = bit_array.get().0 & mask();
                     (current_bits >> bit_index) & 1 == 1
                 };
 
+                // Field covering this bit and its position in the map
+                let field = move || {
+                    register_map.with(|map| {
+                        let map = map.as_ref()?;
+                        let index = map.fields.iter().position(|f| f.contains(bit_index))?;
+                        let f = &map.fields[index];
+                        Some((f.name.clone(), index, f.shift + f.width - 1 == bit_index))
+                    })
+                };
+
+                // Float field of this bit while the value is read as a float
+                let float_field = move || float_fields.get().and_then(|f| FloatField::of(f, bit_index));
+
                 view! {
                     <div
                         class="bit"
                         class:active=move || is_active() && bit_value()
                         class:inactive=move || !is_active()
-                        on:click=move |_| {
+                        class:highlight=move || {
+                            bit_index < 64 && (highlight.get() >> bit_index) & 1 == 1
+                        }
+                        class:diff=move || {
+                            diff.with(|d| d.as_ref().is_some_and(|d| d.get_bit(bit_index as usize)))
+                        }
+                        class:cursor=move || is_cursor(bit_index)
+                        class:selected=move || is_selected(bit_index)
+                        on:mousedown=move |_| {
                             if is_active() {
+                                set_selection.set(Some(Selection::at(bit_index)));
+                                set_dragging.set(true);
+                            }
+                        }
+                        on:mouseenter=move |_| {
+                            if dragging.get_untracked() && is_active() {
+                                set_selection.update(|s| {
+                                    *s = s.map(|s| s.move_to(bit_index, true));
+                                });
+                            }
+                        }
+                        on:click=move |_| {
+                            if wide() {
+                                set_bit_vec.update(|v| v.toggle_bit(bit_index as usize));
+                            } else if is_active() {
                                 set_bit_array.update(|ba| {
                                     ba.0 ^= 1u64 << bit_index;
-                                    ba.0 &= current_mask;
+                                    ba.0 &= mask();
                                 });
                             }
                         }
-                        data-bit=bit_index
+                        class:field-alt=move || field().is_some_and(|(_, index, _)| index % 2 == 1)
+                        class:field-start=move || field().is_some_and(|(_, _, start)| start)
+                        class:float-sign=move || float_field() == Some(FloatField::Sign)
+                        class:float-exponent=move || float_field() == Some(FloatField::Exponent)
+                        class:float-mantissa=move || float_field() == Some(FloatField::Mantissa)
+                        data-bit=move || {
+                            options.get().numbering.label(bit_index, bit_size.get().bits())
+                        }
+                        data-field=move || field().map(|(name, _, _)| name)
                     >
                         {move || if bit_value() { "1" } else { "0" }}
                     </div>
@@ -54,3 +315,94 @@ pub fn BitGrid(
         </div>
     }
 }
+
+/// IEEE 754 field a bit belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FloatField {
+    Sign,
+    Exponent,
+    Mantissa,
+}
+
+impl FloatField {
+    /// Returns the field holding `bit` in `format`, if it is within the format
+    fn of(format: IEEEFormat, bit: u32) -> Option<Self> {
+        let mantissa = format.mantissa_width() as u32;
+        let exponent = mantissa + format.exponent_width() as u32;
+        match bit {
+            b if b < mantissa => Some(FloatField::Mantissa),
+            b if b < exponent => Some(FloatField::Exponent),
+            b if b == exponent => Some(FloatField::Sign),
+            _ => None,
+        }
+    }
+}
+
+/// Grid Options Component
+///
+/// Chooses nibble or byte grouping, whether bit numbers are shown under the
+/// cells, and LSB-0 or MSB-0 numbering for the grid and typed bit ranges.
+#[component]
+pub fn GridOptionsBar(
+    options: ReadSignal<GridOptions>,
+    set_options: WriteSignal<GridOptions>,
+) -> impl IntoView {
+    view! {
+        <div class="grid-options">
+            <label>
+                "Group: "
+                <select on:change=move |ev| {
+                    let group = event_target_value(&ev).parse().unwrap_or(0);
+                    set_options.update(|o| o.group = group);
+                }>
+                    {[(0, "None"), (4, "Nibbles"), (8, "Bytes")]
+                        .into_iter()
+                        .map(|(group, label)| view! {
+                            <option
+                                value=group.to_string()
+                                selected=move || options.get().group == group
+                            >
+                                {label}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || options.get().labels
+                    on:change=move |ev| {
+                        let labels = event_target_checked(&ev);
+                        set_options.update(|o| o.labels = labels);
+                    }
+                />
+                "Bit numbers"
+            </label>
+            <label>
+                "Numbering: "
+                <select on:change=move |ev| {
+                    let numbering = if event_target_value(&ev) == "msb0" {
+                        Numbering::Msb0
+                    } else {
+                        Numbering::Lsb0
+                    };
+                    set_options.update(|o| o.numbering = numbering);
+                }>
+                    <option
+                        value="lsb0"
+                        selected=move || options.get().numbering == Numbering::Lsb0
+                    >
+                        "LSB 0"
+                    </option>
+                    <option
+                        value="msb0"
+                        selected=move || options.get().numbering == Numbering::Msb0
+                    >
+                        "MSB 0"
+                    </option>
+                </select>
+            </label>
+        </div>
+    }
+}
@@ -0,0 +1,98 @@
+//! Bit Statistics Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Population count, zero runs, set bit extremes and parity of one value
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stats {
+    ones: u32,
+    zeros: u32,
+    leading: u32,
+    trailing: u32,
+    highest: Option<u32>,
+    lowest: Option<u32>,
+    odd: bool,
+}
+
+impl Stats {
+    /// Computes the statistics of the value at `width`, reading wide values
+    /// from `bit_vec`
+    fn new(bits: BitArray, bit_vec: &BitVec, width: Width) -> Self {
+        if width.word_count() > 1 {
+            let (len, ones) = (width.bits(), bit_vec.count_ones());
+            let (leading, trailing) = (bit_vec.leading_zeros(), bit_vec.trailing_zeros());
+            return Self {
+                ones,
+                zeros: len - ones,
+                leading,
+                trailing,
+                highest: (ones > 0).then(|| len - 1 - leading),
+                lowest: (ones > 0).then_some(trailing),
+                odd: ones % 2 == 1,
+            };
+        }
+        Self {
+            ones: bits.count_ones(width),
+            zeros: bits.count_zeros(width),
+            leading: bits.leading_zeros(width),
+            trailing: bits.trailing_zeros(width),
+            highest: bits.highest_set_bit(width),
+            lowest: bits.lowest_set_bit(width),
+            odd: bits.parity(width),
+        }
+    }
+}
+
+/// Formats a bit index, or `-` when no bit is set
+fn index_text(index: Option<u32>) -> String {
+    index.map_or("-".to_string(), |i| i.to_string())
+}
+
+/// Bit Statistics Component
+///
+/// Live popcount, leading and trailing zero counts, highest and lowest set
+/// bit and parity of the value, counted within the selected bit size.
+#[component]
+pub fn BitStats(
+    bit_array: ReadSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let stats = move || bit_vec.with(|v| Stats::new(bit_array.get(), v, bit_size.get()));
+
+    view! {
+        <div class="bit-stats">
+            <label>
+                <span class="input-label">Bit stats</span>
+            </label>
+            <table class="bit-stats-table">
+                <tr>
+                    <td>"Popcount"</td>
+                    <td>{move || format!("{} ones, {} zeros", stats().ones, stats().zeros)}</td>
+                </tr>
+                <tr>
+                    <td>"Leading zeros"</td>
+                    <td>{move || stats().leading}</td>
+                </tr>
+                <tr>
+                    <td>"Trailing zeros"</td>
+                    <td>{move || stats().trailing}</td>
+                </tr>
+                <tr>
+                    <td>"Highest set bit"</td>
+                    <td>{move || index_text(stats().highest)}</td>
+                </tr>
+                <tr>
+                    <td>"Lowest set bit"</td>
+                    <td>{move || index_text(stats().lowest)}</td>
+                </tr>
+                <tr>
+                    <td>"Parity"</td>
+                    <td>{move || if stats().odd { "odd" } else { "even" }}</td>
+                </tr>
+            </table>
+        </div>
+    }
+}
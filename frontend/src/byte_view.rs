@@ -0,0 +1,90 @@
+//! Byte Dump Module
+
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Byte Dump Component
+///
+/// Shows the current value as a hex dump in little- and big-endian byte
+/// order with an ASCII column. Each byte is editable in place, and focusing
+/// a byte highlights its 8 bits in the grid.
+#[component]
+pub fn ByteDump(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (error, set_error) = signal(String::new());
+
+    let cell = move |index: u8| {
+        view! {
+            <input
+                type="text"
+                class="byte-cell"
+                maxlength="2"
+                prop:value=move || format!("{:02X}", bit_array.get().get_byte(index))
+                on:focus=move |_| set_highlight.set(0xFF << (index * 8))
+                on:blur=move |_| set_highlight.set(0)
+                on:change=move |ev| {
+                    let text = event_target_value(&ev);
+                    match u8::from_str_radix(text.trim(), 16) {
+                        Ok(byte) => {
+                            set_error.set(String::new());
+                            set_bit_array.update(|bits| bits.set_byte(index, byte));
+                        }
+                        Err(_) => set_error.set(format!("`{}` is not a hex byte", text.trim())),
+                    }
+                }
+            />
+        }
+    };
+
+    let row = move |label: &'static str, big_endian: bool| {
+        let count = bit_size.get().bytes() as u8;
+        let order = move |i: u8| if big_endian { count - 1 - i } else { i };
+        let ascii = move || {
+            let bits = bit_array.get();
+            (0..count)
+                .map(|i| bits.get_byte(order(i)))
+                .map(|b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        char::from(b)
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        };
+        view! {
+            <div class="byte-row">
+                <span class="input-label">{label}</span>
+                {(0..count).map(|i| cell(order(i))).collect_view()}
+                <span class="byte-ascii">{ascii}</span>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="byte-dump">
+            {move || {
+                if bit_size.get().bits() > 64 {
+                    view! { <div>"Byte view covers sizes up to 64 bits"</div> }.into_any()
+                } else {
+                    view! {
+                        <div class="byte-row byte-offsets">
+                            <span class="input-label">"Offset"</span>
+                            {(0..bit_size.get().bytes())
+                                .map(|i| view! { <span class="byte-offset">{format!("+{}", i)}</span> })
+                                .collect_view()}
+                        </div>
+                        {row("LE", false)}
+                        {row("BE", true)}
+                    }
+                    .into_any()
+                }
+            }}
+            <div class="byte-error">{error}</div>
+        </div>
+    }
+}
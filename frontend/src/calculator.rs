@@ -0,0 +1,301 @@
+//! Two-Operand Calculator Module
+
+use crate::bit_grid::{BitGrid, GridOptions};
+use crate::interval_view::FloatOp;
+use bit_operations::arith::{self, AluOp, ArithMode, ArithOp, Flags};
+use bit_operations::bitvec::BitVec;
+use bit_operations::ieee754::IEEEFormat;
+use bit_operations::interval::Interval;
+use bit_operations::parse::{parse_i64, parse_u64};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Formats the flags as `C V Z N`, with clear flags shown as `-`.
+fn flag_text(flags: Flags) -> String {
+    [
+        (flags.carry, 'C'),
+        (flags.overflow, 'V'),
+        (flags.zero, 'Z'),
+        (flags.negative, 'N'),
+    ]
+    .iter()
+    .map(|&(set, name)| if set { name } else { '-' })
+    .map(String::from)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Two-Operand Calculator Component
+///
+/// Keeps a second operand B with its own grid and inputs and shows every
+/// ALU operation of the current value A with B (shifts use a separate
+/// count) together with the carry/borrow, overflow, zero and negative
+/// flags. A+B and A−B follow the globally selected [`ArithMode`], so they
+/// saturate or fail instead of wrapping when asked to, at the unsigned
+/// limits or, with Signed ticked, at INT_MIN and INT_MAX; the carry and
+/// overflow flags still describe the exact result. In float mode at 32 or 64
+/// bits, A and B are also read as binary32/binary64 floats and A+B, A−B,
+/// A×B and A÷B are shown rounded to nearest next to directed-rounding
+/// bounds around the exact result. Any result can be copied back into A.
+#[component]
+pub fn TwoOperandCalculator(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    arith_mode: ReadSignal<ArithMode>,
+    grid_options: ReadSignal<GridOptions>,
+    float_fields: Signal<Option<IEEEFormat>>,
+) -> impl IntoView {
+    let (operand, set_operand) = signal(BitArray(1));
+    let (shift_input, set_shift_input) = signal("1".to_string());
+    let (signed, set_signed) = signal(false);
+    let (error, set_error) = signal(String::new());
+    // B's grid stays within 64 bits, so its wide value is never used
+    let (operand_vec, set_operand_vec) = signal(BitVec::new(64));
+    let (operand_size, set_operand_size) = signal(Width::W64);
+    let (no_highlight, _) = signal(0u64);
+    let (no_diff, _) = signal(None);
+    let (no_map, _) = signal(None);
+    let (selection, set_selection) = signal(None);
+
+    // Effect to follow the global size, capped at 64 bits
+    Effect::new(move |_| {
+        let width = bit_size.get().min(Width::W64);
+        set_operand_size.set(width);
+        set_operand.update(|b| b.0 = width.truncate(b.0));
+    });
+
+    // Decimal text of a value, signed or unsigned as selected
+    let decimal = move |value: u64| {
+        let width = operand_size.get();
+        if signed.get() {
+            BitArray(value).as_signed(width).to_string()
+        } else {
+            width.truncate(value).to_string()
+        }
+    };
+
+    let input_operand = move |text: String, radix: u32| {
+        let width = operand_size.get();
+        let parsed = if radix == 10 && signed.get() {
+            // Keep the bits of negative values within the width
+            parse_i64(&text, radix).map(|value| {
+                let bits = width.truncate(value as u64);
+                (BitArray(bits).as_signed(width) == value).then_some(bits)
+            })
+        } else {
+            parse_u64(&text, radix).map(|value| (value & !width.mask() == 0).then_some(value))
+        };
+        match parsed {
+            Ok(None) => set_error.set(format!("B does not fit in {} bits", width)),
+            Ok(Some(value)) => {
+                set_error.set(String::new());
+                set_operand.set(BitArray(value));
+            }
+            Err(err) => set_error.set(format!("Invalid B: {}", err)),
+        }
+    };
+
+    let shift = move || parse_u64(&shift_input.get(), 10).ok();
+
+    let rows = move || {
+        let width = operand_size.get();
+        let a = width.truncate(bit_array.get().0);
+        let b = operand.get().0;
+        let mode = arith_mode.get();
+        let signed = signed.get();
+        AluOp::ALL
+            .into_iter()
+            .map(|op| {
+                let (rhs, label) = match op {
+                    AluOp::Shl | AluOp::Shr => match shift() {
+                        Some(n) => (n, format!("A {} {}", op, n)),
+                        None => {
+                            return view! {
+                                <tr>
+                                    <td>{format!("A {} n", op)}</td>
+                                    <td>"invalid n"</td>
+                                </tr>
+                            }
+                            .into_any()
+                        }
+                    },
+                    _ => (b, format!("A {} B", op)),
+                };
+                let mut result = arith::alu(op, a, rhs, width);
+                let arith_op = match op {
+                    AluOp::Add => Some(ArithOp::Add),
+                    AluOp::Sub => Some(ArithOp::Sub),
+                    _ => None,
+                };
+                if let Some(arith_op) = arith_op {
+                    let applied = if signed {
+                        arith::apply_signed(arith_op, a, rhs, width, mode)
+                    } else {
+                        arith::apply(arith_op, a, rhs, width, mode)
+                    };
+                    match applied {
+                        Ok(applied) => {
+                            result.value = applied.value;
+                            result.flags.zero = applied.value == 0;
+                            result.flags.negative = applied.value >> (width.bits().min(64) - 1) & 1 == 1;
+                        }
+                        Err(err) => {
+                            return view! {
+                                <tr>
+                                    <td>{label}</td>
+                                    <td colspan="2">{err.to_string()}</td>
+                                    <td class="calculator-flags">{flag_text(result.flags)}</td>
+                                </tr>
+                            }
+                            .into_any()
+                        }
+                    }
+                }
+                view! {
+                    <tr>
+                        <td>{label}</td>
+                        <td>{format!("0x{:0digits$X}", result.value, digits = width.hex_digits())}</td>
+                        <td>{decimal(result.value)}</td>
+                        <td class="calculator-flags">{flag_text(result.flags)}</td>
+                        <td>
+                            <button
+                                class="bit-btn"
+                                on:click=move |_| set_bit_array.set(BitArray(result.value))
+                            >
+                                "→ A"
+                            </button>
+                        </td>
+                    </tr>
+                }
+                .into_any()
+            })
+            .collect_view()
+    };
+
+    // Float results of A and B with bounds, while in float mode
+    let float_rows = move || {
+        let single = match float_fields.get() {
+            Some(IEEEFormat::Single) => true,
+            Some(IEEEFormat::Double) => false,
+            Some(_) => {
+                return view! {
+                    <div class="calculator-error">"Float bounds need binary32 or binary64"</div>
+                }
+                .into_any()
+            }
+            None => return ().into_any(),
+        };
+        let decode = move |bits: u64| {
+            if single {
+                f32::from_bits(bits as u32) as f64
+            } else {
+                f64::from_bits(bits)
+            }
+        };
+        let a = decode(bit_array.get().0);
+        let b = decode(operand.get().0);
+        let rows = FloatOp::ALL
+            .into_iter()
+            .map(|op| {
+                let value = op.nearest(a, b, single);
+                let bounds = op.enclose(Interval::point(a), Interval::point(b), single);
+                let bits = if single {
+                    u64::from((value as f32).to_bits())
+                } else {
+                    value.to_bits()
+                };
+                view! {
+                    <tr>
+                        <td>{format!("A {} B", op.symbol())}</td>
+                        <td>{format!("{:e}", value)}</td>
+                        <td>{bounds.to_string()}</td>
+                        <td>{format!("width {:e}", bounds.width())}</td>
+                        <td>
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(bits))>
+                                "→ A"
+                            </button>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        view! {
+            <label>
+                <span class="input-label">Float</span>
+                {if single { "binary32" } else { "binary64" }}
+            </label>
+            <table class="calculator-results">{rows}</table>
+        }
+        .into_any()
+    };
+
+    view! {
+        <div class="calculator-panel">
+            <label>
+                <span class="input-label">Operand B</span>
+                {move || (bit_size.get().bits() > 64).then_some("low 64 bits of A")}
+            </label>
+            <BitGrid
+                bit_array=operand
+                set_bit_array=set_operand
+                bit_vec=operand_vec
+                set_bit_vec=set_operand_vec
+                bit_size=operand_size
+                highlight=no_highlight
+                diff=no_diff
+                register_map=no_map
+                selection=selection
+                set_selection=set_selection
+                options=grid_options
+                float_fields=float_fields
+            />
+            <div class="calculator-inputs">
+                <label>
+                    "HEX 0x"
+                    <input
+                        type="text"
+                        prop:value=move || format!("{:X}", operand.get().0)
+                        on:input=move |ev| input_operand(event_target_value(&ev), 16)
+                    />
+                </label>
+                <label>
+                    "DEC "
+                    <input
+                        type="text"
+                        prop:value=move || decimal(operand.get().0)
+                        on:input=move |ev| input_operand(event_target_value(&ev), 10)
+                    />
+                </label>
+                <label>
+                    "n "
+                    <input
+                        type="text"
+                        class="calculator-shift"
+                        prop:value=shift_input
+                        on:input=move |ev| set_shift_input.set(event_target_value(&ev))
+                    />
+                </label>
+                <label title="Read A and B as two's complement, so A+B and A−B saturate or fail at INT_MIN and INT_MAX">
+                    <input
+                        type="checkbox"
+                        prop:checked=signed
+                        on:change=move |ev| set_signed.set(event_target_checked(&ev))
+                    />
+                    "Signed"
+                </label>
+                <button
+                    class="bit-btn"
+                    on:click=move |_| {
+                        set_operand.set(BitArray(operand_size.get().truncate(bit_array.get().0)))
+                    }
+                >
+                    "A → B"
+                </button>
+            </div>
+            <div class="calculator-error">{error}</div>
+            <table class="calculator-results">{rows}</table>
+            {float_rows}
+        </div>
+    }
+}
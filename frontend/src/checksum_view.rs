@@ -0,0 +1,154 @@
+//! Checksum Calculator Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::checksum::{self, Crc};
+use bit_operations::parse::parse_u64;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Formats a checksum with as many hex digits as its width needs
+fn hex(value: u64, bits: u32) -> String {
+    format!("0x{:0digits$X}", value, digits = bits.div_ceil(4) as usize)
+}
+
+/// Checksum Calculator Component
+///
+/// Computes the preset CRCs, additive and XOR checksums and a CRC with
+/// user-supplied parameters over the active bytes of the current value, in
+/// little- or big-endian order.
+#[component]
+pub fn ChecksumPanel(
+    bit_array: ReadSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (big_endian, set_big_endian) = signal(true);
+    let (width_input, set_width_input) = signal("16".to_string());
+    let (poly_input, set_poly_input) = signal("1021".to_string());
+    let (init_input, set_init_input) = signal("0".to_string());
+    let (xorout_input, set_xorout_input) = signal("0".to_string());
+    let (refin, set_refin) = signal(false);
+    let (refout, set_refout) = signal(false);
+
+    let bytes = move || {
+        let width = bit_size.get();
+        let mut bytes = if width.word_count() > 1 {
+            bit_vec.with(BitVec::to_le_bytes)
+        } else {
+            width.le_bytes(bit_array.get().0)
+        };
+        if big_endian.get() {
+            bytes.reverse();
+        }
+        bytes
+    };
+
+    let custom = move || {
+        let width = parse_u64(&width_input.get(), 10)
+            .ok()
+            .filter(|w| (1..=64).contains(w))
+            .ok_or("width must be 1 to 64")? as u32;
+        let field = |input: String, name: &'static str| {
+            parse_u64(&input, 16).map_err(|_| format!("invalid {}", name))
+        };
+        Ok::<_, String>(Crc {
+            name: "Custom",
+            width,
+            poly: field(poly_input.get(), "polynomial")?,
+            init: field(init_input.get(), "init")?,
+            refin: refin.get(),
+            refout: refout.get(),
+            xorout: field(xorout_input.get(), "xorout")?,
+        })
+    };
+
+    let rows = move || {
+        let data = bytes();
+        let mut rows: Vec<(String, String)> = Crc::PRESETS
+            .iter()
+            .map(|crc| (crc.name.to_string(), hex(crc.checksum(&data), crc.width)))
+            .collect();
+        rows.push(("Sum8".to_string(), hex(checksum::sum8(&data).into(), 8)));
+        rows.push((
+            "Sum8 two's complement".to_string(),
+            hex(checksum::sum8_complement(&data).into(), 8),
+        ));
+        rows.push(("XOR8".to_string(), hex(checksum::xor8(&data).into(), 8)));
+        rows.push(match custom() {
+            Ok(crc) => (crc.name.to_string(), hex(crc.checksum(&data), crc.width)),
+            Err(err) => ("Custom".to_string(), err),
+        });
+        rows.into_iter()
+            .map(|(name, value)| {
+                view! {
+                    <tr>
+                        <td>{name}</td>
+                        <td>{value}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let text_input =
+        move |label: &'static str, value: ReadSignal<String>, set: WriteSignal<String>| {
+            view! {
+                <label>
+                    {label}
+                    <input
+                        type="text"
+                        prop:value=value
+                        on:input=move |ev| set.set(event_target_value(&ev))
+                    />
+                </label>
+            }
+        };
+
+    view! {
+        <div class="checksum-panel">
+            <label>
+                <span class="input-label">Checksums</span>
+                <select on:change=move |ev| set_big_endian.set(event_target_value(&ev) == "be")>
+                    <option value="be" selected=move || big_endian.get()>
+                        "big-endian"
+                    </option>
+                    <option value="le" selected=move || !big_endian.get()>
+                        "little-endian"
+                    </option>
+                </select>
+            </label>
+            <div class="checksum-bytes">
+                {move || {
+                    bytes()
+                        .iter()
+                        .map(|b| format!("{:02X}", b))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }}
+            </div>
+            <table class="checksum-results">{rows}</table>
+            <div class="checksum-custom">
+                {text_input("width ", width_input, set_width_input)}
+                {text_input("poly 0x", poly_input, set_poly_input)}
+                {text_input("init 0x", init_input, set_init_input)}
+                {text_input("xorout 0x", xorout_input, set_xorout_input)}
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=refin
+                        on:change=move |ev| set_refin.set(event_target_checked(&ev))
+                    />
+                    "refin"
+                </label>
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=refout
+                        on:change=move |ev| set_refout.set(event_target_checked(&ev))
+                    />
+                    "refout"
+                </label>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,196 @@
+//! Clipboard Module
+
+use crate::history::Snapshot;
+use crate::ieee754::{self, IEEEFormat};
+use crate::InputMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::parse::{parse_any, AnyNumber};
+use bit_operations::Width;
+use leptos::ev;
+use leptos::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Copies `text` with the asynchronous clipboard API
+///
+/// # Returns
+/// - `false` if the browser does not offer the clipboard API here.
+pub fn copy_text(text: &str) -> bool {
+    let get = |target: &JsValue, key: &str| js_sys::Reflect::get(target, &JsValue::from_str(key));
+    let Ok(clipboard) = get(&window(), "navigator").and_then(|n| get(&n, "clipboard")) else {
+        return false;
+    };
+    let Ok(write) = get(&clipboard, "writeText").and_then(|f| f.dyn_into::<js_sys::Function>())
+    else {
+        return false;
+    };
+    write.call1(&clipboard, &JsValue::from_str(text)).is_ok()
+}
+
+/// Formats the value as a byte array literal, most significant byte first
+fn byte_literal(value: &BitVec) -> String {
+    let bytes: Vec<String> = value
+        .to_be_bytes()
+        .iter()
+        .map(|b| format!("0x{:02X}", b))
+        .collect();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// Returns the first size from `current` upwards at which `make` succeeds
+fn fitting(current: Width, make: impl Fn(Width) -> Option<BitVec>) -> Option<(Width, BitVec)> {
+    Width::ALL
+        .into_iter()
+        .filter(|w| w.bits() >= current.bits())
+        .find_map(|w| make(w).map(|value| (w, value)))
+}
+
+/// Turns pasted text into a state, keeping the current size when the value
+/// fits and growing to the smallest size that holds it otherwise
+///
+/// # Returns
+/// - The state and a short description of the detected format, or an
+///   error message.
+fn pasted(
+    text: &str,
+    current: Width,
+    sub_format: IEEEFormat,
+) -> Result<(Snapshot, &'static str), String> {
+    let number = parse_any(text).map_err(|e| format!("Not a number: {}", e))?;
+    let (bit_size, value, input_mode, kind) = match number {
+        AnyNumber::Integer {
+            radix,
+            digits,
+            negative,
+        } => {
+            let (width, mut value) = fitting(current, |w| {
+                BitVec::from_str_radix(&digits, radix, w.bits() as usize)
+            })
+            .ok_or("Pasted integer is wider than 256 bits")?;
+            if negative {
+                value.negate();
+            }
+            let kind = match radix {
+                2 => "binary",
+                8 => "octal",
+                16 => "hex",
+                _ => "decimal",
+            };
+            (width, value, InputMode::Integer, kind)
+        }
+        AnyNumber::Bytes(bytes) => {
+            let (width, value) = fitting(current, |w| {
+                let mut padded = vec![0; w.bytes().checked_sub(bytes.len())?];
+                padded.extend_from_slice(&bytes);
+                BitVec::from_be_bytes(&padded, w.bits() as usize)
+            })
+            .ok_or("Pasted bytes are wider than 256 bits")?;
+            (width, value, InputMode::Integer, "byte list")
+        }
+        AnyNumber::Float(num) => {
+            let width = if ieee754::float_format(current, sub_format).is_some() {
+                current
+            } else {
+                Width::W64
+            };
+            let bits = ieee754::encode(num, width, sub_format).unwrap_or(0);
+            let value = BitVec::from_words(&[bits], width.bits() as usize);
+            (width, value, InputMode::Float, "float")
+        }
+    };
+    Ok((
+        Snapshot {
+            value,
+            bit_size,
+            input_mode,
+        },
+        kind,
+    ))
+}
+
+/// Clipboard Component
+///
+/// Copies the current value as hex, binary, unsigned decimal or a byte
+/// array literal. Pasting anywhere outside a text field loads the pasted
+/// value, whether it is written as `0x…`, `0b…`, decimal, a byte list like
+/// `DE AD BE EF` or a float.
+#[component]
+pub fn ClipboardBar(
+    state: Memo<Option<Snapshot>>,
+    load: Callback<Snapshot>,
+    sub_format: ReadSignal<IEEEFormat>,
+) -> impl IntoView {
+    let (status, set_status) = signal(String::new());
+
+    let copy = move |label: &'static str, format: fn(&Snapshot) -> String| {
+        move |_| {
+            let Some(snapshot) = state.get_untracked() else {
+                return;
+            };
+            let text = format(&snapshot);
+            if copy_text(&text) {
+                set_status.set(format!("Copied {}", label));
+            } else {
+                let _ = window().prompt_with_message_and_default("Copy this value", &text);
+            }
+        }
+    };
+
+    let paste = window_event_listener(ev::paste, move |ev| {
+        let in_field = ev
+            .target()
+            .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+            .and_then(|el| {
+                el.closest("input, textarea, select, [contenteditable]")
+                    .ok()
+            })
+            .flatten()
+            .is_some();
+        if in_field {
+            return;
+        }
+        let Some(text) = ev
+            .dyn_ref::<web_sys::ClipboardEvent>()
+            .and_then(|ev| ev.clipboard_data())
+            .and_then(|data| data.get_data("text").ok())
+        else {
+            return;
+        };
+        let current = state
+            .get_untracked()
+            .map_or(Width::W64, |snapshot| snapshot.bit_size);
+        match pasted(&text, current, sub_format.get_untracked()) {
+            Ok((snapshot, kind)) => {
+                ev.prevent_default();
+                load.run(snapshot);
+                set_status.set(format!("Pasted {}", kind));
+            }
+            Err(err) => set_status.set(err),
+        }
+    });
+    on_cleanup(move || paste.remove());
+
+    view! {
+        <div class="clipboard-bar" title="Paste anywhere outside a text field to load a value">
+            <span class="input-label">Copy</span>
+            <button class="bit-btn" on:click=copy("hex", Snapshot::hex)>
+                "Hex"
+            </button>
+            <button
+                class="bit-btn"
+                on:click=copy(
+                    "binary",
+                    |s| format!("0b{:0>bits$}", s.value.to_str_radix(2), bits = s.value.len()),
+                )
+            >
+                "Bin"
+            </button>
+            <button class="bit-btn" on:click=copy("decimal", |s| s.value.to_str_radix(10))>
+                "Dec"
+            </button>
+            <button class="bit-btn" on:click=copy("bytes", |s| byte_literal(&s.value))>
+                "Bytes"
+            </button>
+            <span class="repr-status">{status}</span>
+        </div>
+    }
+}
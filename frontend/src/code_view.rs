@@ -0,0 +1,107 @@
+//! Code Snippet Export Module
+
+use crate::clipboard::copy_text;
+use crate::history::Snapshot;
+use bit_operations::snippet::{snippets, Language};
+use leptos::prelude::*;
+
+/// Returns `name` if it is a valid identifier in all three languages
+fn identifier(name: &str) -> Option<&str> {
+    let name = name.trim();
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    ((first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_'))
+    .then_some(name)
+}
+
+/// Code Export Component
+///
+/// Writes the current value as ready-to-paste C, Rust or Python: an
+/// integer constant of the current bit size, a float constant where the
+/// language has a float type of that size, and big- and little-endian byte
+/// arrays, each with its own Copy button.
+#[component]
+pub fn CodeExport(state: Memo<Option<Snapshot>>) -> impl IntoView {
+    let (language, set_language) = signal(Language::C);
+    let (name, set_name) = signal("value".to_string());
+    let (copied, set_copied) = signal(None::<usize>);
+
+    let list = Memo::new(move |_| {
+        let snapshot = state.get()?;
+        let name = name.get();
+        let name = identifier(&name).unwrap_or("value");
+        Some(snippets(language.get(), &snapshot.value, name))
+    });
+
+    // Effect to clear the copied mark when the snippets change
+    Effect::new(move |_| {
+        list.track();
+        set_copied.set(None);
+    });
+
+    view! {
+        <div class="export-panel">
+            <label>
+                <span class="input-label">Code</span>
+                <input
+                    type="text"
+                    placeholder="identifier"
+                    prop:value=name
+                    on:input=move |ev| set_name.set(event_target_value(&ev))
+                />
+            </label>
+            <div class="export-options">
+                <label>
+                    "Language: "
+                    <select on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(l) = Language::ALL.into_iter().find(|l| l.name() == label) {
+                            set_language.set(l);
+                        }
+                    }>
+                        {Language::ALL
+                            .into_iter()
+                            .map(|l| view! {
+                                <option value=l.name() selected=move || language.get() == l>
+                                    {l.name()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <Show when=move || identifier(&name.get()).is_none()>
+                    <span class="repr-status">"not an identifier, using `value`"</span>
+                </Show>
+            </div>
+            {move || {
+                list.get()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, snippet)| {
+                        let code = snippet.code.clone();
+                        view! {
+                            <div class="code-snippet">
+                                <div class="export-options">
+                                    <span>{snippet.label}</span>
+                                    <button
+                                        class="bit-btn"
+                                        on:click=move |_| {
+                                            if copy_text(&code) {
+                                                set_copied.set(Some(i));
+                                            }
+                                        }
+                                    >
+                                        {move || if copied.get() == Some(i) { "Copied" } else { "Copy" }}
+                                    </button>
+                                </div>
+                                <pre>{snippet.code}</pre>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,118 @@
+//! Color Decoding Module
+
+use bit_operations::color::{PixelFormat, Rgba};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Color Component
+///
+/// Reads the low bits of the current value as a packed pixel (RGB565 at 16
+/// bits, RGBA8888 at 32 bits, RGB888 otherwise) and shows a swatch with the
+/// raw and 8-bit value of each channel. Picking a color writes its channels,
+/// keeping alpha and the bits outside the pixel.
+#[component]
+pub fn ColorPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (format, set_format) = signal(PixelFormat::for_width(bit_size.get_untracked()));
+
+    // Effect to switch to the natural format when the width changes
+    Effect::new(move |_| set_format.set(PixelFormat::for_width(bit_size.get())));
+
+    let color = move || format.get().map(|f| f.unpack(bit_array.get().0));
+
+    let pick = move |ev: web_sys::Event| {
+        let (Some(f), Some(picked)) = (format.get(), Rgba::parse_hex(&event_target_value(&ev)))
+        else {
+            return;
+        };
+        let value = bit_array.get_untracked().0;
+        let a = f.unpack(value).a;
+        set_bit_array.set(BitArray(f.pack(value, Rgba { a, ..picked })));
+    };
+
+    let channels = move || {
+        let f = format.get()?;
+        let value = bit_array.get().0;
+        Some(
+            f.channels()
+                .iter()
+                .map(|channel| {
+                    let raw = channel.extract(value);
+                    view! {
+                        <tr>
+                            <td>{channel.name.to_string()}</td>
+                            <td>{format!("{}/{}", raw, channel.max())}</td>
+                            <td>{format!("{:#04x}", channel.to_u8(raw))}</td>
+                        </tr>
+                    }
+                })
+                .collect_view(),
+        )
+    };
+
+    view! {
+        <div class="color-panel">
+            <label>
+                <span class="input-label">Color</span>
+                <select on:change=move |ev| {
+                    let name = event_target_value(&ev);
+                    set_format.set(PixelFormat::ALL.into_iter().find(|f| f.to_string() == name));
+                }>
+                    {PixelFormat::ALL
+                        .into_iter()
+                        .map(|f| {
+                            view! {
+                                <option
+                                    value=f.to_string()
+                                    selected=move || format.get() == Some(f)
+                                    disabled=move || bit_size.get().bits() < f.bits()
+                                >
+                                    {f.to_string()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <input
+                    type="color"
+                    prop:value=move || color().map(|c| c.to_string()).unwrap_or_default()
+                    prop:disabled=move || format.get().is_none()
+                    on:input=pick
+                />
+            </label>
+            {move || match color() {
+                Some(c) => {
+                    view! {
+                        <div class="color-preview">
+                            <div class="color-swatch">
+                                <div
+                                    class="color-fill"
+                                    style=format!(
+                                        "background-color: rgba({}, {}, {}, {:.3})",
+                                        c.r,
+                                        c.g,
+                                        c.b,
+                                        f64::from(c.a) / 255.0,
+                                    )
+                                ></div>
+                            </div>
+                            <table class="color-channels">
+                                <tr>
+                                    <th>"Ch"</th>
+                                    <th>"Raw"</th>
+                                    <th>"8-bit"</th>
+                                </tr>
+                                {channels}
+                            </table>
+                        </div>
+                    }
+                        .into_any()
+                }
+                None => view! { <div>"Colors need at least 16 bits"</div> }.into_any(),
+            }}
+        </div>
+    }
+}
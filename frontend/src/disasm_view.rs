@@ -0,0 +1,110 @@
+//! Instruction Decoder Module
+
+use bit_operations::disasm::{self, Isa};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Formats the bit ranges of a field mask, e.g. `31:25 11:7`
+fn mask_ranges(mut mask: u32) -> String {
+    let mut ranges = Vec::new();
+    while mask != 0 {
+        let hi = 31 - mask.leading_zeros();
+        let run = (mask << (31 - hi)).leading_ones();
+        let lo = hi + 1 - run;
+        ranges.push(if hi == lo {
+            hi.to_string()
+        } else {
+            format!("{}:{}", hi, lo)
+        });
+        mask &= !(u32::MAX >> (31 - hi) & u32::MAX << lo);
+    }
+    ranges.join(" ")
+}
+
+/// Instruction Decoder Component
+///
+/// Disassembles the 16- or 32-bit value as a RISC-V (RV32IMC) or ARM Thumb
+/// instruction, first halfword in the low bits as loaded from little-endian
+/// memory. Each encoded field is listed with its bit range; hovering a field
+/// highlights its bits in the grid.
+#[component]
+pub fn DisasmPanel(
+    bit_array: ReadSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (isa, set_isa) = signal(Isa::RiscV);
+
+    let decoded = move || {
+        let width = bit_size.get();
+        let bits = width.bits();
+        (bits == 16 || bits == 32)
+            .then(|| disasm::decode(isa.get(), bit_array.get().0 as u32, bits))
+    };
+
+    let field_rows = move |fields: Vec<disasm::Field>| {
+        fields
+            .into_iter()
+            .map(|field| {
+                let mask = u64::from(field.mask);
+                view! {
+                    <tr
+                        class="dns-flag"
+                        on:mouseenter=move |_| set_highlight.set(mask)
+                        on:mouseleave=move |_| set_highlight.set(0)
+                    >
+                        <td>{field.name}</td>
+                        <td>{mask_ranges(field.mask)}</td>
+                        <td>{field.value}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="disasm-panel">
+            <label>
+                <span class="input-label">Instruction</span>
+                <select
+                    class="isa"
+                    on:change=move |ev| {
+                        let name = event_target_value(&ev);
+                        if let Some(i) = Isa::ALL.into_iter().find(|i| i.to_string() == name) {
+                            set_isa.set(i);
+                        }
+                    }
+                >
+                    {Isa::ALL
+                        .into_iter()
+                        .map(|i| {
+                            view! {
+                                <option value=i.to_string() selected=move || isa.get() == i>
+                                    {i.to_string()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            {move || match decoded() {
+                None => {
+                    view! { <div class="dns-error">"Select a 16- or 32-bit size to decode"</div> }
+                        .into_any()
+                }
+                Some(Err(e)) => view! { <div class="dns-error">{e.to_string()}</div> }.into_any(),
+                Some(Ok(insn)) => {
+                    let size = format!("{}-bit", insn.bits);
+                    view! {
+                        <div class="disasm-text">
+                            {insn.to_string()}
+                            <span class="repr-status">{size}</span>
+                        </div>
+                        <table class="dns-flags">{field_rows(insn.fields)}</table>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
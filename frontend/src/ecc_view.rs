@@ -0,0 +1,164 @@
+//! Error Correction Demonstration Module
+
+use bit_operations::ecc::{EccStatus, Secded};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// ECC Component
+///
+/// Encodes the current value with a SECDED Hamming code (Hamming(72,64) at
+/// 64 bits) and keeps its check bits. Flipping data bits in the grid or any
+/// bit of the codeword shown here then demonstrates detection and
+/// correction: the codeword marks flipped bits and the syndrome position.
+#[component]
+pub fn EccPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    // Codeword of the value at the last encode
+    let (encoded, set_encoded) = signal(None::<u128>);
+
+    let code = move || Secded::new(bit_size.get());
+
+    // Effect to drop the codeword when the width changes
+    Effect::new(move |_| {
+        bit_size.track();
+        set_encoded.set(None);
+    });
+
+    let encode = move |_| {
+        if let Some(c) = code() {
+            set_encoded.set(Some(c.encode(bit_array.get_untracked().0)));
+        }
+    };
+
+    // Stored check bits combined with the current data bits
+    let received = move || {
+        let c = code()?;
+        let encoded = encoded.get()?;
+        let checks = encoded & !c.scatter(bit_size.get().mask());
+        Some(checks | c.scatter(bit_array.get().0))
+    };
+
+    let decoded = move || {
+        let (c, received) = (code()?, received()?);
+        Some((c, c.decode(received)))
+    };
+    let correctable = move || {
+        decoded().is_some_and(|(_, result)| matches!(result.status, EccStatus::Corrected(_)))
+    };
+
+    let flip = move |position: u32| {
+        let Some(c) = code() else { return };
+        match c.data_bit(position) {
+            Some(bit) => set_bit_array.update(|bits| bits.toggle_bit(bit as u8)),
+            None => set_encoded.update(|e| {
+                if let Some(e) = e {
+                    *e ^= 1 << position;
+                }
+            }),
+        }
+    };
+
+    let correct = move |_| {
+        let Some((c, result)) = decoded() else { return };
+        if let EccStatus::Corrected(_) = result.status {
+            set_bit_array.set(BitArray(result.data));
+            set_encoded.set(Some(c.encode(result.data)));
+        }
+    };
+
+    let cells = move || {
+        let (encoded, received) = (encoded.get()?, received()?);
+        let (c, result) = decoded()?;
+        let syndrome = match result.status {
+            EccStatus::Corrected(position) => Some(position),
+            _ => None,
+        };
+        Some(
+            (0..c.code_bits())
+                .rev()
+                .map(|position| {
+                    let bit = (received >> position) & 1 == 1;
+                    let title = match c.data_bit(position) {
+                        Some(bit) => format!("position {}: data bit {}", position, bit),
+                        None if position == 0 => "position 0: overall parity".to_string(),
+                        None => format!("position {}: check bit", position),
+                    };
+                    view! {
+                        <div
+                            class="ecc-bit"
+                            class:parity=c.is_parity_position(position)
+                            class:flipped=bit != ((encoded >> position) & 1 == 1)
+                            class:syndrome=syndrome == Some(position)
+                            title=title
+                            on:click=move |_| flip(position)
+                        >
+                            {if bit { "1" } else { "0" }}
+                        </div>
+                    }
+                })
+                .collect_view(),
+        )
+    };
+
+    let status = move || {
+        let (c, result) = decoded()?;
+        let verdict = match result.status {
+            EccStatus::Clean => "no error".to_string(),
+            EccStatus::Corrected(position) => match c.data_bit(position) {
+                Some(bit) => format!("single error in data bit {}, correctable", bit),
+                None => format!("single error in check bit at position {}", position),
+            },
+            EccStatus::Uncorrectable => "double error detected, not correctable".to_string(),
+        };
+        Some(format!(
+            "Syndrome: {} ({:#b}), overall parity {}: {}",
+            result.syndrome,
+            result.syndrome,
+            if result.parity_error { "odd" } else { "even" },
+            verdict
+        ))
+    };
+
+    // Data bit the syndrome points at, outlined in the grid on hover
+    let syndrome_mask = move || {
+        let (c, result) = decoded()?;
+        match result.status {
+            EccStatus::Corrected(position) => c.data_bit(position).map(|bit| 1u64 << bit),
+            _ => None,
+        }
+    };
+
+    view! {
+        <div class="ecc-panel">
+            <label>
+                <span class="input-label">ECC</span>
+                {move || match code() {
+                    Some(c) => format!("SECDED ({},{})", c.code_bits(), c.data_bits()),
+                    None => "SECDED covers sizes up to 64 bits".to_string(),
+                }}
+                <button class="bit-btn" prop:disabled=move || code().is_none() on:click=encode>
+                    "Encode"
+                </button>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || !correctable()
+                    on:click=correct
+                >
+                    "Correct"
+                </button>
+            </label>
+            <div class="ecc-code">{cells}</div>
+            <div
+                class="ecc-status"
+                on:mouseenter=move |_| set_highlight.set(syndrome_mask().unwrap_or(0))
+                on:mouseleave=move |_| set_highlight.set(0)
+            >
+                {move || status().unwrap_or("Encode the value, then flip bits".to_string())}
+            </div>
+        </div>
+    }
+}
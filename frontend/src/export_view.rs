@@ -0,0 +1,214 @@
+//! Bit Grid Image Export Module
+
+use crate::backend::call;
+use bit_operations::bitvec::BitVec;
+use bit_operations::export::{Annotation, GridField, GridImage};
+use bit_operations::net::{BitField, DNS_FLAG_FIELDS, VLAN_TCI_FIELDS};
+use bit_operations::parse::parse_u64;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Field layouts that can be coloured in the exported grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldPreset {
+    None,
+    Float,
+    DnsFlags,
+    VlanTci,
+}
+
+impl FieldPreset {
+    const ALL: [FieldPreset; 4] = [
+        FieldPreset::None,
+        FieldPreset::Float,
+        FieldPreset::DnsFlags,
+        FieldPreset::VlanTci,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            FieldPreset::None => "None",
+            FieldPreset::Float => "IEEE 754",
+            FieldPreset::DnsFlags => "DNS flags",
+            FieldPreset::VlanTci => "VLAN TCI",
+        }
+    }
+
+    fn fields(self, width: Width) -> Vec<GridField> {
+        let float = |exponent: u32, mantissa: u32| {
+            vec![
+                BitField::new("Sign", exponent + mantissa, 1),
+                BitField::new("Exponent", mantissa, exponent),
+                BitField::new("Mantissa", 0, mantissa),
+            ]
+        };
+        let fields = match (self, width) {
+            (FieldPreset::Float, Width::W16) => float(5, 10),
+            (FieldPreset::Float, Width::W32) => float(8, 23),
+            (FieldPreset::Float, Width::W64) => float(11, 52),
+            (FieldPreset::DnsFlags, _) => DNS_FLAG_FIELDS.to_vec(),
+            (FieldPreset::VlanTci, _) => VLAN_TCI_FIELDS.to_vec(),
+            _ => Vec::new(),
+        };
+        fields
+            .into_iter()
+            .filter(|field| field.shift + field.width <= width.bits())
+            .map(GridField::from)
+            .collect()
+    }
+}
+
+/// Parses `bit: note` lines, skipping blank lines.
+fn parse_annotations(text: &str) -> Result<Vec<Annotation>, String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (bit, note) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected `bit: note`, got `{}`", line.trim()))?;
+            let bit = parse_u64(bit, 10).map_err(|err| format!("bit `{}`: {}", bit.trim(), err))?;
+            Ok(Annotation {
+                bit: bit.min(u64::from(u32::MAX)) as u32,
+                text: note.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Grid Export Component
+///
+/// Sends the current grid, with an optional title, coloured field preset and
+/// per-bit notes, to the backend, which writes it as an SVG or PNG file to
+/// the download directory.
+#[component]
+pub fn GridExport(
+    bit_array: ReadSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (title, set_title) = signal(String::new());
+    let (preset, set_preset) = signal(FieldPreset::None);
+    let (notes, set_notes) = signal(String::new());
+    let (format, set_format) = signal("svg".to_string());
+    let (scale, set_scale) = signal(2u32);
+    let (file_name, set_file_name) = signal("bits".to_string());
+    let (status, set_status) = signal(String::new());
+
+    let export = move |_| {
+        let width = bit_size.get();
+        let annotations = match parse_annotations(&notes.get()) {
+            Ok(annotations) => annotations,
+            Err(err) => return set_status.set(format!("Invalid note: {}", err)),
+        };
+        if let Some(note) = annotations.iter().find(|note| note.bit >= width.bits()) {
+            return set_status.set(format!(
+                "Bit {} is outside the {}-bit value",
+                note.bit, width
+            ));
+        }
+        let image = GridImage {
+            value: if width.word_count() > 1 {
+                bit_vec.get()
+            } else {
+                BitVec::from_words(&[width.truncate(bit_array.get().0)], width.bits() as usize)
+            },
+            width,
+            title: title.get(),
+            fields: preset.get().fields(width),
+            annotations,
+        };
+        let args = serde_json::json!({
+            "image": image,
+            "format": format.get(),
+            "scale": scale.get(),
+            "fileName": file_name.get(),
+        });
+        set_status.set("Exporting…".to_string());
+        spawn_local(async move {
+            match call("export_grid", args).await {
+                Ok(path) => {
+                    set_status.set(format!("Saved to {}", path.as_string().unwrap_or_default()))
+                }
+                Err(err) => set_status.set(format!("Export failed: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="export-panel">
+            <label>
+                <span class="input-label">Export</span>
+                <input
+                    type="text"
+                    placeholder="title"
+                    prop:value=title
+                    on:input=move |ev| set_title.set(event_target_value(&ev))
+                />
+            </label>
+            <div class="export-options">
+                <label>
+                    "Fields: "
+                    <select on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(p) = FieldPreset::ALL.into_iter().find(|p| p.label() == label) {
+                            set_preset.set(p);
+                        }
+                    }>
+                        {FieldPreset::ALL
+                            .into_iter()
+                            .map(|p| view! {
+                                <option value=p.label() selected=move || preset.get() == p>
+                                    {p.label()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label>
+                    "Format: "
+                    <select on:change=move |ev| set_format.set(event_target_value(&ev))>
+                        <option value="svg" selected=move || format.get() == "svg">"SVG"</option>
+                        <option value="png" selected=move || format.get() == "png">"PNG"</option>
+                    </select>
+                </label>
+                <label>
+                    "Scale: "
+                    <select
+                        prop:disabled=move || format.get() != "png"
+                        on:change=move |ev| {
+                            if let Ok(s) = event_target_value(&ev).parse() {
+                                set_scale.set(s);
+                            }
+                        }
+                    >
+                        {[1u32, 2, 4]
+                            .into_iter()
+                            .map(|s| view! {
+                                <option value=s.to_string() selected=move || scale.get() == s>
+                                    {format!("{}×", s)}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+            </div>
+            <textarea
+                class="export-notes"
+                rows="3"
+                placeholder="63: sign bit"
+                prop:value=notes
+                on:input=move |ev| set_notes.set(event_target_value(&ev))
+            ></textarea>
+            <div class="export-options">
+                <input
+                    type="text"
+                    prop:value=file_name
+                    on:input=move |ev| set_file_name.set(event_target_value(&ev))
+                />
+                <button class="bit-btn" on:click=export>"Save"</button>
+            </div>
+            <div class="export-status">{status}</div>
+        </div>
+    }
+}
@@ -0,0 +1,66 @@
+//! Expression Input Module
+
+use bit_operations::arith::ArithMode;
+use bit_operations::expr;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Expression Component
+///
+/// Number panel row evaluating a bitwise calculator expression such as
+/// `(0xFF00 >> 4) | (1 << 13) & ~0b1010`, with `x` standing for the current
+/// value. Arithmetic follows the global [`ArithMode`]. The result is
+/// previewed while typing and loaded on Enter.
+#[component]
+pub fn ExpressionRow(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    arith_mode: ReadSignal<ArithMode>,
+) -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+
+    let result = move || {
+        let text = text.get();
+        if text.trim().is_empty() || bit_size.get().word_count() != 1 {
+            return None;
+        }
+        Some(expr::evaluate(
+            &text,
+            bit_array.get().0,
+            bit_size.get(),
+            arith_mode.get(),
+        ))
+    };
+
+    let keydown = move |ev: web_sys::KeyboardEvent| {
+        if ev.key() != "Enter" {
+            return;
+        }
+        if let Some(Ok(value)) = result() {
+            set_bit_array.set(BitArray(value));
+        }
+    };
+
+    let status = move || match result() {
+        None => String::new(),
+        Some(Ok(value)) => format!("= {:#X}", value),
+        Some(Err(e)) => e.to_string(),
+    };
+
+    view! {
+        <label>
+            <span class="input-label">EXPR</span>
+            <input
+                type="text"
+                placeholder="x & ~(1 << 3), Enter to load"
+                title="Operators ~ * / % + - << >> & ^ | with C precedence; x is the current value"
+                prop:value=text
+                prop:disabled=move || bit_size.get().word_count() != 1
+                on:input=move |ev| set_text.set(event_target_value(&ev))
+                on:keydown=keydown
+            />
+            <span class="repr-status">{status}</span>
+        </label>
+    }
+}
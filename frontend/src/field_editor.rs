@@ -0,0 +1,143 @@
+//! Bit Field Editor Module
+
+use crate::bit_grid::{GridOptions, Numbering};
+use bit_operations::parse::parse_u64;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use std::ops::Range;
+
+/// Converts a bit range between LSB-0 positions and the numbers shown under
+/// `numbering` in a `bits`-wide value; the conversion is its own inverse
+fn renumber(range: Range<u32>, bits: u32, numbering: Numbering) -> Range<u32> {
+    match numbering {
+        Numbering::Lsb0 => range,
+        Numbering::Msb0 => bits - range.end..bits - range.start,
+    }
+}
+
+/// Parses a bit range as `lo..hi` (end exclusive) or `hi:lo` (inclusive,
+/// datasheet style, either order) in the given numbering of a `bits`-wide
+/// value, returning LSB-0 positions within the low 64 bits.
+fn parse_range(input: &str, bits: u32, numbering: Numbering) -> Result<Range<u8>, String> {
+    let bound = |s: &str| {
+        parse_u64(s, 10)
+            .ok()
+            .filter(|&b| b <= u64::from(bits))
+            .map(|b| b as u32)
+            .ok_or_else(|| format!("`{}` is not a bit position below {}", s.trim(), bits))
+    };
+    let range = if let Some((lo, hi)) = input.split_once("..") {
+        bound(lo)?..bound(hi)?
+    } else if let Some((a, b)) = input.split_once(':') {
+        let (a, b) = (bound(a)?, bound(b)?);
+        let hi = a.max(b);
+        if hi >= bits {
+            return Err(format!("bit {} is outside the {}-bit value", hi, bits));
+        }
+        a.min(b)..hi + 1
+    } else {
+        return Err("expected `lo..hi` or `hi:lo`".to_string());
+    };
+    if range.start >= range.end {
+        return Err("empty bit range".to_string());
+    }
+    let range = renumber(range, bits, numbering);
+    if range.end > 64 {
+        return Err("fields end at bit 63 at most".to_string());
+    }
+    Ok(range.start as u8..range.end as u8)
+}
+
+/// Bit Field Editor Component
+///
+/// Reads and writes a range of bits of the current value as a hexadecimal
+/// or decimal number, for pulling register fields such as bits 12..20 out of
+/// a word without counting bits in the grid. Ranges are typed and shown in
+/// the grid's LSB-0 or MSB-0 numbering.
+#[component]
+pub fn FieldEditor(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    grid_options: ReadSignal<GridOptions>,
+) -> impl IntoView {
+    let (range_input, set_range_input) = signal("12..20".to_string());
+    let (error, set_error) = signal(String::new());
+
+    let numbering = move || grid_options.get().numbering;
+    let range = move || parse_range(&range_input.get(), bit_size.get().bits(), numbering());
+    let field = move || range().ok().map(|r| bit_array.get().get_field(r));
+
+    let write = move |input: String, radix: u32| {
+        let Ok(r) = range() else { return };
+        let width = r.end - r.start;
+        match parse_u64(&input, radix) {
+            Ok(value) if width < 64 && value >> width != 0 => {
+                set_error.set(format!("value does not fit in {} bits", width))
+            }
+            Ok(value) => {
+                set_error.set(String::new());
+                set_bit_array.update(|bits| bits.set_field(r, value));
+            }
+            Err(err) => set_error.set(err.to_string()),
+        }
+    };
+
+    view! {
+        <div class="field-editor-panel">
+            <label>
+                <span class="input-label">Field</span>
+                <input
+                    type="text"
+                    placeholder="12..20 or 19:12"
+                    prop:value=range_input
+                    on:input=move |ev| {
+                        set_error.set(String::new());
+                        set_range_input.set(event_target_value(&ev));
+                    }
+                />
+            </label>
+            {move || match range() {
+                Err(err) => view! { <div class="field-editor-error">{err}</div> }.into_any(),
+                Ok(r) => {
+                    let shown = renumber(
+                        u32::from(r.start)..u32::from(r.end),
+                        bit_size.get().bits(),
+                        numbering(),
+                    );
+                    view! {
+                        <div class="field-editor-row">
+                            {format!("Bits {}..{} ({} wide)", shown.start, shown.end, r.end - r.start)}
+                            <button
+                                class="bit-btn"
+                                on:click=move |_| set_bit_array.update(|bits| bits.clear_field(r.clone()))
+                            >
+                                "Clear"
+                            </button>
+                        </div>
+                    }
+                    .into_any()
+                }
+            }}
+            <div class="field-editor-row">
+                <label>
+                    "HEX 0x"
+                    <input
+                        type="text"
+                        prop:value=move || field().map(|v| format!("{:X}", v)).unwrap_or_default()
+                        on:input=move |ev| write(event_target_value(&ev), 16)
+                    />
+                </label>
+                <label>
+                    "DEC "
+                    <input
+                        type="text"
+                        prop:value=move || field().map(|v| v.to_string()).unwrap_or_default()
+                        on:input=move |ev| write(event_target_value(&ev), 10)
+                    />
+                </label>
+            </div>
+            <div class="field-editor-error">{error}</div>
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+//! File Mode Module
+
+use bit_operations::file_mode::{Class, FileMode, FileType, Permission};
+use bit_operations::BitArray;
+use leptos::prelude::*;
+
+/// File Mode Component
+///
+/// Reads the low 16 bits of the current value as a Unix `st_mode` word and
+/// shows it in `ls -l` style and octal. The file type select and the
+/// permission checkboxes write their bits back into the value.
+#[component]
+pub fn FileModePanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+) -> impl IntoView {
+    let mode = move || FileMode(bit_array.get().0 as u16);
+
+    // Flips `mask` in the value
+    let toggle = move |mask: u16| {
+        set_bit_array.update(|b| b.0 ^= u64::from(mask));
+    };
+
+    let set_type = move |ev: web_sys::Event| {
+        let name = event_target_value(&ev);
+        let file_type = FileType::ALL.into_iter().find(|t| t.to_string() == name);
+        let value = bit_array.get_untracked().0;
+        let updated = FileMode(value as u16).with_type(file_type).0;
+        set_bit_array.set(BitArray(value & !0xFFFF | u64::from(updated)));
+    };
+
+    let checkbox = move |mask: u16| {
+        view! {
+            <td>
+                <input
+                    type="checkbox"
+                    prop:checked=move || mode().has(mask)
+                    on:change=move |_| toggle(mask)
+                />
+            </td>
+        }
+    };
+
+    view! {
+        <div class="file-mode-panel">
+            <label>
+                <span class="input-label">File mode</span>
+                <select class="file-type" on:change=set_type>
+                    <option value="" selected=move || mode().file_type().is_none()>
+                        "no type"
+                    </option>
+                    {FileType::ALL
+                        .into_iter()
+                        .map(|t| {
+                            view! {
+                                <option
+                                    value=t.to_string()
+                                    selected=move || mode().file_type() == Some(t)
+                                >
+                                    {t.to_string()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <div class="file-mode-text">
+                {move || mode().to_string()}
+                <span class="repr-status">
+                    {move || {
+                        let m = mode();
+                        format!("{:06o}  chmod {:04o}", m.0, m.permissions())
+                    }}
+                </span>
+            </div>
+            <table class="file-mode-bits">
+                <tr>
+                    <th></th>
+                    {Permission::ALL
+                        .into_iter()
+                        .map(|p| view! { <th>{p.symbol().to_string()}</th> })
+                        .collect_view()}
+                    <th colspan="2">"special"</th>
+                </tr>
+                {Class::ALL
+                    .into_iter()
+                    .map(|class| {
+                        let special = match class {
+                            Class::Owner => "setuid",
+                            Class::Group => "setgid",
+                            Class::Other => "sticky",
+                        };
+                        view! {
+                            <tr>
+                                <td>{class.to_string()}</td>
+                                {Permission::ALL
+                                    .into_iter()
+                                    .map(|p| checkbox(p.bit(class)))
+                                    .collect_view()}
+                                {checkbox(class.special())}
+                                <td>{special}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </table>
+        </div>
+    }
+}
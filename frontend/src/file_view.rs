@@ -0,0 +1,182 @@
+//! File Browser Module
+
+use crate::backend::{call_json, open_dialog};
+use bit_operations::bitvec::BitVec;
+use bit_operations::parse::parse_u64;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use serde::Deserialize;
+
+/// A window of file bytes as returned by the backend
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FileWindow {
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+    pub file_len: u64,
+}
+
+/// File Browser Component
+///
+/// Reads a binary file, picked in the native open dialog, through the
+/// desktop backend one value at a time: the window is as wide as the
+/// selected bit size (8 bytes at 64 bits) and is loaded into the value in
+/// little- or big-endian order. Prev and Next step by one window; the offset
+/// field accepts decimal or `0x` hex. A window cut short by the end of the
+/// file is zero-padded.
+#[component]
+pub fn FileBrowser(
+    set_bit_array: WriteSignal<BitArray>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    path: ReadSignal<String>,
+    set_path: WriteSignal<String>,
+) -> impl IntoView {
+    let (little_endian, set_little_endian) = signal(true);
+    let (window, set_window) = signal(None::<FileWindow>);
+    let (offset_text, set_offset_text) = signal("0".to_string());
+    let (error, set_error) = signal(String::new());
+
+    // Loads the window's bytes into the value
+    let show = move |window: &FileWindow| {
+        let width = bit_size.get_untracked();
+        let mut bytes = window.bytes.clone();
+        bytes.resize(width.bytes(), 0);
+        let value = if little_endian.get_untracked() {
+            BitVec::from_le_bytes(&bytes, width.bits() as usize)
+        } else {
+            BitVec::from_be_bytes(&bytes, width.bits() as usize)
+        };
+        let Some(value) = value else {
+            return;
+        };
+        if width.word_count() > 1 {
+            set_bit_vec.set(value);
+        } else {
+            set_bit_array.set(BitArray(value.word(0)));
+        }
+    };
+
+    let read = move |offset: u64| {
+        let path = path.get_untracked();
+        if path.trim().is_empty() {
+            return;
+        }
+        let args = serde_json::json!({
+            "path": path,
+            "offset": offset,
+            "len": bit_size.get_untracked().bytes(),
+        });
+        spawn_local(async move {
+            match call_json::<FileWindow>("read_file_window", args).await {
+                Ok(w) => {
+                    set_error.set(String::new());
+                    set_offset_text.set(format!("{:#X}", w.offset));
+                    show(&w);
+                    set_window.set(Some(w));
+                }
+                Err(err) => set_error.set(err),
+            }
+        });
+    };
+
+    let pick = move |_| {
+        spawn_local(async move {
+            match open_dialog("Open file", None).await {
+                Ok(Some(picked)) => {
+                    set_path.set(picked);
+                    read(0);
+                }
+                Ok(None) => {}
+                Err(err) => set_error.set(err),
+            }
+        });
+    };
+
+    let offset = move || window.with(|w| w.as_ref().map_or(0, |w| w.offset));
+    let step = move || bit_size.get().bytes() as u64;
+    let at_end =
+        move || window.with(|w| w.as_ref().is_none_or(|w| w.offset + step() >= w.file_len));
+
+    // Effect to reread the window when the bit size or byte order changes
+    Effect::new(move |first: Option<()>| {
+        bit_size.track();
+        little_endian.track();
+        if first.is_some() {
+            if let Some(offset) = window.with_untracked(|w| w.as_ref().map(|w| w.offset)) {
+                read(offset);
+            }
+        }
+    });
+
+    let status = move || {
+        if !error.get().is_empty() {
+            return error.get();
+        }
+        window.with(|w| {
+            let Some(w) = w else {
+                return String::new();
+            };
+            let hex: Vec<String> = w.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+            let mut text = format!("{} ({}-byte file)", hex.join(" "), w.file_len);
+            if w.bytes.len() < bit_size.get().bytes() {
+                text.push_str(", zero-padded past the end");
+            }
+            text
+        })
+    };
+
+    view! {
+        <div class="file-browser">
+            <label>
+                <span class="input-label">File</span>
+                <input type="text" placeholder="no file open" readonly prop:value=path />
+                <button class="bit-btn" on:click=pick>
+                    "Open…"
+                </button>
+            </label>
+            <div class="file-nav">
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || offset() == 0
+                    on:click=move |_| read(offset().saturating_sub(step()))
+                >
+                    "Prev"
+                </button>
+                <input
+                    type="text"
+                    class="file-offset"
+                    title="Byte offset, decimal or 0x hex"
+                    prop:value=offset_text
+                    prop:disabled=move || window.with(Option::is_none)
+                    on:input=move |ev| set_offset_text.set(event_target_value(&ev))
+                    on:change=move |ev| {
+                        match parse_u64(&event_target_value(&ev), 10) {
+                            Ok(offset) => read(offset),
+                            Err(e) => set_error.set(format!("Offset: {}", e)),
+                        }
+                    }
+                />
+                <button
+                    class="bit-btn"
+                    prop:disabled=at_end
+                    on:click=move |_| read(offset() + step())
+                >
+                    "Next"
+                </button>
+                <select
+                    class="byte-order"
+                    on:change=move |ev| set_little_endian.set(event_target_value(&ev) == "le")
+                >
+                    <option value="le" selected=little_endian>
+                        "LE"
+                    </option>
+                    <option value="be" selected=move || !little_endian.get()>
+                        "BE"
+                    </option>
+                </select>
+            </div>
+            <div class="file-status">{status}</div>
+        </div>
+    }
+}
@@ -0,0 +1,135 @@
+//! Fixed-Point Interpretation Module
+
+use bit_operations::fixed_point::QFormat;
+use bit_operations::parse::parse_f64;
+use bit_operations::rounding::RoundingMode;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Rounding modes offered for real-to-fixed conversion
+const MODES: [(RoundingMode, &str); 4] = [
+    (RoundingMode::NearestEven, "nearest even"),
+    (RoundingMode::TowardZero, "toward zero"),
+    (RoundingMode::Up, "toward +∞"),
+    (RoundingMode::Down, "toward −∞"),
+];
+
+/// Fixed-Point Component
+///
+/// Interprets the low bits of the current value in a Q format such as
+/// Q16.16 or UQ8.8 and converts a typed real number back into bits with the
+/// chosen rounding mode, saturating at the ends of the format's range.
+#[component]
+pub fn FixedPointPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (format_input, set_format_input) = signal("Q16.16".to_string());
+    let (mode, set_mode) = signal(RoundingMode::NearestEven);
+    let (real_input, set_real_input) = signal(String::new());
+    let (status, set_status) = signal(String::new());
+
+    let format = move || {
+        let format = QFormat::parse(&format_input.get())
+            .ok_or("expected Qm.n or UQm.n, at most 64 bits wide")?;
+        let bits = bit_size.get().bits();
+        if format.bits() > bits {
+            return Err("format is wider than the current bit size");
+        }
+        Ok(format)
+    };
+
+    let convert = move || {
+        let Ok(format) = format() else { return };
+        let text = real_input.get_untracked();
+        if text.trim().is_empty() {
+            return;
+        }
+        let Some(fixed) = parse_f64(&text)
+            .ok()
+            .and_then(|x| format.from_f64(x, mode.get_untracked()))
+        else {
+            set_status.set(format!("`{}` is not a number", text.trim()));
+            return;
+        };
+        set_status.set(
+            match (fixed.saturated, fixed.inexact) {
+                (true, _) => "saturated",
+                (false, true) => "rounded",
+                (false, false) => "exact",
+            }
+            .to_string(),
+        );
+        let range = 0..format.bits() as u8;
+        set_bit_array.update(|bits| bits.set_field(range, fixed.raw));
+    };
+
+    view! {
+        <div class="fixed-point-panel">
+            <label>
+                <span class="input-label">Fixed point</span>
+                <input
+                    type="text"
+                    placeholder="Q16.16 or UQ8.8"
+                    prop:value=format_input
+                    on:input=move |ev| set_format_input.set(event_target_value(&ev))
+                />
+            </label>
+            {move || match format() {
+                Err(err) => view! { <div class="fixed-point-error">{err}</div> }.into_any(),
+                Ok(format) => {
+                    let raw = bit_array.get().0;
+                    view! {
+                        <div class="fixed-point-fields">
+                            <div>{format!("{}: low {} bits", format, format.bits())}</div>
+                            <div>Value: {format.exact(raw)}</div>
+                            <div>
+                                {format!(
+                                    "Range: {} .. {}, resolution {:e}",
+                                    format.min(),
+                                    format.max(),
+                                    format.resolution()
+                                )}
+                            </div>
+                        </div>
+                    }
+                    .into_any()
+                }
+            }}
+            <div class="fixed-point-row">
+                <label>
+                    "Real "
+                    <input
+                        type="text"
+                        placeholder="1.5"
+                        prop:value=real_input
+                        on:input=move |ev| set_real_input.set(event_target_value(&ev))
+                        on:change=move |_| convert()
+                    />
+                </label>
+                <select on:change=move |ev| {
+                    let index = event_target_value(&ev).parse::<usize>().unwrap_or(0);
+                    set_mode.set(MODES[index].0);
+                    convert();
+                }>
+                    {MODES
+                        .iter()
+                        .enumerate()
+                        .map(|(index, &(m, name))| {
+                            view! {
+                                <option value=index.to_string() selected=move || mode.get() == m>
+                                    {name}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <button class="bit-btn" on:click=move |_| convert()>
+                    "→ bits"
+                </button>
+            </div>
+            <div class="fixed-point-status">{status}</div>
+        </div>
+    }
+}
@@ -0,0 +1,122 @@
+//! Fused Multiply-Add Visualization Module
+
+use bit_operations::parse::parse_f64;
+use bit_operations::rounding::{FloatFormat, RoundingMode};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Renders the bits of `value`, marking positions that differ from `other`.
+fn render_bits(value: u64, other: u64, bits: u32) -> impl IntoView {
+    (0..bits)
+        .rev()
+        .map(|i| {
+            let bit = (value >> i) & 1;
+            let differs = ((value ^ other) >> i) & 1 == 1;
+            view! {
+                <span class="fma-bit" class:diff=differs>{bit.to_string()}</span>
+            }
+        })
+        .collect_view()
+}
+
+/// FMA Comparison Component
+///
+/// Computes `a * b + c` in the float format matching the current bit size
+/// once with a single rounding (fused) and once as a rounded multiply followed
+/// by a rounded add, highlighting the bits where the two results differ.
+#[component]
+pub fn FmaComparison(
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    // Defaults chosen so the product's low bits are lost by separate rounding
+    let (a_input, set_a_input) = signal("1.000244140625".to_string());
+    let (b_input, set_b_input) = signal("1.000244140625".to_string());
+    let (c_input, set_c_input) = signal("-1.00048828125".to_string());
+
+    let format = move || match bit_size.get() {
+        Width::W16 => Some(FloatFormat::HALF),
+        Width::W32 => Some(FloatFormat::SINGLE),
+        Width::W64 => Some(FloatFormat::DOUBLE),
+        _ => None,
+    };
+
+    let results = move || {
+        let format = format()?;
+        let parse = |s: String| -> Option<u64> {
+            let x = parse_f64(&s).ok()?;
+            Some(format.encode(x, RoundingMode::NearestEven))
+        };
+        let (a, b, c) = (
+            parse(a_input.get())?,
+            parse(b_input.get())?,
+            parse(c_input.get())?,
+        );
+        Some((
+            format,
+            format.fused_mul_add(a, b, c),
+            format.separate_mul_add(a, b, c),
+        ))
+    };
+
+    let operand =
+        move |label: &'static str, value: ReadSignal<String>, set: WriteSignal<String>| {
+            view! {
+                <label>
+                    {label}
+                    <input
+                        type="text"
+                        prop:value=value
+                        on:input=move |ev| set.set(event_target_value(&ev))
+                    />
+                </label>
+            }
+        };
+
+    view! {
+        <div class="fma-panel">
+            <label>
+                <span class="input-label">FMA</span>
+                <span class="fma-info">"a × b + c"</span>
+            </label>
+            <div class="fma-inputs">
+                {operand("a ", a_input, set_a_input)}
+                {operand("b ", b_input, set_b_input)}
+                {operand("c ", c_input, set_c_input)}
+            </div>
+            {move || match results() {
+                None => view! {
+                    <div class="fma-row">"Select 16, 32 or 64 bit size and enter valid numbers"</div>
+                }.into_any(),
+                Some((format, fused, separate)) => {
+                    let bits = format.bits();
+                    let verdict = if fused == separate {
+                        "Results agree".to_string()
+                    } else {
+                        format!(
+                            "Results differ in {} bit(s)",
+                            (fused ^ separate).count_ones()
+                        )
+                    };
+                    view! {
+                        <div class="fma-row">
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(fused))>
+                                "Fused"
+                            </button>
+                            <span class="fma-bits">{render_bits(fused, separate, bits)}</span>
+                            {format!("{:e}", format.decode(fused))}
+                        </div>
+                        <div class="fma-row">
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(separate))>
+                                "Separate"
+                            </button>
+                            <span class="fma-bits">{render_bits(separate, fused, bits)}</span>
+                            {format!("{:e}", format.decode(separate))}
+                        </div>
+                        <div class="fma-row">{verdict}</div>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
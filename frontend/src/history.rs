@@ -0,0 +1,196 @@
+//! Undo/Redo History Module
+
+use crate::InputMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::session::SavedValue;
+use bit_operations::Width;
+use leptos::prelude::*;
+
+/// The value, bit size and input mode at one point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// Full value; its length is the bit size's width
+    pub value: BitVec,
+    pub bit_size: Width,
+    pub input_mode: InputMode,
+}
+
+impl Snapshot {
+    /// Converts to the form kept in saved sessions
+    pub fn to_saved(&self) -> SavedValue {
+        SavedValue {
+            value: self.value.clone(),
+            bit_size: self.bit_size,
+            float: self.input_mode == InputMode::Float,
+        }
+    }
+
+    /// Converts from the form kept in saved sessions, fitting the value to
+    /// the bit size
+    pub fn from_saved(saved: SavedValue) -> Self {
+        let mut value = saved.value;
+        value.resize(saved.bit_size.bits() as usize);
+        Self {
+            value,
+            bit_size: saved.bit_size,
+            input_mode: if saved.float {
+                InputMode::Float
+            } else {
+                InputMode::Integer
+            },
+        }
+    }
+
+    /// Formats the value as zero-padded hex
+    pub fn hex(&self) -> String {
+        format!(
+            "0x{:0>digits$}",
+            self.value.to_str_radix(16).to_uppercase(),
+            digits = self.value.len().div_ceil(4)
+        )
+    }
+}
+
+/// Bounded list of snapshots with a cursor marking the current one
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: Vec<Snapshot>,
+    cursor: usize,
+    limit: usize,
+}
+
+impl History {
+    /// Creates an empty history keeping at most `limit` snapshots
+    pub fn new(limit: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            cursor: 0,
+            limit,
+        }
+    }
+
+    /// Creates a history from saved snapshots, keeping the newest `limit`
+    pub fn from_entries(mut entries: Vec<Snapshot>, cursor: usize, limit: usize) -> Self {
+        let dropped = entries.len().saturating_sub(limit);
+        entries.drain(..dropped);
+        let cursor = cursor
+            .saturating_sub(dropped)
+            .min(entries.len().saturating_sub(1));
+        Self {
+            entries,
+            cursor,
+            limit,
+        }
+    }
+
+    /// Returns the snapshots, oldest first
+    pub fn entries(&self) -> &[Snapshot] {
+        &self.entries
+    }
+
+    /// Returns the current snapshot, if any
+    pub fn current(&self) -> Option<&Snapshot> {
+        self.entries.get(self.cursor)
+    }
+
+    /// Returns the index of the current snapshot
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Records a new state after the current one, dropping any redo
+    /// entries and the oldest entry beyond the limit; a state equal to the
+    /// current snapshot is ignored.
+    pub fn record(&mut self, snapshot: Snapshot) {
+        if self.current() == Some(&snapshot) {
+            return;
+        }
+        self.entries.truncate(self.cursor + 1);
+        self.entries.push(snapshot);
+        if self.entries.len() > self.limit {
+            self.entries.remove(0);
+        }
+        self.cursor = self.entries.len() - 1;
+    }
+
+    /// Moves the cursor to `index` and returns that snapshot
+    pub fn jump(&mut self, index: usize) -> Option<Snapshot> {
+        let snapshot = self.entries.get(index)?.clone();
+        self.cursor = index;
+        Some(snapshot)
+    }
+
+    /// Returns `true` if there is an older snapshot
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Returns `true` if there is a newer snapshot
+    pub fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.entries.len()
+    }
+}
+
+/// History Panel Component
+///
+/// Undo/Redo buttons and a scrollable list of recent values, newest first;
+/// clicking an entry restores it.
+#[component]
+pub fn HistoryPanel(history: ReadSignal<History>, restore: Callback<usize>) -> impl IntoView {
+    let step = move |back: bool| {
+        let cursor = history.with(History::cursor);
+        restore.run(if back {
+            cursor.saturating_sub(1)
+        } else {
+            cursor + 1
+        });
+    };
+
+    view! {
+        <div class="history-panel">
+            <label>
+                <span class="input-label">History</span>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || !history.with(History::can_undo)
+                    on:click=move |_| step(true)
+                >
+                    "Undo"
+                </button>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || !history.with(History::can_redo)
+                    on:click=move |_| step(false)
+                >
+                    "Redo"
+                </button>
+            </label>
+            <div class="history-list">
+                {move || {
+                    history.with(|h| {
+                        h.entries
+                            .iter()
+                            .enumerate()
+                            .rev()
+                            .map(|(index, snapshot)| {
+                                let mode = match snapshot.input_mode {
+                                    InputMode::Integer => "int",
+                                    InputMode::Float => "float",
+                                };
+                                view! {
+                                    <div
+                                        class="history-entry"
+                                        class:current=index == h.cursor
+                                        on:click=move |_| restore.run(index)
+                                    >
+                                        {format!("{} ({}, {})", snapshot.hex(), snapshot.bit_size, mode)}
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
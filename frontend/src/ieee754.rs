@@ -1,186 +1,260 @@
 //! IEEE 754 Floating Point Decoder Module
 
-use bit_operations::BitArray;
+use bit_operations::bitvec::BitVec;
+use bit_operations::ieee754::{f64_to_e4m3, f64_to_quad, QuadDecoder};
+pub use bit_operations::ieee754::{IEEEDecoder, IEEEFormat};
+use bit_operations::parse::{normalize_digits, parse_u64};
+use bit_operations::rounding::{FloatFormat, RoundingMode};
+use bit_operations::{BitArray, Width};
 use leptos::prelude::*;
 
-/// IEEE 754 Format Enum
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum IEEEFormat {
-    Half,
-    Single,
-    Double,
-}
+/// Float formats selectable at 8 and 16 bits, first one the default
+pub const SUB_FORMATS: [(Width, &[(IEEEFormat, &str)]); 2] = [
+    (
+        Width::W8,
+        &[(IEEEFormat::E4M3, "E4M3"), (IEEEFormat::E5M2, "E5M2")],
+    ),
+    (
+        Width::W16,
+        &[
+            (IEEEFormat::Half, "binary16"),
+            (IEEEFormat::BFloat16, "bfloat16"),
+        ],
+    ),
+];
 
-/// IEEE 754 Decoder structure
-#[derive(Debug, Clone)]
-pub struct IEEEDecoder {
-    pub sign: u8,
-    pub exponent: i32,
-    pub exponent_bits: i32,
-    pub mantissa: u64,
-    pub value: f64,
-    pub special: String,
-    pub format: IEEEFormat,
+/// Returns the sub-formats selectable at `width`, if any
+pub fn sub_formats(width: Width) -> &'static [(IEEEFormat, &'static str)] {
+    SUB_FORMATS
+        .iter()
+        .find(|(w, _)| *w == width)
+        .map_or(&[], |(_, formats)| formats)
 }
 
-impl IEEEDecoder {
-    pub(crate) fn new(bits: u64, bit_size: u64) -> Self {
-        match bit_size {
-            16 => Self::decode_half(bits as u16),
-            32 => Self::decode_single(bits as u32),
-            _ => Self::decode_double(bits),
-        }
+/// Returns the float format used at `width`: `sub_format` when it is one of
+/// the width's sub-formats, else the width's default format
+pub fn float_format(width: Width, sub_format: IEEEFormat) -> Option<IEEEFormat> {
+    let formats = sub_formats(width);
+    if formats.iter().any(|(f, _)| *f == sub_format) {
+        return Some(sub_format);
     }
-
-    fn decode_half(bits: u16) -> Self {
-        let sign = ((bits >> 15) & 1) as u8;
-        let exponent_bits = ((bits >> 10) & 0x1F) as i32;
-        let exponent = exponent_bits - 15;
-        let mantissa = (bits & 0x03FF) as u64;
-
-        let (special, value) = match (exponent_bits, mantissa) {
-            (0x1F, 0) => (
-                if sign == 0 { "+Inf" } else { "-Inf" },
-                if sign == 0 {
-                    f64::INFINITY
-                } else {
-                    f64::NEG_INFINITY
-                },
-            ),
-            (0x1F, _) => ("NaN", f64::NAN),
-            (0, 0) => ("Zero", 0.0),
-            (0, _) => ("Denormalized", Self::half_to_f64(sign, -14, mantissa)),
-            _ => ("Normalized", Self::half_to_f64(sign, exponent, mantissa)),
-        };
-
-        Self {
-            sign,
-            exponent,
-            exponent_bits,
-            mantissa,
-            value,
-            special: special.to_string(),
-            format: IEEEFormat::Half,
-        }
+    match width {
+        Width::W32 => Some(IEEEFormat::Single),
+        Width::W64 => Some(IEEEFormat::Double),
+        _ => formats.first().map(|(f, _)| *f),
     }
+}
 
-    fn decode_single(bits: u32) -> Self {
-        let sign = ((bits >> 31) & 1) as u8;
-        let exponent_bits = ((bits >> 23) & 0xFF) as i32;
-        let exponent = exponent_bits - 127;
-        let mantissa = (bits & 0x007F_FFFF) as u64;
-
-        let (special, value) = match (exponent_bits, mantissa) {
-            (0xFF, 0) => (
-                if sign == 0 { "+Inf" } else { "-Inf" },
-                if sign == 0 {
-                    f64::INFINITY
-                } else {
-                    f64::NEG_INFINITY
-                },
-            ),
-            (0xFF, _) => ("NaN", f64::NAN),
-            (0, 0) => ("Zero", 0.0),
-            (0, _) => ("Denormalized", (mantissa as f64) * 2.0_f64.powi(-126)),
-            _ => ("Normalized", f32::from_bits(bits) as f64),
-        };
-
-        Self {
-            sign,
-            exponent,
-            exponent_bits,
-            mantissa,
-            value,
-            special: special.to_string(),
-            format: IEEEFormat::Single,
-        }
+/// Decodes `bits` in the float format used at `width`
+pub fn decode(bits: u64, width: Width, sub_format: IEEEFormat) -> IEEEDecoder {
+    match float_format(width, sub_format) {
+        Some(format) => IEEEDecoder::with_format(bits, format),
+        None => IEEEDecoder::new(bits, width.bits().into()),
     }
+}
 
-    fn decode_double(bits: u64) -> Self {
-        let sign = ((bits >> 63) & 1) as u8;
-        let exponent_bits = ((bits >> 52) & 0x7FF) as i32;
-        let exponent = exponent_bits - 1023;
-        let mantissa = bits & 0x000F_FFFF_FFFF_FFFF;
-
-        let (special, value) = match (exponent_bits, mantissa) {
-            (0x7FF, 0) => (
-                if sign == 0 { "+Inf" } else { "-Inf" },
-                if sign == 0 {
-                    f64::INFINITY
-                } else {
-                    f64::NEG_INFINITY
-                },
-            ),
-            (0x7FF, _) => ("NaN", f64::NAN),
-            (0, 0) => ("Zero", 0.0),
-            (0, _) => ("Denormalized", (mantissa as f64) * 2.0_f64.powi(-1022)),
-            _ => ("Normalized", f64::from_bits(bits)),
-        };
+/// Encodes `value` in the float format used at `width`, rounding to
+/// nearest; `None` for sizes without a float format
+pub fn encode(value: f64, width: Width, sub_format: IEEEFormat) -> Option<u64> {
+    Some(match float_format(width, sub_format)? {
+        IEEEFormat::E4M3 => u64::from(f64_to_e4m3(value)),
+        IEEEFormat::E5M2 => FloatFormat::E5M2.encode(value, RoundingMode::NearestEven),
+        IEEEFormat::BFloat16 => u64::from(half::bf16::from_f64(value).to_bits()),
+        IEEEFormat::Half => u64::from(half::f16::from_f64(value).to_bits()),
+        IEEEFormat::Single => u64::from((value as f32).to_bits()),
+        _ => value.to_bits(),
+    })
+}
 
-        Self {
-            sign,
-            exponent,
-            exponent_bits,
-            mantissa,
-            value,
-            special: special.to_string(),
-            format: IEEEFormat::Double,
-        }
-    }
+/// Steps `bits` to the neighbouring float in the format used at `width`,
+/// towards +Inf when `up`; `None` for formats without IEEE 754 ordering
+/// rules at the top exponent (E4M3) and sizes without a float format
+pub fn step(bits: u64, width: Width, sub_format: IEEEFormat, up: bool) -> Option<u64> {
+    let layout = float_format(width, sub_format)?.layout()?;
+    Some(if up {
+        layout.next_up(bits)
+    } else {
+        layout.next_down(bits)
+    })
+}
 
-    fn half_to_f64(sign: u8, exponent: i32, mantissa: u64) -> f64 {
-        let sign_mult = if sign == 1 { -1.0 } else { 1.0 };
+/// IEEE 754 Decoder Component
+///
+/// Shows the current value's FP8/half/bfloat16/single/double breakdown with
+/// its ULP and Next/Prev float buttons, and a binary128 breakdown of either a pasted 128-bit pattern or, when that is empty, the
+/// current 128-bit value (or the current value widened to quad precision at
+/// smaller sizes). The exponent and mantissa fields are editable: typing a
+/// biased exponent or a mantissa in hex reassembles the float's bits.
+#[component]
+pub fn IEEE754Display(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    sub_format: ReadSignal<IEEEFormat>,
+) -> impl IntoView {
+    let decoder = move || decode(bit_array.get().0, bit_size.get(), sub_format.get());
+    let (quad_input, set_quad_input) = signal(String::new());
 
-        if exponent == 0 {
-            // Денормализованные числа: exp = -14, без скрытой 1 в мантиссе
-            sign_mult * (mantissa as f64) * 2.0f64.powi(-24) // 2^(-14 - 10)
-        } else {
-            // Обычные числа: exp - 15, добавляем скрытую 1 в мантиссу
-            sign_mult * (1.0 + (mantissa as f64) / 1024.0) * 2.0f64.powi(exponent - 15)
+    let ulp = move || {
+        float_format(bit_size.get(), sub_format.get())
+            .and_then(IEEEFormat::layout)
+            .map(|layout| layout.ulp(bit_array.get().0))
+    };
+    let can_step = move || step(0, bit_size.get(), sub_format.get(), true).is_some();
+    let step_by = move |up: bool| {
+        let bits = bit_array.get_untracked().0;
+        if let Some(next) = step(
+            bits,
+            bit_size.get_untracked(),
+            sub_format.get_untracked(),
+            up,
+        ) {
+            set_bit_array.set(BitArray(next));
         }
-    }
+    };
 
-    fn exponent_bits_count(&self) -> usize {
-        match self.format {
-            IEEEFormat::Half => 5,
-            IEEEFormat::Single => 8,
-            IEEEFormat::Double => 11,
+    // Reassembles the bits with a typed exponent or mantissa field
+    let (field_error, set_field_error) = signal(String::new());
+    let editable = move || float_format(bit_size.get(), sub_format.get()).is_some();
+    let edit_field = move |exponent: bool, text: &str| {
+        let Some(format) = float_format(bit_size.get_untracked(), sub_format.get_untracked())
+        else {
+            return;
+        };
+        let current = decoder();
+        let (name, width, radix) = if exponent {
+            ("Exponent", current.exponent_bits_count(), 10)
+        } else {
+            ("Mantissa", current.mantissa_bits(), 16)
+        };
+        let bits = parse_u64(text, radix)
+            .map_err(|e| e.to_string())
+            .and_then(|field| {
+                let (exponent_bits, mantissa) = if exponent {
+                    (field, current.mantissa)
+                } else {
+                    (current.exponent_bits as u64, field)
+                };
+                IEEEDecoder::encode(format, current.sign, exponent_bits, mantissa)
+                    .ok_or(format!("does not fit in {} bits", width))
+            });
+        match bits {
+            Ok(bits) => {
+                set_field_error.set(String::new());
+                set_bit_array.set(BitArray(bits));
+            }
+            Err(e) => {
+                set_field_error.set(format!("{}: {}", name, e));
+                // Show the unchanged field again
+                set_bit_array.notify();
+            }
         }
-    }
+    };
 
-    fn mantissa_bits(&self) -> usize {
-        match self.format {
-            IEEEFormat::Half => 10,
-            IEEEFormat::Single => 23,
-            IEEEFormat::Double => 52,
+    let quad_bits = move || {
+        let input = quad_input.get();
+        if input.trim().is_empty() {
+            return Some(match bit_size.get() {
+                Width::W128 => {
+                    bit_vec.with(|v| u128::from(v.word(1)) << 64 | u128::from(v.word(0)))
+                }
+                _ => f64_to_quad(decoder().value),
+            });
         }
-    }
-}
-
-/// IEEE 754 Decoder Component
-#[component]
-pub fn IEEE754Display(bit_array: ReadSignal<BitArray>, bit_size: ReadSignal<u64>) -> impl IntoView {
-    let decoder = move || IEEEDecoder::new(bit_array.get().0, bit_size.get());
+        let (radix, digits) = normalize_digits(&input, 16).ok()?;
+        u128::from_str_radix(&digits, radix).ok()
+    };
+    let quad = move || quad_bits().map(QuadDecoder::new);
 
     view! {
         <div class="ieee-fields">
             <div>Format: {move || format!("{:?}", decoder().format)},
                 Sign: {move || decoder().sign},
-                Exponent: {move || format!(
-                    "0b{:0width$b} ({})",
-                    decoder().exponent_bits,
-                    decoder().exponent,
-                    width = decoder().exponent_bits_count()
-                )},
-                Mantissa: {move || format!(
-                    "0x{:01$x}",
-                    decoder().mantissa,
-                    (decoder().mantissa_bits() + 3) / 4
-                )},
+                Exponent:
+                <input
+                    type="text"
+                    class="ieee-field"
+                    title="Biased exponent, decimal or 0b/0x"
+                    prop:value=move || format!(
+                        "0b{:0width$b}",
+                        decoder().exponent_bits,
+                        width = decoder().exponent_bits_count()
+                    )
+                    prop:disabled=move || !editable()
+                    on:change=move |ev| edit_field(true, &event_target_value(&ev))
+                />
+                {move || format!("({}),", decoder().exponent)}
+                Mantissa:
+                <input
+                    type="text"
+                    class="ieee-field"
+                    title="Mantissa field in hex"
+                    prop:value=move || format!(
+                        "0x{:01$x}",
+                        decoder().mantissa,
+                        decoder().mantissa_bits().div_ceil(4)
+                    )
+                    prop:disabled=move || !editable()
+                    on:change=move |ev| edit_field(false, &event_target_value(&ev))
+                />
                 Type: {move || decoder().special.clone()}
+                <Show when=move || !field_error.get().is_empty()>
+                    <div class="repr-status">{field_error}</div>
+                </Show>
                 <div>
-                    Value: {move || format!("{:e}", decoder().value)}
+                    Value: {move || format!("{:e}", decoder().value)},
+                    ULP: {move || ulp().map(|u| format!("{:e}", u)).unwrap_or("n/a".to_string())}
                 </div>
+                <div class="ieee-step">
+                    <button
+                        class="bit-btn"
+                        prop:disabled=move || !can_step()
+                        on:click=move |_| step_by(false)
+                    >
+                        "Prev float"
+                    </button>
+                    <button
+                        class="bit-btn"
+                        prop:disabled=move || !can_step()
+                        on:click=move |_| step_by(true)
+                    >
+                        "Next float"
+                    </button>
+                </div>
+            </div>
+            <div class="quad-fields">
+                <label>
+                    <span class="input-label">binary128</span>
+                    <input
+                        type="text"
+                        placeholder="current value"
+                        prop:value=quad_input
+                        on:input=move |ev| set_quad_input.set(event_target_value(&ev))
+                    />
+                </label>
+                {move || match (quad_bits(), quad()) {
+                    (Some(bits), Some(quad)) => view! {
+                        <div>{format!("Bits: 0x{:032X}", bits)}</div>
+                        <div>Sign: {quad.sign},
+                            Exponent: {format!(
+                                "0b{:0width$b} ({})",
+                                quad.exponent_bits,
+                                quad.exponent,
+                                width = QuadDecoder::EXPONENT_BITS
+                            )},
+                            Mantissa: {format!(
+                                "0x{:01$x}",
+                                quad.mantissa,
+                                QuadDecoder::MANTISSA_BITS / 4
+                            )},
+                            Type: {quad.special.clone()}
+                        </div>
+                        <div>Value: {format!("{:e}", quad.value)}</div>
+                        <div class="quad-exact">Exact: {quad.exact()}</div>
+                    }.into_any(),
+                    _ => view! { <div>"Invalid 128-bit pattern"</div> }.into_any(),
+                }}
             </div>
         </div>
     }
@@ -0,0 +1,185 @@
+//! Float Interval Calculator Module
+
+use bit_operations::ieee754::IEEEDecoder;
+use bit_operations::interval::Interval;
+use bit_operations::parse::{parse_f64, parse_u64};
+use bit_operations::rounding::{next_down_f64, next_up_f64};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Arithmetic operation applied to the two operands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FloatOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl FloatOp {
+    pub(crate) const ALL: [FloatOp; 4] = [FloatOp::Add, FloatOp::Sub, FloatOp::Mul, FloatOp::Div];
+
+    pub(crate) fn symbol(self) -> &'static str {
+        match self {
+            FloatOp::Add => "+",
+            FloatOp::Sub => "−",
+            FloatOp::Mul => "×",
+            FloatOp::Div => "÷",
+        }
+    }
+
+    fn apply(self, a: f64, b: f64) -> f64 {
+        match self {
+            FloatOp::Add => a + b,
+            FloatOp::Sub => a - b,
+            FloatOp::Mul => a * b,
+            FloatOp::Div => a / b,
+        }
+    }
+
+    fn apply_interval(self, a: Interval, b: Interval) -> Interval {
+        match self {
+            FloatOp::Add => a + b,
+            FloatOp::Sub => a - b,
+            FloatOp::Mul => a * b,
+            FloatOp::Div => a / b,
+        }
+    }
+
+    /// Returns `a op b` rounded to nearest in binary32 (`single`) or binary64
+    pub(crate) fn nearest(self, a: f64, b: f64, single: bool) -> f64 {
+        let result = self.apply(a, b);
+        if single {
+            result as f32 as f64
+        } else {
+            result
+        }
+    }
+
+    /// Returns an interval around every exact `a op b` with operands in `a`
+    /// and `b`, widened to binary32 bounds when `single`
+    pub(crate) fn enclose(self, a: Interval, b: Interval, single: bool) -> Interval {
+        let result = self.apply_interval(a, b);
+        if single {
+            result.round_out_f32()
+        } else {
+            result
+        }
+    }
+}
+
+/// Converts typed decimal text into an interval containing the exact value.
+///
+/// Integers below 2^53 are exact; anything else may have been rounded by the
+/// parser, so it is widened by one ULP on each side.
+fn parse_operand(input: &str) -> Option<Interval> {
+    let value = parse_f64(input).ok()?;
+    let exact = parse_u64(input.trim().trim_start_matches('-'), 10).is_ok_and(|i| i < 1 << 53);
+    Some(if exact || !value.is_finite() {
+        Interval::point(value)
+    } else {
+        Interval::new(next_down_f64(value), next_up_f64(value))
+    })
+}
+
+/// Float Interval Component
+///
+/// Computes `A op B` in the current float format next to a rigorous interval
+/// around the exact result. Accumulating feeds the interval back in as `A`, so
+/// chained operations show how rounding uncertainty grows.
+#[component]
+pub fn IntervalCalculator(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (op, set_op) = signal(FloatOp::Add);
+    let (b_input, set_b_input) = signal("0.1".to_string());
+    // Accumulated (nearest value, enclosing interval) from earlier operations
+    let (accumulated, set_accumulated) = signal(None::<(f64, Interval)>);
+
+    let single = move || bit_size.get() == Width::W32;
+    let current = move || IEEEDecoder::new(bit_array.get().0, bit_size.get().bits().into()).value;
+
+    let a_value = move || accumulated.get().map_or_else(current, |(value, _)| value);
+    let a_interval = move || {
+        accumulated
+            .get()
+            .map_or_else(|| Interval::point(current()), |(_, interval)| interval)
+    };
+
+    // Nearest result in the current format, plus the enclosing interval
+    let nearest = move || {
+        let b = parse_operand(&b_input.get())?;
+        Some(op.get().nearest(a_value(), b.midpoint(), single()))
+    };
+    let enclosure = move || {
+        let b = parse_operand(&b_input.get())?;
+        Some(op.get().enclose(a_interval(), b, single()))
+    };
+
+    let accumulate = move |_| set_accumulated.set(nearest().zip(enclosure()));
+    let reset = move |_| set_accumulated.set(None);
+    let load_result = move |_| {
+        if let Some(value) = nearest() {
+            let bits = if single() {
+                u64::from((value as f32).to_bits())
+            } else {
+                value.to_bits()
+            };
+            set_bit_array.set(BitArray(bits));
+        }
+    };
+
+    view! {
+        <div class="interval-panel">
+            <label>
+                <span class="input-label">Interval</span>
+                <span class="interval-info">
+                    {move || if single() { "binary32" } else { "binary64" }}
+                </span>
+            </label>
+            <div class="interval-row">
+                "A = "
+                {move || match accumulated.get() {
+                    Some((value, interval)) => format!("{:e} in {}", value, interval),
+                    None => format!("{:e}", current()),
+                }}
+            </div>
+            <div class="bit-operations">
+                {FloatOp::ALL.into_iter().map(|o| view! {
+                    <button
+                        class="bit-btn"
+                        class:selected=move || op.get() == o
+                        on:click=move |_| set_op.set(o)
+                    >
+                        {o.symbol()}
+                    </button>
+                }).collect_view()}
+            </div>
+            <label>
+                <span class="input-label">B</span>
+                <input
+                    type="text"
+                    prop:value=b_input
+                    on:input=move |ev| set_b_input.set(event_target_value(&ev))
+                />
+            </label>
+            <div class="interval-row">
+                "Result: "
+                {move || nearest().map_or("invalid B".to_string(), |r| format!("{:e}", r))}
+            </div>
+            <div class="interval-row">
+                "Bounds: "
+                {move || enclosure().map_or(String::new(), |i| {
+                    format!("{}  width {:e}", i, i.width())
+                })}
+            </div>
+            <div class="bit-operations">
+                <button class="bit-btn" on:click=accumulate>"Accumulate"</button>
+                <button class="bit-btn" on:click=reset>"Reset"</button>
+                <button class="bit-btn" on:click=load_result>"Load result"</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,99 @@
+//! Macro Recorder Module
+
+use bit_operations::script::BitOp;
+use leptos::prelude::*;
+
+/// Macro Recorder Component
+///
+/// While recording, the bit operation buttons (shifts, rotates, Not, Clr,
+/// Set, swaps and reversals) append their operation to a list that Replay
+/// applies to the current value in one step. The list is also shown as JSON,
+/// the argument the backend's `apply_ops` command takes, and can be edited
+/// or pasted there.
+#[component]
+pub fn MacroRecorder(
+    recording: ReadSignal<bool>,
+    set_recording: WriteSignal<bool>,
+    ops: ReadSignal<Vec<BitOp>>,
+    set_ops: WriteSignal<Vec<BitOp>>,
+    replay: Callback<Vec<BitOp>, Result<(), String>>,
+) -> impl IntoView {
+    let (status, set_status) = signal(String::new());
+
+    let toggle_recording = move |_| {
+        if !recording.get() {
+            set_ops.set(Vec::new());
+        }
+        set_status.set(String::new());
+        set_recording.update(|on| *on = !*on);
+    };
+
+    let run = move |_| {
+        let count = ops.with(Vec::len);
+        match replay.run(ops.get()) {
+            Ok(()) => set_status.set(format!("Replayed {} operations", count)),
+            Err(e) => set_status.set(format!("Operation {}; nothing applied", e)),
+        }
+    };
+
+    let json = move || serde_json::to_string(&ops.get()).unwrap_or_default();
+    let edit_json = move |ev: web_sys::Event| {
+        let text = event_target_value(&ev);
+        match serde_json::from_str::<Vec<BitOp>>(&text) {
+            Ok(list) => {
+                set_ops.set(list);
+                set_status.set(String::new());
+            }
+            Err(e) => {
+                set_status.set(format!("Invalid operation list: {}", e));
+                set_ops.notify();
+            }
+        }
+    };
+
+    view! {
+        <div class="macro-recorder">
+            <label>
+                <span class="input-label">Macro</span>
+                <button
+                    class="bit-btn"
+                    class:recording=recording
+                    title="Record bit operation buttons"
+                    on:click=toggle_recording
+                >
+                    {move || if recording.get() { "Stop" } else { "Record" }}
+                </button>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || recording.get() || ops.with(Vec::is_empty)
+                    on:click=run
+                >
+                    "Replay"
+                </button>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || ops.with(Vec::is_empty)
+                    on:click=move |_| set_ops.set(Vec::new())
+                >
+                    "Clear"
+                </button>
+                <span class="repr-status">{status}</span>
+            </label>
+            <div class="macro-ops">
+                {move || {
+                    ops.get()
+                        .into_iter()
+                        .map(|op| view! { <span class="macro-op">{op.to_string()}</span> })
+                        .collect_view()
+                }}
+            </div>
+            <input
+                type="text"
+                class="macro-json"
+                title="Operation list for the apply_ops backend command"
+                prop:value=json
+                on:change=edit_json
+            />
+        </div>
+    }
+}
@@ -2,23 +2,129 @@
 //!
 //! This application provides interactive visualization and manipulation of 64-bit values
 //! with support for multiple numeric representations, character encoding display,
-//! IEEE 754 decoding, number distribution visualization, and special value generation.
+//! IEEE 754 decoding, number distribution visualization, special value generation,
+//! template-driven structured decoding, and an arbitrary-precision integer mode.
 
+mod accumulation;
+mod address_view;
+mod arithmetic;
+mod backend;
+mod base_view;
+mod bcd_view;
+mod big_int;
 mod bit_grid;
+mod bit_stats;
+mod byte_view;
+mod calculator;
+mod checksum_view;
+mod clipboard;
+mod code_view;
+mod color_view;
+mod disasm_view;
+mod ecc_view;
+mod export_view;
+mod expr_view;
+mod field_editor;
+mod file_mode_view;
+mod file_view;
+mod fixed_point_view;
+mod fma_view;
+mod history;
 mod ieee754;
+mod interval_view;
+mod macro_view;
+mod mutation;
+mod packet_view;
+mod permalink;
+mod permute_view;
 mod plot;
+mod precision_view;
+mod range_view;
+mod register_view;
+mod round_trip;
+mod search_view;
+mod session_view;
+mod settings_view;
 mod special_values;
+mod stochastic;
+mod struct_view;
+mod template_view;
+mod timestamp_view;
+mod unicode_view;
+mod varint_view;
+mod watch_view;
+mod workspace;
 // mod number_repr_bitops;
 
 use crate::plot::DistributionPlot;
-use bit_grid::BitGrid;
-use bit_operations::BitArray;
-use hex;
-use ieee754::IEEE754Display;
+use accumulation::AccumulationExplorer;
+use address_view::AddressRow;
+use arithmetic::ArithmeticOperations;
+use base_view::BaseEncodingRows;
+use bcd_view::BcdRow;
+use big_int::BigIntPanel;
+use bit_grid::{BitGrid, GridOptions, GridOptionsBar, Selection};
+use bit_operations::arith::ArithMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::expr::Watch;
+use bit_operations::parse::{group_digits, normalize_digits, parse_f64, NumberError};
+use bit_operations::register_map::RegisterMap;
+use bit_operations::script::{self, BitOp};
+use bit_operations::session::Session;
+use bit_operations::settings::Settings;
+use bit_operations::sized::{BitError, SizedBitArray};
+use bit_operations::unicode::Encoding;
+use bit_operations::{BitArray, Width};
+use bit_stats::BitStats;
+use byte_view::ByteDump;
+use calculator::TwoOperandCalculator;
+use checksum_view::ChecksumPanel;
+use clipboard::ClipboardBar;
+use code_view::CodeExport;
+use color_view::ColorPanel;
+use disasm_view::DisasmPanel;
+use ecc_view::EccPanel;
+use export_view::GridExport;
+use expr_view::ExpressionRow;
+use field_editor::FieldEditor;
+use file_mode_view::FileModePanel;
+use file_view::FileBrowser;
+use fixed_point_view::FixedPointPanel;
+use fma_view::FmaComparison;
+use history::{History, HistoryPanel, Snapshot};
+use ieee754::{float_format, sub_formats, IEEE754Display, IEEEFormat};
+use interval_view::IntervalCalculator;
 use leptos::prelude::*;
 use leptos::*;
+use macro_view::MacroRecorder;
+use mutation::MutationFuzzer;
+use packet_view::{DnsDecoder, EthernetDecoder};
+use permalink::{Link, PermalinkButton};
+use permute_view::PermutationPanel;
+use precision_view::PrecisionExplorer;
+use range_view::RangeOperations;
+use register_view::RegisterMapPanel;
+use round_trip::RoundTripTester;
+use search_view::PatternSearch;
+use session_view::SessionPanel;
+use settings_view::SettingsPanel;
 use special_values::SpecialValueGenerator;
 use std::cmp::PartialEq;
+use stochastic::StochasticRounding;
+use struct_view::StructOverlay;
+use template_view::TemplateDecoder;
+use timestamp_view::TimestampRow;
+use unicode_view::UnicodeRow;
+use varint_view::VarintPanel;
+use wasm_bindgen::JsCast;
+use watch_view::WatchPanel;
+use workspace::{Workspace, WorkspacePanel};
+
+/// Number of snapshots kept in the undo history
+const HISTORY_LEN: usize = 100;
+
+/// Number of workspace slots a new session starts with
+const WORKSPACE_SLOTS: usize = 3;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 /// Represents the input interpretation mode
@@ -29,17 +135,95 @@ enum InputMode {
     Float,
 }
 
+/// Editable number fields of the main panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Dec,
+    DecSigned,
+    Bin,
+    Gray,
+    Hex,
+    HexBe,
+    HexLe,
+    Oct,
+}
+
+impl Field {
+    /// Digits per group when digit grouping is enabled
+    fn group_size(self) -> usize {
+        match self {
+            Field::Dec | Field::DecSigned | Field::Oct => 3,
+            Field::Bin | Field::Gray | Field::Hex => 4,
+            Field::HexBe | Field::HexLe => 2,
+        }
+    }
+}
+
+/// Digit grouping choices: label and separator
+const DIGIT_SEPARATORS: [(&str, Option<char>); 4] = [
+    ("none", None),
+    ("_", Some('_')),
+    ("'", Some('\'')),
+    ("space", Some(' ')),
+];
+
+/// Returns `true` for key presses typed into a text field or select, which
+/// handle their own keys
+fn in_field(ev: &web_sys::KeyboardEvent) -> bool {
+    ev.target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .is_some_and(|el| matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+}
+
 /// Main application state and UI component
 #[component]
 fn App() -> impl IntoView {
     // Signal for storing and updating the 64-bit value
     let (bit_array, set_bit_array) = signal(BitArray::new());
-    let (bit_size, set_bit_size): (ReadSignal<u64>, WriteSignal<u64>) = signal(64);
+    let (bit_size, set_bit_size) = signal(Width::W64);
+    // Full value for widths beyond 64 bits; its low word mirrors `bit_array`
+    let (bit_vec, set_bit_vec) = signal(BitVec::new(64));
+    let (arith_mode, set_arith_mode) = signal(ArithMode::default());
+    let (editing, set_editing) = signal(None::<Field>);
+    // Field whose text failed to parse or fit, with the reason
+    let (input_error, set_input_error) = signal(None::<(Field, String)>);
+    let (digit_separator, set_digit_separator) = signal(None::<char>);
+    // Count used by the shift and rotate buttons
+    let (shift_amount, set_shift_amount) = signal(1u32);
+    // Whether growing the bit size sign-extends rather than zero-extends
+    let (sign_widen, set_sign_widen) = signal(false);
+    // Width whose low bits the Sext/Zext/Trunc buttons start from
+    let (extend_from, set_extend_from) = signal(Width::W8);
+    // Set bits dropped by the last narrowing of the bit size
+    let (resize_warning, set_resize_warning) = signal(String::new());
+    // Bits outlined in the grid by decoders pointing at a field
+    let (highlight, set_highlight) = signal(0u64);
+    // Bits differing between two compared workspace slots
+    let (diff, set_diff) = signal(None::<BitVec>);
+    let (selection, set_selection) = signal(None::<Selection>);
+    // File shared by the file browser and the pattern search
+    let (file_path, set_file_path) = signal(String::new());
+    let (grid_options, set_grid_options) = signal(GridOptions::default());
+    // Loaded register description labelling the grid's bits by field
+    let (register_map, set_register_map) = signal(None::<RegisterMap>);
+    let (history, set_history) = signal(History::new(HISTORY_LEN));
+    let (workspace, set_workspace) = signal(Workspace::new(WORKSPACE_SLOTS));
+    let (watches, set_watches) = signal(Vec::<Watch>::new());
+    let (settings, set_settings) = signal(settings_view::defaults());
+    let (show_settings, set_show_settings) = signal(false);
+    // Wide value of a snapshot being restored from a narrow size, picked up
+    // by the resize effect instead of widening the low word
+    let restored = StoredValue::new(None::<BitVec>);
 
     // Signals for input fields
     let (input_mode, set_input_mode) = signal(InputMode::Integer);
+    // Float format chosen at sizes with several (FP8 E4M3/E5M2, binary16/bfloat16)
+    let (sub_format, set_sub_format) = signal(IEEEFormat::Half);
     let (dec_input, set_dec_input) = signal(String::new());
+    let (dec_signed_input, set_dec_signed_input) = signal(String::new());
     let (bin_input, set_bin_input) = signal(String::new());
+    let (gray_input, set_gray_input) = signal(String::new());
+    let (show_gray, set_show_gray) = signal(false);
     let (hex_input, set_hex_input) = signal(String::new());
     let (hex_be_input, set_hex_be_input) = signal(String::new());
     let (hex_le_input, set_hex_le_input) = signal(String::new());
@@ -48,46 +232,357 @@ fn App() -> impl IntoView {
     let (utf8_input, set_utf8_input) = signal(String::new());
 
     // Calculate mask based on selected bit size
-    let mask = move || match bit_size.get() {
-        8 => 0xFFu64,
-        16 => 0xFFFFu64,
-        32 => 0xFFFFFFFFu64,
-        _ => u64::MAX,
+    let mask = move || bit_size.get().mask();
+    let wide = move || bit_size.get().word_count() > 1;
+    // Float format whose fields the grid colours while in float mode
+    let float_fields = Signal::derive(move || {
+        (input_mode.get() == InputMode::Float)
+            .then(|| float_format(bit_size.get(), sub_format.get()))
+            .flatten()
+    });
+
+    // Effect to resize the wide value when the bit size changes, starting
+    // from the current 64-bit value when coming from a narrow size
+    Effect::new(move |previous: Option<Width>| {
+        let width = bit_size.get();
+        if width.word_count() > 1 {
+            let len = width.bits() as usize;
+            match previous {
+                Some(prev) if prev.word_count() > 1 => set_bit_vec.update(|v| v.resize(len)),
+                _ => set_bit_vec.set(
+                    restored
+                        .try_update_value(Option::take)
+                        .flatten()
+                        .unwrap_or_else(|| BitVec::from_words(&[bit_array.get_untracked().0], len)),
+                ),
+            }
+        }
+        width
+    });
+
+    // Effects keeping the low word of the wide value and `bit_array` in step,
+    // so panels working on 64-bit values see and edit the low word
+    Effect::new(move |_| {
+        let low = bit_array.get().0;
+        if wide() && bit_vec.with_untracked(|v| v.word(0)) != low {
+            set_bit_vec.update(|v| v.set_word(0, low));
+        }
+    });
+    Effect::new(move |_| {
+        let low = bit_vec.with(|v| v.word(0));
+        if wide() && bit_array.get_untracked().0 != low {
+            set_bit_array.set(BitArray(low));
+        }
+    });
+
+    // Current value, bit size and input mode; `None` at the moments where
+    // the wide value and `bit_array` are not yet in step
+    let state = Memo::new(move |_| {
+        let width = bit_size.get();
+        let low = width.truncate(bit_array.get().0);
+        let value = if width.word_count() > 1 {
+            let value = bit_vec.get();
+            if value.len() != width.bits() as usize || value.word(0) != low {
+                return None;
+            }
+            value
+        } else {
+            BitVec::from_words(&[low], width.bits() as usize)
+        };
+        Some(Snapshot {
+            value,
+            bit_size: width,
+            input_mode: input_mode.get(),
+        })
+    });
+
+    // Effect to record every change in the history
+    Effect::new(move |_| {
+        let Some(snapshot) = state.get() else {
+            return;
+        };
+        if restored.with_value(Option::is_some) {
+            return;
+        }
+        set_history.update(|h| h.record(snapshot));
+    });
+
+    // Loads a saved state
+    let load = Callback::new(move |snapshot: Snapshot| {
+        if snapshot.bit_size.word_count() > 1 {
+            if wide() {
+                set_bit_vec.set(snapshot.value.clone());
+            } else {
+                restored.set_value(Some(snapshot.value.clone()));
+            }
+        }
+        set_bit_array.set(BitArray(snapshot.value.word(0)));
+        set_bit_size.set(snapshot.bit_size);
+        set_input_mode.set(snapshot.input_mode);
+    });
+
+    // Loads a permalink, on startup and when the URL hash is edited
+    let open_link = move |link: Link| {
+        if state.get_untracked().as_ref() != Some(&link.snapshot) {
+            load.run(link.snapshot);
+        }
+        if let Some(format) = link.sub_format {
+            set_sub_format.set(format);
+        }
+    };
+    if let Some(link) = permalink::read_location() {
+        open_link(link);
+    }
+    let hashchange = window_event_listener(ev::hashchange, move |_| {
+        if let Some(link) = permalink::read_location() {
+            open_link(link);
+        }
+    });
+    on_cleanup(move || hashchange.remove());
+
+    // Starts at the saved bit size, input mode and watches once the settings
+    // are read, unless a permalink or an autosaved session was loaded first
+    let start = Callback::new(move |settings: Settings| {
+        // Watches a session already opened replace the saved ones
+        if watches.with_untracked(Vec::is_empty) {
+            set_watches.set(settings.watches.clone());
+        } else {
+            set_settings.update(|s| s.watches = watches.get_untracked());
+        }
+        let untouched = Snapshot {
+            value: BitVec::new(64),
+            bit_size: Width::W64,
+            input_mode: InputMode::Integer,
+        };
+        if permalink::read_location().is_some() || state.get_untracked() != Some(untouched) {
+            return;
+        }
+        set_history.set(History::new(HISTORY_LEN));
+        load.run(Snapshot {
+            value: BitVec::new(settings.bit_size.bits() as usize),
+            bit_size: settings.bit_size,
+            input_mode: if settings.float {
+                InputMode::Float
+            } else {
+                InputMode::Integer
+            },
+        });
+    });
+
+    // Effect to keep the watch list in the settings, which saves it
+    Effect::new(move |_| {
+        let current = watches.get();
+        if settings.with_untracked(|s| s.watches != current) {
+            set_settings.update(|s| s.watches = current);
+        }
+    });
+
+    // Effect to apply the colour theme, which the stylesheet picks up from
+    // the root element's `data-theme` attribute
+    Effect::new(move |_| {
+        let theme = settings.with(|s| s.theme);
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("data-theme", theme.label());
+        }
+    });
+
+    // Display position of a section in the main view, after the fixed bars
+    let order = move |id: &str| {
+        settings.with(|s| {
+            let position = s.sections.iter().position(|section| section.id == id);
+            position.map_or(0, |i| i + 1).to_string()
+        })
+    };
+    let hidden = move |id: &str| {
+        settings.with(|s| {
+            s.sections
+                .iter()
+                .any(|section| section.id == id && !section.visible)
+        })
     };
 
+    // Restores the history entry at `index`
+    let restore = Callback::new(move |index: usize| {
+        if let Some(snapshot) = set_history.try_update(|h| h.jump(index)).flatten() {
+            load.run(snapshot);
+        }
+    });
+
+    // Everything a saved session keeps; `None` while the value is in flux
+    let session = Signal::derive(move || {
+        let current = state.get()?;
+        let mut session = Session::new(current.to_saved());
+        workspace.with(|w| {
+            session.slots = w.to_saved(&current);
+            session.active_slot = w.active();
+        });
+        session.register_map = register_map.get();
+        session.watches = watches.get();
+        history.with(|h| {
+            session.history = h.entries().iter().map(Snapshot::to_saved).collect();
+            session.history_cursor = h.cursor();
+        });
+        Some(session)
+    });
+
+    // Opens a saved session
+    let apply_session = Callback::new(move |session: Session| {
+        set_workspace.set(Workspace::from_saved(
+            session.slots,
+            session.active_slot,
+            WORKSPACE_SLOTS,
+        ));
+        set_register_map.set(session.register_map);
+        set_watches.set(session.watches);
+        let entries = session
+            .history
+            .into_iter()
+            .map(Snapshot::from_saved)
+            .collect();
+        set_history.set(History::from_entries(
+            entries,
+            session.history_cursor,
+            HISTORY_LEN,
+        ));
+        load.run(Snapshot::from_saved(session.current));
+    });
+
+    // Ctrl+Z / Ctrl+Y (or Ctrl+Shift+Z) outside text fields, which keep
+    // their own undo
+    let keydown = window_event_listener(ev::keydown, move |ev| {
+        if in_field(&ev) || !(ev.ctrl_key() || ev.meta_key()) {
+            return;
+        }
+        let key = ev.key().to_lowercase();
+        if key != "z" && key != "y" {
+            return;
+        }
+        ev.prevent_default();
+        let redo = key == "y" || ev.shift_key();
+        let target = history.with_untracked(|h| {
+            if redo {
+                h.can_redo().then(|| h.cursor() + 1)
+            } else {
+                h.can_undo().then(|| h.cursor() - 1)
+            }
+        });
+        if let Some(index) = target {
+            restore.run(index);
+        }
+    });
+    on_cleanup(move || keydown.remove());
+
     // Effect to update all fields when bit array changes
     Effect::new(move |_| {
-        let current = bit_array.get().0 & mask();
-        let le_bytes = current.to_le_bytes();
-        let be_bytes = current.to_be_bytes();
-        let byte_count = (bit_size.get() / 8) as usize;
+        let width = bit_size.get();
+        let current = width.truncate(bit_array.get().0);
+        let wide_value = (width.word_count() > 1).then(|| bit_vec.get());
+        let (le_bytes, be_bytes) = match &wide_value {
+            Some(value) => (value.to_le_bytes(), value.to_be_bytes()),
+            None => (width.le_bytes(current), width.be_bytes(current)),
+        };
+        // DEC, BIN, HEX and OCT digits
+        let digits = |radix: u32| match &wide_value {
+            Some(value) => value.to_str_radix(radix).to_uppercase(),
+            None => match radix {
+                2 => format!("{:b}", current),
+                8 => format!("{:o}", current),
+                16 => format!("{:X}", current),
+                _ => current.to_string(),
+            },
+        };
+
+        // The field being edited keeps its raw text until it loses focus
+        let editing = editing.get();
+        let separator = digit_separator.get();
+        let show = |field: Field, set: WriteSignal<String>, prefix: &str, digits: String| {
+            if editing != Some(field) {
+                let digits = match separator {
+                    Some(sep) => group_digits(&digits, field.group_size(), sep),
+                    None => digits,
+                };
+                set.set(format!("{}{}", prefix, digits));
+            }
+        };
 
-        if input_mode.get() == InputMode::Integer {
-            set_dec_input.set(current.to_string()); // Regular integer
+        if input_mode.get() == InputMode::Integer || wide_value.is_some() {
+            show(Field::Dec, set_dec_input, "", digits(10)); // Regular integer
         } else {
-            let _float_value = match bit_size.get() {
-                16 => half::f16::from_bits(current as u16).to_f64(),
-                32 => f32::from_bits((current & 0xFFFFFFFF) as u32) as f64,
-                64 => f64::from_bits(current),
+            let _float_value = match width {
+                Width::W16 => half::f16::from_bits(current as u16).to_f64(),
+                Width::W32 => f32::from_bits(current as u32) as f64,
+                Width::W64 => f64::from_bits(current),
                 _ => 0.0,
             };
             // set_dec_input.set(float_value.to_string());
         }
 
+        // Two's complement interpretation; the sign goes in the prefix so
+        // only the digits are grouped
+        let (sign, magnitude) = match &wide_value {
+            Some(value) if value.is_negative() => {
+                let mut magnitude = value.clone();
+                magnitude.negate();
+                ("-", magnitude.to_str_radix(10))
+            }
+            Some(value) => ("", value.to_str_radix(10)),
+            None => {
+                let signed = BitArray(current).as_signed(width);
+                (
+                    if signed < 0 { "-" } else { "" },
+                    signed.unsigned_abs().to_string(),
+                )
+            }
+        };
+        show(Field::DecSigned, set_dec_signed_input, sign, magnitude);
+
         // Update numeric representations
-        set_bin_input.set(format!(
-            "0b{:0width$b}",
-            current,
-            width = bit_size.get() as usize
-        ));
-        set_hex_input.set(format!("0x{:X}", current));
-        set_hex_be_input.set(format!("0x{}", hex::encode(&be_bytes[8 - byte_count..8])));
-        set_hex_le_input.set(format!("0x{}", hex::encode(&le_bytes[0..byte_count])));
-        set_oct_input.set(format!("0o{:o}", current));
+        show(
+            Field::Bin,
+            set_bin_input,
+            "0b",
+            format!("{:0>width$}", digits(2), width = width.bits() as usize),
+        );
+        // Gray code of values up to 64 bits
+        if wide_value.is_none() {
+            show(
+                Field::Gray,
+                set_gray_input,
+                "0b",
+                format!(
+                    "{:0>width$b}",
+                    BitArray(current).to_gray().0,
+                    width = width.bits() as usize
+                ),
+            );
+        } else {
+            set_gray_input.set(String::new());
+        }
+        let lowercase = settings.with(|s| s.lowercase_hex);
+        let case = |digits: String| {
+            if lowercase {
+                digits.to_lowercase()
+            } else {
+                digits.to_uppercase()
+            }
+        };
+        show(Field::Hex, set_hex_input, "0x", case(digits(16)));
+        show(
+            Field::HexBe,
+            set_hex_be_input,
+            "0x",
+            case(hex::encode(&be_bytes)),
+        );
+        show(
+            Field::HexLe,
+            set_hex_le_input,
+            "0x",
+            case(hex::encode(&le_bytes)),
+        );
+        show(Field::Oct, set_oct_input, "0o", digits(8));
 
         // Update character representations
-        let relevant_bytes_le = &le_bytes[0..byte_count];
-        let ascii_str: String = relevant_bytes_le
+        let ascii_str: String = le_bytes
             .iter()
             .map(|&b| {
                 if (32..=126).contains(&b) {
@@ -99,7 +594,7 @@ fn App() -> impl IntoView {
             .collect();
         set_ascii_input.set(ascii_str);
 
-        let utf8_str = String::from_utf8_lossy(&be_bytes[8 - byte_count..8]).into_owned();
+        let utf8_str = String::from_utf8_lossy(&be_bytes).into_owned();
         set_utf8_input.set(if utf8_str.is_empty() {
             " ".into()
         } else {
@@ -118,204 +613,345 @@ fn App() -> impl IntoView {
         set_bit_array.set(BitArray(value & mask()));
     };
 
-    // Input handlers with validation
+    // Parses integer text in any radix into the value at the current size;
+    // empty text leaves the value alone
+    let update_from_text = move |input: &str, radix: u32| -> Result<(), BitError> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+        let width = bit_size.get();
+        if width.word_count() > 1 {
+            let (radix, digits) = normalize_digits(input, radix)?;
+            let value = BitVec::from_str_radix(&digits, radix, width.bits() as usize)
+                .ok_or(BitError::Overflow { bits: width.bits() })?;
+            set_bit_vec.set(value);
+        } else {
+            let sized = SizedBitArray::from_str_radix(input, radix, width.bits())?;
+            set_bit_array.set(sized.value());
+        }
+        Ok(())
+    };
+
+    // Marks `field` invalid with the error's message, or clears the mark
+    let report = move |field: Field, result: Result<(), BitError>| {
+        set_input_error.set(result.err().map(|e| (field, e.to_string())));
+    };
+    let invalid =
+        move |field: Field| input_error.with(|e| e.as_ref().is_some_and(|(f, _)| *f == field));
+
+    // Effect to drop the error mark once its field loses focus
+    Effect::new(move |_| {
+        let editing = editing.get();
+        if input_error.with_untracked(|e| e.as_ref().is_some_and(|(f, _)| Some(*f) != editing)) {
+            set_input_error.set(None);
+        }
+    });
+
+    // Input handlers: keep the raw text while editing, update the value
+    // whenever it parses and fits, and mark the field otherwise
     let input_dec = move |ev: web_sys::Event| {
         let input = event_target_value(&ev);
+        set_dec_input.set(input.clone());
 
-        if input_mode.get() == InputMode::Integer {
-            // Integer mode: only allow digits
-            let filtered = input
-                .chars()
-                .filter(|c| c.is_ascii_digit())
-                .collect::<String>();
-            set_dec_input.set(filtered.clone());
-            if let Ok(num) = filtered.parse::<u64>() {
-                update_value(num);
-            }
-        } else {
-            // Float mode: allow digits, decimal point, exponents, and signs
-            let mut has_point = false;
-            let mut has_exponent = false;
-            let mut filtered_chars = Vec::new();
-
-            for (i, c) in input.chars().enumerate() {
-                match c {
-                    '-' => {
-                        // Allow minus at start or after exponent
-                        if i == 0
-                            || (has_exponent
-                                && filtered_chars
-                                    .last()
-                                    .map_or(false, |&lc| lc == 'e' || lc == 'E'))
-                        {
-                            filtered_chars.push(c);
-                        }
-                    }
-                    '+' => {
-                        // Allow plus only after exponent
-                        if has_exponent
-                            && filtered_chars
-                                .last()
-                                .map_or(false, |&lc| lc == 'e' || lc == 'E')
-                        {
-                            filtered_chars.push(c);
-                        }
-                    }
-                    '.' => {
-                        // Allow single decimal point before exponent
-                        if !has_point && !has_exponent {
-                            has_point = true;
-                            filtered_chars.push(c);
-                        }
+        if input_mode.get() == InputMode::Integer || wide() {
+            report(Field::Dec, update_from_text(&input, 10));
+        } else if !input.trim().is_empty() {
+            let result = parse_f64(&input).map(|num| {
+                let bits = ieee754::encode(num, bit_size.get(), sub_format.get()).unwrap_or(0);
+                set_bit_array.set(BitArray(bits));
+            });
+            report(Field::Dec, result.map_err(BitError::from));
+        }
+    };
+
+    // Signed input only updates the value when it is in range for the size
+    let input_dec_signed = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_dec_signed_input.set(input.clone());
+        if input.trim().is_empty() {
+            return;
+        }
+
+        let width = bit_size.get();
+        let result = if width.word_count() > 1 {
+            let trimmed = input.trim_start();
+            let (negative, rest) = match trimmed.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, trimmed),
+            };
+            normalize_digits(rest, 10)
+                .map_err(BitError::from)
+                .and_then(|(radix, digits)| {
+                    let mut value = BitVec::from_str_radix(&digits, radix, width.bits() as usize)
+                        .ok_or(BitError::Overflow { bits: width.bits() })?;
+                    let zero = value.count_ones() == 0;
+                    if negative {
+                        value.negate();
                     }
-                    'e' | 'E' => {
-                        // Allow single exponent after digits
-                        if !has_exponent && !filtered_chars.is_empty() {
-                            has_exponent = true;
-                            filtered_chars.push(c);
-                        }
+                    if !zero && value.is_negative() != negative {
+                        return Err(BitError::Overflow { bits: width.bits() });
                     }
-                    c if c.is_ascii_digit() => filtered_chars.push(c),
-                    _ => (),
-                }
-            }
+                    set_bit_vec.set(value);
+                    Ok(())
+                })
+        } else {
+            SizedBitArray::from_signed_str(&input, width.bits())
+                .map(|sized| set_bit_array.set(sized.value()))
+        };
+        report(Field::DecSigned, result);
+    };
 
-            let filtered = filtered_chars.into_iter().collect::<String>();
-            set_dec_input.set(filtered.clone());
+    let input_bin = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_bin_input.set(input.clone());
+        report(Field::Bin, update_from_text(&input, 2));
+    };
 
-            // Add leading zero for decimal fractions
-            let filtered = if filtered.starts_with('.') {
-                format!("0{}", filtered)
-            } else if filtered.is_empty() {
-                "0".to_string()
-            } else {
-                filtered
-            };
+    // Gray input converts back to binary; not available beyond 64 bits
+    let input_gray = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_gray_input.set(input.clone());
+        if !wide() && !input.trim().is_empty() {
+            let result = SizedBitArray::from_str_radix(&input, 2, bit_size.get().bits())
+                .map(|sized| set_bit_array.set(sized.value().from_gray()));
+            report(Field::Gray, result);
+        }
+    };
+
+    let input_hex = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_hex_input.set(input.clone());
+        report(Field::Hex, update_from_text(&input, 16));
+    };
 
-            // Parse and update bits
-            if let Ok(num) = filtered.parse::<f64>() {
-                let bits = match bit_size.get() {
-                    16 => u64::from(half::f16::from_f64(num).to_bits() as u16),
-                    32 => u64::from((num as f32).to_bits() as u32),
-                    64 => num.to_bits(),
-                    _ => 0,
+    // Byte fields wait for whole bytes, but report text that is not hex or
+    // has more bytes than the size holds
+    let input_hex_bytes = move |ev: web_sys::Event, big_endian: bool| {
+        let input = event_target_value(&ev);
+        let width = bit_size.get();
+        let field = if big_endian {
+            Field::HexBe
+        } else {
+            Field::HexLe
+        };
+        let bytes = match normalize_digits(&input, 16) {
+            Ok((16, digits)) => hex::decode(digits).ok(),
+            // A 0b or 0o prefix names another radix
+            Ok(_) => {
+                let prefix = input.trim().chars().nth(1).unwrap_or('0');
+                report(field, Err(NumberError::InvalidDigit(prefix).into()));
+                None
+            }
+            Err(e) => {
+                if !input.trim().is_empty() {
+                    report(field, Err(e.into()));
+                }
+                None
+            }
+        };
+        if let Some(bytes) = bytes {
+            if bytes.len() > width.bytes() {
+                report(field, Err(BitError::Overflow { bits: width.bits() }));
+            } else if width.word_count() > 1 {
+                let len = width.bits() as usize;
+                let value = if big_endian {
+                    BitVec::from_be_bytes(&bytes, len)
+                } else {
+                    BitVec::from_le_bytes(&bytes, len)
                 };
-                set_bit_array.set(BitArray(bits));
+                if let Some(value) = value {
+                    set_bit_vec.set(value);
+                    report(field, Ok(()));
+                }
+            } else {
+                let value = if big_endian {
+                    width.from_be_bytes(&bytes)
+                } else {
+                    width.from_le_bytes(&bytes)
+                };
+                if let Some(value) = value {
+                    update_value(value);
+                    report(field, Ok(()));
+                }
             }
         }
+        input
     };
+    let input_hex_be = move |ev: web_sys::Event| set_hex_be_input.set(input_hex_bytes(ev, true));
+    let input_hex_le = move |ev: web_sys::Event| set_hex_le_input.set(input_hex_bytes(ev, false));
 
-    let input_bin = move |ev: web_sys::Event| {
-        let mut val = event_target_value(&ev).replace("0b", "").replace(' ', "");
-        val = val.chars().filter(|c| *c == '0' || *c == '1').collect();
-        let filtered = if val.is_empty() { "0" } else { &val };
-        set_bin_input.set(format!("0b{}", filtered));
-        if let Ok(num) = u64::from_str_radix(filtered, 2) {
-            update_value(num);
-        }
+    let input_oct = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_oct_input.set(input.clone());
+        report(Field::Oct, update_from_text(&input, 8));
     };
 
-    let input_hex = move |ev: web_sys::Event| {
-        let mut val = event_target_value(&ev)
-            .replace("0x", "")
-            .replace(' ', "")
-            .to_uppercase();
-        val = val.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-        let max_len = bit_size.get() / 4;
-        val.truncate(max_len as usize);
-        let filtered = if val.is_empty() { "0" } else { &val };
-        set_hex_input.set(format!("0x{}", filtered));
-        if let Ok(num) = u64::from_str_radix(filtered, 16) {
-            update_value(num);
+    // Macro recorder: button operations captured while recording
+    let (recording, set_recording) = signal(false);
+    let (recorded, set_recorded) = signal(Vec::<BitOp>::new());
+
+    // Applies operations in order to the current value, wide values as a
+    // whole; the value is left unchanged if any of them fails
+    let apply_ops = move |ops: &[BitOp]| -> Result<(), String> {
+        let width = bit_size.get();
+        let describe = |(i, e): (usize, BitError)| format!("{} (`{}`): {}", i + 1, ops[i], e);
+        if width.word_count() > 1 {
+            let mut value = bit_vec.get();
+            ops.iter()
+                .enumerate()
+                .try_for_each(|(i, &op)| script::apply_wide(&mut value, op).map_err(|e| (i, e)))
+                .map_err(describe)?;
+            set_bit_vec.set(value);
+        } else {
+            let mut sized = BitArray(width.truncate(bit_array.get().0))
+                .with_width(width.bits())
+                .map_err(|e| e.to_string())?;
+            sized.apply_all(ops).map_err(describe)?;
+            set_bit_array.set(sized.value());
         }
+        Ok(())
     };
+    let replay = Callback::new(move |ops: Vec<BitOp>| apply_ops(&ops));
 
-    let input_hex_be = move |ev: web_sys::Event| {
-        let mut val = event_target_value(&ev)
-            .replace("0x", "")
-            .replace(' ', "")
-            .to_uppercase();
-        val = val.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-        let expected_len = (bit_size.get() / 4) as usize;
-        val.truncate(expected_len);
-        set_hex_be_input.set(format!("0x{}", val));
-        if val.len() == expected_len {
-            if let Ok(bytes) = hex::decode(&val) {
-                // Convert big-endian bytes to value
-                let value = bytes.iter().enumerate().fold(0u64, |acc, (i, &b)| {
-                    acc | (b as u64) << ((bytes.len() - 1 - i) * 8)
-                });
-                update_value(value);
-            }
+    // Bit operations, recorded while the macro recorder is on
+    let run = move |op: BitOp| {
+        if apply_ops(&[op]).is_ok() && recording.get_untracked() {
+            set_recorded.update(|ops| ops.push(op));
         }
     };
+    let lsh = move || run(BitOp::Shl(shift_amount.get()));
+    let rsh = move || run(BitOp::Shr(shift_amount.get()));
+    let sar = move || run(BitOp::Sar(shift_amount.get()));
+    let not = move || run(BitOp::Not);
+    let clear = move || run(BitOp::ClearAll);
+    let set_all = move || run(BitOp::SetAll);
+    let lshr = move || run(BitOp::Rotl(shift_amount.get()));
+    let rshr = move || run(BitOp::Rotr(shift_amount.get()));
+
+    // Single-key shortcuts for the bit operations outside text fields
+    let shortcuts = window_event_listener(ev::keydown, move |ev| {
+        if in_field(&ev) || ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+            return;
+        }
+        match ev.key().to_lowercase().as_str() {
+            "l" => lsh(),
+            "r" => rsh(),
+            "a" => sar(),
+            "[" => lshr(),
+            "]" => rshr(),
+            "n" => not(),
+            "c" => clear(),
+            "s" => set_all(),
+            _ => return,
+        }
+        ev.prevent_default();
+    });
+    on_cleanup(move || shortcuts.remove());
 
-    let input_hex_le = move |ev: web_sys::Event| {
-        let mut val = event_target_value(&ev)
-            .replace("0x", "")
-            .replace(' ', "")
-            .to_uppercase();
-        val = val.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-        let expected_len = (bit_size.get() / 4) as usize;
-        val.truncate(expected_len);
-        set_hex_le_input.set(format!("0x{}", val));
-        if val.len() == expected_len {
-            if let Ok(bytes) = hex::decode(&val) {
-                // Convert little-endian bytes to value
-                let value = bytes
-                    .iter()
-                    .enumerate()
-                    .fold(0u64, |acc, (i, &b)| acc | (b as u64) << (i * 8));
-                update_value(value);
+    // Byte swaps within lanes of `lane`, and bit/byte reversal at the bit size
+    let swap = move |lane: Width| run(BitOp::SwapBytes(lane));
+    let rev_bytes = move |_| run(BitOp::ReverseBytes);
+    let rev_bits = move |_| run(BitOp::ReverseBits);
+
+    // Binary to packed BCD and back, like the x86 DAA adjustment after
+    // binary arithmetic; left unchanged if the result does not fit or a
+    // nibble is not a decimal digit
+    let to_bcd = move |_| {
+        let width = bit_size.get();
+        if let Some(bcd) = bit_array.get().to_bcd() {
+            if width.truncate(bcd.0) == bcd.0 {
+                update_value(bcd.0);
             }
         }
     };
-
-    let input_oct = move |ev: web_sys::Event| {
-        let mut val = event_target_value(&ev).replace("0o", "").replace(' ', "");
-        val = val.chars().filter(|c| ('0'..='7').contains(c)).collect();
-        let filtered = if val.is_empty() { "0" } else { &val };
-        set_oct_input.set(format!("0o{}", filtered));
-        if let Ok(num) = u64::from_str_radix(filtered, 8) {
-            update_value(num);
+    let from_bcd = move |_| {
+        if let Ok(value) = bit_array.get().from_bcd() {
+            update_value(value.0);
         }
     };
 
-    // Bit operations
-    let lsh = move |_| set_bit_array.update(|ba| *ba = BitArray((ba.0 << 1) & mask()));
-    let rsh = move |_| set_bit_array.update(|ba| *ba = BitArray((ba.0 >> 1) & mask()));
-    let not = move |_| set_bit_array.update(|ba| *ba = BitArray((!ba.0) & mask()));
-    let clear = move |_| set_bit_array.set(BitArray(0));
-    let set_all = move |_| set_bit_array.set(BitArray(mask()));
-    let lshr = move |_| {
-        set_bit_array.update(|ba| {
-            let size = bit_size.get();
-            *ba = BitArray((ba.0 << 1 | ba.0 >> (size - 1)) & mask())
-        })
-    };
-    let rshr = move |_| {
-        set_bit_array.update(|ba| {
-            let size = bit_size.get();
-            *ba = BitArray((ba.0 >> 1 | ba.0 << (size - 1)) & mask())
-        })
+    // Changes the bit size, sign- or zero-extending the value when it
+    // grows and warning when shrinking drops set bits
+    let resize = move |new_size: Width, sign: bool| {
+        let Some(mut snapshot) = state.get_untracked() else {
+            set_bit_size.set(new_size);
+            return;
+        };
+        let (old_bits, new_bits) = (snapshot.value.len(), new_size.bits() as usize);
+        let lost = if new_bits < old_bits {
+            snapshot
+                .value
+                .range(new_bits, old_bits - new_bits)
+                .count_ones()
+        } else {
+            0
+        };
+        set_resize_warning.set(match lost {
+            0 => String::new(),
+            1 => format!("Truncating to {} bits dropped 1 set bit", new_bits),
+            n => format!("Truncating to {} bits dropped {} set bits", new_bits, n),
+        });
+        if sign {
+            snapshot.value.sign_extend(new_bits);
+        } else {
+            snapshot.value.resize(new_bits);
+        }
+        snapshot.bit_size = new_size;
+        load.run(snapshot);
     };
 
     // Bit size selector
-    let update_bit_size = move |new_size: u64| {
-        set_bit_size.set(new_size);
+    let update_bit_size = move |new_size: Width| {
+        resize(new_size, sign_widen.get_untracked());
+    };
+
+    // Extends the low bits of the narrower width picked next to the
+    // buttons across the current bit size
+    let extend = move |sign: bool| {
+        let (from, width) = (extend_from.get_untracked(), bit_size.get_untracked());
+        if from.bits() >= width.bits() {
+            return;
+        }
+        set_resize_warning.set(String::new());
+        if width.word_count() == 1 {
+            let value = bit_array.get_untracked();
+            update_value(if sign {
+                value.sign_extend(from, width).0
+            } else {
+                value.zero_extend(from, width).0
+            });
+        } else {
+            set_bit_vec.update(|v| {
+                let len = v.len();
+                let mut low = v.range(0, from.bits() as usize);
+                if sign {
+                    low.sign_extend(len);
+                } else {
+                    low.resize(len);
+                }
+                *v = low;
+            });
+        }
+    };
+    let narrower = move || extend_from.get().bits() < bit_size.get().bits();
+    let truncate = move || {
+        let from = extend_from.get_untracked();
+        if from.bits() < bit_size.get_untracked().bits() {
+            resize(from, false);
+        }
     };
 
     view! {
         <div class="main-container">
             <div class="bit-size-selector">
                 <span class="bit-size-label">Bit Size:</span>
-                {[8, 16, 32, 64].into_iter().map(|size| view! {
+                {Width::ALL.into_iter().map(|size| view! {
                     <label>
                         <input
                             type="radio"
                             name="bit-size"
-                            value=size
-                            checked=move || bit_size.get() == size
+                            value=size.bits()
+                            prop:checked=move || bit_size.get() == size
                             on:change=move |_| update_bit_size(size)
                         />
                         {size.to_string()}
@@ -324,7 +960,7 @@ fn App() -> impl IntoView {
                     <label class="float-mode">
                 <input
                     type="checkbox"
-                    checked=move || matches!(input_mode.get(), InputMode::Float)
+                    prop:checked=move || matches!(input_mode.get(), InputMode::Float)
                     on:change=move |ev| {
                         let checked = event_target_checked(&ev);
                         set_input_mode.set(if checked {
@@ -336,38 +972,284 @@ fn App() -> impl IntoView {
                 />
                 "Float"
             </label>
+                <Show when=move || !sub_formats(bit_size.get()).is_empty()>
+                    <select
+                        class="sub-format"
+                        on:change=move |ev| {
+                            let label = event_target_value(&ev);
+                            if let Some((format, _)) = sub_formats(bit_size.get_untracked())
+                                .iter()
+                                .find(|(_, l)| *l == label)
+                            {
+                                set_sub_format.set(*format);
+                            }
+                        }
+                    >
+                        {move || {
+                            let width = bit_size.get();
+                            sub_formats(width)
+                                .iter()
+                                .map(|&(format, label)| view! {
+                                    <option
+                                        value=label
+                                        selected=move || float_format(width, sub_format.get()) == Some(format)
+                                    >
+                                        {label}
+                                    </option>
+                                })
+                                .collect_view()
+                        }}
+                    </select>
+                </Show>
+                <label class="arith-mode">
+                    "Overflow:"
+                    <select on:change=move |ev| {
+                        let selected = event_target_value(&ev);
+                        if let Some(mode) = ArithMode::ALL
+                            .into_iter()
+                            .find(|mode| mode.to_string() == selected)
+                        {
+                            set_arith_mode.set(mode);
+                        }
+                    }>
+                        {ArithMode::ALL.into_iter().map(|mode| view! {
+                            <option
+                                value=mode.to_string()
+                                selected=move || arith_mode.get() == mode
+                            >
+                                {mode.to_string()}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label class="arith-mode">
+                    "Widen:"
+                    <select
+                        title="How the value fills the new high bits when the bit size grows"
+                        on:change=move |ev| set_sign_widen.set(event_target_value(&ev) == "sext")
+                    >
+                        <option value="zext" selected=move || !sign_widen.get()>
+                            "Zero-extend"
+                        </option>
+                        <option value="sext" selected=sign_widen>
+                            "Sign-extend"
+                        </option>
+                    </select>
+                </label>
+                <label class="arith-mode">
+                    "Group:"
+                    <select on:change=move |ev| {
+                        let selected = event_target_value(&ev);
+                        if let Some((_, sep)) = DIGIT_SEPARATORS
+                            .into_iter()
+                            .find(|(label, _)| *label == selected)
+                        {
+                            set_digit_separator.set(sep);
+                        }
+                    }>
+                        {DIGIT_SEPARATORS.into_iter().map(|(label, sep)| view! {
+                            <option
+                                value=label
+                                selected=move || digit_separator.get() == sep
+                            >
+                                {label}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <PermalinkButton state=state sub_format=sub_format />
+                <ClipboardBar state=state load=load sub_format=sub_format />
+                <button
+                    class="bit-btn"
+                    class:active=show_settings
+                    title="Theme, panel layout and startup defaults"
+                    on:click=move |_| set_show_settings.update(|open| *open = !*open)
+                >
+                    "Settings"
+                </button>
             </div>
+            <div class="input-error">{resize_warning}</div>
+            <SettingsPanel
+                settings=settings
+                set_settings=set_settings
+                start=start
+                open=show_settings
+            />
 
-            <div class="decoder-generator-container">
-                <BitGrid bit_array=bit_array set_bit_array=set_bit_array bit_size=bit_size />
+            <div
+                class="grid-section"
+                class:section-hidden=move || hidden("grid")
+                style:order=move || order("grid")
+            >
+                <GridOptionsBar options=grid_options set_options=set_grid_options />
+                <div class="decoder-generator-container">
+                    <BitGrid
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                        highlight=highlight
+                        diff=diff
+                        register_map=register_map
+                        selection=selection
+                        set_selection=set_selection
+                        options=grid_options
+                        float_fields=float_fields
+                    />
+                    <ByteDump
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                        set_highlight=set_highlight
+                    />
+                </div>
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("registers")
+                style:order=move || order("registers")
+            >
+                <RegisterMapPanel
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                    set_bit_size=set_bit_size
+                    register_map=register_map
+                    set_register_map=set_register_map
+                    set_highlight=set_highlight
+                />
+                <HistoryPanel history=history restore=restore />
+                <WorkspacePanel
+                    workspace=workspace
+                    set_workspace=set_workspace
+                    state=state
+                    load=load
+                    set_diff=set_diff
+                />
             </div>
 
-            <div class="decoder-generator-container">
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("numbers")
+                style:order=move || order("numbers")
+            >
                 <div class="number-repr">
                     <label>
                         <span class="input-label">DEC</span>
-                        <input type="text" prop:value=dec_input on:input=input_dec/>
+                        <input
+                            type="text"
+                            prop:value=dec_input
+                            class:invalid=move || invalid(Field::Dec)
+                            on:input=input_dec
+                            on:focus=move |_| set_editing.set(Some(Field::Dec))
+                            on:blur=move |_| set_editing.set(None)
+                        />
+                    </label>
+                    <label>
+                        <span class="input-label">"DEC ±"</span>
+                        <input
+                            type="text"
+                            prop:value=dec_signed_input
+                            class:invalid=move || invalid(Field::DecSigned)
+                            on:input=input_dec_signed
+                            on:focus=move |_| set_editing.set(Some(Field::DecSigned))
+                            on:blur=move |_| set_editing.set(None)
+                        />
                     </label>
                     <label>
                         <span class="input-label">BIN</span>
-                        <input type="text" prop:value=bin_input on:input=input_bin/>
+                        <input
+                            type="text"
+                            prop:value=bin_input
+                            class:invalid=move || invalid(Field::Bin)
+                            on:input=input_bin
+                            on:focus=move |_| set_editing.set(Some(Field::Bin))
+                            on:blur=move |_| set_editing.set(None)
+                        />
+                        <input
+                            type="checkbox"
+                            title="Show Gray code"
+                            prop:checked=show_gray
+                            on:change=move |ev| set_show_gray.set(event_target_checked(&ev))
+                        />
                     </label>
+                    <Show when=move || show_gray.get()>
+                        <label>
+                            <span class="input-label">GRAY</span>
+                            <input
+                                type="text"
+                                placeholder="up to 64 bits"
+                                prop:value=gray_input
+                                prop:disabled=wide
+                                class:invalid=move || invalid(Field::Gray)
+                                on:input=input_gray
+                                on:focus=move |_| set_editing.set(Some(Field::Gray))
+                                on:blur=move |_| set_editing.set(None)
+                            />
+                        </label>
+                    </Show>
                     <label>
                         <span class="input-label">HEX</span>
-                        <input type="text" prop:value=hex_input on:input=input_hex/>
+                        <input
+                            type="text"
+                            prop:value=hex_input
+                            class:invalid=move || invalid(Field::Hex)
+                            on:input=input_hex
+                            on:focus=move |_| set_editing.set(Some(Field::Hex))
+                            on:blur=move |_| set_editing.set(None)
+                        />
                     </label>
                     <label>
                         <span class="input-label">HEX BE</span>
-                        <input type="text" prop:value=hex_be_input on:input=input_hex_be/>
+                        <input
+                            type="text"
+                            prop:value=hex_be_input
+                            class:invalid=move || invalid(Field::HexBe)
+                            on:input=input_hex_be
+                            on:focus=move |_| set_editing.set(Some(Field::HexBe))
+                            on:blur=move |_| set_editing.set(None)
+                        />
                     </label>
                     <label>
                         <span class="input-label">HEX LE</span>
-                        <input type="text" prop:value=hex_le_input on:input=input_hex_le/>
+                        <input
+                            type="text"
+                            prop:value=hex_le_input
+                            class:invalid=move || invalid(Field::HexLe)
+                            on:input=input_hex_le
+                            on:focus=move |_| set_editing.set(Some(Field::HexLe))
+                            on:blur=move |_| set_editing.set(None)
+                        />
                     </label>
                     <label>
                         <span class="input-label">OCT</span>
-                        <input type="text" prop:value=oct_input on:input=input_oct/>
+                        <input
+                            type="text"
+                            prop:value=oct_input
+                            class:invalid=move || invalid(Field::Oct)
+                            on:input=input_oct
+                            on:focus=move |_| set_editing.set(Some(Field::Oct))
+                            on:blur=move |_| set_editing.set(None)
+                        />
                     </label>
+                    {move || {
+                        input_error
+                            .get()
+                            .map(|(_, message)| view! { <div class="input-error">{message}</div> })
+                    }}
+                    <BcdRow
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                    />
+                    <BaseEncodingRows
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                    />
                     <label>
                         <span class="input-label">ASCII</span>
                         <input type="text" prop:value=ascii_input readonly/>
@@ -376,6 +1258,48 @@ fn App() -> impl IntoView {
                         <span class="input-label">UTF-8</span>
                         <input type="text" prop:value=utf8_input readonly/>
                     </label>
+                    <UnicodeRow
+                        encoding=Encoding::Utf16Le
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                    />
+                    <UnicodeRow
+                        encoding=Encoding::Utf16Be
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                    />
+                    <UnicodeRow
+                        encoding=Encoding::Utf32Be
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                    />
+                    <TimestampRow
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                    />
+                    <AddressRow
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                    />
+                    <ExpressionRow
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                        arith_mode=arith_mode
+                    />
                 </div>
 
                 <div class="special-generator">
@@ -383,23 +1307,316 @@ fn App() -> impl IntoView {
                         <span class="input-label">Bit operations</span>
                     </label>
                     <div class="bit-operations">
-                        <button class="bit-btn" on:click=lsh>"Lsh"</button>
-                        <button class="bit-btn" on:click=rsh>"Rsh"</button>
-                        <button class="bit-btn" on:click=lshr>"Lshr"</button>
-                        <button class="bit-btn" on:click=rshr>"Rshr"</button>
-                        <button class="bit-btn" on:click=not>"Not"</button>
-                        <button class="bit-btn" on:click=clear>"Clr"</button>
-                        <button class="bit-btn" on:click=set_all>"Set"</button>
+                        <input
+                            type="number"
+                            class="shift-amount"
+                            min="1"
+                            max="63"
+                            title="Shift/rotate amount"
+                            prop:value=move || shift_amount.get().to_string()
+                            on:change=move |ev| {
+                                let n = event_target_value(&ev).trim().parse::<u32>().unwrap_or(1);
+                                set_shift_amount.set(n.clamp(1, 63));
+                            }
+                        />
+                        <button class="bit-btn" title="Shift left (L)" on:click=move |_| lsh()>
+                            "Lsh"
+                        </button>
+                        <button class="bit-btn" title="Shift right (R)" on:click=move |_| rsh()>
+                            "Rsh"
+                        </button>
+                        <button
+                            class="bit-btn"
+                            title="Arithmetic shift right (A)"
+                            on:click=move |_| sar()
+                        >
+                            "Sar"
+                        </button>
+                        <button class="bit-btn" title="Rotate left ([)" on:click=move |_| lshr()>
+                            "Lshr"
+                        </button>
+                        <button class="bit-btn" title="Rotate right (])" on:click=move |_| rshr()>
+                            "Rshr"
+                        </button>
+                        <button class="bit-btn" title="Invert all bits (N)" on:click=move |_| not()>
+                            "Not"
+                        </button>
+                        <button class="bit-btn" title="Clear all bits (C)" on:click=move |_| clear()>
+                            "Clr"
+                        </button>
+                        <button class="bit-btn" title="Set all bits (S)" on:click=move |_| set_all()>
+                            "Set"
+                        </button>
+                        {[Width::W16, Width::W32, Width::W64]
+                            .into_iter()
+                            .map(|lane| view! {
+                                <button
+                                    class="bit-btn"
+                                    prop:disabled=move || bit_size.get().bits() < lane.bits()
+                                    on:click=move |_| swap(lane)
+                                >
+                                    {format!("Swap{}", lane.bits())}
+                                </button>
+                            })
+                            .collect_view()}
+                        <button class="bit-btn" on:click=rev_bits>"RevBits"</button>
+                        <button class="bit-btn" on:click=rev_bytes>
+                            "RevBytes"
+                        </button>
+                        <button class="bit-btn" prop:disabled=wide on:click=to_bcd>
+                            "ToBCD"
+                        </button>
+                        <button class="bit-btn" prop:disabled=wide on:click=from_bcd>
+                            "FromBCD"
+                        </button>
+                        <select
+                            class="extend-from"
+                            title="Narrower width for Sext, Zext and Trunc"
+                            on:change=move |ev| {
+                                let bits = event_target_value(&ev).parse().unwrap_or(8);
+                                set_extend_from.set(Width::from_bits(bits).unwrap_or(Width::W8));
+                            }
+                        >
+                            {Width::ALL[..Width::ALL.len() - 1].iter().map(|&width| view! {
+                                <option
+                                    value=width.bits()
+                                    selected=move || extend_from.get() == width
+                                >
+                                    {width.to_string()}
+                                </option>
+                            }).collect_view()}
+                        </select>
+                        <button
+                            class="bit-btn"
+                            title="Sign-extend the low bits of the chosen width across the bit size"
+                            prop:disabled=move || !narrower()
+                            on:click=move |_| extend(true)
+                        >
+                            "Sext"
+                        </button>
+                        <button
+                            class="bit-btn"
+                            title="Zero-extend the low bits of the chosen width across the bit size"
+                            prop:disabled=move || !narrower()
+                            on:click=move |_| extend(false)
+                        >
+                            "Zext"
+                        </button>
+                        <button
+                            class="bit-btn"
+                            title="Switch to the chosen width, dropping the high bits"
+                            prop:disabled=move || !narrower()
+                            on:click=move |_| truncate()
+                        >
+                            "Trunc"
+                        </button>
                     </div>
-                     <SpecialValueGenerator set_bit_array=set_bit_array bit_size=bit_size />
+                    <MacroRecorder
+                        recording=recording
+                        set_recording=set_recording
+                        ops=recorded
+                        set_ops=set_recorded
+                        replay=replay
+                    />
+                    <RangeOperations
+                        selection=selection
+                        set_selection=set_selection
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_vec=bit_vec
+                        set_bit_vec=set_bit_vec
+                        bit_size=bit_size
+                        shift_amount=shift_amount
+                        grid_options=grid_options
+                    />
+                    <ArithmeticOperations
+                        bit_array=bit_array
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                        arith_mode=arith_mode
+                    />
+                     <SpecialValueGenerator
+                        set_bit_array=set_bit_array
+                        bit_size=bit_size
+                        sub_format=sub_format
+                    />
                 </div>
             </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("calculator")
+                style:order=move || order("calculator")
+            >
+                <TwoOperandCalculator
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                    arith_mode=arith_mode
+                    grid_options=grid_options
+                    float_fields=float_fields
+                />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("fixed-point")
+                style:order=move || order("fixed-point")
+            >
+                <FixedPointPanel bit_array=bit_array set_bit_array=set_bit_array bit_size=bit_size />
+                <ChecksumPanel bit_array=bit_array bit_vec=bit_vec bit_size=bit_size />
+            </div>
 
-            <div class="decoder-generator-container">
-                <IEEE754Display bit_array=bit_array bit_size=bit_size />
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("ieee754")
+                style:order=move || order("ieee754")
+            >
+                <IEEE754Display
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_vec=bit_vec
+                    bit_size=bit_size
+                    sub_format=sub_format
+                />
+                <IntervalCalculator
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("rounding")
+                style:order=move || order("rounding")
+            >
+                <StochasticRounding set_bit_array=set_bit_array bit_size=bit_size />
+                <FmaComparison set_bit_array=set_bit_array bit_size=bit_size />
             </div>
-            <div class="input-operations-container">
-                <DistributionPlot bit_array=bit_array.into() bit_size=bit_size/>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("accumulation")
+                style:order=move || order("accumulation")
+            >
+                <AccumulationExplorer set_bit_array=set_bit_array bit_size=bit_size />
+                <RoundTripTester set_bit_array=set_bit_array bit_size=bit_size />
+            </div>
+            <div
+                class="input-operations-container"
+                class:section-hidden=move || hidden("precision")
+                style:order=move || order("precision")
+            >
+                <PrecisionExplorer />
+            </div>
+            <div
+                class="input-operations-container"
+                class:section-hidden=move || hidden("plot")
+                style:order=move || order("plot")
+            >
+                <DistributionPlot
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_vec=bit_vec
+                    set_bit_vec=set_bit_vec
+                    bit_size=bit_size
+                    sub_format=sub_format
+                    input_mode=input_mode
+                />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("inspect")
+                style:order=move || order("inspect")
+            >
+                <MutationFuzzer
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                    set_highlight=set_highlight
+                />
+                <FieldEditor
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                    grid_options=grid_options
+                />
+                <BitStats bit_array=bit_array bit_vec=bit_vec bit_size=bit_size />
+                <WatchPanel
+                    bit_array=bit_array
+                    bit_size=bit_size
+                    arith_mode=arith_mode
+                    watches=watches
+                    set_watches=set_watches
+                />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("encodings")
+                style:order=move || order("encodings")
+            >
+                <EccPanel
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_size=bit_size
+                    set_highlight=set_highlight
+                />
+                <ColorPanel bit_array=bit_array set_bit_array=set_bit_array bit_size=bit_size />
+                <VarintPanel bit_array=bit_array set_bit_array=set_bit_array bit_size=bit_size />
+                <FileModePanel bit_array=bit_array set_bit_array=set_bit_array />
+                <PermutationPanel bit_array=bit_array set_bit_array=set_bit_array bit_size=bit_size />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("decoders")
+                style:order=move || order("decoders")
+            >
+                <TemplateDecoder bit_array=bit_array bit_vec=bit_vec bit_size=bit_size />
+                <StructOverlay
+                    bit_array=bit_array
+                    bit_vec=bit_vec
+                    bit_size=bit_size
+                    file_path=file_path
+                    load=load
+                />
+                <DnsDecoder
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    set_highlight=set_highlight
+                />
+                <EthernetDecoder
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    set_highlight=set_highlight
+                />
+                <DisasmPanel bit_array=bit_array bit_size=bit_size set_highlight=set_highlight />
+            </div>
+            <div
+                class="decoder-generator-container"
+                class:section-hidden=move || hidden("tools")
+                style:order=move || order("tools")
+            >
+                <BigIntPanel
+                    bit_array=bit_array
+                    set_bit_array=set_bit_array
+                    bit_vec=bit_vec
+                    set_bit_vec=set_bit_vec
+                    bit_size=bit_size
+                />
+                <GridExport bit_array=bit_array bit_vec=bit_vec bit_size=bit_size />
+                <CodeExport state=state />
+                <SessionPanel session=session apply=apply_session />
+                <FileBrowser
+                    set_bit_array=set_bit_array
+                    set_bit_vec=set_bit_vec
+                    bit_size=bit_size
+                    path=file_path
+                    set_path=set_file_path
+                />
+                <PatternSearch
+                    set_bit_array=set_bit_array
+                    set_bit_vec=set_bit_vec
+                    bit_size=bit_size
+                    set_selection=set_selection
+                    file_path=file_path
+                />
             </div>
 
         </div>
@@ -0,0 +1,260 @@
+//! Bit Mutation (Fuzz) Module
+
+use bit_operations::ieee754::IEEEDecoder;
+use bit_operations::parse::{parse_f64, parse_u64};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use std::time::Duration;
+
+/// Number of hits kept in the log
+const LOG_LEN: usize = 8;
+
+/// Comparison applied to each mutated value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchOp {
+    Off,
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    And,
+}
+
+impl WatchOp {
+    const ALL: [WatchOp; 6] = [
+        WatchOp::Off,
+        WatchOp::Eq,
+        WatchOp::Ne,
+        WatchOp::Lt,
+        WatchOp::Gt,
+        WatchOp::And,
+    ];
+
+    fn symbol(self) -> &'static str {
+        match self {
+            WatchOp::Off => "off",
+            WatchOp::Eq => "==",
+            WatchOp::Ne => "!=",
+            WatchOp::Lt => "<",
+            WatchOp::Gt => ">",
+            WatchOp::And => "&",
+        }
+    }
+
+    /// Returns `true` when `value op operand` holds; `&` tests for any
+    /// common set bit.
+    fn matches(self, value: u64, operand: u64) -> bool {
+        match self {
+            WatchOp::Off => false,
+            WatchOp::Eq => value == operand,
+            WatchOp::Ne => value != operand,
+            WatchOp::Lt => value < operand,
+            WatchOp::Gt => value > operand,
+            WatchOp::And => value & operand != 0,
+        }
+    }
+}
+
+/// Returns the float class of `value` if it is NaN, infinite or subnormal in
+/// the float format matching `width`.
+fn special_class(value: u64, width: Width) -> Option<String> {
+    if !matches!(width, Width::W16 | Width::W32 | Width::W64) {
+        return None;
+    }
+    let decoder = IEEEDecoder::new(value, width.bits().into());
+    matches!(
+        decoder.special.as_str(),
+        "NaN" | "+Inf" | "-Inf" | "Denormalized"
+    )
+    .then_some(decoder.special)
+}
+
+/// Mutation Mode Component
+///
+/// Flips random bits of the current value on a timer, so every decoder on
+/// the page follows the mutated value, and logs ticks that produce a special
+/// float class or satisfy a watch condition. Flipped bits are highlighted in
+/// the grid.
+#[component]
+pub fn MutationFuzzer(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (rate_input, set_rate_input) = signal("0.02".to_string());
+    let (low_input, set_low_input) = signal("0".to_string());
+    let (high_input, set_high_input) = signal("63".to_string());
+    let (period_input, set_period_input) = signal("250".to_string());
+    let (seed_input, set_seed_input) = signal("1".to_string());
+    let (watch_op, set_watch_op) = signal(WatchOp::Off);
+    let (watch_input, set_watch_input) = signal("0".to_string());
+    let (stop_on_hit, set_stop_on_hit) = signal(true);
+    let (running, set_running) = signal(false);
+    let (ticks, set_ticks) = signal(0u64);
+    let (hit, set_hit) = signal(false);
+    let (log, set_log) = signal(Vec::<String>::new());
+    let (error, set_error) = signal(String::new());
+    let timer = StoredValue::new(None::<IntervalHandle>);
+    let rng = StoredValue::new(SmallRng::seed_from_u64(1));
+
+    let halt = move || {
+        if let Some(handle) = timer.get_value() {
+            handle.clear();
+        }
+        timer.set_value(None);
+        set_running.set(false);
+    };
+    let stop = move || {
+        halt();
+        set_highlight.set(0);
+    };
+    on_cleanup(stop);
+
+    let start = move || {
+        let width = bit_size.get();
+        let settings = (|| {
+            let rate = parse_f64(&rate_input.get())
+                .ok()
+                .filter(|r| (0.0..=1.0).contains(r))
+                .ok_or("rate must be between 0 and 1")?;
+            let low = parse_u64(&low_input.get(), 10).map_err(|_| "invalid low bit")?;
+            let high = parse_u64(&high_input.get(), 10).map_err(|_| "invalid high bit")?;
+            // Wider sizes mutate the low word shared with the other panels
+            let high = high.min(u64::from(width.bits().min(64)) - 1);
+            if low > high {
+                return Err("low bit is above high bit");
+            }
+            let period = parse_u64(&period_input.get(), 10)
+                .ok()
+                .filter(|&ms| ms >= 10)
+                .ok_or("period must be at least 10 ms")?;
+            let seed = parse_u64(&seed_input.get(), 10).map_err(|_| "invalid seed")?;
+            let operand = parse_u64(&watch_input.get(), 16).map_err(|_| "invalid watch value")?;
+            let mask = (u64::MAX >> (63 - high)) & (u64::MAX << low);
+            Ok((rate, mask, period, seed, operand))
+        })();
+        let (rate, mask, period, seed, operand) = match settings {
+            Ok(settings) => settings,
+            Err(err) => return set_error.set(err.to_string()),
+        };
+        set_error.set(String::new());
+        set_ticks.set(0);
+        set_log.set(Vec::new());
+        rng.set_value(SmallRng::seed_from_u64(seed));
+
+        let op = watch_op.get();
+        let tick = move || {
+            let mut value = bit_array.get_untracked();
+            let mut flipped = 0;
+            rng.update_value(|rng| flipped = value.mutate(mask, rate, rng));
+            set_bit_array.set(value);
+            set_highlight.set(flipped);
+            set_ticks.update(|t| *t += 1);
+
+            let mut reasons = Vec::new();
+            if let Some(class) = special_class(value.0, width) {
+                reasons.push(class);
+            }
+            if op.matches(value.0, operand) {
+                reasons.push(format!("{} 0x{:X}", op.symbol(), operand));
+            }
+            set_hit.set(!reasons.is_empty());
+            if !reasons.is_empty() {
+                let entry = format!(
+                    "#{} 0x{:0digits$X} {}",
+                    ticks.get_untracked(),
+                    value.0,
+                    reasons.join(", "),
+                    digits = width.hex_digits()
+                );
+                set_log.update(|log| {
+                    log.insert(0, entry);
+                    log.truncate(LOG_LEN);
+                });
+                if stop_on_hit.get_untracked() {
+                    halt();
+                }
+            }
+        };
+
+        stop();
+        match set_interval_with_handle(tick, Duration::from_millis(period)) {
+            Ok(handle) => {
+                timer.set_value(Some(handle));
+                set_running.set(true);
+            }
+            Err(_) => set_error.set("could not start timer".to_string()),
+        }
+    };
+
+    let field = |label: &'static str, value: ReadSignal<String>, set: WriteSignal<String>| {
+        view! {
+            <label>
+                {label}
+                <input
+                    type="text"
+                    prop:value=value
+                    on:input=move |ev| set.set(event_target_value(&ev))
+                />
+            </label>
+        }
+    };
+
+    view! {
+        <div class="mutation-panel" class:hit=hit>
+            <label>
+                <span class="input-label">Mutation</span>
+                <button
+                    class="bit-btn"
+                    on:click=move |_| if running.get() { stop() } else { start() }
+                >
+                    {move || if running.get() { "Stop" } else { "Start" }}
+                </button>
+                <span class="mutation-ticks">{move || format!("{} ticks", ticks.get())}</span>
+            </label>
+            <div class="mutation-options">
+                {field("Rate ", rate_input, set_rate_input)}
+                {field("Bits ", low_input, set_low_input)}
+                {field("to ", high_input, set_high_input)}
+                {field("ms ", period_input, set_period_input)}
+                {field("Seed ", seed_input, set_seed_input)}
+            </div>
+            <div class="mutation-options">
+                <label>
+                    "Watch value "
+                    <select on:change=move |ev| {
+                        let symbol = event_target_value(&ev);
+                        if let Some(op) = WatchOp::ALL.into_iter().find(|op| op.symbol() == symbol) {
+                            set_watch_op.set(op);
+                        }
+                    }>
+                        {WatchOp::ALL
+                            .into_iter()
+                            .map(|op| view! {
+                                <option value=op.symbol() selected=move || watch_op.get() == op>
+                                    {op.symbol()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                {field("0x", watch_input, set_watch_input)}
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=stop_on_hit
+                        on:change=move |ev| set_stop_on_hit.set(event_target_checked(&ev))
+                    />
+                    "Stop on hit"
+                </label>
+            </div>
+            <div class="mutation-error">{error}</div>
+            <div class="mutation-log">
+                {move || log.get().into_iter().map(|entry| view! { <div>{entry}</div> }).collect_view()}
+            </div>
+        </div>
+    }
+}
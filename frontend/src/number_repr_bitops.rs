@@ -2,7 +2,7 @@
 
 use leptos::*;
 use leptos::prelude::*;
-use bit_operations::BitArray;
+use bit_operations::{BitArray, Width};
 use hex;
 use web_sys::MouseEvent;
 
@@ -11,14 +11,9 @@ use web_sys::MouseEvent;
 pub fn NumberReprAndBitOps(
     bit_array: ReadSignal<BitArray>,
     set_bit_array: WriteSignal<BitArray>,
-    bit_size: ReadSignal<u64>,
+    bit_size: ReadSignal<Width>,
 ) -> impl IntoView {
-    let mask = move || match bit_size.get() {
-        8 => 0xFFu64,
-        16 => 0xFFFFu64,
-        32 => 0xFFFFFFFFu64,
-        _ => u64::MAX,
-    };
+    let mask = move || bit_size.get().mask();
 
     let current_value = move || bit_array.get().0 & mask();
 
@@ -70,7 +65,7 @@ pub fn NumberReprAndBitOps(
             .filter(|c| c.is_ascii_hexdigit())
             .collect::<String>();
 
-        let byte_count = (bit_size.get() / 8) as usize;
+        let byte_count = bit_size.get().bytes();
         let padded_val = format!("{:0>width$}", val, width = byte_count * 2);
 
         if let Ok(bytes) = hex::decode(&padded_val) {
@@ -87,7 +82,7 @@ pub fn NumberReprAndBitOps(
             .filter(|c| c.is_ascii_hexdigit())
             .collect::<String>();
 
-        let byte_count = (bit_size.get() / 8) as usize;
+        let byte_count = bit_size.get().bytes();
         let padded_val = format!("{:0>width$}", val, width = byte_count * 2);
 
         if let Ok(bytes) = hex::decode(&padded_val) {
@@ -119,7 +114,7 @@ pub fn NumberReprAndBitOps(
     ];
 
     // Character representations
-    let byte_count = move || (bit_size.get() / 8) as usize;
+    let byte_count = move || bit_size.get().bytes();
     let ascii_str = move || {
         let bytes = current_value().to_le_bytes();
         String::from_utf8_lossy(&bytes[..byte_count()])
@@ -145,7 +140,7 @@ pub fn NumberReprAndBitOps(
                 </label>
                 <label><span class="input-label">BIN</span>
                     <input type="text"
-                        value=move || format!("0b{:0width$b}", current_value(), width=bit_size.get() as usize)
+                        value=move || format!("0b{:0width$b}", current_value(), width=bit_size.get().bits() as usize)
                         on:input=input_bin
                     />
                 </label>
@@ -157,13 +152,13 @@ pub fn NumberReprAndBitOps(
                 </label>
                 <label><span class="input-label">HEX BE</span>
                     <input type="text"
-                        value=move || format!("0x{:0width$X}", current_value(), width=(bit_size.get()/4) as usize)
+                        value=move || format!("0x{:0width$X}", current_value(), width=bit_size.get().hex_digits())
                         on:input=input_hex_be
                     />
                 </label>
                 <label><span class="input-label">HEX LE</span>
                     <input type="text"
-                        value=move || format!("0x{:0width$X}", current_value(), width=(bit_size.get()/4) as usize)
+                        value=move || format!("0x{:0width$X}", current_value(), width=bit_size.get().hex_digits())
                         on:input=input_hex_le
                     />
                 </label>
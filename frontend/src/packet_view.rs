@@ -0,0 +1,269 @@
+//! Packet Header Decoder Module
+
+use crate::template_view::parse_hex_blob;
+use bit_operations::net::{
+    dns_opcode_name, dns_rcode_name, ethertype_name, BitField, DnsHeader, EthernetHeader,
+    MacAddress, VlanTag, DNS_FLAG_FIELDS, VLAN_TCI_FIELDS,
+};
+use bit_operations::BitArray;
+use leptos::prelude::*;
+
+/// DNS Header Decoder Component
+///
+/// Decodes a 12-byte DNS header from pasted packet bytes, or treats the low
+/// 16 bits of the current value as the flags word when no bytes are given.
+/// Hovering a flag highlights its bits in the grid.
+#[component]
+pub fn DnsDecoder(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (bytes_input, set_bytes_input) = signal(String::new());
+
+    let header = move || {
+        let input = bytes_input.get();
+        if input.trim().is_empty() {
+            let flags = bit_array.get().0 as u16;
+            Ok(DnsHeader {
+                id: 0,
+                flags,
+                qdcount: 0,
+                ancount: 0,
+                nscount: 0,
+                arcount: 0,
+            })
+        } else {
+            let bytes = parse_hex_blob(&input).ok_or("Invalid hex bytes")?;
+            DnsHeader::parse(&bytes).ok_or("A DNS header needs 12 bytes")
+        }
+    };
+    let from_packet = move || !bytes_input.get().trim().is_empty();
+
+    let flag_rows = move |h: DnsHeader| {
+        DNS_FLAG_FIELDS
+            .iter()
+            .map(|field| {
+                let value = field.extract(h.flags.into());
+                let meaning = match field.name {
+                    "QR" if value == 1 => "response".to_string(),
+                    "QR" => "query".to_string(),
+                    "Opcode" => dns_opcode_name(value as u8).to_string(),
+                    "RCODE" => dns_rcode_name(value as u8).to_string(),
+                    _ => String::new(),
+                };
+                let mask = field.mask();
+                let bits = if field.width == 1 {
+                    format!("bit {}", field.shift)
+                } else {
+                    format!("bits {}-{}", field.shift + field.width - 1, field.shift)
+                };
+                view! {
+                    <tr
+                        class="dns-flag"
+                        class:set=value != 0
+                        on:mouseenter=move |_| set_highlight.set(mask)
+                        on:mouseleave=move |_| set_highlight.set(0)
+                    >
+                        <td>{field.name}</td>
+                        <td>{bits}</td>
+                        <td>{value.to_string()}</td>
+                        <td>{meaning}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="dns-decoder">
+            <label>
+                <span class="input-label">DNS header</span>
+                <input
+                    type="text"
+                    placeholder="packet hex bytes, empty = current value as flags"
+                    prop:value=bytes_input
+                    on:input=move |ev| set_bytes_input.set(event_target_value(&ev))
+                />
+            </label>
+            {move || match header() {
+                Err(message) => view! { <div class="dns-error">{message}</div> }.into_any(),
+                Ok(h) => view! {
+                    <Show when=from_packet>
+                        <div class="dns-row">
+                            {format!(
+                                "ID 0x{:04X}  QD {}  AN {}  NS {}  AR {}",
+                                h.id, h.qdcount, h.ancount, h.nscount, h.arcount
+                            )}
+                            <button
+                                class="bit-btn"
+                                on:click=move |_| set_bit_array.set(BitArray(h.flags.into()))
+                            >
+                                "Flags → value"
+                            </button>
+                        </div>
+                    </Show>
+                    <div class="dns-row">{format!("Flags 0x{:04X}", h.flags)}</div>
+                    <table class="dns-flags">{flag_rows(h)}</table>
+                }.into_any(),
+            }}
+        </div>
+    }
+}
+
+/// Describes an EtherType as `0xXXXX (name)`.
+fn describe_ethertype(ethertype: u16) -> String {
+    match ethertype_name(ethertype) {
+        Some(name) => format!("0x{:04X} ({})", ethertype, name),
+        None => format!("0x{:04X}", ethertype),
+    }
+}
+
+/// Describes the address kind bits of a MAC address.
+fn describe_mac(mac: MacAddress) -> String {
+    let scope = if mac.is_broadcast() {
+        "broadcast"
+    } else if mac.is_multicast() {
+        "multicast"
+    } else {
+        "unicast"
+    };
+    let admin = if mac.is_local() { "local" } else { "global" };
+    format!("{} ({}, {})", mac, scope, admin)
+}
+
+/// Renders one row per bit field of `word`; hovering a row highlights the
+/// field's bits in the grid.
+fn field_rows(
+    fields: &'static [BitField],
+    word: u64,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    fields
+        .iter()
+        .map(move |field| {
+            let mask = field.mask();
+            view! {
+                <tr
+                    class="dns-flag"
+                    on:mouseenter=move |_| set_highlight.set(mask)
+                    on:mouseleave=move |_| set_highlight.set(0)
+                >
+                    <td>{field.name}</td>
+                    <td>{field.extract(word).to_string()}</td>
+                </tr>
+            }
+        })
+        .collect_view()
+}
+
+/// Renders a VLAN tag breakdown.
+fn render_vlan(
+    label: &'static str,
+    tag: VlanTag,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    view! {
+        <div class="dns-row">
+            {format!("{}: TPID {}  TCI 0x{:04X}", label, describe_ethertype(tag.tpid), tag.tci)}
+        </div>
+        <table class="dns-flags">
+            {field_rows(&VLAN_TCI_FIELDS, tag.tci.into(), set_highlight)}
+        </table>
+    }
+}
+
+/// Ethernet Header Decoder Component
+///
+/// Decodes MAC addresses, VLAN tags and the EtherType from pasted frame
+/// bytes. When no bytes are given, the low 48 bits of the current value are
+/// shown as a MAC address and the low 16 bits as a VLAN TCI word.
+#[component]
+pub fn EthernetDecoder(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (bytes_input, set_bytes_input) = signal(String::new());
+
+    let decoded = move || {
+        let input = bytes_input.get();
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+        let bytes = parse_hex_blob(&input).ok_or("Invalid hex bytes")?;
+        EthernetHeader::parse(&bytes)
+            .map(Some)
+            .ok_or("An Ethernet header needs at least 14 bytes")
+    };
+
+    let address_bit = move |label: &'static str, bit: u32| {
+        view! {
+            <tr
+                class="dns-flag"
+                on:mouseenter=move |_| set_highlight.set(1 << bit)
+                on:mouseleave=move |_| set_highlight.set(0)
+            >
+                <td>{label}</td>
+                <td>{move || ((bit_array.get().0 >> bit) & 1).to_string()}</td>
+            </tr>
+        }
+    };
+
+    let mac_button = move |label: &'static str, mac: MacAddress| {
+        view! {
+            <div class="dns-row">
+                <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(mac.to_u64()))>
+                    {label}
+                </button>
+                {describe_mac(mac)}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="dns-decoder">
+            <label>
+                <span class="input-label">Ethernet header</span>
+                <input
+                    type="text"
+                    placeholder="frame hex bytes, empty = current value as MAC"
+                    prop:value=bytes_input
+                    on:input=move |ev| set_bytes_input.set(event_target_value(&ev))
+                />
+            </label>
+            {move || match decoded() {
+                Err(message) => view! { <div class="dns-error">{message}</div> }.into_any(),
+                Ok(None) => {
+                    let value = bit_array.get().0;
+                    let tag = VlanTag { tpid: 0x8100, tci: value as u16 };
+                    view! {
+                        <div class="dns-row">
+                            {format!("MAC {}", describe_mac(MacAddress::from_u64(value)))}
+                        </div>
+                        <table class="dns-flags">
+                            {address_bit("I/G", MacAddress::GROUP_BIT)}
+                            {address_bit("U/L", MacAddress::LOCAL_BIT)}
+                        </table>
+                        {render_vlan("As VLAN tag", tag, set_highlight)}
+                    }
+                    .into_any()
+                }
+                Ok(Some(header)) => view! {
+                    {mac_button("Dst", header.destination)}
+                    {mac_button("Src", header.source)}
+                    {header.vlan.map(|tag| render_vlan("VLAN", tag, set_highlight))}
+                    {header.inner_vlan.map(|tag| render_vlan("Inner VLAN", tag, set_highlight))}
+                    <div class="dns-row">
+                        {format!(
+                            "EtherType {}  header {} bytes",
+                            describe_ethertype(header.ethertype),
+                            header.header_len()
+                        )}
+                    </div>
+                }
+                .into_any(),
+            }}
+        </div>
+    }
+}
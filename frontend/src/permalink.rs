@@ -0,0 +1,155 @@
+//! Permalink Module
+
+use crate::clipboard::copy_text;
+use crate::history::Snapshot;
+use crate::ieee754::{float_format, sub_formats, IEEEFormat, SUB_FORMATS};
+use crate::InputMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::Width;
+use leptos::prelude::*;
+
+/// A state encoded in the URL hash, with the float format chosen at sizes
+/// that have several
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    pub snapshot: Snapshot,
+    pub sub_format: Option<IEEEFormat>,
+}
+
+impl Link {
+    /// Formats the link as a URL hash, e.g. `#v=0xDEADBEEF&w=32&f=float`
+    pub fn to_hash(&self) -> String {
+        let snapshot = &self.snapshot;
+        let mode = match snapshot.input_mode {
+            InputMode::Integer => "int",
+            InputMode::Float => "float",
+        };
+        let mut hash = format!(
+            "#v={}&w={}&f={}",
+            snapshot.hex(),
+            snapshot.bit_size.bits(),
+            mode
+        );
+        let label = sub_formats(snapshot.bit_size)
+            .iter()
+            .find(|(f, _)| self.sub_format == Some(*f))
+            .map(|(_, label)| label);
+        if let Some(label) = label {
+            hash.push_str("&fmt=");
+            hash.push_str(label);
+        }
+        hash
+    }
+
+    /// Parses a URL hash written by [`Link::to_hash`]; missing keys default
+    /// to a zero value, 64 bits and integer mode.
+    pub fn parse(hash: &str) -> Option<Self> {
+        let mut digits = "0";
+        let mut bit_size = Width::W64;
+        let mut input_mode = InputMode::Integer;
+        let mut sub_format = None;
+        let mut any = false;
+        for pair in hash.trim_start_matches('#').split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            any = true;
+            match key {
+                "v" => {
+                    digits = value
+                        .strip_prefix("0x")
+                        .or_else(|| value.strip_prefix("0X"))
+                        .unwrap_or(value)
+                }
+                "w" => bit_size = Width::from_bits(value.parse().ok()?)?,
+                "f" => {
+                    input_mode = match value {
+                        "float" => InputMode::Float,
+                        _ => InputMode::Integer,
+                    }
+                }
+                "fmt" => {
+                    sub_format = SUB_FORMATS
+                        .iter()
+                        .flat_map(|(_, formats)| formats.iter())
+                        .find(|(_, label)| label.eq_ignore_ascii_case(value))
+                        .map(|(f, _)| *f)
+                }
+                _ => {}
+            }
+        }
+        if !any {
+            return None;
+        }
+        let value = BitVec::from_str_radix(digits, 16, bit_size.bits() as usize)?;
+        Some(Self {
+            snapshot: Snapshot {
+                value,
+                bit_size,
+                input_mode,
+            },
+            sub_format,
+        })
+    }
+}
+
+/// Reads the link in the page's URL hash, if any
+pub fn read_location() -> Option<Link> {
+    Link::parse(&window().location().hash().ok()?)
+}
+
+/// Replaces the page's URL hash without adding a browser history entry
+fn write_location(link: &Link) {
+    let hash = link.to_hash();
+    let location = window().location();
+    if location.hash().ok().as_deref() != Some(hash.as_str()) {
+        let _ = location.replace(&hash);
+    }
+}
+
+/// Permalink Component
+///
+/// Keeps the URL hash in step with the current value, bit size, input mode
+/// and float format, so reloading or opening a copied link restores them.
+/// Copy link puts the full URL on the clipboard.
+#[component]
+pub fn PermalinkButton(
+    state: Memo<Option<Snapshot>>,
+    sub_format: ReadSignal<IEEEFormat>,
+) -> impl IntoView {
+    let (copied, set_copied) = signal(false);
+
+    let link = Memo::new(move |_| {
+        let snapshot = state.get()?;
+        let sub_format = float_format(snapshot.bit_size, sub_format.get());
+        Some(Link {
+            snapshot,
+            sub_format,
+        })
+    });
+
+    // Effect to mirror the state in the URL hash
+    Effect::new(move |_| {
+        if let Some(link) = link.get() {
+            write_location(&link);
+            set_copied.set(false);
+        }
+    });
+
+    let copy = move |_| {
+        let Ok(url) = window().location().href() else {
+            return;
+        };
+        if copy_text(&url) {
+            set_copied.set(true);
+        } else {
+            let _ = window().prompt_with_message_and_default("Copy this link", &url);
+        }
+    };
+
+    view! {
+        <button class="bit-btn" title="Copy a link that opens this exact value" on:click=copy>
+            {move || if copied.get() { "Copied" } else { "Copy link" }}
+        </button>
+    }
+}
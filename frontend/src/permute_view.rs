@@ -0,0 +1,155 @@
+//! Bit Permutation Module
+
+use bit_operations::permute::{Permutation, PermutationError};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Permutations offered by the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    ReverseInBytes,
+    MortonEncode,
+    MortonDecode,
+    Custom,
+}
+
+impl Kind {
+    const ALL: [Kind; 4] = [
+        Kind::ReverseInBytes,
+        Kind::MortonEncode,
+        Kind::MortonDecode,
+        Kind::Custom,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::ReverseInBytes => "Reverse bits in bytes",
+            Kind::MortonEncode => "Morton encode",
+            Kind::MortonDecode => "Morton decode",
+            Kind::Custom => "Custom table",
+        }
+    }
+
+    /// Builds the permutation for `width`; custom tables are parsed from
+    /// `table`
+    fn build(self, width: Width, table: &str) -> Result<Permutation, PermutationError> {
+        Ok(match self {
+            Kind::ReverseInBytes => Permutation::reverse_bits_in_bytes(width),
+            Kind::MortonEncode => Permutation::morton_encode(width),
+            Kind::MortonDecode => Permutation::morton_decode(width),
+            Kind::Custom => return Permutation::parse(table, width),
+        })
+    }
+}
+
+/// Permutation Component
+///
+/// Moves the bits of the current value with a built-in permutation (bit
+/// reversal within each byte, Morton interleaving of the two halves and its
+/// inverse) or a custom table listing the source bit of each output bit.
+/// The result is previewed before Apply writes it. Up to 64 bits.
+#[component]
+pub fn PermutationPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (kind, set_kind) = signal(Kind::ReverseInBytes);
+    let (table, set_table) = signal(String::new());
+
+    let permutation = move || kind.get().build(bit_size.get(), &table.get());
+
+    let preview = move || {
+        let width = bit_size.get();
+        permutation().map(|p| {
+            let mut value = bit_array.get();
+            let before = value.0;
+            value.permute(&p);
+            format!(
+                "0x{:0w$X} → 0x{:0w$X}",
+                before,
+                value.0,
+                w = width.hex_digits()
+            )
+        })
+    };
+
+    let apply = move |_| {
+        if let Ok(p) = permutation() {
+            let mut value = bit_array.get_untracked();
+            value.permute(&p);
+            set_bit_array.set(value);
+        }
+    };
+
+    // Copies the chosen permutation into the custom table to edit it
+    let edit = move |_| {
+        if let Ok(p) = permutation() {
+            let sources: Vec<String> = p.sources().iter().map(u8::to_string).collect();
+            set_table.set(sources.join(" "));
+            set_kind.set(Kind::Custom);
+        }
+    };
+
+    view! {
+        <div class="permute-panel">
+            <label>
+                <span class="input-label">Permute</span>
+            </label>
+            <Show
+                when=move || bit_size.get().word_count() == 1
+                fallback=|| view! { <div>"Permutations cover sizes up to 64 bits"</div> }
+            >
+                <div class="varint-load">
+                    <select on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(k) = Kind::ALL.into_iter().find(|k| k.label() == label) {
+                            set_kind.set(k);
+                        }
+                    }>
+                        {Kind::ALL
+                            .into_iter()
+                            .map(|k| {
+                                view! {
+                                    <option value=k.label() selected=move || kind.get() == k>
+                                        {k.label()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <button
+                        class="bit-btn"
+                        title="Copy this permutation into the custom table"
+                        prop:disabled=move || permutation().is_err()
+                        on:click=edit
+                    >
+                        "Edit"
+                    </button>
+                    <button
+                        class="bit-btn"
+                        prop:disabled=move || permutation().is_err()
+                        on:click=apply
+                    >
+                        "Apply"
+                    </button>
+                </div>
+                <Show when=move || kind.get() == Kind::Custom>
+                    <textarea
+                        class="template-source"
+                        rows="3"
+                        placeholder="source bit of output bit 0, 1, 2, …, e.g. 7 6 5 4 3 2 1 0"
+                        prop:value=table
+                        on:input=move |ev| set_table.set(event_target_value(&ev))
+                    ></textarea>
+                </Show>
+                <div class="varint-status">
+                    {move || match preview() {
+                        Ok(text) => text,
+                        Err(e) => e.to_string(),
+                    }}
+                </div>
+            </Show>
+        </div>
+    }
+}
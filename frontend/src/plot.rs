@@ -1,102 +1,430 @@
 //! Number Distribution Plot Module
 
-use super::ieee754::IEEEDecoder;
-use bit_operations::BitArray;
+use super::ieee754::{decode, encode, float_format, IEEEFormat};
+use crate::InputMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::decimal;
+use bit_operations::{BitArray, Width};
+use leptos::ev;
+use leptos::html::Div;
 use leptos::prelude::*;
 
+/// Percent of the axis on each side of the centre kept for zero
+const ZERO_GAP: f64 = 2.0;
+/// Percent of the axis at each end kept for the infinities
+const INF_GAP: f64 = 4.0;
+/// Most exponent ticks drawn on each side of zero
+const MAX_TICKS: i32 = 5;
+
+/// Returns the binary exponents of the smallest subnormal, the smallest
+/// normal and just past the largest finite magnitude of `format`
+fn exponent_range(format: IEEEFormat) -> (i32, i32, i32) {
+    let mantissa = format.mantissa_width() as i32;
+    let bias = (1 << (format.exponent_width() - 1)) - 1;
+    // E4M3 keeps finite values in the top exponent
+    let max = if format.layout().is_some() {
+        bias
+    } else {
+        bias + 1
+    };
+    (1 - bias - mantissa, 1 - bias, max + 1)
+}
+
+/// Maps a binary exponent to its distance from zero, in percent of the axis
+fn exponent_offset(exponent: f64, format: IEEEFormat) -> f64 {
+    let (low, _, high) = exponent_range(format);
+    let t = ((exponent - f64::from(low)) / f64::from(high - low)).clamp(0.0, 1.0);
+    ZERO_GAP + t * (50.0 - ZERO_GAP - INF_GAP)
+}
+
 /// Plot Position Calculator
-pub fn calculate_plot_position(bits: u64, bit_size: u64) -> f64 {
-    let decoder = IEEEDecoder::new(bits, bit_size);
-    let value = decoder.value;
-
-    match decoder.special.as_str() {
-        "NaN" => return 50.0,
-        "+Inf" => return 100.0,
-        "-Inf" => return 0.0,
-        "Zero" => return 50.0,
-        _ => (),
+///
+/// Places `value` on a signed-log axis: zero at the centre, magnitudes from
+/// the smallest subnormal to the largest finite value spread by binary
+/// exponent on either side, and the infinities at the ends.
+///
+/// # Returns
+/// - The position in percent, or `None` for NaN.
+pub fn plot_position(value: f64, format: IEEEFormat) -> Option<f64> {
+    if value.is_nan() {
+        return None;
     }
+    let offset = match value.abs() {
+        0.0 => 0.0,
+        m if m.is_infinite() => 50.0,
+        m => exponent_offset(m.log2(), format),
+    };
+    Some(if value.is_sign_negative() {
+        50.0 - offset
+    } else {
+        50.0 + offset
+    })
+}
 
-    let is_negative = value.is_sign_negative();
-    let abs_value = value.abs();
+/// Returns the value at `percent` along the axis, the inverse of
+/// [`plot_position`]; the ends give the infinities, or the largest finite
+/// value for formats without them
+fn position_value(percent: f64, format: IEEEFormat) -> f64 {
+    let offset = (percent - 50.0).abs();
+    let sign = if percent < 50.0 { -1.0 } else { 1.0 };
+    let (low, _, high) = exponent_range(format);
+    let magnitude = if offset < ZERO_GAP / 2.0 {
+        0.0
+    } else if offset > 50.0 - INF_GAP / 2.0 && format.layout().is_some() {
+        f64::INFINITY
+    } else {
+        let t = ((offset - ZERO_GAP) / (50.0 - ZERO_GAP - INF_GAP)).clamp(0.0, 1.0);
+        // Just below the top exponent is the largest finite value
+        let exponent = f64::from(low) + t * f64::from(high - low);
+        2f64.powf(exponent.min(f64::from(high) - 1e-9))
+    };
+    sign * magnitude
+}
 
-    match decoder.special.as_str() {
-        "Denormalized" => {
-            let min_normal = 2.0f64.powi(match decoder.format {
-                super::ieee754::IEEEFormat::Half => -14,
-                super::ieee754::IEEEFormat::Single => -126,
-                super::ieee754::IEEEFormat::Double => -1022,
-            });
+/// Returns the exponents ticked on each side of zero: every power of two
+/// step apart, with at most [`MAX_TICKS`] ticks
+fn ticks(format: IEEEFormat) -> Vec<i32> {
+    let (low, _, high) = exponent_range(format);
+    let mut step = 1;
+    while (high - low) / step > MAX_TICKS {
+        step *= 2;
+    }
+    (low..=high).filter(|e| e.rem_euclid(step) == 0).collect()
+}
 
-            let ratio = abs_value / min_normal;
-            if is_negative {
-                30.0 * ratio
-            } else {
-                70.0 + 30.0 * ratio
-            }
-        }
-        "Normalized" => {
-            let (min_exp, max_exp) = match decoder.format {
-                super::ieee754::IEEEFormat::Half => (-14, 15),
-                super::ieee754::IEEEFormat::Single => (-126, 127),
-                super::ieee754::IEEEFormat::Double => (-1022, 1023),
-            };
+/// Returns the largest value of the most significant word of a `bits`-wide
+/// value
+fn top_max(bits: usize) -> u64 {
+    u64::MAX >> (64 - (bits - 64 * ((bits - 1) / 64)))
+}
 
-            let log_val = abs_value.log2().clamp(min_exp as f64, max_exp as f64);
-            let range = (max_exp - min_exp) as f64;
-            let normalized = (log_val - min_exp as f64) / range * 60.0 + 20.0;
+/// Integer Plot Position Calculator
+///
+/// Places `value` on a linear number line from 0 to the largest unsigned
+/// value of its width, read from its most significant word. The midpoint
+/// falls between the signed maximum and minimum, where two's-complement
+/// values wrap.
+///
+/// # Returns
+/// The position in percent.
+pub fn integer_position(value: &BitVec) -> f64 {
+    let top = value.word((value.len() - 1) / 64);
+    top as f64 / top_max(value.len()) as f64 * 100.0
+}
 
-            if is_negative {
-                40.0 - normalized
-            } else {
-                60.0 + normalized
-            }
-        }
-        _ => 50.0,
+/// Returns the `bits`-wide value at `percent` along the integer number line,
+/// the inverse of [`integer_position`]
+fn integer_value(percent: f64, bits: usize) -> BitVec {
+    let mut value = BitVec::new(bits);
+    if percent >= 100.0 {
+        value.set_all();
+        return value;
     }
+    let max = top_max(bits);
+    let top = ((percent / 100.0).max(0.0) * max as f64).round() as u64;
+    value.set_word((bits - 1) / 64, top.min(max));
+    value
 }
 
 /// Distribution Plot Component
+///
+/// In Float mode, shows where the current float lies on a signed-log axis
+/// with a tick per few binary exponents, mirrored on the negative side, and
+/// the subnormal ranges shaded. In Integer mode the axis is a number line
+/// from 0 to the width's maximum, with the signed wrap point at the
+/// midpoint and the values negative as signed shaded. Hovering the marker
+/// shows the exact value (and the float's ULP); clicking or dragging along
+/// the axis sets the nearest value at that point.
 #[component]
 pub fn DistributionPlot(
     bit_array: ReadSignal<BitArray>,
-    bit_size: ReadSignal<u64>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    sub_format: ReadSignal<IEEEFormat>,
+    input_mode: ReadSignal<InputMode>,
 ) -> impl IntoView {
+    let axis = NodeRef::<Div>::new();
+    let (dragging, set_dragging) = signal(false);
+    let integer = move || input_mode.get() == InputMode::Integer;
+    let decoder = move || decode(bit_array.get().0, bit_size.get(), sub_format.get());
+    let format = move || decoder().format;
+    let value = move || {
+        let width = bit_size.get();
+        if width.word_count() > 1 {
+            bit_vec.get()
+        } else {
+            BitVec::from_words(&[bit_array.get().0 & width.mask()], width.bits() as usize)
+        }
+    };
+
     let position = move || {
+        let position = if integer() {
+            integer_position(&value())
+        } else {
+            plot_position(decoder().value, format()).unwrap_or(50.0)
+        };
+        format!("{}%", position)
+    };
+
+    // Sets the value to the nearest one at the mouse position
+    let set_at = move |ev: &web_sys::MouseEvent| {
+        let Some(axis) = axis.get_untracked() else {
+            return;
+        };
+        let rect = axis.get_bounding_client_rect();
+        if rect.width() <= 0.0 {
+            return;
+        }
+        let percent = (f64::from(ev.client_x()) - rect.left()) / rect.width() * 100.0;
+        let (width, sub_format) = (bit_size.get_untracked(), sub_format.get_untracked());
+        if input_mode.get_untracked() == InputMode::Integer {
+            let value = integer_value(percent, width.bits() as usize);
+            if width.word_count() > 1 {
+                set_bit_vec.set(value);
+            } else {
+                set_bit_array.set(BitArray(value.word(0)));
+            }
+            return;
+        }
+        let Some(format) = float_format(width, sub_format) else {
+            return;
+        };
+        if let Some(bits) = encode(position_value(percent, format), width, sub_format) {
+            set_bit_array.set(BitArray(bits));
+        }
+    };
+
+    let mouseup = window_event_listener(ev::mouseup, move |_| set_dragging.set(false));
+    on_cleanup(move || mouseup.remove());
+
+    let tooltip = move || {
+        if integer() {
+            let value = value();
+            let signed = if value.is_negative() {
+                let mut magnitude = value.clone();
+                magnitude.negate();
+                format!("-{}", magnitude.to_str_radix(10))
+            } else {
+                value.to_str_radix(10)
+            };
+            return format!("{}, signed {}", value.to_str_radix(10), signed);
+        }
+        let decoder = decoder();
+        let value = decoder.value;
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        let ulp = decoder
+            .format
+            .layout()
+            .map(|layout| format!("{:e}", layout.ulp(bit_array.get().0)))
+            .unwrap_or("n/a".to_string());
         format!(
-            "{}%",
-            calculate_plot_position(bit_array.get().0, bit_size.get()).clamp(0.0, 100.0)
+            "{} ({}), ULP {}",
+            decimal::exact(value),
+            decoder.special,
+            ulp
         )
     };
 
+    // Shaded ranges: the subnormals on each side of zero, or the values
+    // negative as signed
+    let zones = move || {
+        if integer() {
+            return vec![(50.0, 50.0, "zone signed-negative")];
+        }
+        let (low, min_normal, _) = exponent_range(format());
+        let width = exponent_offset(f64::from(min_normal), format())
+            - exponent_offset(f64::from(low), format());
+        vec![
+            (50.0 - ZERO_GAP - width, width, "zone subnormal"),
+            (50.0 + ZERO_GAP, width, "zone subnormal"),
+        ]
+    };
+
+    let float_ticks = move || {
+        let format = format();
+        ticks(format)
+            .into_iter()
+            .flat_map(|e| {
+                let offset = exponent_offset(f64::from(e), format);
+                // Labelled on the positive side only
+                [(50.0 - offset, None), (50.0 + offset, Some(e))]
+            })
+            .map(|(left, exponent)| {
+                view! {
+                    <div class="plot-tick" style:left=format!("{}%", left)>
+                        {exponent.map(|e| view! { <span>"2"<sup>{e}</sup></span> })}
+                    </div>
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
+    let integer_ticks = move || {
+        let bits = bit_size.get().bits();
+        view! {
+            <div class="plot-tick" style:left="0%">
+                <span>"0"</span>
+            </div>
+            <div class="plot-tick" style:left="25%"></div>
+            <div
+                class="plot-tick wrap"
+                style:left="50%"
+                title=format!("i{} wraps from max to min here", bits)
+            >
+                <span>"2"<sup>{bits - 1}</sup></span>
+            </div>
+            <div class="plot-tick" style:left="75%"></div>
+            <div class="plot-tick" style:left="100%">
+                <span>"2"<sup>{bits}</sup>"-1"</span>
+            </div>
+        }
+        .into_any()
+    };
+
     view! {
         <div class="distribution-plot">
-            <div class="plot-axis">
-                <div class="zone negative-inf"></div>
-                <div class="zone negative-subnormal"></div>
-                <div class="zone negative-normal"></div>
-                <div class="zone positive-normal"></div>
-                <div class="zone positive-subnormal"></div>
-                <div class="zone positive-inf"></div>
-
-                <div class="plot-marker" style:left=position>
-                    <div class="plot-tooltip">
-                        {move || {
-                            let _decoder = IEEEDecoder::new(bit_array.get().0, 64);
-
-                        }}
-                    </div>
+            <div
+                class="plot-axis"
+                node_ref=axis
+                title="Click or drag to set the value"
+                on:mousedown=move |ev| {
+                    ev.prevent_default();
+                    set_dragging.set(true);
+                    set_at(&ev);
+                }
+                on:mousemove=move |ev| {
+                    if dragging.get_untracked() {
+                        set_at(&ev);
+                    }
+                }
+            >
+                {move || {
+                    zones()
+                        .into_iter()
+                        .map(|(left, width, class)| view! {
+                            <div
+                                class=class
+                                style:left=format!("{}%", left)
+                                style:width=format!("{}%", width)
+                            ></div>
+                        })
+                        .collect_view()
+                }}
+                {move || if integer() { integer_ticks() } else { float_ticks() }}
+                <div
+                    class="plot-marker"
+                    class:nan=move || !integer() && decoder().value.is_nan()
+                    style:left=position
+                >
+                    <div class="plot-tooltip">{tooltip}</div>
                 </div>
             </div>
             <div class="plot-labels">
-                <span class="label-left">-Inf</span>
-                <span class="label-subnormal">Subnormal</span>
-                <span class="label-normal">Normal</span>
-                <span class="label-zero">0</span>
-                <span class="label-normal">Normal</span>
-                <span class="label-subnormal">Subnormal</span>
-                <span class="label-right">Inf</span>
+                {move || if integer() {
+                    view! {
+                        <span class="label-left">"0"</span>
+                        <span class="label-zero">"signed wrap"</span>
+                        <span class="label-right">"max"</span>
+                    }
+                    .into_any()
+                } else {
+                    view! {
+                        <span class="label-left">"-Inf"</span>
+                        <span class="label-zero">"0"</span>
+                        <span class="label-right">"Inf"</span>
+                    }
+                    .into_any()
+                }}
             </div>
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_floats_on_a_signed_log_axis() {
+        let format = IEEEFormat::Single;
+        assert_eq!(plot_position(0.0, format), Some(50.0));
+        assert_eq!(plot_position(-0.0, format), Some(50.0));
+        assert_eq!(plot_position(f64::INFINITY, format), Some(100.0));
+        assert_eq!(plot_position(f64::NEG_INFINITY, format), Some(0.0));
+        assert_eq!(plot_position(f64::NAN, format), None);
+
+        let values = [f64::MIN_POSITIVE, 1e-40, 1.0, 2.0, 1e30, f32::MAX as f64];
+        let positions: Vec<f64> = values
+            .iter()
+            .map(|&v| plot_position(v, format).unwrap())
+            .collect();
+        assert!(positions
+            .iter()
+            .all(|&p| p > 50.0 && p < 100.0 - INF_GAP / 2.0));
+        let mirrored = plot_position(-1.0, format).unwrap();
+        assert!((mirrored + plot_position(1.0, format).unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn float_positions_increase_with_magnitude() {
+        let format = IEEEFormat::Half;
+        let mut last = 50.0;
+        for exponent in -24..16 {
+            let position = plot_position(2f64.powi(exponent), format).unwrap();
+            assert!(position > last, "2^{}", exponent);
+            last = position;
+        }
+    }
+
+    #[test]
+    fn reads_floats_back_from_positions() {
+        for format in [IEEEFormat::Half, IEEEFormat::Single, IEEEFormat::Double] {
+            assert_eq!(position_value(50.0, format), 0.0);
+            assert_eq!(position_value(100.0, format), f64::INFINITY);
+            assert_eq!(position_value(0.0, format), f64::NEG_INFINITY);
+            for value in [0.5, 1.0, 1024.0, -8.0] {
+                let back = position_value(plot_position(value, format).unwrap(), format);
+                assert!((back / value - 1.0).abs() < 1e-9, "{} {:?}", value, format);
+            }
+        }
+        // E4M3 has no infinities, so the end of the axis is its largest value
+        let end = position_value(100.0, IEEEFormat::E4M3);
+        assert!(end.is_finite() && end > 400.0);
+    }
+
+    #[test]
+    fn places_integers_on_a_linear_axis() {
+        let value = |words: &[u64], bits| BitVec::from_words(words, bits);
+        assert_eq!(integer_position(&value(&[0], 8)), 0.0);
+        assert_eq!(integer_position(&value(&[0xFF], 8)), 100.0);
+        assert_eq!(integer_position(&value(&[u64::MAX], 64)), 100.0);
+        // The signed wrap point sits at the midpoint
+        let below = integer_position(&value(&[0x7F], 8));
+        let above = integer_position(&value(&[0x80], 8));
+        assert!(below < 50.0 && above > 50.0);
+        // Wide values are placed by their most significant word
+        let wide = value(&[u64::MAX, 0, 0, 1 << 63], 256);
+        assert!((integer_position(&wide) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reads_integers_back_from_positions() {
+        for bits in [8, 16] {
+            for raw in 0..1u64 << bits {
+                let value = BitVec::from_words(&[raw], bits);
+                assert_eq!(integer_value(integer_position(&value), bits), value);
+            }
+        }
+        assert_eq!(integer_value(-5.0, 8), BitVec::new(8));
+        assert_eq!(integer_value(100.0, 256).count_ones(), 256);
+        assert_eq!(integer_value(50.0, 8).word(0), 0x80);
+        let wide = integer_value(50.0, 128);
+        assert_eq!(wide.word(0), 0);
+        assert_eq!(wide.word(1), 1 << 63);
+    }
+}
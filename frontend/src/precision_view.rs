@@ -0,0 +1,67 @@
+//! Precision Loss Explorer Module
+
+use bit_operations::precision::{convert_all, FORMATS};
+use leptos::prelude::*;
+
+/// Precision Explorer Component
+///
+/// Rounds a decimal number into binary16, bfloat16, binary32 and binary64
+/// side by side, showing each format's bit pattern, the exact value it
+/// stores, the absolute and relative error and which way the number was
+/// rounded.
+#[component]
+pub fn PrecisionExplorer() -> impl IntoView {
+    let (input, set_input) = signal("0.1".to_string());
+    let conversions = move || convert_all(&input.get());
+
+    view! {
+        <div class="precision-panel">
+            <label>
+                <span class="input-label">Precision loss</span>
+                <input
+                    type="text"
+                    prop:value=input
+                    on:input=move |ev| set_input.set(event_target_value(&ev))
+                />
+            </label>
+            {move || match conversions() {
+                None => view! { <div class="round-trip-row">"Invalid number"</div> }.into_any(),
+                Some(list) => view! {
+                    <table class="precision-table">
+                        <tr>
+                            <th>"Format"</th>
+                            <th>"Bits"</th>
+                            <th>"Stored exactly"</th>
+                            <th>"Error"</th>
+                            <th>"Relative"</th>
+                            <th>"Rounded"</th>
+                        </tr>
+                        {list
+                            .into_iter()
+                            .zip(FORMATS)
+                            .map(|(c, (format, name))| {
+                                let rounded = c.direction.relative_to_zero(c.value < 0.0);
+                                view! {
+                                    <tr class:inexact=rounded != "exact">
+                                        <td>{name}</td>
+                                        <td>
+                                            {format!(
+                                                "0x{:0width$X}",
+                                                c.bits,
+                                                width = format.bits() as usize / 4
+                                            )}
+                                        </td>
+                                        <td class="round-trip-exact">{c.exact}</td>
+                                        <td>{format!("{:+e}", c.error)}</td>
+                                        <td>{format!("{:+.3e}", c.relative_error)}</td>
+                                        <td>{format!("{:?}, {}", c.direction, rounded)}</td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </table>
+                }.into_any(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,214 @@
+//! Bit Range Operations Module
+
+use crate::bit_grid::{GridOptions, Selection};
+use bit_operations::bitvec::BitVec;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// An operation applied to the selected bits only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeOp {
+    Set,
+    Clear,
+    Invert,
+    Shl,
+    Shr,
+}
+
+impl RangeOp {
+    const ALL: [RangeOp; 5] = [
+        RangeOp::Set,
+        RangeOp::Clear,
+        RangeOp::Invert,
+        RangeOp::Shl,
+        RangeOp::Shr,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            RangeOp::Set => "Set",
+            RangeOp::Clear => "Clr",
+            RangeOp::Invert => "Not",
+            RangeOp::Shl => "Lsh",
+            RangeOp::Shr => "Rsh",
+        }
+    }
+}
+
+/// Parses a range typed as `high:low` (Verilog order), `low..high` or a
+/// single bit, returning the lowest and highest number typed
+fn parse_range(text: &str) -> Option<(u32, u32)> {
+    let text = text.trim();
+    let (a, b) = text
+        .split_once(':')
+        .or_else(|| text.split_once("..="))
+        .or_else(|| text.split_once(".."))
+        .unwrap_or((text, text));
+    let (a, b) = (a.trim().parse::<u32>().ok()?, b.trim().parse::<u32>().ok()?);
+    Some((a.min(b), a.max(b)))
+}
+
+/// Range Operations Component
+///
+/// Works on the bits selected in the grid, by dragging or with Shift and
+/// the arrow keys, or typed as `15:8`. Set, Clr, Not and the shifts change
+/// only the selected bits, shifting by the bit operations' amount within
+/// the range. Extract replaces the value with the range's bits moved down
+/// to bit 0. Typed and shown ranges follow the grid's numbering.
+#[component]
+pub fn RangeOperations(
+    selection: ReadSignal<Option<Selection>>,
+    set_selection: WriteSignal<Option<Selection>>,
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    shift_amount: ReadSignal<u32>,
+    grid_options: ReadSignal<GridOptions>,
+) -> impl IntoView {
+    let wide = move || bit_size.get().word_count() > 1;
+    let (text, set_text) = signal(String::new());
+    let (error, set_error) = signal(false);
+
+    // Lowest and highest selected bit within the bit size
+    let range = Memo::new(move |_| {
+        let msb = bit_size.get().bits() - 1;
+        let (low, high) = selection.get()?.range();
+        (low <= msb).then(|| (low, high.min(msb)))
+    });
+
+    // Effect to show the grid's selection in the range field
+    Effect::new(move |_| {
+        let bits = bit_size.get().bits();
+        let label = |bit| grid_options.get().numbering.label(bit, bits).unwrap_or(bit);
+        set_text.set(match range.get() {
+            Some((low, high)) if low != high => format!("{}:{}", label(high), label(low)),
+            Some((low, _)) => label(low).to_string(),
+            None => String::new(),
+        });
+        set_error.set(false);
+    });
+
+    // The selected bits moved down to bit 0
+    let field = move || {
+        let (low, high) = range.get()?;
+        let len = (high - low + 1) as usize;
+        Some(if wide() {
+            bit_vec.with(|v| v.range(low as usize, len))
+        } else {
+            let value = bit_array.get().get_field(low as u8..high as u8 + 1);
+            BitVec::from_words(&[value], len)
+        })
+    };
+
+    let apply = move |op: RangeOp| {
+        let Some((low, high)) = range.get_untracked() else {
+            return;
+        };
+        let n = shift_amount.get_untracked();
+        if wide() {
+            set_bit_vec.update(|v| {
+                let mut bits = v.range(low as usize, (high - low + 1) as usize);
+                match op {
+                    RangeOp::Set => bits.set_all(),
+                    RangeOp::Clear => bits.clear(),
+                    RangeOp::Invert => bits.invert(),
+                    RangeOp::Shl => bits.shift_left(n as usize),
+                    RangeOp::Shr => bits.shift_right(n as usize),
+                }
+                v.set_range(low as usize, &bits);
+            });
+        } else {
+            let bits = low as u8..high as u8 + 1;
+            set_bit_array.update(|ba| match op {
+                RangeOp::Set => ba.fill_field(bits),
+                RangeOp::Clear => ba.clear_field(bits),
+                RangeOp::Invert => ba.invert_field(bits),
+                RangeOp::Shl => ba.shl_field(bits, n),
+                RangeOp::Shr => ba.shr_field(bits, n),
+            });
+        }
+    };
+
+    let extract = move |_| {
+        let Some(mut value) = field() else {
+            return;
+        };
+        let width = bit_size.get_untracked();
+        if width.word_count() > 1 {
+            value.resize(width.bits() as usize);
+            set_bit_vec.set(value);
+        } else {
+            set_bit_array.set(BitArray(value.word(0)));
+        }
+        set_selection.set(None);
+    };
+
+    let select = move |ev| {
+        let typed = event_target_value(&ev);
+        if typed.trim().is_empty() {
+            set_selection.set(None);
+            return;
+        }
+        let bits = bit_size.get_untracked().bits();
+        let numbering = grid_options.get_untracked().numbering;
+        let range = parse_range(&typed)
+            .and_then(|(a, b)| Some((numbering.bit(a, bits)?, numbering.bit(b, bits)?)));
+        match range {
+            Some((a, b)) => set_selection.set(Some(Selection::span(a.min(b), a.max(b)))),
+            None => set_error.set(true),
+        }
+    };
+
+    let status = move || {
+        if error.get() {
+            return format!(
+                "Not a range: use high:low within 0..{}",
+                bit_size.get().bits()
+            );
+        }
+        match field() {
+            Some(value) => format!(
+                "{} bits = 0x{} = {}",
+                value.len(),
+                value.to_str_radix(16).to_uppercase(),
+                value.to_str_radix(10)
+            ),
+            None => "Drag across the grid or type high:low".to_string(),
+        }
+    };
+
+    view! {
+        <div class="range-panel">
+            <label>
+                <span class="input-label">Bit range</span>
+            </label>
+            <div class="bit-operations">
+                <input
+                    type="text"
+                    placeholder="15:8"
+                    prop:value=text
+                    on:input=move |ev| set_text.set(event_target_value(&ev))
+                    on:change=select
+                />
+                {RangeOp::ALL
+                    .into_iter()
+                    .map(|op| view! {
+                        <button
+                            class="bit-btn"
+                            prop:disabled=move || range.get().is_none()
+                            on:click=move |_| apply(op)
+                        >
+                            {op.label()}
+                        </button>
+                    })
+                    .collect_view()}
+                <button class="bit-btn" prop:disabled=move || range.get().is_none() on:click=extract>
+                    "Extract"
+                </button>
+            </div>
+            <div class="arithmetic-status">{status}</div>
+        </div>
+    }
+}
@@ -0,0 +1,178 @@
+//! Register Map Module
+
+use bit_operations::register_map::{RegisterField, RegisterMap};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Map shown until the user edits the description
+const EXAMPLE: &str = "register CTRL 32
+field EN     0     \"Enable\"
+field MODE   5:4   off=0 slow=1 fast=2
+field IRQ    7:6   none=0 rx=1 tx=2 both=3
+field DIV    15:8  \"Clock divider\"
+field ID     31:24";
+
+/// Parses a register map in the line-based format, or as JSON when the
+/// description starts with `{`.
+fn parse_map(src: &str) -> Result<RegisterMap, String> {
+    if src.trim_start().starts_with('{') {
+        let map: RegisterMap = serde_json::from_str(src).map_err(|err| err.to_string())?;
+        map.validate()?;
+        Ok(map)
+    } else {
+        RegisterMap::parse(src).map_err(|err| err.to_string())
+    }
+}
+
+/// Register Map Component
+///
+/// Loads a register description naming the fields of the current value and
+/// their symbolic values. The grid labels and groups its bits by field, and
+/// each field can be read and set here by symbol or number; hovering a field
+/// highlights its bits.
+#[component]
+pub fn RegisterMapPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    set_bit_size: WriteSignal<Width>,
+    register_map: ReadSignal<Option<RegisterMap>>,
+    set_register_map: WriteSignal<Option<RegisterMap>>,
+    set_highlight: WriteSignal<u64>,
+) -> impl IntoView {
+    let (source, set_source) = signal(EXAMPLE.to_string());
+    let (error, set_error) = signal(String::new());
+    let (enabled, set_enabled) = signal(false);
+
+    // Effect to reparse the description, switching to the register's size
+    Effect::new(move |_| {
+        if !enabled.get() {
+            set_register_map.set(None);
+            return;
+        }
+        match parse_map(&source.get()) {
+            Ok(map) => {
+                set_error.set(String::new());
+                if bit_size.get_untracked() != map.width {
+                    set_bit_size.set(map.width);
+                }
+                set_register_map.set(Some(map));
+            }
+            Err(err) => {
+                set_error.set(err);
+                set_register_map.set(None);
+            }
+        }
+    });
+
+    let write = move |field: &RegisterField, text: &str| match field.resolve(text) {
+        Some(raw) => {
+            if let Some(value) = field.insert(bit_array.get_untracked().0, raw) {
+                set_error.set(String::new());
+                set_bit_array.set(BitArray(value));
+            }
+        }
+        None => set_error.set(format!(
+            "`{}` is not a value of {}",
+            text.trim(),
+            field.name
+        )),
+    };
+
+    let row = move |field: RegisterField| {
+        let mask = field.mask();
+        let bits = if field.width == 1 {
+            format!("{}", field.shift)
+        } else {
+            format!("{}:{}", field.shift + field.width - 1, field.shift)
+        };
+        let value = {
+            let field = field.clone();
+            move || field.extract(bit_array.get().0)
+        };
+        let editor = if field.values.is_empty() {
+            let field = field.clone();
+            let value = value.clone();
+            view! {
+                <input
+                    type="text"
+                    prop:value=move || format!("0x{:X}", value())
+                    on:change=move |ev| write(&field, &event_target_value(&ev))
+                />
+            }
+            .into_any()
+        } else {
+            let options = field.values.clone();
+            let field = field.clone();
+            let value = value.clone();
+            view! {
+                <select on:change=move |ev| write(&field, &event_target_value(&ev))>
+                    {options
+                        .into_iter()
+                        .map(|v| {
+                            let raw = v.value;
+                            let value = value.clone();
+                            view! {
+                                <option value=v.name.clone() selected=move || value() == raw>
+                                    {v.name.clone()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            }
+            .into_any()
+        };
+        let symbol = {
+            let field = field.clone();
+            let value = value.clone();
+            move || {
+                field
+                    .symbol(value())
+                    .map(str::to_string)
+                    .unwrap_or_default()
+            }
+        };
+        view! {
+            <tr
+                title=field.description.clone()
+                on:mouseenter=move |_| set_highlight.set(mask)
+                on:mouseleave=move |_| set_highlight.set(0)
+            >
+                <td>{field.name.clone()}</td>
+                <td>{bits}</td>
+                <td>{move || format!("0x{:X}", value())}</td>
+                <td>{symbol}</td>
+                <td>{editor}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <div class="register-map-panel">
+            <label>
+                <span class="input-label">Register map</span>
+                <input
+                    type="checkbox"
+                    prop:checked=enabled
+                    on:change=move |ev| set_enabled.set(event_target_checked(&ev))
+                />
+                {move || register_map.get().map(|map| map.name).unwrap_or_default()}
+            </label>
+            <textarea
+                class="register-map-source"
+                rows="6"
+                prop:value=source
+                on:input=move |ev| set_source.set(event_target_value(&ev))
+            ></textarea>
+            <div class="register-map-error">{error}</div>
+            <table class="register-map-fields">
+                {move || {
+                    register_map
+                        .get()
+                        .map(|map| map.fields.into_iter().map(row).collect_view())
+                }}
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,91 @@
+//! Float-to-String Round-Trip Tester Module
+
+use bit_operations::decimal;
+use bit_operations::rounding::FloatFormat;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Round-Trip Tester Component
+///
+/// Parses a decimal string into the nearest value of the float format
+/// matching the current bit size and shows the shortest string that parses
+/// back to the same bits, the exact value stored, and whether the original
+/// string survives a print/parse round trip.
+#[component]
+pub fn RoundTripTester(
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (input, set_input) = signal("0.1".to_string());
+
+    let format = move || match bit_size.get() {
+        Width::W16 => Some(FloatFormat::HALF),
+        Width::W32 => Some(FloatFormat::SINGLE),
+        Width::W64 => Some(FloatFormat::DOUBLE),
+        _ => None,
+    };
+
+    let result = move || {
+        let format = format()?;
+        let bits = decimal::parse(format, &input.get())?;
+        let shortest = decimal::shortest(format, bits);
+        let exact = decimal::exact(format.decode(bits));
+        Some((bits, shortest, exact))
+    };
+
+    view! {
+        <div class="round-trip-panel">
+            <label>
+                <span class="input-label">Round trip</span>
+                <input
+                    type="text"
+                    prop:value=input
+                    on:input=move |ev| set_input.set(event_target_value(&ev))
+                />
+            </label>
+            {move || match (format(), result()) {
+                (None, _) => view! {
+                    <div class="round-trip-row">"Select 16, 32 or 64 bit size"</div>
+                }.into_any(),
+                (Some(_), None) => view! {
+                    <div class="round-trip-row">"Invalid number"</div>
+                }.into_any(),
+                (Some(format), Some((bits, shortest, exact))) => {
+                    let original = input.get();
+                    let round_trips = decimal::same_value(&original, &shortest);
+                    let is_exact = decimal::same_value(&original, &exact);
+                    view! {
+                        <div class="round-trip-row">
+                            <button class="bit-btn" on:click=move |_| set_bit_array.set(BitArray(bits))>
+                                "Bits"
+                            </button>
+                            {format!(
+                                "0x{:0width$X}",
+                                bits,
+                                width = format.bits() as usize / 4
+                            )}
+                        </div>
+                        <div class="round-trip-row">{format!("Shortest: {}", shortest)}</div>
+                        <div class="round-trip-row round-trip-exact">
+                            {format!("Exact: {}", exact)}
+                        </div>
+                        <div class="round-trip-row" class:mismatch=!round_trips>
+                            {if round_trips {
+                                "Original round-trips".to_string()
+                            } else {
+                                format!("Original does not round-trip: prints back as {}", shortest)
+                            }}
+                        </div>
+                        <div class="round-trip-row">
+                            {if is_exact {
+                                "Original is stored exactly"
+                            } else {
+                                "Original is not exactly representable"
+                            }}
+                        </div>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,226 @@
+//! Bit Pattern Search Module
+
+use crate::backend::call_json;
+use crate::bit_grid::Selection;
+use crate::file_view::FileWindow;
+use bit_operations::bitvec::BitVec;
+use bit_operations::parse::normalize_digits;
+use bit_operations::search::{self, BitPattern};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use serde::Deserialize;
+
+/// Most hits kept from a pasted buffer, as for files in the backend
+const MAX_HITS: usize = 10_000;
+/// Most hits listed; Prev and Next step through all of them
+const MAX_LISTED: usize = 500;
+
+/// Hits of a file search as returned by the backend
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct SearchHits {
+    hits: Vec<u64>,
+    truncated: bool,
+    file_len: u64,
+}
+
+/// Decodes pasted hex bytes, ignoring separators and an `0x` prefix
+fn parse_blob(text: &str) -> Result<Vec<u8>, String> {
+    let (_, digits) = normalize_digits(text, 16).map_err(|e| format!("Buffer: {}", e))?;
+    hex::decode(digits).map_err(|_| "Buffer: odd number of hex digits".to_string())
+}
+
+/// Pattern Search Component
+///
+/// Finds a bit pattern with don't-care bits, e.g. `0b10?1` or `0xA?`, at any
+/// bit alignment in the file opened in the file browser (searched by the
+/// backend) or in pasted hex bytes. Hits are listed as `byte.bit` offsets;
+/// clicking one, or stepping with Prev/Next, loads the bits from that
+/// offset into the value with the pattern at its top and selects it.
+#[component]
+pub fn PatternSearch(
+    set_bit_array: WriteSignal<BitArray>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    set_selection: WriteSignal<Option<Selection>>,
+    file_path: ReadSignal<String>,
+) -> impl IntoView {
+    let (pattern_text, set_pattern_text) = signal(String::new());
+    let (from_file, set_from_file) = signal(true);
+    let (blob, set_blob) = signal(String::new());
+    let (pattern, set_pattern) = signal(None::<BitPattern>);
+    let (hits, set_hits) = signal(Vec::<u64>::new());
+    let (current, set_current) = signal(None::<usize>);
+    let (status, set_status) = signal(String::new());
+
+    let show_hits = move |found: Vec<u64>, truncated: bool, bytes: u64| {
+        set_status.set(match (found.len(), truncated) {
+            (0, _) => format!("No hits in {} bytes", bytes),
+            (n, false) => format!("{} hits in {} bytes", n, bytes),
+            (n, true) => format!("First {} hits in {} bytes", n, bytes),
+        });
+        set_hits.set(found);
+        set_current.set(None);
+    };
+
+    let run = move || {
+        let found = match BitPattern::parse(&pattern_text.get()) {
+            Ok(found) => found,
+            Err(e) => {
+                set_status.set(format!("Pattern: {}", e));
+                return;
+            }
+        };
+        set_pattern.set(Some(found));
+        if from_file.get() {
+            let args = serde_json::json!({ "path": file_path.get(), "pattern": found });
+            set_status.set("Searching…".to_string());
+            spawn_local(async move {
+                match call_json::<SearchHits>("search_file", args).await {
+                    Ok(result) => show_hits(result.hits, result.truncated, result.file_len),
+                    Err(e) => set_status.set(e),
+                }
+            });
+        } else {
+            match parse_blob(&blob.get()) {
+                Ok(data) => {
+                    let mut found: Vec<u64> =
+                        search::find(&data, found).take(MAX_HITS + 1).collect();
+                    let truncated = found.len() > MAX_HITS;
+                    found.truncate(MAX_HITS);
+                    show_hits(found, truncated, data.len() as u64);
+                }
+                Err(e) => set_status.set(e),
+            }
+        }
+    };
+
+    // Loads the bits of `data` from `bit_offset` into the value and selects
+    // the pattern's bits at its top
+    let load = move |data: &[u8], bit_offset: u64| {
+        let width = bit_size.get_untracked();
+        let bits = width.bits();
+        let mut bytes = vec![0; width.bytes()];
+        search::read_bits(data, bit_offset, &mut bytes);
+        if width.word_count() > 1 {
+            if let Some(value) = BitVec::from_be_bytes(&bytes, bits as usize) {
+                set_bit_vec.set(value);
+            }
+        } else if let Some(value) = width.from_be_bytes(&bytes) {
+            set_bit_array.set(BitArray(value));
+        }
+        let len = pattern.get_untracked().map_or(1, |p| p.len).min(bits);
+        set_selection.set(Some(Selection::span(bits - len, bits - 1)));
+    };
+
+    let jump = move |index: usize| {
+        let Some(offset) = hits.with_untracked(|h| h.get(index).copied()) else {
+            return;
+        };
+        set_current.set(Some(index));
+        if from_file.get_untracked() {
+            let args = serde_json::json!({
+                "path": file_path.get_untracked(),
+                "offset": offset / 8,
+                "len": bit_size.get_untracked().bytes() + 1,
+            });
+            spawn_local(async move {
+                match call_json::<FileWindow>("read_file_window", args).await {
+                    Ok(window) => load(&window.bytes, offset % 8),
+                    Err(e) => set_status.set(e),
+                }
+            });
+        } else if let Ok(data) = parse_blob(&blob.get_untracked()) {
+            load(&data, offset);
+        }
+    };
+
+    let step = move |forward: bool| {
+        let count = hits.with(Vec::len);
+        let next = match (current.get(), forward) {
+            (None, true) => 0,
+            (None, false) => count.saturating_sub(1),
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        jump(next);
+    };
+
+    view! {
+        <div class="pattern-search">
+            <label>
+                <span class="input-label">Search</span>
+                <input
+                    type="text"
+                    placeholder="0b10?1, 0xA?"
+                    title="Bits to find; ? is a don't-care bit, octal digit or hex nibble"
+                    prop:value=pattern_text
+                    on:input=move |ev| set_pattern_text.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            run();
+                        }
+                    }
+                />
+                <select on:change=move |ev| set_from_file.set(event_target_value(&ev) == "file")>
+                    <option value="file" selected=from_file>
+                        "in file"
+                    </option>
+                    <option value="hex" selected=move || !from_file.get()>
+                        "in hex"
+                    </option>
+                </select>
+                <button class="bit-btn" on:click=move |_| run()>
+                    "Find"
+                </button>
+            </label>
+            <Show when=move || !from_file.get()>
+                <textarea
+                    class="search-blob"
+                    placeholder="hex bytes, e.g. DE AD BE EF"
+                    prop:value=blob
+                    on:input=move |ev| set_blob.set(event_target_value(&ev))
+                ></textarea>
+            </Show>
+            <div class="file-nav">
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || hits.with(Vec::is_empty)
+                    on:click=move |_| step(false)
+                >
+                    "Prev"
+                </button>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || hits.with(Vec::is_empty)
+                    on:click=move |_| step(true)
+                >
+                    "Next"
+                </button>
+                <span class="search-status">{status}</span>
+            </div>
+            <div class="search-hits">
+                {move || {
+                    hits.with(|h| {
+                        h.iter()
+                            .take(MAX_LISTED)
+                            .enumerate()
+                            .map(|(index, &offset)| {
+                                view! {
+                                    <span
+                                        class="search-hit"
+                                        class:current=move || current.get() == Some(index)
+                                        title=format!("bit offset {}", offset)
+                                        on:click=move |_| jump(index)
+                                    >
+                                        {format!("{:#X}.{}", offset / 8, offset % 8)}
+                                    </span>
+                                }
+                            })
+                            .collect_view()
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
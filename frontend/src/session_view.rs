@@ -0,0 +1,143 @@
+//! Session Save/Load Module
+
+use crate::backend::{call, call_json, open_dialog, save_dialog, DialogFilter};
+use crate::permalink;
+use bit_operations::session::Session;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// File type of saved sessions in the file dialogs
+const SESSION_FILES: DialogFilter = DialogFilter {
+    name: "Bitwise session",
+    extensions: &["json"],
+};
+
+/// Loads a session file through the backend; an empty path means the
+/// default session file
+async fn load(path: String) -> Result<Session, String> {
+    call_json("load_session", serde_json::json!({ "path": path })).await
+}
+
+/// Session Component
+///
+/// Saves the workspace slots, register map and undo history to a JSON file
+/// and loads them back through the desktop backend, picking the file in the
+/// native save and open dialogs. With autosave on, the
+/// session is written to the default session file when the app exits and
+/// reopened on the next start unless the URL carries a permalink.
+#[component]
+pub fn SessionPanel(session: Signal<Option<Session>>, apply: Callback<Session>) -> impl IntoView {
+    let (autosave, set_autosave) = signal(false);
+    let (status, set_status) = signal(String::new());
+
+    let current = move || {
+        session.get().map(|s| Session {
+            autosave: autosave.get(),
+            ..s
+        })
+    };
+
+    let open = move |session: Session| {
+        set_autosave.set(session.autosave);
+        apply.run(session);
+    };
+
+    // Reopen the autosaved session on startup
+    if permalink::read_location().is_none() {
+        spawn_local(async move {
+            if let Ok(session) = load(String::new()).await {
+                if session.autosave {
+                    open(session);
+                    set_status.set("Restored the autosaved session".to_string());
+                }
+            }
+        });
+    }
+
+    // Effect to hand the backend the session to write on exit. Once
+    // autosave was on, turning it off still writes the session, flagged so
+    // the next start does not reopen it.
+    let was_on = StoredValue::new(false);
+    Effect::new(move |_| {
+        if autosave.get() {
+            was_on.set_value(true);
+        } else if !was_on.get_value() {
+            spawn_local(async move {
+                let _ = call("set_autosave", serde_json::json!({ "state": null })).await;
+            });
+            return;
+        }
+        let Some(state) = current() else {
+            return;
+        };
+        spawn_local(async move {
+            let _ = call("set_autosave", serde_json::json!({ "state": state })).await;
+        });
+    });
+
+    let save = move |_| {
+        let Some(state) = current() else {
+            return;
+        };
+        spawn_local(async move {
+            let picked = save_dialog("Save session", Some(SESSION_FILES), "session.json").await;
+            let path = match picked {
+                Ok(Some(path)) => path,
+                Ok(None) => return,
+                Err(err) => {
+                    set_status.set(format!("Save failed: {}", err));
+                    return;
+                }
+            };
+            set_status.set("Saving…".to_string());
+            let args = serde_json::json!({ "path": path, "state": state });
+            match call("save_session", args).await {
+                Ok(saved) => set_status.set(format!(
+                    "Saved to {}",
+                    saved.as_string().unwrap_or_default()
+                )),
+                Err(err) => set_status.set(format!("Save failed: {}", err)),
+            }
+        });
+    };
+
+    let load_clicked = move |_| {
+        spawn_local(async move {
+            let path = match open_dialog("Load session", Some(SESSION_FILES)).await {
+                Ok(Some(path)) => path,
+                Ok(None) => return,
+                Err(err) => {
+                    set_status.set(format!("Load failed: {}", err));
+                    return;
+                }
+            };
+            set_status.set("Loading…".to_string());
+            match load(path).await {
+                Ok(session) => {
+                    open(session);
+                    set_status.set("Session loaded".to_string());
+                }
+                Err(err) => set_status.set(format!("Load failed: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="session-panel">
+            <div class="export-options">
+                <span class="input-label">Session</span>
+                <button class="bit-btn" on:click=save>"Save…"</button>
+                <button class="bit-btn" on:click=load_clicked>"Load…"</button>
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=autosave
+                        on:change=move |ev| set_autosave.set(event_target_checked(&ev))
+                    />
+                    "Autosave on exit"
+                </label>
+            </div>
+            <div class="export-status">{status}</div>
+        </div>
+    }
+}
@@ -0,0 +1,292 @@
+//! Settings Module
+
+use crate::backend::{self, call, call_json};
+use bit_operations::settings::{Settings, Theme};
+use bit_operations::Width;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Local storage key of the settings in the web build
+const STORAGE_KEY: &str = "bitwise.settings";
+
+/// Panel sections of the main view that can be reordered and hidden, by id
+/// and label, in default order
+pub const SECTIONS: [(&str, &str); 14] = [
+    ("grid", "Bit grid and byte dump"),
+    ("registers", "Register map, history and workspace"),
+    ("numbers", "Number fields and operations"),
+    ("calculator", "Two-operand calculator"),
+    ("fixed-point", "Fixed point and checksums"),
+    ("ieee754", "IEEE 754 and intervals"),
+    ("rounding", "Stochastic rounding and FMA"),
+    ("accumulation", "Accumulation and round trips"),
+    ("precision", "Precision explorer"),
+    ("plot", "Distribution plot"),
+    ("inspect", "Fuzzer, field editor, stats and watches"),
+    (
+        "encodings",
+        "ECC, color, varints, file mode and permutations",
+    ),
+    (
+        "decoders",
+        "Template, struct, packet and instruction decoders",
+    ),
+    ("tools", "Big integers, export, sessions and files"),
+];
+
+/// Returns the ids of [`SECTIONS`]
+fn section_ids() -> Vec<&'static str> {
+    SECTIONS.iter().map(|(id, _)| *id).collect()
+}
+
+/// Returns the default settings, with every section shown in default order
+pub fn defaults() -> Settings {
+    let mut settings = Settings::default();
+    settings.sections = settings.arrange(&section_ids());
+    settings
+}
+
+/// Returns the label of a section id
+fn section_label(id: &str) -> &'static str {
+    SECTIONS
+        .iter()
+        .find(|(i, _)| *i == id)
+        .map_or("", |(_, label)| label)
+}
+
+/// Returns the browser's local storage, if it is available
+fn storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+/// Loads the settings from the desktop backend's config file or, in the
+/// web build, local storage, with the sections arranged over [`SECTIONS`]
+async fn load() -> Result<Settings, String> {
+    let mut settings = if backend::is_desktop() {
+        call_json("load_settings", serde_json::json!({})).await?
+    } else {
+        match storage().and_then(|s| s.get_item(STORAGE_KEY).ok().flatten()) {
+            Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string())?,
+            None => Settings::default(),
+        }
+    };
+    settings.sections = settings.arrange(&section_ids());
+    Ok(settings)
+}
+
+/// Saves the settings where [`load`] reads them
+async fn save(settings: Settings) -> Result<(), String> {
+    if backend::is_desktop() {
+        call("save_settings", serde_json::json!({ "settings": settings })).await?;
+        return Ok(());
+    }
+    let json = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+    storage()
+        .ok_or("Local storage is not available")?
+        .set_item(STORAGE_KEY, &json)
+        .map_err(|_| "Could not write to local storage".to_string())
+}
+
+/// Settings Component
+///
+/// Preferences kept between runs: the colour theme, the bit size and input
+/// mode the viewer starts at, the case of hex digits, which panel sections
+/// are shown in what order and the watch list. Sections are reordered by dragging
+/// them in the list or with the arrow buttons. The settings are loaded on
+/// startup, handed to `start`, and saved on every change, to local storage
+/// in the web build and to `settings.json` in the app config folder in the
+/// desktop app.
+#[component]
+pub fn SettingsPanel(
+    settings: ReadSignal<Settings>,
+    set_settings: WriteSignal<Settings>,
+    start: Callback<Settings>,
+    open: ReadSignal<bool>,
+) -> impl IntoView {
+    let (status, set_status) = signal(String::new());
+    // Index of the section being dragged
+    let (dragging, set_dragging) = signal(None::<usize>);
+
+    // Settings are only saved once the stored ones were read
+    let loaded = StoredValue::new(false);
+    spawn_local(async move {
+        let result = load().await;
+        loaded.set_value(true);
+        match result {
+            Ok(stored) => {
+                set_settings.set(stored.clone());
+                start.run(stored);
+            }
+            Err(e) => set_status.set(format!("Could not load settings: {}", e)),
+        }
+    });
+
+    Effect::new(move |_| {
+        let current = settings.get();
+        if loaded.get_value() {
+            spawn_local(async move {
+                if let Err(e) = save(current).await {
+                    set_status.set(format!("Could not save settings: {}", e));
+                }
+            });
+        }
+    });
+
+    let move_section =
+        move |from: usize, to: usize| set_settings.update(|s| s.move_section(from, to));
+
+    let reset_layout = move |_| {
+        set_settings.update(|s| s.sections = defaults().sections);
+    };
+
+    view! {
+        <div class="settings-panel" class:section-hidden=move || !open.get()>
+            <div class="settings-options">
+                <label>
+                    "Theme:"
+                    <select on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(theme) = Theme::ALL.into_iter().find(|t| t.label() == label) {
+                            set_settings.update(|s| s.theme = theme);
+                        }
+                    }>
+                        {Theme::ALL
+                            .into_iter()
+                            .map(|theme| {
+                                view! {
+                                    <option
+                                        value=theme.label()
+                                        selected=move || settings.with(|s| s.theme == theme)
+                                    >
+                                        {theme.label()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+                <label title="Bit size and input mode on startup, unless a link or autosaved session sets them">
+                    "Start at:"
+                    <select on:change=move |ev| {
+                        let selected = event_target_value(&ev);
+                        if let Some(width) = Width::ALL
+                            .into_iter()
+                            .find(|w| w.to_string() == selected)
+                        {
+                            set_settings.update(|s| s.bit_size = width);
+                        }
+                    }>
+                        {Width::ALL
+                            .into_iter()
+                            .map(|width| {
+                                view! {
+                                    <option
+                                        value=width.to_string()
+                                        selected=move || settings.with(|s| s.bit_size == width)
+                                    >
+                                        {width.to_string()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings.with(|s| s.float)
+                        on:change=move |ev| {
+                            let checked = event_target_checked(&ev);
+                            set_settings.update(|s| s.float = checked);
+                        }
+                    />
+                    "Float"
+                </label>
+                <label>
+                    "Hex digits:"
+                    <select on:change=move |ev| {
+                        let lower = event_target_value(&ev) == "lower";
+                        set_settings.update(|s| s.lowercase_hex = lower);
+                    }>
+                        <option
+                            value="upper"
+                            selected=move || settings.with(|s| !s.lowercase_hex)
+                        >
+                            "0xABCD"
+                        </option>
+                        <option value="lower" selected=move || settings.with(|s| s.lowercase_hex)>
+                            "0xabcd"
+                        </option>
+                    </select>
+                </label>
+                <button class="bit-btn" on:click=reset_layout>
+                    "Reset layout"
+                </button>
+            </div>
+            <ol class="section-list">
+                {move || {
+                    let sections = settings.with(|s| s.sections.clone());
+                    let last = sections.len().saturating_sub(1);
+                    sections
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, section)| {
+                            view! {
+                                <li
+                                    draggable="true"
+                                    class:dragging=move || dragging.get() == Some(index)
+                                    on:dragstart=move |ev| {
+                                        if let Some(data) = ev.data_transfer() {
+                                            let _ = data.set_data("text/plain", &index.to_string());
+                                        }
+                                        set_dragging.set(Some(index));
+                                    }
+                                    on:dragover=move |ev| ev.prevent_default()
+                                    on:drop=move |ev| {
+                                        ev.prevent_default();
+                                        if let Some(from) = dragging.get_untracked() {
+                                            move_section(from, index);
+                                        }
+                                        set_dragging.set(None);
+                                    }
+                                    on:dragend=move |_| set_dragging.set(None)
+                                >
+                                    <input
+                                        type="checkbox"
+                                        title="Show this section"
+                                        prop:checked=section.visible
+                                        on:change=move |ev| {
+                                            let checked = event_target_checked(&ev);
+                                            set_settings
+                                                .update(|s| {
+                                                    if let Some(section) = s.sections.get_mut(index) {
+                                                        section.visible = checked;
+                                                    }
+                                                });
+                                        }
+                                    />
+                                    <span class="section-label">{section_label(&section.id)}</span>
+                                    <button
+                                        class="bit-btn"
+                                        title="Move up"
+                                        prop:disabled=index == 0
+                                        on:click=move |_| move_section(index, index.saturating_sub(1))
+                                    >
+                                        "↑"
+                                    </button>
+                                    <button
+                                        class="bit-btn"
+                                        title="Move down"
+                                        prop:disabled=index == last
+                                        on:click=move |_| move_section(index, index + 1)
+                                    >
+                                        "↓"
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ol>
+            <div class="template-error">{status}</div>
+        </div>
+    }
+}
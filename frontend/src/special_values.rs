@@ -1,64 +1,145 @@
 //! Special Value Generator Module
 
-use bit_operations::BitArray;
+use crate::ieee754::{float_format, IEEEFormat};
+use bit_operations::parse::parse_u64;
+use bit_operations::{patterns, BitArray, Width};
 use leptos::prelude::*;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
 
-const SPECIAL_VALUES: &[(&str, fn(u64) -> u64)] = &[
-    ("NaN (Quiet)", |bits| match bits {
-        16 => 0x7E00,
-        32 => 0x7FC00000,
-        64 => 0x7FF8000000000000,
-        _ => 0,
-    }),
-    ("NaN (Signaling)", |bits| match bits {
-        16 => 0x7C01,
-        32 => 0x7F800001,
-        64 => 0x7FF0000000000001,
-        _ => 0,
+/// Generates the bit pattern of a special value in the given float format,
+/// or `None` if the format has no such value
+type SpecialValueFn = fn(IEEEFormat) -> Option<u64>;
+
+const SPECIAL_VALUES: &[(&str, SpecialValueFn)] = &[
+    ("NaN (Quiet)", |format| {
+        Some(match format {
+            IEEEFormat::E4M3 => 0x7F,
+            IEEEFormat::E5M2 => 0x7E,
+            IEEEFormat::Half => 0x7E00,
+            IEEEFormat::BFloat16 => 0x7FC0,
+            IEEEFormat::Single => 0x7FC00000,
+            _ => 0x7FF8000000000000,
+        })
     }),
-    ("+Inf", |bits| match bits {
-        16 => 0x7C00,
-        32 => 0x7F800000,
-        64 => 0x7FF0000000000000,
-        _ => 0,
+    // E4M3 has a single NaN encoding
+    ("NaN (Signaling)", |format| match format {
+        IEEEFormat::E4M3 => None,
+        IEEEFormat::E5M2 => Some(0x7D),
+        IEEEFormat::Half => Some(0x7C01),
+        IEEEFormat::BFloat16 => Some(0x7F81),
+        IEEEFormat::Single => Some(0x7F800001),
+        _ => Some(0x7FF0000000000001),
     }),
-    ("-Inf", |bits| match bits {
-        16 => 0xFC00,
-        32 => 0xFF800000,
-        64 => 0xFFF0000000000000,
-        _ => 0,
+    ("+Inf", |format| match format {
+        IEEEFormat::E4M3 => None,
+        IEEEFormat::E5M2 => Some(0x7C),
+        IEEEFormat::Half => Some(0x7C00),
+        IEEEFormat::BFloat16 => Some(0x7F80),
+        IEEEFormat::Single => Some(0x7F800000),
+        _ => Some(0x7FF0000000000000),
     }),
-    ("+0", |_| 0),
-    ("-0", |bits| match bits {
-        16 => 0x8000,
-        32 => 0x80000000,
-        64 => 0x8000000000000000,
-        _ => 0,
+    ("-Inf", |format| match format {
+        IEEEFormat::E4M3 => None,
+        IEEEFormat::E5M2 => Some(0xFC),
+        IEEEFormat::Half => Some(0xFC00),
+        IEEEFormat::BFloat16 => Some(0xFF80),
+        IEEEFormat::Single => Some(0xFF800000),
+        _ => Some(0xFFF0000000000000),
     }),
-    ("Min Pos", |bits| match bits {
-        16 => 0x0001,
-        32 => 0x00000001,
-        64 => 0x0000000000000001,
-        _ => 0,
+    ("+0", |_| Some(0)),
+    ("-0", |format| {
+        Some(match format {
+            IEEEFormat::E4M3 | IEEEFormat::E5M2 => 0x80,
+            IEEEFormat::Half | IEEEFormat::BFloat16 => 0x8000,
+            IEEEFormat::Single => 0x80000000,
+            _ => 0x8000000000000000,
+        })
     }),
-    ("Max Pos", |bits| match bits {
-        16 => 0x7BFF,
-        32 => 0x7F7FFFFF,
-        64 => 0x7FEFFFFFFFFFFFFF,
-        _ => 0,
+    ("Min Pos", |_| Some(1)),
+    ("Max Pos", |format| {
+        Some(match format {
+            IEEEFormat::E4M3 => 0x7E,
+            IEEEFormat::E5M2 => 0x7B,
+            IEEEFormat::Half => 0x7BFF,
+            IEEEFormat::BFloat16 => 0x7F7F,
+            IEEEFormat::Single => 0x7F7FFFFF,
+            _ => 0x7FEFFFFFFFFFFFFF,
+        })
     }),
 ];
 
+/// Walking sequence stepped by the Next button
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Walk {
+    One,
+    Zero,
+}
+
 /// Special Value Generator Component
+///
+/// Float special values for the current format, and integer test patterns
+/// at the current bit size: random values, random values with a given
+/// popcount, alternating bits, walking one/zero sequences stepped with
+/// Next, and powers of two.
 #[component]
 pub fn SpecialValueGenerator(
     set_bit_array: WriteSignal<BitArray>,
-    bit_size: ReadSignal<u64>,
+    bit_size: ReadSignal<Width>,
+    sub_format: ReadSignal<IEEEFormat>,
 ) -> impl IntoView {
-    let mask = move || match bit_size.get() {
-        16 => 0xFFFFu64,
-        32 => 0xFFFFFFFFu64,
-        _ => u64::MAX,
+    let format = move || float_format(bit_size.get(), sub_format.get());
+    let rng = StoredValue::new(SmallRng::seed_from_u64(js_sys::Date::now() as u64));
+    let (count_input, set_count_input) = signal("1".to_string());
+    let (walk, set_walk) = signal(None::<(Walk, u32)>);
+    let (error, set_error) = signal(String::new());
+
+    let apply = move |value: u64| {
+        set_error.set(String::new());
+        set_bit_array.set(BitArray(value));
+    };
+    // Popcount or exponent typed next to the buttons
+    let count = move || {
+        let count = parse_u64(&count_input.get_untracked(), 10)
+            .ok()
+            .and_then(|n| u32::try_from(n).ok());
+        if count.is_none() {
+            set_error.set("invalid count".to_string());
+        }
+        count
+    };
+    let step_walk = move |kind: Walk, step: u32| {
+        let width = bit_size.get_untracked();
+        set_walk.set(Some((kind, step)));
+        apply(match kind {
+            Walk::One => patterns::walking_one(width, step),
+            Walk::Zero => patterns::walking_zero(width, step),
+        });
+    };
+    let random = move |_| {
+        let width = bit_size.get_untracked();
+        rng.update_value(|rng| apply(patterns::random(width, rng)));
+    };
+    let random_ones = move |_| {
+        let Some(ones) = count() else {
+            return;
+        };
+        let width = bit_size.get_untracked();
+        let mut value = None;
+        rng.update_value(|rng| value = patterns::random_with_popcount(width, ones, rng));
+        match value {
+            Some(value) => apply(value),
+            None => set_error.set(format!("more than {} ones", width.bits().min(64))),
+        }
+    };
+    let power = move |_| {
+        let Some(exponent) = count() else {
+            return;
+        };
+        match patterns::power_of_two(bit_size.get_untracked(), exponent) {
+            Some(value) => apply(value),
+            None => set_error.set(format!("2^{} does not fit", exponent)),
+        }
     };
 
     view! {
@@ -67,17 +148,70 @@ pub fn SpecialValueGenerator(
             </label>
             <div class="bit-operations">
                 {SPECIAL_VALUES.iter().map(|(name, gen)| {
-                    let value = move || gen(bit_size.get()) & mask();
+                    let value = move || format().and_then(gen);
                     view! {
                         <button
                             class="bit-btn"
-                            on:click=move |_| set_bit_array.set(BitArray(value()))
-                            disabled=move || value() == 0
+                            on:click=move |_| {
+                                if let Some(value) = value() {
+                                    set_bit_array.set(BitArray(value));
+                                }
+                            }
+                            prop:disabled=move || value().is_none()
                         >
                             {*name}
                         </button>
                     }
                 }).collect_view()}
             </div>
+            <div class="bit-operations">
+                <button class="bit-btn" on:click=random>
+                    "Random"
+                </button>
+                <input
+                    type="text"
+                    class="pattern-count"
+                    title="Popcount for Random N, exponent for 2^N"
+                    prop:value=count_input
+                    on:input=move |ev| set_count_input.set(event_target_value(&ev))
+                />
+                <button class="bit-btn" on:click=random_ones>
+                    "Random N"
+                </button>
+                <button class="bit-btn" on:click=power>
+                    "2^N"
+                </button>
+                <button
+                    class="bit-btn"
+                    on:click=move |_| apply(patterns::alternating(bit_size.get_untracked(), true))
+                >
+                    "0xAA…"
+                </button>
+                <button
+                    class="bit-btn"
+                    on:click=move |_| apply(patterns::alternating(bit_size.get_untracked(), false))
+                >
+                    "0x55…"
+                </button>
+                <button class="bit-btn" on:click=move |_| step_walk(Walk::One, 0)>
+                    "Walk 1"
+                </button>
+                <button class="bit-btn" on:click=move |_| step_walk(Walk::Zero, 0)>
+                    "Walk 0"
+                </button>
+                <button
+                    class="bit-btn"
+                    title="Next step of the walk"
+                    prop:disabled=move || walk.get().is_none()
+                    on:click=move |_| {
+                        if let Some((kind, step)) = walk.get_untracked() {
+                            step_walk(kind, (step + 1) % bit_size.get_untracked().bits().min(64));
+                        }
+                    }
+                >
+                    "Next"
+                </button>
+            </div>
+            <div class="input-error">{error}</div>
     }
 }
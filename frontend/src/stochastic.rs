@@ -0,0 +1,164 @@
+//! Stochastic Rounding Demonstration Module
+
+use bit_operations::parse::parse_f64;
+use bit_operations::rounding::{FloatFormat, RoundingMode};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// Outcome counts of repeated stochastic rounding
+#[derive(Debug, Clone, PartialEq)]
+struct TrialSummary {
+    down_bits: u64,
+    up_bits: u64,
+    down_value: f64,
+    up_value: f64,
+    up_count: usize,
+    trials: usize,
+    mean: f64,
+}
+
+/// Runs `trials` seeded stochastic roundings of `x` into `format`.
+fn run_trials(format: FloatFormat, x: f64, trials: usize, seed: u64) -> TrialSummary {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let down_bits = format.encode(x, RoundingMode::Down);
+    let up_bits = format.encode(x, RoundingMode::Up);
+    let (down_value, up_value) = (format.decode(down_bits), format.decode(up_bits));
+
+    let mut up_count = 0;
+    let mut sum = 0.0;
+    for _ in 0..trials {
+        let bits = format.encode_stochastic(x, rng.random::<f64>());
+        if bits == up_bits && up_bits != down_bits {
+            up_count += 1;
+        }
+        sum += format.decode(bits);
+    }
+
+    TrialSummary {
+        down_bits,
+        up_bits,
+        down_value,
+        up_value,
+        up_count,
+        trials,
+        mean: sum / trials.max(1) as f64,
+    }
+}
+
+/// Stochastic Rounding Component
+///
+/// Converts a typed decimal into the float format matching the current bit
+/// size (half or single) with stochastic rounding and shows how the outcomes
+/// of N seeded trials split between the two neighbouring values.
+#[component]
+pub fn StochasticRounding(
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (input, set_input) = signal("0.1".to_string());
+    let (trials, set_trials) = signal(1000usize);
+    let (seed, set_seed) = signal(42u64);
+
+    let format = move || match bit_size.get() {
+        Width::W16 => Some(FloatFormat::HALF),
+        Width::W32 => Some(FloatFormat::SINGLE),
+        _ => None,
+    };
+
+    let summary = move || {
+        let format = format()?;
+        let x = parse_f64(&input.get()).ok()?;
+        Some(run_trials(format, x, trials.get(), seed.get()))
+    };
+
+    let bar = move |count: usize, total: usize| {
+        format!("{}%", count as f64 * 100.0 / total.max(1) as f64)
+    };
+
+    view! {
+        <div class="stochastic-panel">
+            <label>
+                <span class="input-label">Stochastic rounding</span>
+            </label>
+            <div class="stochastic-inputs">
+                <label>
+                    "x "
+                    <input
+                        type="text"
+                        prop:value=input
+                        on:input=move |ev| set_input.set(event_target_value(&ev))
+                    />
+                </label>
+                <label>
+                    "N "
+                    <input
+                        type="number"
+                        min="1"
+                        max="100000"
+                        prop:value=move || trials.get().to_string()
+                        on:input=move |ev| {
+                            if let Ok(n) = event_target_value(&ev).parse::<usize>() {
+                                set_trials.set(n.clamp(1, 100_000));
+                            }
+                        }
+                    />
+                </label>
+                <label>
+                    "seed "
+                    <input
+                        type="number"
+                        prop:value=move || seed.get().to_string()
+                        on:input=move |ev| {
+                            if let Ok(s) = event_target_value(&ev).parse::<u64>() {
+                                set_seed.set(s);
+                            }
+                        }
+                    />
+                </label>
+            </div>
+            {move || match (format(), summary()) {
+                (None, _) => view! {
+                    <div class="stochastic-row">"Select 16 or 32 bit size"</div>
+                }.into_any(),
+                (Some(_), None) => view! {
+                    <div class="stochastic-row">"Invalid number"</div>
+                }.into_any(),
+                (Some(_), Some(s)) => {
+                    let down_count = s.trials - s.up_count;
+                    let (down_bits, up_bits) = (s.down_bits, s.up_bits);
+                    view! {
+                        <div class="stochastic-row">
+                            <button
+                                class="bit-btn"
+                                on:click=move |_| set_bit_array.set(BitArray(down_bits))
+                            >
+                                {format!("↓ {:e} (0x{:X})", s.down_value, s.down_bits)}
+                            </button>
+                            <div class="stochastic-bar">
+                                <div style:width=bar(down_count, s.trials)></div>
+                            </div>
+                            {format!("{} / {}", down_count, s.trials)}
+                        </div>
+                        <div class="stochastic-row">
+                            <button
+                                class="bit-btn"
+                                on:click=move |_| set_bit_array.set(BitArray(up_bits))
+                            >
+                                {format!("↑ {:e} (0x{:X})", s.up_value, s.up_bits)}
+                            </button>
+                            <div class="stochastic-bar">
+                                <div style:width=bar(s.up_count, s.trials)></div>
+                            </div>
+                            {format!("{} / {}", s.up_count, s.trials)}
+                        </div>
+                        <div class="stochastic-row">
+                            {format!("Mean of outcomes: {:e}", s.mean)}
+                        </div>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
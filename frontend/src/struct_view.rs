@@ -0,0 +1,297 @@
+//! Struct Overlay Module
+
+use crate::backend::call_json;
+use crate::file_view::FileWindow;
+use crate::history::Snapshot;
+use crate::template_view::parse_hex_blob;
+use crate::InputMode;
+use bit_operations::bitvec::BitVec;
+use bit_operations::layout::{FieldValue, Layout, Scalar, ScalarKind};
+use bit_operations::parse::parse_u64;
+use bit_operations::template::Endian;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+const DEFAULT_LAYOUT: &str = "struct header {
+    uint8_t  flags;
+    uint16_t len;
+    uint32_t crc;
+};";
+
+/// Where the overlaid bytes come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    /// The current value's bytes, least significant first
+    Value,
+    /// Pasted hex bytes
+    Hex,
+    /// A window of the file opened in the file browser
+    File,
+}
+
+/// Short type name such as `u16` or `f32`
+fn scalar_name(scalar: Scalar) -> String {
+    let prefix = match scalar.kind {
+        ScalarKind::Unsigned => 'u',
+        ScalarKind::Signed => 'i',
+        ScalarKind::Float => 'f',
+    };
+    format!("{}{}", prefix, scalar.size * 8)
+}
+
+/// Formats `data[range]` as hex bytes, or nothing past its end
+fn hex_bytes(data: &[u8], offset: usize, len: usize) -> String {
+    data.iter()
+        .skip(offset)
+        .take(len)
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Struct Overlay Component
+///
+/// Lays out a C-like struct description (`u8 flags; u16 len; u32 crc;`)
+/// with natural alignment or `#pragma pack` packing and slices the current
+/// value's bytes, pasted hex bytes or a window of the open file at a byte
+/// offset into its fields, with the padding shown between them. Clicking a
+/// field loads it into the grid at its own bit size, in Float mode for
+/// `float`/`double` fields.
+#[component]
+pub fn StructOverlay(
+    bit_array: ReadSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+    file_path: ReadSignal<String>,
+    load: Callback<Snapshot>,
+) -> impl IntoView {
+    let (source_text, set_source_text) = signal(DEFAULT_LAYOUT.to_string());
+    let (pack, set_pack) = signal(8usize);
+    let (little_endian, set_little_endian) = signal(true);
+    let (source, set_source) = signal(Source::Value);
+    let (hex_input, set_hex_input) = signal(String::new());
+    let (offset_text, set_offset_text) = signal("0".to_string());
+    let (file_bytes, set_file_bytes) = signal(Vec::<u8>::new());
+    let (status, set_status) = signal(String::new());
+
+    let layout = move || Layout::parse(&source_text.get(), pack.get());
+
+    let data = move || match source.get() {
+        Source::Value => {
+            let width = bit_size.get();
+            Some(if width.word_count() > 1 {
+                bit_vec.with(BitVec::to_le_bytes)
+            } else {
+                width.le_bytes(bit_array.get().0)
+            })
+        }
+        Source::Hex => parse_hex_blob(&hex_input.get()),
+        Source::File => Some(file_bytes.get()),
+    };
+
+    // Reads as many bytes as the struct needs from the file offset
+    let read = move || {
+        let offset = match parse_u64(&offset_text.get_untracked(), 10) {
+            Ok(offset) => offset,
+            Err(e) => {
+                set_status.set(format!("Offset: {}", e));
+                return;
+            }
+        };
+        let Ok(layout) = layout() else {
+            return;
+        };
+        let args = serde_json::json!({
+            "path": file_path.get_untracked(),
+            "offset": offset,
+            "len": layout.size,
+        });
+        spawn_local(async move {
+            match call_json::<FileWindow>("read_file_window", args).await {
+                Ok(window) => {
+                    set_status.set(String::new());
+                    set_file_bytes.set(window.bytes);
+                }
+                Err(e) => set_status.set(e),
+            }
+        });
+    };
+
+    let open = move |value: &FieldValue| {
+        let (Some(bits), Some(width)) =
+            (value.bits, Width::from_bits(value.scalar.size as u32 * 8))
+        else {
+            return;
+        };
+        load.run(Snapshot {
+            value: BitVec::from_words(&[bits], width.bits() as usize),
+            bit_size: width,
+            input_mode: if value.scalar.kind == ScalarKind::Float {
+                InputMode::Float
+            } else {
+                InputMode::Integer
+            },
+        });
+    };
+
+    let summary = move || match layout() {
+        Ok(layout) => {
+            let padding: usize = layout.padding.iter().map(|r| r.len()).sum();
+            format!(
+                "{} bytes, aligned to {}, {} padding",
+                layout.size, layout.align, padding
+            )
+        }
+        Err(e) => e.to_string(),
+    };
+
+    let table = move || {
+        let Ok(layout) = layout() else {
+            return None;
+        };
+        let Some(data) = data() else {
+            return Some(
+                view! { <div class="template-error">"Invalid hex bytes"</div> }.into_any(),
+            );
+        };
+        let endian = if little_endian.get() {
+            Endian::Little
+        } else {
+            Endian::Big
+        };
+        // Fields and padding in offset order
+        let mut rows: Vec<(usize, AnyView)> = layout
+            .decode(&data, endian)
+            .into_iter()
+            .map(|value| {
+                let offset = value.offset;
+                let size = value.scalar.size;
+                let row = view! {
+                    <tr
+                        class="struct-field"
+                        class:missing=value.bits.is_none()
+                        title="Load into the grid"
+                        on:click={
+                            let value = value.clone();
+                            move |_| open(&value)
+                        }
+                    >
+                        <td>{format!("{:#X}", offset)}</td>
+                        <td class="template-name">{value.name.clone()}</td>
+                        <td>{scalar_name(value.scalar)}</td>
+                        <td>{hex_bytes(&data, offset, size)}</td>
+                        <td>{value.display()}</td>
+                    </tr>
+                };
+                (offset, row.into_any())
+            })
+            .collect();
+        rows.extend(layout.padding.iter().map(|range| {
+            let row = view! {
+                <tr class="struct-padding">
+                    <td>{format!("{:#X}", range.start)}</td>
+                    <td>"(padding)"</td>
+                    <td>{format!("{} bytes", range.len())}</td>
+                    <td>{hex_bytes(&data, range.start, range.len())}</td>
+                    <td></td>
+                </tr>
+            };
+            (range.start, row.into_any())
+        }));
+        rows.sort_by_key(|(offset, _)| *offset);
+        Some(
+            view! {
+                <table class="precision-table struct-table">
+                    <tr>
+                        <th>"Offset"</th>
+                        <th>"Field"</th>
+                        <th>"Type"</th>
+                        <th>"Bytes"</th>
+                        <th>"Value"</th>
+                    </tr>
+                    {rows.into_iter().map(|(_, row)| row).collect_view()}
+                </table>
+            }
+            .into_any(),
+        )
+    };
+
+    view! {
+        <div class="template-decoder struct-overlay">
+            <label>
+                <span class="input-label">Struct</span>
+                <select on:change=move |ev| {
+                    set_pack.set(event_target_value(&ev).parse().unwrap_or(8))
+                }>
+                    <option value="8" selected=move || pack.get() == 8>
+                        "natural"
+                    </option>
+                    <option value="4" selected=move || pack.get() == 4>
+                        "pack(4)"
+                    </option>
+                    <option value="2" selected=move || pack.get() == 2>
+                        "pack(2)"
+                    </option>
+                    <option value="1" selected=move || pack.get() == 1>
+                        "packed"
+                    </option>
+                </select>
+                <select
+                    class="byte-order"
+                    on:change=move |ev| set_little_endian.set(event_target_value(&ev) == "le")
+                >
+                    <option value="le" selected=little_endian>
+                        "LE"
+                    </option>
+                    <option value="be" selected=move || !little_endian.get()>
+                        "BE"
+                    </option>
+                </select>
+                <select on:change=move |ev| {
+                    set_source.set(match event_target_value(&ev).as_str() {
+                        "hex" => Source::Hex,
+                        "file" => Source::File,
+                        _ => Source::Value,
+                    })
+                }>
+                    <option value="value">"current value"</option>
+                    <option value="hex">"hex bytes"</option>
+                    <option value="file">"file"</option>
+                </select>
+            </label>
+            <textarea
+                class="template-source"
+                rows="6"
+                prop:value=source_text
+                on:input=move |ev| set_source_text.set(event_target_value(&ev))
+            ></textarea>
+            <Show when=move || source.get() == Source::Hex>
+                <input
+                    type="text"
+                    placeholder="hex bytes, e.g. 01 00 08 00 DE AD BE EF"
+                    prop:value=hex_input
+                    on:input=move |ev| set_hex_input.set(event_target_value(&ev))
+                />
+            </Show>
+            <Show when=move || source.get() == Source::File>
+                <div class="file-nav">
+                    <input
+                        type="text"
+                        class="file-offset"
+                        title="Byte offset of the struct in the file, decimal or 0x hex"
+                        prop:value=offset_text
+                        on:input=move |ev| set_offset_text.set(event_target_value(&ev))
+                        on:change=move |_| read()
+                    />
+                    <button class="bit-btn" on:click=move |_| read()>
+                        "Read"
+                    </button>
+                </div>
+            </Show>
+            <div class="template-magic">{summary}</div>
+            <div class="template-error">{status}</div>
+            {table}
+        </div>
+    }
+}
@@ -0,0 +1,138 @@
+//! Binary Template Decoder Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::magic::MagicDb;
+use bit_operations::template::{Node, NodeValue, Template};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+const DEFAULT_TEMPLATE: &str = "endian little;
+u8 kind;
+u8 flags;
+u16 length;
+if (kind == 1) {
+    u32 crc;
+} else {
+    u8 payload[4];
+}";
+
+/// Parses a hex blob such as `DE AD BE EF` or `0xdeadbeef` into bytes.
+pub fn parse_hex_blob(input: &str) -> Option<Vec<u8>> {
+    let digits: String = input
+        .replace("0x", "")
+        .replace("0X", "")
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',' && *c != ':')
+        .collect();
+    hex::decode(digits).ok()
+}
+
+/// Renders decoded nodes as a nested list.
+fn render_nodes(nodes: Vec<Node>) -> AnyView {
+    view! {
+        <ul class="template-tree">
+            {nodes.into_iter().map(|node| {
+                let location = match &node.bits {
+                    Some(bits) => format!("@{} [{}] bits {}–{}", node.offset, node.size, bits.start, bits.end - 1),
+                    None => format!("@{} [{}]", node.offset, node.size),
+                };
+                match node.value {
+                    NodeValue::Unsigned(v) => view! {
+                        <li>
+                            <span class="template-name">{node.name}</span>
+                            " = " {format!("{} (0x{:X})", v, v)}
+                            <span class="template-offset">{location}</span>
+                        </li>
+                    }.into_any(),
+                    NodeValue::Signed(v) => view! {
+                        <li>
+                            <span class="template-name">{node.name}</span>
+                            " = " {v.to_string()}
+                            <span class="template-offset">{location}</span>
+                        </li>
+                    }.into_any(),
+                    NodeValue::Array(children) => view! {
+                        <li>
+                            <span class="template-name">{node.name}</span>
+                            <span class="template-offset">{location}</span>
+                            {render_nodes(children)}
+                        </li>
+                    }.into_any(),
+                }
+            }).collect_view()}
+        </ul>
+    }
+    .into_any()
+}
+
+/// Template Decoder Component
+///
+/// Decodes pasted bytes (or the active bytes of the current value, little
+/// endian, when no bytes are given) with a user-authored binary template and
+/// identifies the file format from their leading magic number.
+#[component]
+pub fn TemplateDecoder(
+    bit_array: ReadSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (source, set_source) = signal(DEFAULT_TEMPLATE.to_string());
+    let (bytes_input, set_bytes_input) = signal(String::new());
+    let magic = StoredValue::new(MagicDb::builtin());
+
+    let data = move || {
+        let input = bytes_input.get();
+        if input.trim().is_empty() {
+            let width = bit_size.get();
+            Some(if width.word_count() > 1 {
+                bit_vec.with(BitVec::to_le_bytes)
+            } else {
+                width.le_bytes(bit_array.get().0)
+            })
+        } else {
+            parse_hex_blob(&input)
+        }
+    };
+
+    let format_name = move || {
+        let data = data()?;
+        magic.with_value(|db| db.identify(&data).map(|sig| sig.name.clone()))
+    };
+
+    let result = move || {
+        let Some(data) = data() else {
+            return view! { <div class="template-error">"Invalid hex bytes"</div> }.into_any();
+        };
+        match Template::parse(&source.get()).and_then(|t| t.decode(&data)) {
+            Ok(nodes) => render_nodes(nodes),
+            Err(err) => view! { <div class="template-error">{err.to_string()}</div> }.into_any(),
+        }
+    };
+
+    view! {
+        <div class="template-decoder">
+            <label>
+                <span class="input-label">Template</span>
+            </label>
+            <textarea
+                class="template-source"
+                rows="8"
+                prop:value=source
+                on:input=move |ev| set_source.set(event_target_value(&ev))
+            ></textarea>
+            <label>
+                <span class="input-label">Bytes</span>
+                <input
+                    type="text"
+                    placeholder="hex bytes, empty = current value (LE)"
+                    prop:value=bytes_input
+                    on:input=move |ev| set_bytes_input.set(event_target_value(&ev))
+                />
+            </label>
+            <div class="template-magic">
+                "Format: " {move || format_name().unwrap_or_else(|| "unknown".to_string())}
+            </div>
+            {result}
+        </div>
+    }
+}
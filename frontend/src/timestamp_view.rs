@@ -0,0 +1,95 @@
+//! Unix Timestamp Module
+
+use bit_operations::timestamp::{DateTime, TimeUnit};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Unix Timestamp Component
+///
+/// Number panel row reading the current value as a signed Unix timestamp in
+/// seconds or milliseconds, or as an unsigned Windows `FILETIME` or NTP
+/// seconds count, shown as a UTC date. Typing a date such as
+/// `2024-02-29 13:45:07` writes the matching timestamp.
+#[component]
+pub fn TimestampRow(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (unit, set_unit) = signal(TimeUnit::Seconds);
+    let (text, set_text) = signal(String::new());
+    let (editing, set_editing) = signal(false);
+
+    // Effect to show the current value's date unless the field is being edited
+    Effect::new(move |_| {
+        let width = bit_size.get();
+        let unit = unit.get();
+        let bits = bit_array.get();
+        if editing.get() {
+            return;
+        }
+        let value = if unit.is_signed() {
+            bits.as_signed(width)
+        } else {
+            i64::try_from(bits.0).unwrap_or(i64::MAX)
+        };
+        set_text.set(if width.bits() > 64 {
+            "n/a beyond 64 bits".to_string()
+        } else {
+            DateTime::from_unix(value, unit).to_string()
+        });
+    });
+
+    let input = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_text.set(input.clone());
+        let width = bit_size.get();
+        let unit = unit.get();
+        let value = DateTime::parse(&input).and_then(|date| date.to_unix(unit));
+        if let Some(value) = value {
+            let fits = if unit.is_signed() {
+                BitArray::from_signed(value, width).as_signed(width) == value
+            } else {
+                value >= 0 && value as u64 & !width.mask() == 0
+            };
+            if width.bits() <= 64 && fits {
+                set_bit_array.set(BitArray::from_signed(value, width));
+            }
+        }
+    };
+
+    view! {
+        <label>
+            <span class="input-label">TIME</span>
+            <input
+                type="text"
+                title="Timestamp as UTC, e.g. 2024-02-29 13:45:07"
+                prop:value=text
+                prop:disabled=move || bit_size.get().word_count() != 1
+                on:input=input
+                on:focus=move |_| set_editing.set(true)
+                on:blur=move |_| set_editing.set(false)
+            />
+            <select
+                class="time-unit"
+                on:change=move |ev| {
+                    let value = event_target_value(&ev);
+                    if let Some(u) = TimeUnit::ALL.into_iter().find(|u| u.to_string() == value) {
+                        set_unit.set(u);
+                    }
+                }
+            >
+                {TimeUnit::ALL
+                    .into_iter()
+                    .map(|u| {
+                        view! {
+                            <option value=u.to_string() selected=move || unit.get() == u>
+                                {u.to_string()}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+        </label>
+    }
+}
@@ -0,0 +1,98 @@
+//! UTF-16/UTF-32 Text Module
+
+use bit_operations::bitvec::BitVec;
+use bit_operations::unicode::Encoding;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Unicode Text Component
+///
+/// Number panel row reading the value's big-endian bytes as UTF-16 or UTF-32
+/// text, like the UTF-8 row, with leading NUL characters dropped as padding.
+/// Unpaired surrogates and out-of-range code points show as U+FFFD and mark
+/// the row invalid. Typing text writes its encoding right-aligned in the
+/// value, zero-padded on the left.
+#[component]
+pub fn UnicodeRow(
+    encoding: Encoding,
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_vec: ReadSignal<BitVec>,
+    set_bit_vec: WriteSignal<BitVec>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+    let (editing, set_editing) = signal(false);
+
+    // Invalid code units in the current value
+    let (invalid, set_invalid) = signal(Vec::<u32>::new());
+
+    // Effect to decode the current value unless the field is being edited
+    Effect::new(move |_| {
+        let width = bit_size.get();
+        let bytes = if width.word_count() > 1 {
+            bit_vec.get().to_be_bytes()
+        } else {
+            width.be_bytes(bit_array.get().0)
+        };
+        let mut errors = Vec::new();
+        let decoded: String = encoding
+            .decode(&bytes)
+            .skip_while(|c| *c == Ok('\0'))
+            .map(|c| {
+                c.unwrap_or_else(|unit| {
+                    errors.push(unit);
+                    char::REPLACEMENT_CHARACTER
+                })
+            })
+            .collect();
+        set_invalid.set(errors);
+        if !editing.get() {
+            set_text.set(decoded);
+        }
+    });
+
+    let input = move |ev: web_sys::Event| {
+        let input = event_target_value(&ev);
+        set_text.set(input.clone());
+        let width = bit_size.get();
+        let mut bytes = vec![0; width.bytes()];
+        let Some(len) = encoding.encode(&input, &mut bytes) else {
+            return;
+        };
+        let padding = bytes.len() - len;
+        bytes.rotate_right(padding);
+        if width.word_count() > 1 {
+            if let Some(value) = BitVec::from_be_bytes(&bytes, width.bits() as usize) {
+                set_bit_vec.set(value);
+            }
+        } else if let Some(value) = width.from_be_bytes(&bytes) {
+            set_bit_array.set(BitArray(value));
+        }
+    };
+
+    let status = move || {
+        invalid.with(|units| {
+            if units.is_empty() {
+                return String::new();
+            }
+            let units: Vec<String> = units.iter().map(|u| format!("{:#06X}", u)).collect();
+            format!("invalid: {}", units.join(", "))
+        })
+    };
+
+    view! {
+        <label>
+            <span class="input-label">{encoding.to_string()}</span>
+            <input
+                type="text"
+                title="Characters encoded in the value's big-endian bytes"
+                prop:value=text
+                on:input=input
+                on:focus=move |_| set_editing.set(true)
+                on:blur=move |_| set_editing.set(false)
+            />
+            <span class="repr-status">{status}</span>
+        </label>
+    }
+}
@@ -0,0 +1,182 @@
+//! LEB128 Varint Module
+
+use crate::template_view::parse_hex_blob;
+use bit_operations::varint;
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// Varint encodings shown by the panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Unsigned,
+    Signed,
+    ZigZag,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::Unsigned, Kind::Signed, Kind::ZigZag];
+
+    fn label(self) -> &'static str {
+        match self {
+            Kind::Unsigned => "ULEB128",
+            Kind::Signed => "SLEB128",
+            Kind::ZigZag => "ZigZag",
+        }
+    }
+
+    /// Encodes the value at `width`, read as signed for the signed kinds
+    fn encode(self, value: BitArray, width: Width) -> Vec<u8> {
+        let varint = match self {
+            Kind::Unsigned => varint::encode_unsigned(width.truncate(value.0)),
+            Kind::Signed => varint::encode_signed(value.as_signed(width)),
+            Kind::ZigZag => varint::encode_unsigned(varint::zigzag(value.as_signed(width))),
+        };
+        varint.as_bytes().to_vec()
+    }
+
+    /// Decodes a varint into the bits of a `width`-bit value and the number
+    /// of bytes read
+    fn decode(self, bytes: &[u8], width: Width) -> Result<(u64, usize), String> {
+        let too_wide = || format!("value does not fit in {} bits", width.bits());
+        let (value, len) = match self {
+            Kind::Unsigned => {
+                let (value, len) = varint::decode_unsigned(bytes).map_err(|e| e.to_string())?;
+                if width.truncate(value) != value {
+                    return Err(too_wide());
+                }
+                return Ok((value, len));
+            }
+            Kind::Signed => varint::decode_signed(bytes),
+            Kind::ZigZag => {
+                varint::decode_unsigned(bytes).map(|(v, len)| (varint::unzigzag(v), len))
+            }
+        }
+        .map_err(|e| e.to_string())?;
+        let bits = BitArray::from_signed(value, width);
+        if bits.as_signed(width) != value {
+            return Err(too_wide());
+        }
+        Ok((bits.0, len))
+    }
+}
+
+/// Formats bytes as spaced hex pairs
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Varint Component
+///
+/// Shows the current value as unsigned LEB128, signed LEB128 and zigzag
+/// LEB128 (Protocol Buffers `sint`) bytes, and loads a pasted varint byte
+/// sequence in the chosen encoding. Up to 64 bits.
+#[component]
+pub fn VarintPanel(
+    bit_array: ReadSignal<BitArray>,
+    set_bit_array: WriteSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+) -> impl IntoView {
+    let (input, set_input) = signal(String::new());
+    let (kind, set_kind) = signal(Kind::Unsigned);
+
+    // Result of decoding the pasted bytes
+    let decoded = move || {
+        let input = input.get();
+        if input.trim().is_empty() {
+            return None;
+        }
+        Some(match parse_hex_blob(&input) {
+            Some(bytes) => kind
+                .get()
+                .decode(&bytes, bit_size.get())
+                .map(|(value, len)| (value, len, bytes.len())),
+            None => Err("expected hex bytes such as AC 02".to_string()),
+        })
+    };
+
+    let load = move |_| {
+        if let Some(Ok((value, _, _))) = decoded() {
+            set_bit_array.set(BitArray(value));
+        }
+    };
+
+    let status = move || match decoded() {
+        None => String::new(),
+        Some(Err(e)) => e,
+        Some(Ok((value, len, total))) => {
+            let width = bit_size.get();
+            let shown = match kind.get() {
+                Kind::Unsigned => value.to_string(),
+                _ => BitArray(value).as_signed(width).to_string(),
+            };
+            if len == total {
+                format!("= {}", shown)
+            } else {
+                format!("= {} ({} of {} bytes read)", shown, len, total)
+            }
+        }
+    };
+
+    view! {
+        <div class="varint-panel">
+            <label>
+                <span class="input-label">Varint</span>
+            </label>
+            <Show
+                when=move || bit_size.get().word_count() == 1
+                fallback=|| view! { <div>"Varints cover sizes up to 64 bits"</div> }
+            >
+                <table class="varint-table">
+                    {Kind::ALL
+                        .into_iter()
+                        .map(|k| {
+                            view! {
+                                <tr>
+                                    <td>{k.label()}</td>
+                                    <td>{move || hex_bytes(&k.encode(bit_array.get(), bit_size.get()))}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </table>
+                <div class="varint-load">
+                    <select on:change=move |ev| {
+                        let label = event_target_value(&ev);
+                        if let Some(k) = Kind::ALL.into_iter().find(|k| k.label() == label) {
+                            set_kind.set(k);
+                        }
+                    }>
+                        {Kind::ALL
+                            .into_iter()
+                            .map(|k| {
+                                view! {
+                                    <option value=k.label() selected=move || kind.get() == k>
+                                        {k.label()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <input
+                        type="text"
+                        placeholder="AC 02"
+                        prop:value=input
+                        on:input=move |ev| set_input.set(event_target_value(&ev))
+                    />
+                    <button
+                        class="bit-btn"
+                        prop:disabled=move || !matches!(decoded(), Some(Ok(_)))
+                        on:click=load
+                    >
+                        "Load"
+                    </button>
+                </div>
+                <div class="varint-status">{status}</div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,174 @@
+//! Watch Expression Module
+
+use bit_operations::arith::ArithMode;
+use bit_operations::expr::{self, ExprError, Watch, WatchBase};
+use bit_operations::{BitArray, Width};
+use leptos::prelude::*;
+
+/// A watch's expression, its result and whether the result differs from
+/// the previous evaluation
+type WatchResult = (String, Result<u64, ExprError>, bool);
+
+/// Returns the width expressions are evaluated at; beyond 64 bits they see
+/// the low word
+fn eval_width(width: Width) -> Width {
+    if width.word_count() > 1 {
+        Width::W64
+    } else {
+        width
+    }
+}
+
+/// Watch Component
+///
+/// Debugger-style watch list of expressions over the current value, such
+/// as `x & 0xFF`, `(x >> 12) & 0xF` or `popcount(x)`, re-evaluated on every
+/// change and shown in a base chosen per watch. Results that changed with
+/// the last edit are highlighted. Arithmetic follows the global
+/// [`ArithMode`]. The list is kept in saved sessions.
+/// Beyond 64 bits the expressions see the low 64 bits.
+#[component]
+pub fn WatchPanel(
+    bit_array: ReadSignal<BitArray>,
+    bit_size: ReadSignal<Width>,
+    arith_mode: ReadSignal<ArithMode>,
+    watches: ReadSignal<Vec<Watch>>,
+    set_watches: WriteSignal<Vec<Watch>>,
+) -> impl IntoView {
+    let (text, set_text) = signal(String::new());
+
+    let results = Memo::new(move |previous: Option<&Vec<WatchResult>>| {
+        let width = eval_width(bit_size.get());
+        let x = bit_array.get().0;
+        let mode = arith_mode.get();
+        watches.with(|w| {
+            w.iter()
+                .enumerate()
+                .map(|(i, watch)| {
+                    let result = expr::evaluate(&watch.expression, x, width, mode);
+                    // A watch only counts as changed against its own last result
+                    let changed =
+                        previous
+                            .and_then(|p| p.get(i))
+                            .is_some_and(|(expression, last, _)| {
+                                *expression == watch.expression && *last != result
+                            });
+                    (watch.expression.clone(), result, changed)
+                })
+                .collect()
+        })
+    });
+
+    let add = move || {
+        let expression = text.get_untracked().trim().to_string();
+        if expression.is_empty() {
+            return;
+        }
+        set_watches.update(|w| {
+            w.push(Watch {
+                expression,
+                base: WatchBase::default(),
+            })
+        });
+        set_text.set(String::new());
+    };
+
+    view! {
+        <div class="watch-panel">
+            <label>
+                <span class="input-label">Watch</span>
+                <input
+                    type="text"
+                    placeholder="(x >> 12) & 0xF, popcount(x)"
+                    title="Expression over the current value x, as in the EXPR row; Enter adds it"
+                    prop:value=text
+                    on:input=move |ev| set_text.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" {
+                            add();
+                        }
+                    }
+                />
+                <button class="bit-btn" on:click=move |_| add()>
+                    "Add"
+                </button>
+            </label>
+            <table class="watch-table">
+                {move || {
+                    watches
+                        .get()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, watch)| {
+                            let result = move || results.with(|r| r.get(index).cloned());
+                            let changed = move || result().is_some_and(|(_, _, c)| c);
+                            view! {
+                                <tr class:changed=changed>
+                                    <td class="template-name">{watch.expression.clone()}</td>
+                                    <td class="watch-value">
+                                        {move || match result() {
+                                            Some((_, Ok(value), _)) => {
+                                                watch.base.format(value, eval_width(bit_size.get()))
+                                            }
+                                            Some((_, Err(e), _)) => e.to_string(),
+                                            None => String::new(),
+                                        }}
+                                    </td>
+                                    <td>
+                                        <select
+                                            class="byte-order"
+                                            on:change=move |ev| {
+                                                let label = event_target_value(&ev);
+                                                if let Some(base) = WatchBase::ALL
+                                                    .into_iter()
+                                                    .find(|b| b.label() == label)
+                                                {
+                                                    set_watches
+                                                        .update(|w| {
+                                                            if let Some(watch) = w.get_mut(index) {
+                                                                watch.base = base;
+                                                            }
+                                                        });
+                                                }
+                                            }
+                                        >
+                                            {WatchBase::ALL
+                                                .into_iter()
+                                                .map(|base| {
+                                                    view! {
+                                                        <option
+                                                            value=base.label()
+                                                            selected=watch.base == base
+                                                        >
+                                                            {base.label()}
+                                                        </option>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </select>
+                                    </td>
+                                    <td>
+                                        <button
+                                            class="bit-btn"
+                                            title="Remove this watch"
+                                            on:click=move |_| {
+                                                set_watches
+                                                    .update(|w| {
+                                                        if index < w.len() {
+                                                            w.remove(index);
+                                                        }
+                                                    })
+                                            }
+                                        >
+                                            "×"
+                                        </button>
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </table>
+        </div>
+    }
+}
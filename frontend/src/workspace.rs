@@ -0,0 +1,297 @@
+//! Workspace Module
+
+use crate::history::Snapshot;
+use bit_operations::bitvec::BitVec;
+use bit_operations::session::SavedSlot;
+use leptos::prelude::*;
+
+/// Most slots a workspace holds, one per letter
+const MAX_SLOTS: usize = 26;
+
+/// A named slot and the state saved in it, if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub name: String,
+    pub state: Option<Snapshot>,
+}
+
+/// Named value slots A, B, C, ... with one active slot being edited
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    slots: Vec<Slot>,
+    active: usize,
+}
+
+impl Workspace {
+    /// Creates a workspace with `count` empty slots, the first one active
+    pub fn new(count: usize) -> Self {
+        let mut workspace = Self {
+            slots: Vec::new(),
+            active: 0,
+        };
+        for _ in 0..count.clamp(1, MAX_SLOTS) {
+            workspace.add();
+        }
+        workspace
+    }
+
+    /// Creates a workspace from saved slots; without any, it gets `count`
+    /// empty slots
+    pub fn from_saved(saved: Vec<SavedSlot>, active: usize, count: usize) -> Self {
+        if saved.is_empty() {
+            return Self::new(count);
+        }
+        let slots: Vec<Slot> = saved
+            .into_iter()
+            .take(MAX_SLOTS)
+            .map(|slot| Slot {
+                name: slot.name,
+                state: slot.value.map(Snapshot::from_saved),
+            })
+            .collect();
+        Self {
+            active: active.min(slots.len() - 1),
+            slots,
+        }
+    }
+
+    /// Converts the slots to the form kept in saved sessions, with
+    /// `current` in the active slot
+    pub fn to_saved(&self, current: &Snapshot) -> Vec<SavedSlot> {
+        (0..self.slots.len())
+            .map(|index| SavedSlot {
+                name: self.slots[index].name.clone(),
+                value: self.state(index, Some(current)).map(|s| s.to_saved()),
+            })
+            .collect()
+    }
+
+    /// Returns the slots in order
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    /// Returns the index of the active slot
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Returns `true` if another slot can be added
+    pub fn can_add(&self) -> bool {
+        self.slots.len() < MAX_SLOTS
+    }
+
+    /// Appends an empty slot named after the next letter
+    pub fn add(&mut self) {
+        if self.can_add() {
+            let name = char::from(b'A' + self.slots.len() as u8).to_string();
+            self.slots.push(Slot { name, state: None });
+        }
+    }
+
+    /// Saves `state` in slot `index`
+    pub fn save(&mut self, index: usize, state: Snapshot) {
+        if let Some(slot) = self.slots.get_mut(index) {
+            slot.state = Some(state);
+        }
+    }
+
+    /// Makes slot `index` active, first saving `current` in the slot being
+    /// left; an empty slot starts from `current`.
+    ///
+    /// # Returns
+    /// - The state to load, or `None` if `index` is out of range.
+    pub fn switch(&mut self, index: usize, current: Snapshot) -> Option<Snapshot> {
+        let target = self.slots.get(index)?.state.clone();
+        self.save(self.active, current.clone());
+        self.active = index;
+        let state = target.unwrap_or(current);
+        self.save(index, state.clone());
+        Some(state)
+    }
+
+    /// Returns the state of slot `index`, using `current` for the active
+    /// slot since it is being edited
+    pub fn state(&self, index: usize, current: Option<&Snapshot>) -> Option<Snapshot> {
+        if index == self.active {
+            return current.cloned();
+        }
+        self.slots.get(index)?.state.clone()
+    }
+}
+
+/// Returns the bits that differ between two values, zero-extending the
+/// shorter one
+pub fn diff(a: &BitVec, b: &BitVec) -> BitVec {
+    let len = a.len().max(b.len());
+    let words: Vec<u64> = (0..len.div_ceil(64))
+        .map(|i| a.word(i) ^ b.word(i))
+        .collect();
+    BitVec::from_words(&words, len)
+}
+
+/// Workspace Component
+///
+/// Lists the workspace slots; clicking a slot's name switches to it,
+/// keeping the current value in the slot being left, and Save copies the
+/// current value into a slot. Comparing two slots underlines the bits where
+/// they differ in the grid.
+#[component]
+pub fn WorkspacePanel(
+    workspace: ReadSignal<Workspace>,
+    set_workspace: WriteSignal<Workspace>,
+    state: Memo<Option<Snapshot>>,
+    load: Callback<Snapshot>,
+    set_diff: WriteSignal<Option<BitVec>>,
+) -> impl IntoView {
+    let (compare, set_compare) = signal(false);
+    let (left, set_left) = signal(0usize);
+    let (right, set_right) = signal(1usize);
+
+    let switch = move |index: usize| {
+        let Some(current) = state.get_untracked() else {
+            return;
+        };
+        if let Some(snapshot) = set_workspace
+            .try_update(|w| w.switch(index, current))
+            .flatten()
+        {
+            load.run(snapshot);
+        }
+    };
+
+    let save = move |index: usize| {
+        if let Some(current) = state.get_untracked() {
+            set_workspace.update(|w| w.save(index, current));
+        }
+    };
+
+    // Bits differing between the compared slots, if both hold a value
+    let differing = move || {
+        let current = state.get();
+        workspace.with(|w| {
+            let a = w.state(left.get(), current.as_ref())?;
+            let b = w.state(right.get(), current.as_ref())?;
+            Some(diff(&a.value, &b.value))
+        })
+    };
+
+    // Effect to underline the differing bits in the grid while comparing
+    Effect::new(move |_| {
+        set_diff.set(if compare.get() { differing() } else { None });
+    });
+
+    let slot_select = move |selected: ReadSignal<usize>, set_selected: WriteSignal<usize>| {
+        view! {
+            <select
+                class="workspace-slot"
+                on:change=move |ev| {
+                    if let Ok(index) = event_target_value(&ev).parse() {
+                        set_selected.set(index);
+                    }
+                }
+            >
+                {move || {
+                    workspace
+                        .with(|w| {
+                            w.slots()
+                                .iter()
+                                .enumerate()
+                                .map(|(index, slot)| {
+                                    view! {
+                                        <option
+                                            value=index.to_string()
+                                            selected=move || selected.get() == index
+                                        >
+                                            {slot.name.clone()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                }}
+            </select>
+        }
+    };
+
+    let status = move || {
+        if !compare.get() {
+            return String::new();
+        }
+        match differing() {
+            Some(bits) => format!("{} bits differ", bits.count_ones()),
+            None => "both slots need a value".to_string(),
+        }
+    };
+
+    view! {
+        <div class="workspace-panel">
+            <label>
+                <span class="input-label">Workspace</span>
+                <button
+                    class="bit-btn"
+                    prop:disabled=move || !workspace.with(Workspace::can_add)
+                    on:click=move |_| set_workspace.update(Workspace::add)
+                >
+                    "+ Slot"
+                </button>
+            </label>
+            <table class="workspace-slots">
+                {move || {
+                    let current = state.get();
+                    workspace
+                        .with(|w| {
+                            w.slots()
+                                .iter()
+                                .enumerate()
+                                .map(|(index, slot)| {
+                                    let value = w
+                                        .state(index, current.as_ref())
+                                        .map(|s| format!("{} ({})", s.hex(), s.bit_size))
+                                        .unwrap_or_else(|| "empty".to_string());
+                                    view! {
+                                        <tr class:current=index == w.active()>
+                                            <td>
+                                                <button
+                                                    class="bit-btn"
+                                                    title="Switch to this slot"
+                                                    on:click=move |_| switch(index)
+                                                >
+                                                    {slot.name.clone()}
+                                                </button>
+                                            </td>
+                                            <td>{value}</td>
+                                            <td>
+                                                <button
+                                                    class="bit-btn"
+                                                    title="Copy the current value into this slot"
+                                                    prop:disabled=index == w.active()
+                                                    on:click=move |_| save(index)
+                                                >
+                                                    "Save"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                }}
+            </table>
+            <div class="workspace-compare">
+                <label>
+                    <input
+                        type="checkbox"
+                        prop:checked=compare
+                        on:change=move |ev| set_compare.set(event_target_checked(&ev))
+                    />
+                    "Compare"
+                </label>
+                {slot_select(left, set_left)}
+                "with"
+                {slot_select(right, set_right)}
+                <span class="repr-status">{status}</span>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,78 @@
+//! Bit Pattern Search Benchmarks
+//!
+//! Times [`search::find`] over a 1 MiB random buffer for a few pattern
+//! shapes, next to a naive scan that compares the pattern bit by bit at
+//! every offset. Run with `cargo bench -p bit_operations`.
+
+use bit_operations::search::{self, BitPattern};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+/// Size of the searched buffer in bytes
+const BUFFER_LEN: usize = 1 << 20;
+
+/// Returns a reproducible buffer of random bytes
+fn buffer() -> Vec<u8> {
+    let mut data = vec![0; BUFFER_LEN];
+    SmallRng::seed_from_u64(0x5EED).fill_bytes(&mut data);
+    data
+}
+
+/// Counts matches by reading every bit of every candidate offset
+fn naive_count(data: &[u8], pattern: BitPattern) -> usize {
+    let total = data.len() as u64 * 8;
+    let len = u64::from(pattern.len);
+    let bit = |i: u64| u64::from(data[(i / 8) as usize] >> (7 - i % 8) & 1);
+    (0..=total.saturating_sub(len))
+        .filter(|&offset| {
+            (0..len).all(|i| {
+                let shift = len - 1 - i;
+                pattern.mask >> shift & 1 == 0 || bit(offset + i) == pattern.value >> shift & 1
+            })
+        })
+        .count()
+}
+
+fn bench_find(c: &mut Criterion) {
+    let data = buffer();
+    let patterns = [
+        ("byte", BitPattern::parse("0xA5").unwrap()),
+        ("nibble-wildcards", BitPattern::parse("0b1?1?").unwrap()),
+        ("32-bit", BitPattern::parse("0xDEADBEEF").unwrap()),
+        (
+            "64-bit-masked",
+            BitPattern::parse("0xCAFE????????BABE").unwrap(),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("find");
+    group.throughput(Throughput::Bytes(BUFFER_LEN as u64));
+    for (name, pattern) in patterns {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &pattern, |b, &p| {
+            b.iter(|| search::find(black_box(&data), p).count())
+        });
+    }
+    group.finish();
+
+    // The naive scan is slow enough that a slice of the buffer suffices
+    let slice = &data[..BUFFER_LEN / 16];
+    let mut group = c.benchmark_group("naive");
+    group.throughput(Throughput::Bytes(slice.len() as u64));
+    group.sample_size(10);
+    for (name, pattern) in patterns {
+        assert_eq!(
+            search::find(slice, pattern).count(),
+            naive_count(slice, pattern),
+            "{} disagrees with the naive scan",
+            name
+        );
+        group.bench_with_input(BenchmarkId::from_parameter(name), &pattern, |b, &p| {
+            b.iter(|| naive_count(black_box(slice), p))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_find);
+criterion_main!(benches);
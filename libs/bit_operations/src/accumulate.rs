@@ -0,0 +1,117 @@
+//! Floating Point Accumulation
+//!
+//! Step-by-step summation of a sequence in a chosen [`FloatFormat`], naively
+//! and with Kahan compensation, next to an exact reference sum.
+
+use crate::rounding::FloatFormat;
+use alloc::vec::Vec;
+
+/// Exact sum of `f64` values.
+///
+/// Keeps a list of non-overlapping partial sums (Shewchuk's algorithm, as in
+/// Python's `math.fsum`), so no information is lost until [`value`] rounds the
+/// total once.
+///
+/// [`value`]: ExactSum::value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExactSum {
+    partials: Vec<f64>,
+}
+
+impl ExactSum {
+    /// Creates an empty sum.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `x` exactly.
+    pub fn add(&mut self, mut x: f64) {
+        let mut kept = 0;
+        for i in 0..self.partials.len() {
+            let mut y = self.partials[i];
+            if x.abs() < y.abs() {
+                core::mem::swap(&mut x, &mut y);
+            }
+            let hi = x + y;
+            let lo = y - (hi - x);
+            if lo != 0.0 {
+                self.partials[kept] = lo;
+                kept += 1;
+            }
+            x = hi;
+        }
+        self.partials.truncate(kept);
+        self.partials.push(x);
+    }
+
+    /// Returns the exact sum correctly rounded to `f64`.
+    pub fn value(&self) -> f64 {
+        let mut rest = self.partials.iter().rev().copied().peekable();
+        let mut hi = rest.next().unwrap_or(0.0);
+        while let Some(y) = rest.next() {
+            let x = hi;
+            hi = x + y;
+            let lo = y - (hi - x);
+            if lo != 0.0 {
+                // A remainder of exactly half an ulp is decided by the sign of
+                // the next partial, which the plain sum cannot see
+                if rest.peek().is_some_and(|&next| (lo < 0.0) == (next < 0.0)) {
+                    let twice = lo * 2.0;
+                    let rounded = hi + twice;
+                    if twice == rounded - hi {
+                        hi = rounded;
+                    }
+                }
+                break;
+            }
+        }
+        hi
+    }
+}
+
+/// Running sums after one step of an accumulation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccumulationStep {
+    /// Naive running sum, as a bit pattern of the format.
+    pub naive: u64,
+    /// Kahan-compensated running sum, as a bit pattern of the format.
+    pub kahan: u64,
+    /// Exact running sum of the terms, rounded to `f64`.
+    pub exact: f64,
+}
+
+/// Sums `terms` repeated `repeat` times in `format`.
+///
+/// # Arguments
+/// - `format`: The format every intermediate sum is rounded to.
+/// - `terms`: The sequence as bit patterns of `format`.
+/// - `repeat`: How many times the sequence is added.
+///
+/// # Returns
+/// One [`AccumulationStep`] per added term.
+pub fn accumulate(format: FloatFormat, terms: &[u64], repeat: usize) -> Vec<AccumulationStep> {
+    let mut naive = 0u64;
+    let mut kahan = 0u64;
+    let mut compensation = 0u64;
+    let mut exact = ExactSum::new();
+    let mut steps = Vec::with_capacity(terms.len() * repeat);
+
+    for &term in terms.iter().cycle().take(terms.len() * repeat) {
+        naive = format.add(naive, term);
+
+        // Kahan: y = term - c; t = sum + y; c = (t - sum) - y; sum = t
+        let y = format.add(term, format.neg(compensation));
+        let t = format.add(kahan, y);
+        compensation = format.add(format.add(t, format.neg(kahan)), format.neg(y));
+        kahan = t;
+
+        exact.add(format.decode(term));
+        steps.push(AccumulationStep {
+            naive,
+            kahan,
+            exact: exact.value(),
+        });
+    }
+    steps
+}
@@ -0,0 +1,476 @@
+//! Width-Aware Integer Arithmetic
+//!
+//! Unsigned ([`apply`]) and two's complement ([`apply_signed`]) arithmetic
+//! on values of a given [`Width`] with a selectable [`ArithMode`] deciding
+//! what happens at the boundaries (e.g. `UINT_MAX + 1` or `INT_MAX + 1`),
+//! and a two-operand [`alu`] reporting CPU-style carry and overflow flags.
+
+use crate::Width;
+use core::fmt;
+
+/// Boundary behaviour of arithmetic operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArithMode {
+    /// Results wrap around modulo 2^width.
+    #[default]
+    Wrapping,
+    /// Results clamp to the smallest or largest representable value.
+    Saturating,
+    /// Out-of-range results are reported as an error.
+    Checked,
+}
+
+impl ArithMode {
+    /// All modes in display order.
+    pub const ALL: [ArithMode; 3] = [
+        ArithMode::Wrapping,
+        ArithMode::Saturating,
+        ArithMode::Checked,
+    ];
+}
+
+impl fmt::Display for ArithMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ArithMode::Wrapping => "Wrapping",
+            ArithMode::Saturating => "Saturating",
+            ArithMode::Checked => "Checked",
+        })
+    }
+}
+
+/// Binary arithmetic operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArithOp {
+    /// Addition.
+    Add,
+    /// Subtraction.
+    Sub,
+    /// Multiplication.
+    Mul,
+    /// Division, truncating toward zero.
+    Div,
+}
+
+impl fmt::Display for ArithOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "×",
+            ArithOp::Div => "÷",
+        })
+    }
+}
+
+/// Which boundary an out-of-range result crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// The exact result exceeded the largest value.
+    Above,
+    /// The exact result fell below the smallest value.
+    Below,
+}
+
+/// Result of an arithmetic operation that did not fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArithResult {
+    /// The value after wrapping or saturating.
+    pub value: u64,
+    /// The boundary crossed by the exact result, if any.
+    pub overflow: Option<Overflow>,
+}
+
+/// Errors reported by arithmetic operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArithError {
+    /// The exact result is out of range in [`ArithMode::Checked`].
+    Overflow(Overflow),
+    /// The divisor is zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for ArithError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArithError::Overflow(Overflow::Above) => f.write_str("overflow above maximum"),
+            ArithError::Overflow(Overflow::Below) => f.write_str("overflow below minimum"),
+            ArithError::DivisionByZero => f.write_str("division by zero"),
+        }
+    }
+}
+
+/// Applies `op` to two unsigned values of the given width.
+///
+/// # Arguments
+/// - `op`: The operation.
+/// - `a`, `b`: The operands; bits above `width` are ignored.
+/// - `width`: The value width.
+/// - `mode`: What to do with out-of-range results.
+///
+/// # Returns
+/// - `Ok(result)` with the wrapped or saturated value and the boundary
+///   crossed, if any.
+/// - `Err(ArithError)` on division by zero, or on overflow in
+///   [`ArithMode::Checked`].
+pub fn apply(
+    op: ArithOp,
+    a: u64,
+    b: u64,
+    width: Width,
+    mode: ArithMode,
+) -> Result<ArithResult, ArithError> {
+    let (a, b) = (width.truncate(a) as u128, width.truncate(b) as u128);
+    let max = width.mask() as u128;
+
+    let (exact, overflow) = match op {
+        ArithOp::Add => (a + b, None),
+        ArithOp::Sub if b > a => (a.wrapping_sub(b), Some(Overflow::Below)),
+        ArithOp::Sub => (a - b, None),
+        ArithOp::Mul => (a * b, None),
+        ArithOp::Div if b == 0 => return Err(ArithError::DivisionByZero),
+        ArithOp::Div => (a / b, None),
+    };
+    let overflow = overflow.or((exact > max).then_some(Overflow::Above));
+
+    let value = match (overflow, mode) {
+        (None, _) | (Some(_), ArithMode::Wrapping) => exact as u64 & width.mask(),
+        (Some(Overflow::Above), ArithMode::Saturating) => width.mask(),
+        (Some(Overflow::Below), ArithMode::Saturating) => 0,
+        (Some(o), ArithMode::Checked) => return Err(ArithError::Overflow(o)),
+    };
+    Ok(ArithResult { value, overflow })
+}
+
+/// Applies `op` to two two's complement values of the given width.
+///
+/// # Arguments
+/// - `op`: The operation.
+/// - `a`, `b`: The operands as raw bits; bits above `width` are ignored.
+/// - `width`: The value width; sizes beyond 64 bits act on the low 64.
+/// - `mode`: What to do with out-of-range results.
+///
+/// # Returns
+/// - `Ok(result)` with the raw bits of the wrapped or saturated value and
+///   the boundary crossed, if any; saturation clamps to the signed minimum
+///   or maximum, e.g. `0x7F + 1` gives `0x7F` at 8 bits.
+/// - `Err(ArithError)` on division by zero, or on overflow in
+///   [`ArithMode::Checked`]. `MIN / -1` overflows above the maximum.
+pub fn apply_signed(
+    op: ArithOp,
+    a: u64,
+    b: u64,
+    width: Width,
+    mode: ArithMode,
+) -> Result<ArithResult, ArithError> {
+    let bits = width.bits().min(64);
+    let signed = |value: u64| i128::from(((value << (64 - bits)) as i64) >> (64 - bits));
+    let (a, b) = (signed(a), signed(b));
+    let max = (1i128 << (bits - 1)) - 1;
+    let min = -max - 1;
+
+    let exact = match op {
+        ArithOp::Add => a + b,
+        ArithOp::Sub => a - b,
+        ArithOp::Mul => a * b,
+        ArithOp::Div if b == 0 => return Err(ArithError::DivisionByZero),
+        ArithOp::Div => a / b,
+    };
+    let overflow = if exact > max {
+        Some(Overflow::Above)
+    } else if exact < min {
+        Some(Overflow::Below)
+    } else {
+        None
+    };
+
+    let value = match (overflow, mode) {
+        (None, _) | (Some(_), ArithMode::Wrapping) => exact as u64 & width.mask(),
+        (Some(Overflow::Above), ArithMode::Saturating) => max as u64 & width.mask(),
+        (Some(Overflow::Below), ArithMode::Saturating) => min as u64 & width.mask(),
+        (Some(o), ArithMode::Checked) => return Err(ArithError::Overflow(o)),
+    };
+    Ok(ArithResult { value, overflow })
+}
+
+/// Two-operand operations of a simple ALU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AluOp {
+    /// Bitwise AND.
+    And,
+    /// Bitwise OR.
+    Or,
+    /// Bitwise XOR.
+    Xor,
+    /// Wrapping addition.
+    Add,
+    /// Wrapping subtraction.
+    Sub,
+    /// Logical shift left by the second operand.
+    Shl,
+    /// Logical shift right by the second operand.
+    Shr,
+}
+
+impl AluOp {
+    /// All operations in display order.
+    pub const ALL: [AluOp; 7] = [
+        AluOp::And,
+        AluOp::Or,
+        AluOp::Xor,
+        AluOp::Add,
+        AluOp::Sub,
+        AluOp::Shl,
+        AluOp::Shr,
+    ];
+}
+
+impl fmt::Display for AluOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AluOp::And => "&",
+            AluOp::Or => "|",
+            AluOp::Xor => "^",
+            AluOp::Add => "+",
+            AluOp::Sub => "−",
+            AluOp::Shl => "<<",
+            AluOp::Shr => ">>",
+        })
+    }
+}
+
+/// Status flags of an ALU operation, as in a CPU flags register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flags {
+    /// Unsigned carry out of an addition, borrow of a subtraction, or the
+    /// last bit shifted out.
+    pub carry: bool,
+    /// Signed (two's complement) overflow of an addition or subtraction.
+    pub overflow: bool,
+    /// The result is zero.
+    pub zero: bool,
+    /// The sign bit of the result is set.
+    pub negative: bool,
+}
+
+/// Result of an ALU operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AluResult {
+    /// The result, wrapped to the width.
+    pub value: u64,
+    /// Flags describing the result.
+    pub flags: Flags,
+}
+
+/// Applies a two-operand ALU operation to values of the given width.
+///
+/// # Arguments
+/// - `op`: The operation.
+/// - `a`, `b`: The operands; bits above `width` are ignored. For shifts `b`
+///   is the shift count, taken in full rather than truncated to the width,
+///   and counts of the width or more shift out every bit.
+/// - `width`: The value width; sizes beyond 64 bits act on the low 64.
+///
+/// # Returns
+/// The wrapped result with its carry/borrow, overflow, zero and negative
+/// flags. Carry and overflow are always clear for AND, OR and XOR.
+pub fn alu(op: AluOp, a: u64, b: u64, width: Width) -> AluResult {
+    let bits = width.bits().min(64);
+    let count = b;
+    let (a, b) = (width.truncate(a), width.truncate(b));
+    let sign = 1u64 << (bits - 1);
+    let bit = |value: u64, pos: u64| pos < u64::from(bits) && (value >> pos) & 1 == 1;
+
+    let (value, carry, overflow) = match op {
+        AluOp::And => (a & b, false, false),
+        AluOp::Or => (a | b, false, false),
+        AluOp::Xor => (a ^ b, false, false),
+        AluOp::Add => {
+            let (sum, wrapped) = a.overflowing_add(b);
+            let value = width.truncate(sum);
+            let carry = wrapped || sum != value;
+            (value, carry, (a ^ value) & (b ^ value) & sign != 0)
+        }
+        AluOp::Sub => {
+            let value = width.truncate(a.wrapping_sub(b));
+            (value, b > a, (a ^ b) & (a ^ value) & sign != 0)
+        }
+        AluOp::Shl => match count {
+            0 => (a, false, false),
+            n if n >= u64::from(bits) => (0, n == u64::from(bits) && bit(a, 0), false),
+            n => (width.truncate(a << n), bit(a, u64::from(bits) - n), false),
+        },
+        AluOp::Shr => match count {
+            0 => (a, false, false),
+            n if n >= u64::from(bits) => (0, n == u64::from(bits) && bit(a, n - 1), false),
+            n => (a >> n, bit(a, n - 1), false),
+        },
+    };
+    AluResult {
+        value,
+        flags: Flags {
+            carry,
+            overflow,
+            zero: value == 0,
+            negative: value & sign != 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned(op: ArithOp, a: u64, b: u64, mode: ArithMode) -> Result<u64, ArithError> {
+        apply(op, a, b, Width::W8, mode).map(|r| r.value)
+    }
+
+    fn signed(op: ArithOp, a: i8, b: i8, mode: ArithMode) -> Result<i8, ArithError> {
+        apply_signed(op, a as u8 as u64, b as u8 as u64, Width::W8, mode).map(|r| r.value as i8)
+    }
+
+    #[test]
+    fn unsigned_boundaries_follow_the_mode() {
+        use ArithMode::*;
+        assert_eq!(unsigned(ArithOp::Add, 0xFF, 1, Wrapping), Ok(0));
+        assert_eq!(unsigned(ArithOp::Add, 0xFF, 1, Saturating), Ok(0xFF));
+        assert_eq!(
+            unsigned(ArithOp::Add, 0xFF, 1, Checked),
+            Err(ArithError::Overflow(Overflow::Above))
+        );
+        assert_eq!(unsigned(ArithOp::Sub, 0, 1, Wrapping), Ok(0xFF));
+        assert_eq!(unsigned(ArithOp::Sub, 0, 1, Saturating), Ok(0));
+        assert_eq!(
+            unsigned(ArithOp::Sub, 0, 1, Checked),
+            Err(ArithError::Overflow(Overflow::Below))
+        );
+        assert_eq!(unsigned(ArithOp::Mul, 0x10, 0x10, Saturating), Ok(0xFF));
+        assert_eq!(unsigned(ArithOp::Div, 7, 2, Checked), Ok(3));
+        assert_eq!(
+            unsigned(ArithOp::Div, 7, 0, Wrapping),
+            Err(ArithError::DivisionByZero)
+        );
+        let result = apply(ArithOp::Add, u64::MAX, 1, Width::W64, Saturating).unwrap();
+        assert_eq!(result.value, u64::MAX);
+        assert_eq!(result.overflow, Some(Overflow::Above));
+    }
+
+    #[test]
+    fn signed_boundaries_follow_the_mode() {
+        use ArithMode::*;
+        assert_eq!(signed(ArithOp::Add, i8::MAX, 1, Wrapping), Ok(i8::MIN));
+        assert_eq!(signed(ArithOp::Add, i8::MAX, 1, Saturating), Ok(i8::MAX));
+        assert_eq!(
+            signed(ArithOp::Add, i8::MAX, 1, Checked),
+            Err(ArithError::Overflow(Overflow::Above))
+        );
+        assert_eq!(signed(ArithOp::Sub, i8::MIN, 1, Wrapping), Ok(i8::MAX));
+        assert_eq!(signed(ArithOp::Sub, i8::MIN, 1, Saturating), Ok(i8::MIN));
+        assert_eq!(
+            signed(ArithOp::Sub, i8::MIN, 1, Checked),
+            Err(ArithError::Overflow(Overflow::Below))
+        );
+        assert_eq!(signed(ArithOp::Mul, -16, 16, Saturating), Ok(i8::MIN));
+        assert_eq!(signed(ArithOp::Mul, -16, -16, Saturating), Ok(i8::MAX));
+        assert_eq!(signed(ArithOp::Div, i8::MIN, -1, Wrapping), Ok(i8::MIN));
+        assert_eq!(
+            signed(ArithOp::Div, i8::MIN, -1, Checked),
+            Err(ArithError::Overflow(Overflow::Above))
+        );
+        assert_eq!(signed(ArithOp::Div, -7, 2, Checked), Ok(-3));
+        assert_eq!(
+            signed(ArithOp::Div, 1, 0, Saturating),
+            Err(ArithError::DivisionByZero)
+        );
+        // In range for signed, out of range for unsigned
+        assert_eq!(signed(ArithOp::Sub, 0, 1, Checked), Ok(-1));
+    }
+
+    #[test]
+    fn signed_results_at_64_bits() {
+        let result = apply_signed(
+            ArithOp::Add,
+            i64::MAX as u64,
+            1,
+            Width::W64,
+            ArithMode::Saturating,
+        )
+        .unwrap();
+        assert_eq!(result.value, i64::MAX as u64);
+        assert_eq!(result.overflow, Some(Overflow::Above));
+        let result = apply_signed(
+            ArithOp::Mul,
+            i64::MIN as u64,
+            1,
+            Width::W64,
+            ArithMode::Checked,
+        )
+        .unwrap();
+        assert_eq!(result.value, i64::MIN as u64);
+        assert_eq!(result.overflow, None);
+    }
+
+    #[test]
+    fn shift_counts_are_not_truncated_to_the_width() {
+        for op in [AluOp::Shl, AluOp::Shr] {
+            let result = alu(op, 0xFF, 256, Width::W8);
+            assert_eq!(result.value, 0, "{}", op);
+            assert!(!result.flags.carry);
+            assert!(result.flags.zero);
+            assert_eq!(alu(op, 0xFFFF, 0x1_0000, Width::W16).value, 0);
+            assert_eq!(alu(op, 0xFF, 0x101, Width::W8).value, 0);
+        }
+    }
+
+    #[test]
+    fn shifts_set_carry_to_the_last_bit_out() {
+        assert_eq!(alu(AluOp::Shl, 0x81, 1, Width::W8).value, 0x02);
+        assert!(alu(AluOp::Shl, 0x81, 1, Width::W8).flags.carry);
+        assert!(alu(AluOp::Shl, 0x01, 8, Width::W8).flags.carry);
+        assert!(!alu(AluOp::Shl, 0x01, 9, Width::W8).flags.carry);
+        assert_eq!(alu(AluOp::Shr, 0x81, 1, Width::W8).value, 0x40);
+        assert!(alu(AluOp::Shr, 0x81, 1, Width::W8).flags.carry);
+        assert!(alu(AluOp::Shr, 0x80, 8, Width::W8).flags.carry);
+        assert_eq!(alu(AluOp::Shl, 5, 0, Width::W8).value, 5);
+        assert!(alu(AluOp::Shr, u64::MAX, 64, Width::W64).flags.carry);
+    }
+
+    #[test]
+    fn add_and_sub_report_carry_and_overflow() {
+        let add = alu(AluOp::Add, 0xFF, 1, Width::W8);
+        assert_eq!(add.value, 0);
+        assert!(add.flags.carry && add.flags.zero && !add.flags.overflow);
+        let add = alu(AluOp::Add, 0x7F, 1, Width::W8);
+        assert_eq!(add.value, 0x80);
+        assert!(!add.flags.carry && add.flags.overflow && add.flags.negative);
+        let sub = alu(AluOp::Sub, 0, 1, Width::W8);
+        assert_eq!(sub.value, 0xFF);
+        assert!(sub.flags.carry && !sub.flags.overflow);
+        let sub = alu(AluOp::Sub, 0x80, 1, Width::W8);
+        assert!(!sub.flags.carry && sub.flags.overflow);
+        assert!(alu(AluOp::Add, u64::MAX, 1, Width::W64).flags.carry);
+    }
+
+    #[test]
+    fn logic_ops_ignore_bits_above_the_width() {
+        let result = alu(AluOp::Or, 0x1F0, 0x20F, Width::W8);
+        assert_eq!(result.value, 0xFF);
+        assert_eq!(
+            result.flags,
+            Flags {
+                negative: true,
+                ..Flags::default()
+            }
+        );
+        assert_eq!(alu(AluOp::And, 0xF0, 0x0F, Width::W8).value, 0);
+        assert_eq!(alu(AluOp::Xor, 0xFF, 0x0F, Width::W8).value, 0xF0);
+    }
+}
@@ -0,0 +1,201 @@
+//! Base64 and Base32
+//!
+//! RFC 4648 Base64 and Base32 encoding of byte sequences. Encoding uses the
+//! standard alphabets with `=` padding; decoding is lenient about padding,
+//! whitespace, the case of Base32 letters and the URL-safe Base64 characters
+//! `-` and `_`, but rejects truncated input and nonzero trailing bits.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A binary-to-text encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BaseEncoding {
+    /// Base64, 6 bits per character.
+    Base64,
+    /// Base32, 5 bits per character.
+    Base32,
+}
+
+impl BaseEncoding {
+    /// All encodings in display order.
+    pub const ALL: [BaseEncoding; 2] = [BaseEncoding::Base64, BaseEncoding::Base32];
+
+    /// Returns the number of bits per character.
+    fn char_bits(self) -> u32 {
+        match self {
+            BaseEncoding::Base64 => 6,
+            BaseEncoding::Base32 => 5,
+        }
+    }
+
+    /// Returns the number of characters the output is padded to a multiple
+    /// of.
+    fn group_len(self) -> usize {
+        match self {
+            BaseEncoding::Base64 => 4,
+            BaseEncoding::Base32 => 8,
+        }
+    }
+
+    fn alphabet(self) -> &'static [u8] {
+        match self {
+            BaseEncoding::Base64 => BASE64_ALPHABET,
+            BaseEncoding::Base32 => BASE32_ALPHABET,
+        }
+    }
+
+    /// Returns the value of an encoded character.
+    fn digit(self, c: char) -> Option<u32> {
+        match (self, c) {
+            (BaseEncoding::Base64, '-') => Some(62),
+            (BaseEncoding::Base64, '_') => Some(63),
+            (BaseEncoding::Base32, c) => {
+                let c = c.to_ascii_uppercase();
+                BASE32_ALPHABET.iter().position(|&a| char::from(a) == c)
+            }
+            (BaseEncoding::Base64, c) => BASE64_ALPHABET.iter().position(|&a| char::from(a) == c),
+        }
+        .map(|d| d as u32)
+    }
+
+    /// Encodes `bytes` with `=` padding.
+    pub fn encode(self, bytes: &[u8]) -> String {
+        let bits = self.char_bits();
+        let alphabet = self.alphabet();
+        let mut text = String::new();
+        let (mut buffer, mut count) = (0u32, 0);
+        for &byte in bytes {
+            buffer = buffer << 8 | u32::from(byte);
+            count += 8;
+            while count >= bits {
+                count -= bits;
+                text.push(char::from(
+                    alphabet[(buffer >> count) as usize & ((1 << bits) - 1)],
+                ));
+            }
+        }
+        if count > 0 {
+            let index = (buffer << (bits - count)) as usize & ((1 << bits) - 1);
+            text.push(char::from(alphabet[index]));
+        }
+        while text.len() % self.group_len() != 0 {
+            text.push('=');
+        }
+        text
+    }
+
+    /// Decodes `text`, ignoring whitespace and trailing `=` padding.
+    ///
+    /// # Returns
+    /// - The decoded bytes, or `None` for characters outside the alphabet,
+    ///   a length no encoder produces, or nonzero bits after the last byte.
+    pub fn decode(self, text: &str) -> Option<Vec<u8>> {
+        let bits = self.char_bits();
+        let text = text.trim_end_matches(|c: char| c == '=' || c.is_whitespace());
+        let mut bytes = Vec::new();
+        let (mut buffer, mut count) = (0u32, 0);
+        for c in text.chars().filter(|c| !c.is_whitespace()) {
+            buffer = (buffer << bits | self.digit(c)?) & 0xffff;
+            count += bits;
+            if count >= 8 {
+                count -= 8;
+                bytes.push((buffer >> count) as u8);
+            }
+        }
+        (count < bits && buffer & ((1 << count) - 1) == 0).then_some(bytes)
+    }
+}
+
+impl fmt::Display for BaseEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BaseEncoding::Base64 => "Base64",
+            BaseEncoding::Base32 => "Base32",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Width;
+
+    #[test]
+    fn matches_rfc_4648_vectors() {
+        let vectors = [
+            ("", "", ""),
+            ("f", "Zg==", "MY======"),
+            ("fo", "Zm8=", "MZXQ===="),
+            ("foo", "Zm9v", "MZXW6==="),
+            ("foob", "Zm9vYg==", "MZXW6YQ="),
+            ("fooba", "Zm9vYmE=", "MZXW6YTB"),
+            ("foobar", "Zm9vYmFy", "MZXW6YTBOI======"),
+        ];
+        for (plain, base64, base32) in vectors {
+            assert_eq!(BaseEncoding::Base64.encode(plain.as_bytes()), base64);
+            assert_eq!(BaseEncoding::Base32.encode(plain.as_bytes()), base32);
+            assert_eq!(
+                BaseEncoding::Base64.decode(base64).unwrap(),
+                plain.as_bytes()
+            );
+            assert_eq!(
+                BaseEncoding::Base32.decode(base32).unwrap(),
+                plain.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_every_width() {
+        for width in Width::ALL {
+            let len = width.bits() as usize / 8;
+            let patterns: [Vec<u8>; 4] = [
+                alloc::vec![0; len],
+                alloc::vec![0xFF; len],
+                (0..len as u8).map(|i| i.wrapping_mul(37) ^ 0xA5).collect(),
+                (0..len as u8).map(|i| 0x80 >> (i % 8)).collect(),
+            ];
+            for encoding in BaseEncoding::ALL {
+                for bytes in &patterns {
+                    let text = encoding.encode(bytes);
+                    assert_eq!(text.len() % encoding.group_len(), 0);
+                    assert_eq!(
+                        encoding.decode(&text).as_ref(),
+                        Some(bytes),
+                        "{} {}",
+                        encoding,
+                        text
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_leniently() {
+        assert_eq!(
+            BaseEncoding::Base64.decode("Zm9v\nYmFy").unwrap(),
+            b"foobar"
+        );
+        assert_eq!(BaseEncoding::Base64.decode("Zg").unwrap(), b"f");
+        assert_eq!(BaseEncoding::Base64.decode("-_8=").unwrap(), [0xFB, 0xFF]);
+        assert_eq!(BaseEncoding::Base32.decode("mzxw6ytb").unwrap(), b"fooba");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(BaseEncoding::Base64.decode("Z"), None);
+        assert_eq!(BaseEncoding::Base64.decode("Zh=="), None);
+        assert_eq!(BaseEncoding::Base64.decode("Zm9v!"), None);
+        assert_eq!(BaseEncoding::Base32.decode("MZX"), None);
+        assert_eq!(BaseEncoding::Base32.decode("MZ1W6"), None);
+        assert_eq!(BaseEncoding::Base32.decode("MZ======"), None);
+    }
+}
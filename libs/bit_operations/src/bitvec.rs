@@ -0,0 +1,490 @@
+//! Multi-Word Bit Vector
+//!
+//! [`BitVec`] mirrors the [`BitArray`](crate::BitArray) API for fixed widths
+//! beyond 64 bits, such as 128- and 256-bit registers. Bits are stored in
+//! 64-bit words (word 0 is least significant); bits at or above the length
+//! are always zero, so shifts and rotations behave like a register of exactly
+//! that width.
+
+use crate::parse;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A fixed-length array of bits backed by 64-bit words.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Creates a BitVec of `len` bits, all set to 0.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Creates a BitVec of `len` bits from little-endian words; missing words
+    /// are zero and bits beyond `len` are dropped.
+    pub fn from_words(words: &[u64], len: usize) -> Self {
+        let mut bits = Self::new(len);
+        for (dst, &src) in bits.words.iter_mut().zip(words) {
+            *dst = src;
+        }
+        bits.mask_top();
+        bits
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the backing words, least significant first.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Retrieves the 64-bit word at `index` (0 = least significant), or 0
+    /// past the end.
+    pub fn word(&self, index: usize) -> u64 {
+        self.words.get(index).copied().unwrap_or(0)
+    }
+
+    /// Replaces the 64-bit word at `index`; words past the end are ignored.
+    pub fn set_word(&mut self, index: usize, word: u64) {
+        if let Some(w) = self.words.get_mut(index) {
+            *w = word;
+            self.mask_top();
+        }
+    }
+
+    /// Changes the length, keeping the low bits.
+    pub fn resize(&mut self, len: usize) {
+        self.words.resize(len.div_ceil(64), 0);
+        self.len = len;
+        self.mask_top();
+    }
+
+    /// Changes the length, filling new high bits with copies of the sign
+    /// bit; shrinking keeps the low bits like [`resize`](Self::resize).
+    pub fn sign_extend(&mut self, len: usize) {
+        let (old, negative) = (self.len, self.is_negative());
+        self.resize(len);
+        if negative {
+            (old..len).for_each(|pos| self.set_bit(pos));
+        }
+    }
+
+    /// Clears the unused bits of the top word.
+    fn mask_top(&mut self) {
+        let used = self.len % 64;
+        if let (Some(top), true) = (self.words.last_mut(), used != 0) {
+            *top &= (1 << used) - 1;
+        }
+    }
+
+    /// Sets a specific bit to 1 at the given position; out-of-range
+    /// positions are ignored.
+    pub fn set_bit(&mut self, pos: usize) {
+        if pos < self.len {
+            self.words[pos / 64] |= 1 << (pos % 64);
+        }
+    }
+
+    /// Clears a specific bit (sets it to 0) at the given position.
+    pub fn clear_bit(&mut self, pos: usize) {
+        if pos < self.len {
+            self.words[pos / 64] &= !(1 << (pos % 64));
+        }
+    }
+
+    /// Toggles a specific bit at the given position.
+    pub fn toggle_bit(&mut self, pos: usize) {
+        if pos < self.len {
+            self.words[pos / 64] ^= 1 << (pos % 64);
+        }
+    }
+
+    /// Retrieves the value of a specific bit at the given position.
+    ///
+    /// # Returns
+    /// - `true` if the bit is 1, `false` if it is 0 or out of range.
+    pub fn get_bit(&self, pos: usize) -> bool {
+        pos < self.len && (self.words[pos / 64] >> (pos % 64)) & 1 == 1
+    }
+
+    /// Returns the `len` bits starting at bit `shift` as a new BitVec.
+    pub fn range(&self, shift: usize, len: usize) -> Self {
+        let mut range = self.clone();
+        range.shift_right(shift);
+        range.resize(len);
+        range
+    }
+
+    /// Replaces the bits starting at bit `shift` with `value`; bits of
+    /// `value` past the end of this BitVec are dropped.
+    pub fn set_range(&mut self, shift: usize, value: &BitVec) {
+        for i in 0..value.len {
+            if value.get_bit(i) {
+                self.set_bit(shift + i);
+            } else {
+                self.clear_bit(shift + i);
+            }
+        }
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// Counts the zero bits above the highest set bit, or `len` if none is
+    /// set.
+    pub fn leading_zeros(&self) -> u32 {
+        (0..self.len)
+            .rev()
+            .position(|pos| self.get_bit(pos))
+            .unwrap_or(self.len) as u32
+    }
+
+    /// Counts the zero bits below the lowest set bit, or `len` if none is
+    /// set.
+    pub fn trailing_zeros(&self) -> u32 {
+        (0..self.len)
+            .position(|pos| self.get_bit(pos))
+            .unwrap_or(self.len) as u32
+    }
+
+    /// Sets every bit to 0.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Sets every bit to 1.
+    pub fn set_all(&mut self) {
+        self.words.fill(u64::MAX);
+        self.mask_top();
+    }
+
+    /// Inverts every bit.
+    pub fn invert(&mut self) {
+        for w in &mut self.words {
+            *w = !*w;
+        }
+        self.mask_top();
+    }
+
+    /// Replaces the value with its two's complement negation, wrapping at
+    /// the vector's width.
+    pub fn negate(&mut self) {
+        self.invert();
+        for w in &mut self.words {
+            let (sum, carry) = w.overflowing_add(1);
+            *w = sum;
+            if !carry {
+                break;
+            }
+        }
+        self.mask_top();
+    }
+
+    /// Returns `true` if the top bit, the sign bit in two's complement, is set.
+    pub fn is_negative(&self) -> bool {
+        self.len > 0 && self.get_bit(self.len - 1)
+    }
+
+    /// Shifts towards the most significant bit by `n`, filling with zeros.
+    pub fn shift_left(&mut self, n: usize) {
+        if n >= self.len {
+            return self.clear();
+        }
+        let (word_shift, bit_shift) = (n / 64, n % 64);
+        for i in (0..self.words.len()).rev() {
+            let lo = i
+                .checked_sub(word_shift)
+                .map_or(0, |src| self.words[src] << bit_shift);
+            let carry = match i.checked_sub(word_shift + 1) {
+                Some(src) if bit_shift != 0 => self.words[src] >> (64 - bit_shift),
+                _ => 0,
+            };
+            self.words[i] = lo | carry;
+        }
+        self.mask_top();
+    }
+
+    /// Shifts towards the least significant bit by `n`, filling with zeros.
+    pub fn shift_right(&mut self, n: usize) {
+        if n >= self.len {
+            return self.clear();
+        }
+        let (word_shift, bit_shift) = (n / 64, n % 64);
+        let count = self.words.len();
+        for i in 0..count {
+            let hi = self
+                .words
+                .get(i + word_shift)
+                .map_or(0, |&w| w >> bit_shift);
+            let carry = match self.words.get(i + word_shift + 1) {
+                Some(&w) if bit_shift != 0 => w << (64 - bit_shift),
+                _ => 0,
+            };
+            self.words[i] = hi | carry;
+        }
+    }
+
+    /// Shifts towards the least significant bit by `n`, filling with copies
+    /// of the top (sign) bit.
+    pub fn shift_right_arithmetic(&mut self, n: usize) {
+        let negative = self.is_negative();
+        self.shift_right(n);
+        if negative {
+            for pos in self.len.saturating_sub(n)..self.len {
+                self.set_bit(pos);
+            }
+        }
+    }
+
+    /// Rotates towards the most significant bit by `n`; bits shifted out at
+    /// the top re-enter at the bottom.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        let mut wrapped = self.clone();
+        wrapped.shift_right(self.len - n);
+        self.shift_left(n);
+        for (w, x) in self.words.iter_mut().zip(&wrapped.words) {
+            *w |= x;
+        }
+    }
+
+    /// Rotates towards the least significant bit by `n`.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        self.rotate_left(self.len - n % self.len);
+    }
+
+    /// Reverses the byte order within each `lane`-bit group, counting from
+    /// the least significant byte; a lane of `len` bits reverses all bytes.
+    ///
+    /// # Panics
+    /// Panics if `lane` is not a positive multiple of 8.
+    pub fn swap_bytes(&mut self, lane: usize) {
+        assert!(
+            lane > 0 && lane % 8 == 0,
+            "lane of {} bits is not a whole number of bytes",
+            lane
+        );
+        let mut bytes = self.to_le_bytes();
+        for chunk in bytes.chunks_mut(lane / 8) {
+            chunk.reverse();
+        }
+        if let Some(swapped) = Self::from_le_bytes(&bytes, self.len) {
+            *self = swapped;
+        }
+    }
+
+    /// Reverses the bit order, so bit 0 trades places with the top bit.
+    pub fn reverse_bits(&mut self) {
+        let mut reversed = Self::new(self.len);
+        for pos in (0..self.len).filter(|&pos| self.get_bit(pos)) {
+            reversed.set_bit(self.len - 1 - pos);
+        }
+        *self = reversed;
+    }
+
+    /// Returns the bytes in little-endian order, `len / 8` rounded up.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        bytes.truncate(self.len.div_ceil(8));
+        bytes
+    }
+
+    /// Returns the bytes in big-endian order.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Assembles a value of `len` bits from little-endian bytes.
+    ///
+    /// # Returns
+    /// - `Some(value)` if exactly `len / 8` (rounded up) bytes were
+    ///   supplied, `None` otherwise.
+    pub fn from_le_bytes(bytes: &[u8], len: usize) -> Option<Self> {
+        if bytes.len() != len.div_ceil(8) {
+            return None;
+        }
+        let words: Vec<u64> = bytes
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0u64, |acc, &b| (acc << 8) | u64::from(b))
+            })
+            .collect();
+        Some(Self::from_words(&words, len))
+    }
+
+    /// Assembles a value of `len` bits from big-endian bytes.
+    pub fn from_be_bytes(bytes: &[u8], len: usize) -> Option<Self> {
+        let mut le = bytes.to_vec();
+        le.reverse();
+        Self::from_le_bytes(&le, len)
+    }
+
+    /// Parses digits in the given radix into a value of `len` bits, ignoring
+    /// digit group separators (see [`parse::is_separator`]).
+    ///
+    /// # Arguments
+    /// - `digits`: The digits without a radix prefix.
+    /// - `radix`: The base, 2 to 36.
+    /// - `len`: The width of the result in bits.
+    ///
+    /// # Returns
+    /// - `Some(value)` on success, `None` for empty or invalid input or a
+    ///   value that does not fit in `len` bits.
+    pub fn from_str_radix(digits: &str, radix: u32, len: usize) -> Option<Self> {
+        let mut value = Self::new(len);
+        let mut any = false;
+        for c in digits.chars().filter(|&c| !parse::is_separator(c)) {
+            let mut carry = u128::from(c.to_digit(radix)?);
+            for w in &mut value.words {
+                let product = u128::from(*w) * u128::from(radix) + carry;
+                *w = product as u64;
+                carry = product >> 64;
+            }
+            let used = len % 64;
+            let top_overflow = used != 0 && value.words.last().is_some_and(|&w| w >> used != 0);
+            if carry != 0 || top_overflow {
+                return None;
+            }
+            any = true;
+        }
+        any.then_some(value)
+    }
+
+    /// Parses digits in the given radix into a BitVec just long enough for
+    /// the value: the fewest whole 64-bit words, at least one.
+    ///
+    /// # Returns
+    /// - `Some(value)` on success, `None` for empty or invalid input.
+    pub fn from_str_radix_fit(digits: &str, radix: u32) -> Option<Self> {
+        let count = digits.chars().filter(|&c| !parse::is_separator(c)).count();
+        // Each digit needs at most ceil(log2(radix)) bits
+        let digit_bits = (u32::BITS - (radix.max(2) - 1).leading_zeros()) as usize;
+        let len = (count * digit_bits).div_ceil(64).max(1) * 64;
+        let mut value = Self::from_str_radix(digits, radix, len)?;
+        let used = len - value.leading_zeros() as usize;
+        value.resize(used.div_ceil(64).max(1) * 64);
+        Some(value)
+    }
+
+    /// Formats the value in the given radix (without prefix), 2 to 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let mut words = self.words.clone();
+        let mut digits = Vec::new();
+        loop {
+            let mut remainder = 0u128;
+            for w in words.iter_mut().rev() {
+                let current = (remainder << 64) | u128::from(*w);
+                *w = (current / u128::from(radix)) as u64;
+                remainder = current % u128::from(radix);
+            }
+            digits.push(char::from_digit(remainder as u32, radix).unwrap_or('0'));
+            if words.iter().all(|&w| w == 0) {
+                break;
+            }
+        }
+        digits.iter().rev().collect()
+    }
+}
+
+impl fmt::Display for BitVec {
+    /// Prints bits from most to least significant, with a space after every
+    /// 8 bits.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in (0..self.len).rev() {
+            write!(f, "{}", u8::from(self.get_bit(i)))?;
+            if i % 8 == 0 && i != 0 {
+                write!(f, " ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_bits_across_words() {
+        let bits = BitVec::from_words(&[0x8000_0000_0000_0001, 0x10], 128);
+        assert_eq!(bits.count_ones(), 3);
+        assert_eq!(bits.leading_zeros(), 59);
+        assert_eq!(bits.trailing_zeros(), 0);
+
+        let high = BitVec::from_words(&[0, 0, 0, 1 << 63], 256);
+        assert_eq!(high.count_ones(), 1);
+        assert_eq!(high.leading_zeros(), 0);
+        assert_eq!(high.trailing_zeros(), 255);
+    }
+
+    #[test]
+    fn counts_zero_vectors_by_length() {
+        for len in [1, 64, 100, 256] {
+            let bits = BitVec::new(len);
+            assert_eq!(bits.count_ones(), 0);
+            assert_eq!(bits.leading_zeros(), len as u32);
+            assert_eq!(bits.trailing_zeros(), len as u32);
+        }
+    }
+
+    #[test]
+    fn parses_to_a_fitting_length() {
+        let digits = "340282366920938463463374607431768211456";
+        let value = BitVec::from_str_radix_fit(digits, 10).unwrap();
+        assert_eq!(value.len(), 192);
+        assert_eq!(value.words(), [0, 0, 1]);
+        assert_eq!(value.to_str_radix(10), digits);
+
+        assert_eq!(BitVec::from_str_radix_fit("0", 10).unwrap().len(), 64);
+        assert_eq!(
+            BitVec::from_str_radix_fit("000_000ff", 16).unwrap().len(),
+            64
+        );
+        let max = BitVec::from_str_radix_fit(&"f".repeat(32), 16).unwrap();
+        assert_eq!(max.len(), 128);
+        assert_eq!(max.count_ones(), 128);
+        assert_eq!(BitVec::from_str_radix_fit("", 10), None);
+        assert_eq!(BitVec::from_str_radix_fit("12a", 10), None);
+    }
+
+    #[test]
+    fn ignores_bits_past_the_length() {
+        let bits = BitVec::from_words(&[u64::MAX, u64::MAX], 100);
+        assert_eq!(bits.count_ones(), 100);
+        assert_eq!(bits.leading_zeros(), 0);
+        let mut top = BitVec::new(100);
+        top.set_bit(99);
+        assert_eq!(top.leading_zeros(), 0);
+        assert_eq!(top.trailing_zeros(), 99);
+    }
+}
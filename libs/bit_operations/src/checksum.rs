@@ -0,0 +1,186 @@
+//! CRCs and Simple Checksums
+//!
+//! A bitwise CRC engine described by the usual Rocksoft parameters (width,
+//! polynomial, initial value, input/output reflection and final XOR), a few
+//! common presets, and additive and XOR byte checksums.
+
+/// Parameters of a CRC algorithm of up to 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crc {
+    /// Display name, e.g. `CRC-32`.
+    pub name: &'static str,
+    /// CRC width in bits, 1 to 64.
+    pub width: u32,
+    /// Generator polynomial without the implicit top bit, not reflected.
+    pub poly: u64,
+    /// Register value before the first byte, not reflected.
+    pub init: u64,
+    /// Process each input byte least significant bit first.
+    pub refin: bool,
+    /// Reflect the register before the final XOR.
+    pub refout: bool,
+    /// Value XORed into the result.
+    pub xorout: u64,
+}
+
+impl Crc {
+    /// CRC-8/SMBUS, the plain CRC-8 with polynomial 0x07.
+    pub const CRC8: Crc = Crc {
+        name: "CRC-8",
+        width: 8,
+        poly: 0x07,
+        init: 0,
+        refin: false,
+        refout: false,
+        xorout: 0,
+    };
+
+    /// CRC-16/CCITT-FALSE (also CRC-16/IBM-3740), used by XMODEM-style
+    /// protocols with an all-ones start value.
+    pub const CRC16_CCITT: Crc = Crc {
+        name: "CRC-16/CCITT",
+        width: 16,
+        poly: 0x1021,
+        init: 0xFFFF,
+        refin: false,
+        refout: false,
+        xorout: 0,
+    };
+
+    /// CRC-16/MODBUS.
+    pub const CRC16_MODBUS: Crc = Crc {
+        name: "CRC-16/MODBUS",
+        width: 16,
+        poly: 0x8005,
+        init: 0xFFFF,
+        refin: true,
+        refout: true,
+        xorout: 0,
+    };
+
+    /// CRC-32 as used by Ethernet, zlib and PNG.
+    pub const CRC32: Crc = Crc {
+        name: "CRC-32",
+        width: 32,
+        poly: 0x04C1_1DB7,
+        init: 0xFFFF_FFFF,
+        refin: true,
+        refout: true,
+        xorout: 0xFFFF_FFFF,
+    };
+
+    /// The presets in display order.
+    pub const PRESETS: [Crc; 4] = [Crc::CRC8, Crc::CRC16_CCITT, Crc::CRC16_MODBUS, Crc::CRC32];
+
+    /// Returns the mask of the CRC's `width` bits.
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    /// Computes the CRC of `data`, one bit at a time.
+    ///
+    /// # Returns
+    /// The CRC in the low `width` bits; the check value of the presets over
+    /// the ASCII bytes `123456789` is e.g. `0xCBF43926` for CRC-32.
+    ///
+    /// # Panics
+    /// Panics if `width` is 0 or more than 64.
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        assert!(
+            (1..=64).contains(&self.width),
+            "CRC width {} is not between 1 and 64",
+            self.width
+        );
+        let mask = self.mask();
+        let top = 1u64 << (self.width - 1);
+        let mut crc = self.init & mask;
+        for &byte in data {
+            let byte = if self.refin {
+                byte.reverse_bits()
+            } else {
+                byte
+            };
+            for i in (0..8).rev() {
+                let feedback = (crc & top != 0) != ((byte >> i) & 1 == 1);
+                crc = (crc << 1) & mask;
+                if feedback {
+                    crc ^= self.poly & mask;
+                }
+            }
+        }
+        if self.refout {
+            crc = crc.reverse_bits() >> (64 - self.width);
+        }
+        (crc ^ self.xorout) & mask
+    }
+}
+
+/// Sums the bytes modulo 256.
+pub fn sum8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |acc, &b| acc.wrapping_add(b))
+}
+
+/// Returns the two's complement of [`sum8`], the byte that makes the total
+/// sum zero (as in Intel HEX records).
+pub fn sum8_complement(data: &[u8]) -> u8 {
+    sum8(data).wrapping_neg()
+}
+
+/// XORs the bytes together (longitudinal redundancy check).
+pub fn xor8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |acc, &b| acc ^ b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn preset_check_values() {
+        assert_eq!(Crc::CRC8.checksum(CHECK), 0xF4);
+        assert_eq!(Crc::CRC16_CCITT.checksum(CHECK), 0x29B1);
+        assert_eq!(Crc::CRC16_MODBUS.checksum(CHECK), 0x4B37);
+        assert_eq!(Crc::CRC32.checksum(CHECK), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn custom_parameters() {
+        // CRC-16/XMODEM: CCITT polynomial from zero
+        let xmodem = Crc {
+            name: "CRC-16/XMODEM",
+            init: 0,
+            ..Crc::CRC16_CCITT
+        };
+        assert_eq!(xmodem.checksum(CHECK), 0x31C3);
+        // CRC-64/XZ exercises the full 64-bit width
+        let xz = Crc {
+            name: "CRC-64/XZ",
+            width: 64,
+            poly: 0x42F0_E1EB_A9EA_3693,
+            init: u64::MAX,
+            refin: true,
+            refout: true,
+            xorout: u64::MAX,
+        };
+        assert_eq!(xz.checksum(CHECK), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn empty_input_is_init_then_xorout() {
+        assert_eq!(Crc::CRC8.checksum(&[]), 0);
+        assert_eq!(Crc::CRC16_CCITT.checksum(&[]), 0xFFFF);
+        assert_eq!(Crc::CRC32.checksum(&[]), 0);
+    }
+
+    #[test]
+    fn byte_checksums() {
+        assert_eq!(sum8(CHECK), 0xDD);
+        assert_eq!(sum8(&[0xFF, 0x02]), 0x01);
+        assert_eq!(sum8_complement(CHECK), 0x23);
+        assert_eq!(sum8(CHECK).wrapping_add(sum8_complement(CHECK)), 0);
+        assert_eq!(xor8(CHECK), 0x31);
+        assert_eq!(xor8(&[]), 0);
+    }
+}
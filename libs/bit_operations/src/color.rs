@@ -0,0 +1,289 @@
+//! Packed Pixel Colors
+//!
+//! Packing and unpacking of RGB565, RGB888 and RGBA8888 pixels, with the
+//! red channel in the most significant bits. Channels narrower than 8 bits
+//! are widened by bit replication (`0b11111` becomes `0xff`) and narrowed
+//! with rounding, so a narrow channel survives a round trip through 8 bits.
+
+use crate::Width;
+use core::fmt;
+
+/// One channel of a packed pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Channel {
+    /// Channel name: `'R'`, `'G'`, `'B'` or `'A'`.
+    pub name: char,
+    /// Position of the channel's least significant bit.
+    pub shift: u32,
+    /// Number of bits in the channel.
+    pub bits: u32,
+}
+
+impl Channel {
+    /// Returns the largest raw channel value.
+    pub fn max(&self) -> u64 {
+        (1 << self.bits) - 1
+    }
+
+    /// Returns the raw channel value from a packed pixel.
+    pub fn extract(&self, value: u64) -> u64 {
+        (value >> self.shift) & self.max()
+    }
+
+    /// Returns `value` with the channel replaced by the low bits of `raw`.
+    pub fn insert(&self, value: u64, raw: u64) -> u64 {
+        value & !(self.max() << self.shift) | (raw & self.max()) << self.shift
+    }
+
+    /// Widens a raw channel value to 8 bits by bit replication.
+    pub fn to_u8(&self, raw: u64) -> u8 {
+        let raw = raw & self.max();
+        let mut wide = 0;
+        let mut filled = 0;
+        while filled < 8 {
+            wide = wide << self.bits | raw;
+            filled += self.bits;
+        }
+        (wide >> (filled - 8)) as u8
+    }
+
+    /// Narrows an 8-bit value to the channel width, rounding to nearest.
+    pub fn from_u8(&self, value: u8) -> u64 {
+        (u64::from(value) * self.max() + 127) / 255
+    }
+}
+
+/// An 8-bit-per-channel color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgba {
+    /// Red.
+    pub r: u8,
+    /// Green.
+    pub g: u8,
+    /// Blue.
+    pub b: u8,
+    /// Alpha; 255 is opaque.
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Parses a CSS-style `#rrggbb` color, as produced by an HTML color
+    /// input. The leading `#` is optional; alpha is set to 255.
+    ///
+    /// # Returns
+    /// - The color, or `None` if the text is not six hex digits.
+    pub fn parse_hex(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let digits = text.strip_prefix('#').unwrap_or(text);
+        if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let rgb = u32::from_str_radix(digits, 16).ok()?;
+        Some(Self {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+            a: 255,
+        })
+    }
+}
+
+impl fmt::Display for Rgba {
+    /// Formats as `#rrggbb`, ignoring alpha.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// A packed pixel layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelFormat {
+    /// 16-bit `RRRRRGGG GGGBBBBB`.
+    Rgb565,
+    /// 24-bit `0xRRGGBB`.
+    Rgb888,
+    /// 32-bit `0xRRGGBBAA`.
+    Rgba8888,
+}
+
+/// Shorthand for the channel tables below.
+const fn channel(name: char, shift: u32, bits: u32) -> Channel {
+    Channel { name, shift, bits }
+}
+
+const RGB565: [Channel; 3] = [channel('R', 11, 5), channel('G', 5, 6), channel('B', 0, 5)];
+const RGB888: [Channel; 3] = [channel('R', 16, 8), channel('G', 8, 8), channel('B', 0, 8)];
+const RGBA8888: [Channel; 4] = [
+    channel('R', 24, 8),
+    channel('G', 16, 8),
+    channel('B', 8, 8),
+    channel('A', 0, 8),
+];
+
+impl PixelFormat {
+    /// All formats in display order.
+    pub const ALL: [PixelFormat; 3] = [
+        PixelFormat::Rgb565,
+        PixelFormat::Rgb888,
+        PixelFormat::Rgba8888,
+    ];
+
+    /// Returns the natural format for `width`: RGB565 at 16 bits, RGBA8888
+    /// at 32 bits and RGB888 in the low 24 bits of wider values, or `None`
+    /// at 8 bits.
+    pub fn for_width(width: Width) -> Option<Self> {
+        match width {
+            Width::W8 => None,
+            Width::W16 => Some(PixelFormat::Rgb565),
+            Width::W32 => Some(PixelFormat::Rgba8888),
+            _ => Some(PixelFormat::Rgb888),
+        }
+    }
+
+    /// Returns the number of bits in a pixel.
+    pub fn bits(self) -> u32 {
+        match self {
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb888 => 24,
+            PixelFormat::Rgba8888 => 32,
+        }
+    }
+
+    /// Returns the channels, most significant first.
+    pub fn channels(self) -> &'static [Channel] {
+        match self {
+            PixelFormat::Rgb565 => &RGB565,
+            PixelFormat::Rgb888 => &RGB888,
+            PixelFormat::Rgba8888 => &RGBA8888,
+        }
+    }
+
+    /// Unpacks the low [`bits`](Self::bits) of `value`. Formats without an
+    /// alpha channel are opaque.
+    pub fn unpack(self, value: u64) -> Rgba {
+        let mut color = Rgba {
+            a: 255,
+            ..Rgba::default()
+        };
+        for channel in self.channels() {
+            let c = channel.to_u8(channel.extract(value));
+            match channel.name {
+                'R' => color.r = c,
+                'G' => color.g = c,
+                'B' => color.b = c,
+                _ => color.a = c,
+            }
+        }
+        color
+    }
+
+    /// Packs `color` into `value`, leaving bits outside the pixel unchanged.
+    pub fn pack(self, value: u64, color: Rgba) -> u64 {
+        self.channels().iter().fold(value, |value, channel| {
+            let c = match channel.name {
+                'R' => color.r,
+                'G' => color.g,
+                'B' => color.b,
+                _ => color.a,
+            };
+            channel.insert(value, channel.from_u8(c))
+        })
+    }
+}
+
+impl fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            PixelFormat::Rgb565 => "RGB565",
+            PixelFormat::Rgb888 => "RGB888",
+            PixelFormat::Rgba8888 => "RGBA8888",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+
+    #[test]
+    fn unpacks_rgb565() {
+        let format = PixelFormat::Rgb565;
+        assert_eq!(format.unpack(0xF800), rgba(255, 0, 0, 255));
+        assert_eq!(format.unpack(0x07E0), rgba(0, 255, 0, 255));
+        assert_eq!(format.unpack(0x001F), rgba(0, 0, 255, 255));
+        assert_eq!(format.unpack(0xFFFF), rgba(255, 255, 255, 255));
+        // 0b10000 widens to 0b10000100, 0b100000 to 0b10000010
+        assert_eq!(format.unpack(0x8410), rgba(0x84, 0x82, 0x84, 255));
+    }
+
+    #[test]
+    fn packs_rgb565_with_rounding() {
+        let format = PixelFormat::Rgb565;
+        assert_eq!(format.pack(0, rgba(255, 255, 255, 0)), 0xFFFF);
+        assert_eq!(format.pack(0, rgba(0x80, 0x80, 0x80, 255)), 0x8410);
+        assert_eq!(format.pack(0, rgba(3, 1, 4, 255)), 0x0000);
+        assert_eq!(format.pack(0, rgba(5, 3, 5, 255)), 0x0821);
+    }
+
+    #[test]
+    fn rgb565_survives_a_round_trip() {
+        let format = PixelFormat::Rgb565;
+        for value in 0..=0xFFFF {
+            assert_eq!(format.pack(0, format.unpack(value)), value);
+        }
+    }
+
+    #[test]
+    fn packs_and_unpacks_rgb888_and_rgba8888() {
+        let color = rgba(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(PixelFormat::Rgb888.pack(0, color), 0x12_3456);
+        assert_eq!(
+            PixelFormat::Rgb888.unpack(0x12_3456),
+            rgba(0x12, 0x34, 0x56, 255)
+        );
+        assert_eq!(PixelFormat::Rgba8888.pack(0, color), 0x1234_5678);
+        assert_eq!(PixelFormat::Rgba8888.unpack(0x1234_5678), color);
+    }
+
+    #[test]
+    fn packing_keeps_bits_outside_the_pixel() {
+        let value = 0xDEAD_0000_00FF_FFFF;
+        let packed = PixelFormat::Rgb888.pack(value, rgba(1, 2, 3, 4));
+        assert_eq!(packed, 0xDEAD_0000_0001_0203);
+        assert_eq!(
+            PixelFormat::Rgb565.pack(0xAB_0000, Rgba::default()),
+            0xAB_0000
+        );
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(Rgba::parse_hex("#ff8000"), Some(rgba(255, 128, 0, 255)));
+        assert_eq!(Rgba::parse_hex(" 0A0b0C "), Some(rgba(10, 11, 12, 255)));
+        assert_eq!(Rgba::parse_hex("#fff"), None);
+        assert_eq!(Rgba::parse_hex("#ggggggg"), None);
+    }
+
+    #[test]
+    fn picks_a_format_per_width() {
+        assert_eq!(PixelFormat::for_width(Width::W8), None);
+        assert_eq!(
+            PixelFormat::for_width(Width::W16),
+            Some(PixelFormat::Rgb565)
+        );
+        assert_eq!(
+            PixelFormat::for_width(Width::W32),
+            Some(PixelFormat::Rgba8888)
+        );
+        assert_eq!(
+            PixelFormat::for_width(Width::W64),
+            Some(PixelFormat::Rgb888)
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Decimal String Conversion
+//!
+//! Correctly rounded decimal-to-float parsing for any [`FloatFormat`], the
+//! shortest decimal string that round-trips to a given bit pattern, and the
+//! exact decimal expansion of a binary float.
+
+use crate::rounding::{FloatFormat, RoundingMode};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A finite decimal number `±0.d1d2d3… × 10^exp` without leading or trailing
+/// zero digits; zero has no digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Decimal {
+    negative: bool,
+    digits: Vec<u8>,
+    exp: i64,
+}
+
+impl Decimal {
+    /// Parses `[+-]digits[.digits][e[+-]digits]`.
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (negative, s) = match s.as_bytes().first()? {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i64>().ok()?),
+            None => (s, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+
+        let mut digits = Vec::with_capacity(int.len() + frac.len());
+        for c in int.bytes().chain(frac.bytes()) {
+            if !c.is_ascii_digit() {
+                return None;
+            }
+            digits.push(c - b'0');
+        }
+        let mut exp = exp.checked_add(int.len() as i64)?;
+        let leading = digits.iter().take_while(|&&d| d == 0).count();
+        digits.drain(..leading);
+        exp -= leading as i64;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            exp = 0;
+        }
+        Some(Self {
+            negative,
+            digits,
+            exp,
+        })
+    }
+
+    /// Compares magnitudes.
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        match (self.digits.is_empty(), other.digits.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self
+                .exp
+                .cmp(&other.exp)
+                .then_with(|| self.digits.cmp(&other.digits)),
+        }
+    }
+
+    /// Returns `true` if both denote the same number (`-0` equals `0`).
+    fn same_value(&self, other: &Self) -> bool {
+        self.digits == other.digits
+            && self.exp == other.exp
+            && (self.digits.is_empty() || self.negative == other.negative)
+    }
+}
+
+/// Returns `true` if `s` is a finite decimal number, however large.
+pub(crate) fn is_decimal(s: &str) -> bool {
+    Decimal::parse(s).is_some()
+}
+
+/// Returns `a - b` for two finite decimal strings, rounded to the nearest
+/// `f64` only once the exact difference is known.
+///
+/// # Returns
+/// - `None` if either string is not a finite decimal.
+pub(crate) fn difference(a: &str, b: &str) -> Option<f64> {
+    let (a, b) = (Decimal::parse(a)?, Decimal::parse(b)?);
+    // Both magnitudes as integers scaled by 10^low, most significant first
+    let low = [&a, &b]
+        .iter()
+        .filter(|d| !d.digits.is_empty())
+        .map(|d| d.exp - d.digits.len() as i64)
+        .min()
+        .unwrap_or(0);
+    let scaled = |d: &Decimal| {
+        let mut digits = d.digits.clone();
+        if !digits.is_empty() {
+            digits.resize((d.exp - low) as usize, 0);
+        }
+        digits
+    };
+    let (x, y) = (scaled(&a), scaled(&b));
+
+    let (negative, digits) = if a.negative != b.negative {
+        (a.negative, add_digits(&x, &y))
+    } else if a.cmp_magnitude(&b) == Ordering::Less {
+        (!a.negative, sub_digits(&y, &x))
+    } else {
+        (a.negative, sub_digits(&x, &y))
+    };
+    let text: String = digits.iter().map(|&d| char::from(b'0' + d)).collect();
+    let sign = if negative { "-" } else { "" };
+    alloc::format!("{}0{}e{}", sign, text, low).parse().ok()
+}
+
+/// Adds two digit strings, most significant digit first.
+fn add_digits(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let offset = long.len() - short.len();
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0;
+    for i in (0..long.len()).rev() {
+        let sum = long[i] + carry + if i >= offset { short[i - offset] } else { 0 };
+        carry = sum / 10;
+        out.push(sum % 10);
+    }
+    out.push(carry);
+    out.reverse();
+    out
+}
+
+/// Subtracts digit string `b` from the larger or equal `a`, most significant
+/// digit first.
+fn sub_digits(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let offset = a.len() - b.len();
+    let mut out = a.to_vec();
+    let mut borrow = 0;
+    for i in (0..out.len()).rev() {
+        let sub = borrow + if i >= offset { b[i - offset] } else { 0 };
+        borrow = u8::from(out[i] < sub);
+        out[i] = out[i] + 10 * borrow - sub;
+    }
+    out
+}
+
+/// Multiplies a little-endian base-10^9 number by `factor` in place.
+fn mul_small(limbs: &mut Vec<u32>, factor: u32) {
+    let mut carry = 0u64;
+    for limb in limbs.iter_mut() {
+        let product = u64::from(*limb) * u64::from(factor) + carry;
+        *limb = (product % 1_000_000_000) as u32;
+        carry = product / 1_000_000_000;
+    }
+    while carry > 0 {
+        limbs.push((carry % 1_000_000_000) as u32);
+        carry /= 1_000_000_000;
+    }
+}
+
+/// Formats a little-endian base-10^9 number as decimal digits.
+fn limbs_to_string(limbs: &[u32]) -> String {
+    let mut out = String::new();
+    for (i, limb) in limbs.iter().rev().enumerate() {
+        if i == 0 {
+            out += &limb.to_string();
+        } else {
+            out += &alloc::format!("{:09}", limb);
+        }
+    }
+    out
+}
+
+/// Returns the exact decimal expansion of `x` in plain notation.
+///
+/// Every finite binary float is a terminating decimal, so e.g. `0.1f64`
+/// expands to `0.1000000000000000055511151231257827021181583404541015625`.
+/// NaN and infinities are returned as `NaN`, `inf` and `-inf`.
+pub fn exact(x: f64) -> String {
+    if !x.is_finite() {
+        return x.to_string();
+    }
+    let bits = x.to_bits();
+    let exp_field = ((bits >> 52) & 0x7FF) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exp) = if exp_field == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, exp_field - 1075)
+    };
+    exact_parts(x.is_sign_negative(), mantissa.into(), exp)
+}
+
+/// Returns the exact decimal expansion of `±mantissa * 2^exp` in plain
+/// notation.
+pub(crate) fn exact_parts(negative: bool, mantissa: u128, exp: i32) -> String {
+    let sign = if negative { "-" } else { "" };
+    if mantissa == 0 {
+        return alloc::format!("{}0", sign);
+    }
+
+    // x = mantissa * 2^exp; for negative exp, x = mantissa * 5^-exp / 10^-exp
+    let mut limbs = Vec::new();
+    let mut m = mantissa;
+    while m > 0 {
+        limbs.push((m % 1_000_000_000) as u32);
+        m /= 1_000_000_000;
+    }
+    let (factor, mut count) = if exp >= 0 { (2, exp) } else { (5, -exp) };
+    while count > 0 {
+        let step = count.min(13);
+        mul_small(&mut limbs, (factor as u32).pow(step as u32));
+        count -= step;
+    }
+    let digits = limbs_to_string(&limbs);
+    if exp >= 0 {
+        return alloc::format!("{}{}", sign, digits);
+    }
+
+    let point = -exp as usize;
+    let digits = if digits.len() <= point {
+        alloc::format!("{}{}", "0".repeat(point + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+    let (int, frac) = digits.split_at(digits.len() - point);
+    match frac.trim_end_matches('0') {
+        "" => alloc::format!("{}{}", sign, int),
+        frac => alloc::format!("{}{}.{}", sign, int, frac),
+    }
+}
+
+/// Parses a decimal string into the nearest value of `format`, ties to even.
+///
+/// # Arguments
+/// - `format`: The target format (at most binary64).
+/// - `s`: A decimal such as `-1.25e-3`, or `inf`/`nan`.
+///
+/// # Returns
+/// - `Some(bits)` with the correctly rounded bit pattern, `None` if `s` is
+///   not a number.
+pub fn parse(format: FloatFormat, s: &str) -> Option<u64> {
+    let y: f64 = s.trim().parse().ok()?;
+    let nearest = format.encode(y, RoundingMode::NearestEven);
+    if format == FloatFormat::DOUBLE || !y.is_finite() {
+        return Some(nearest);
+    }
+
+    // Rounding to binary64 first can land exactly on a midpoint of the target
+    // format although the decimal lies strictly on one side of it
+    let down = format.encode(y, RoundingMode::Down);
+    let up = format.encode(y, RoundingMode::Up);
+    if down == up || y != format.decode(down) / 2.0 + format.decode(up) / 2.0 {
+        return Some(nearest);
+    }
+    let (Some(decimal), Some(midpoint)) = (Decimal::parse(s), Decimal::parse(&exact(y))) else {
+        return Some(nearest);
+    };
+    Some(match decimal.cmp_magnitude(&midpoint) {
+        Ordering::Equal => nearest,
+        // down/up are ordered by value, so a larger magnitude means away
+        // from zero
+        Ordering::Greater if y > 0.0 => up,
+        Ordering::Greater => down,
+        Ordering::Less if y > 0.0 => down,
+        Ordering::Less => up,
+    })
+}
+
+/// Returns the shortest decimal string that parses back to `bits`.
+///
+/// Values between `1e-5` and `1e16` use plain notation, others scientific.
+/// NaN and infinities are returned as `NaN`, `inf` and `-inf`.
+pub fn shortest(format: FloatFormat, bits: u64) -> String {
+    let value = format.decode(bits);
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    if format == FloatFormat::DOUBLE {
+        return plain_or_scientific(value);
+    }
+    for precision in 0..17 {
+        let candidate = alloc::format!("{:.*e}", precision, value);
+        if parse(format, &candidate) == Some(bits) {
+            // At most 9 significant digits, so the binary64 nearest to the
+            // candidate prints back as the same digits
+            return plain_or_scientific(candidate.parse().unwrap_or(value));
+        }
+    }
+    plain_or_scientific(value)
+}
+
+/// Formats `x` in plain notation when its magnitude is moderate.
+fn plain_or_scientific(x: f64) -> String {
+    if x == 0.0 || (1e-5..1e16).contains(&x.abs()) {
+        alloc::format!("{}", x)
+    } else {
+        alloc::format!("{:e}", x)
+    }
+}
+
+/// Returns `true` if two decimal strings denote the same number.
+///
+/// Formatting differences such as `1.50` vs `1.5e0` are ignored; strings
+/// that are not finite decimals compare by case-insensitive text.
+pub fn same_value(a: &str, b: &str) -> bool {
+    match (Decimal::parse(a), Decimal::parse(b)) {
+        (Some(a), Some(b)) => a.same_value(&b),
+        _ => a.trim().eq_ignore_ascii_case(b.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_to_the_nearest_value() {
+        assert_eq!(parse(FloatFormat::HALF, "0.1"), Some(0x2E66));
+        assert_eq!(parse(FloatFormat::BFLOAT16, "0.1"), Some(0x3DCD));
+        assert_eq!(parse(FloatFormat::SINGLE, "0.1"), Some(0x3DCC_CCCD));
+        assert_eq!(parse(FloatFormat::DOUBLE, "0.1"), Some(0.1f64.to_bits()));
+        assert_eq!(parse(FloatFormat::HALF, "-2"), Some(0xC000));
+        assert_eq!(parse(FloatFormat::HALF, "65504"), Some(0x7BFF));
+        assert_eq!(parse(FloatFormat::HALF, "65520"), Some(0x7C00));
+        assert_eq!(parse(FloatFormat::HALF, "1e-10"), Some(0));
+        assert_eq!(parse(FloatFormat::HALF, "-inf"), Some(0xFC00));
+        assert_eq!(parse(FloatFormat::HALF, "0x10"), None);
+        assert_eq!(parse(FloatFormat::HALF, ""), None);
+    }
+
+    #[test]
+    fn avoids_double_rounding_at_midpoints() {
+        // Halfway between 1 and the next binary16 value; binary64 rounds the
+        // decimal onto the midpoint, which must not then tie to even
+        assert_eq!(parse(FloatFormat::HALF, "1.00048828125"), Some(0x3C00));
+        assert_eq!(
+            parse(FloatFormat::HALF, "1.00048828125000000000001"),
+            Some(0x3C01)
+        );
+        assert_eq!(parse(FloatFormat::HALF, "1.00146484375"), Some(0x3C02));
+        assert_eq!(
+            parse(FloatFormat::HALF, "1.00146484374999999999999"),
+            Some(0x3C01)
+        );
+        assert_eq!(
+            parse(FloatFormat::HALF, "-1.00048828125000000000001"),
+            Some(0xBC01)
+        );
+    }
+
+    #[test]
+    fn expands_exactly() {
+        assert_eq!(
+            exact(0.1),
+            "0.1000000000000000055511151231257827021181583404541015625"
+        );
+        assert_eq!(exact(2f64.powi(70)), "1180591620717411303424");
+        assert_eq!(exact(-0.0), "-0");
+        assert_eq!(exact(-1.5), "-1.5");
+        assert_eq!(exact(f64::NEG_INFINITY), "-inf");
+        let tiny = exact(5e-324);
+        let (int, frac) = tiny.split_once('.').unwrap();
+        assert_eq!(int, "0");
+        assert_eq!(frac.len(), 1074);
+        assert!(frac.ends_with("5"));
+    }
+
+    #[test]
+    fn finds_the_shortest_round_trip() {
+        assert_eq!(shortest(FloatFormat::HALF, 0x2E66), "0.1");
+        assert_eq!(shortest(FloatFormat::SINGLE, 0x3DCC_CCCD), "0.1");
+        assert_eq!(shortest(FloatFormat::DOUBLE, 0.1f64.to_bits()), "0.1");
+        assert_eq!(shortest(FloatFormat::HALF, 0x7BFF), "65500");
+        assert_eq!(shortest(FloatFormat::HALF, 0x0001), "6e-8");
+        assert_eq!(shortest(FloatFormat::HALF, 0x7C00), "inf");
+        for bits in 0..0x7C00 {
+            let text = shortest(FloatFormat::HALF, bits);
+            assert_eq!(parse(FloatFormat::HALF, &text), Some(bits), "{}", text);
+        }
+    }
+
+    #[test]
+    fn compares_decimal_values() {
+        assert!(same_value("1.50", "1.5e0"));
+        assert!(same_value("-0.001", "-1e-3"));
+        assert!(same_value("INF", "inf"));
+        assert!(!same_value("0.1", "0.10000001"));
+        assert!(!same_value("1", "-1"));
+        assert_eq!(difference("0.0999755859375", "0.1"), Some(-2.44140625e-5));
+        assert_eq!(difference("1", "x"), None);
+    }
+}
@@ -0,0 +1,1147 @@
+//! Instruction Decoding
+//!
+//! Disassembly of single RV32IMC (RISC-V with the multiply and compressed
+//! extensions, plus `Zicsr`) and ARM Thumb instructions, with the bit range
+//! of every encoded field so the fields can be shown on the bit grid. A
+//! value holds the instruction as loaded little-endian: the first halfword
+//! is the low half. Thumb covers the 16-bit instruction set of ARMv6-M plus
+//! `cbz`/`cbnz` and `it` from ARMv7-M; of the 32-bit Thumb-2 encodings only
+//! `bl` and `blx` are decoded.
+//!
+//! Branch targets are written relative to the instruction, e.g. `.+8`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// An instruction set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Isa {
+    /// RV32IMC with `Zicsr`; 16-bit compressed or 32-bit instructions.
+    RiscV,
+    /// ARM Thumb; 16-bit instructions and 32-bit `bl`/`blx`.
+    Thumb,
+}
+
+impl Isa {
+    /// All instruction sets in display order.
+    pub const ALL: [Isa; 2] = [Isa::RiscV, Isa::Thumb];
+}
+
+impl fmt::Display for Isa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Isa::RiscV => "RV32IMC",
+            Isa::Thumb => "Thumb",
+        })
+    }
+}
+
+/// Error produced while decoding an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisasmError {
+    /// The first halfword starts a 32-bit instruction but only 16 bits were
+    /// supplied.
+    Truncated,
+    /// The encoding is reserved, illegal or not covered by the decoder.
+    Unknown,
+}
+
+impl fmt::Display for DisasmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DisasmError::Truncated => "32-bit instruction needs a 32-bit value",
+            DisasmError::Unknown => "unknown or unsupported encoding",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DisasmError {}
+
+/// One encoded field of an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Field {
+    /// Field name, e.g. `"rd"` or `"imm[11:5]"`.
+    pub name: &'static str,
+    /// The instruction bits holding the field.
+    pub mask: u32,
+    /// The decoded field, e.g. `"a0"` or `"-16"`.
+    pub value: String,
+}
+
+/// A decoded instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Instruction {
+    /// Mnemonic, e.g. `"addi"` or `"c.lw"`.
+    pub mnemonic: String,
+    /// Operands in assembler order, e.g. `["a0", "8(sp)"]`.
+    pub operands: Vec<String>,
+    /// Encoded fields, most significant first.
+    pub fields: Vec<Field>,
+    /// Instruction length in bits: 16 or 32.
+    pub bits: u32,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.mnemonic)?;
+        if !self.operands.is_empty() {
+            write!(f, " {}", self.operands.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the instruction at the start of `word`.
+///
+/// # Arguments
+/// - `isa`: The instruction set.
+/// - `word`: The instruction bits, first halfword in the low half.
+/// - `bits`: How many bits of `word` are available: 16 or 32.
+///
+/// # Returns
+/// - The instruction, or an error for a truncated or unknown encoding.
+pub fn decode(isa: Isa, word: u32, bits: u32) -> Result<Instruction, DisasmError> {
+    let first = word & 0xffff;
+    let long = match isa {
+        Isa::RiscV => first & 0b11 == 0b11,
+        Isa::Thumb => first >> 11 >= 0b11101,
+    };
+    if long && bits < 32 {
+        return Err(DisasmError::Truncated);
+    }
+    let insn = match (isa, long) {
+        (Isa::RiscV, true) => riscv32(word),
+        (Isa::RiscV, false) => riscv16(first),
+        (Isa::Thumb, true) => thumb32(word),
+        (Isa::Thumb, false) => thumb16(first),
+    };
+    insn.map(Builder::finish).ok_or(DisasmError::Unknown)
+}
+
+/// Extracts bits `hi..=lo` of `w`.
+fn bits(w: u32, hi: u32, lo: u32) -> u32 {
+    (w >> lo) & (u32::MAX >> (31 - (hi - lo)))
+}
+
+/// Returns the mask of bits `hi..=lo`.
+fn mask(hi: u32, lo: u32) -> u32 {
+    (u32::MAX >> (31 - (hi - lo))) << lo
+}
+
+/// Sign-extends the low `n` bits of `v`.
+fn sext(v: u32, n: u32) -> i32 {
+    ((v << (32 - n)) as i32) >> (32 - n)
+}
+
+/// Assembles an immediate scattered over the instruction. Each part moves
+/// bits `hi..=lo` of `w` to bit `dst` upward.
+///
+/// # Returns
+/// The immediate and the mask of the instruction bits it came from.
+fn gather(w: u32, parts: &[(u32, u32, u32)]) -> (u32, u32) {
+    parts.iter().fold((0, 0), |(value, m), &(hi, lo, dst)| {
+        (value | bits(w, hi, lo) << dst, m | mask(hi, lo))
+    })
+}
+
+/// Formats a branch offset relative to the instruction.
+fn relative(offset: i32) -> String {
+    if offset < 0 {
+        format!(".-{}", offset.unsigned_abs())
+    } else {
+        format!(".+{}", offset)
+    }
+}
+
+const RISCV_REGS: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+const ARM_REGS: [&str; 16] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "sp", "lr",
+    "pc",
+];
+
+const ARM_CONDS: [&str; 15] = [
+    "eq", "ne", "cs", "cc", "mi", "pl", "vs", "vc", "hi", "ls", "ge", "lt", "gt", "le", "al",
+];
+
+/// Collects the mnemonic, operands and fields of an instruction.
+struct Builder {
+    word: u32,
+    insn: Instruction,
+}
+
+impl Builder {
+    fn new(word: u32, bits: u32, mnemonic: impl Into<String>) -> Self {
+        Self {
+            word,
+            insn: Instruction {
+                mnemonic: mnemonic.into(),
+                operands: Vec::new(),
+                fields: Vec::new(),
+                bits,
+            },
+        }
+    }
+
+    fn field(&mut self, name: &'static str, mask: u32, value: impl ToString) -> &mut Self {
+        self.insn.fields.push(Field {
+            name,
+            mask,
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Adds the contiguous field `hi..=lo`, shown in binary.
+    fn raw(&mut self, name: &'static str, hi: u32, lo: u32) -> &mut Self {
+        let value = format!(
+            "{:#0w$b}",
+            bits(self.word, hi, lo),
+            w = (hi - lo + 3) as usize
+        );
+        self.field(name, mask(hi, lo), value)
+    }
+
+    /// Adds a RISC-V register field `hi..=lo`, offset by `base` for the
+    /// compressed 3-bit register fields, and returns the register name.
+    fn xreg(&mut self, name: &'static str, hi: u32, lo: u32, base: u32) -> &'static str {
+        let reg = RISCV_REGS[(bits(self.word, hi, lo) + base) as usize];
+        self.field(name, mask(hi, lo), reg);
+        reg
+    }
+
+    /// Adds an ARM register field `hi..=lo` and returns the register name.
+    fn areg(&mut self, name: &'static str, hi: u32, lo: u32) -> &'static str {
+        let reg = ARM_REGS[bits(self.word, hi, lo) as usize];
+        self.field(name, mask(hi, lo), reg);
+        reg
+    }
+
+    /// Returns the bits covered by the fields so far.
+    fn covered(&self) -> u32 {
+        self.insn.fields.iter().fold(0, |m, f| m | f.mask)
+    }
+
+    fn op(&mut self, operand: impl ToString) -> &mut Self {
+        self.insn.operands.push(operand.to_string());
+        self
+    }
+
+    fn finish(mut self) -> Instruction {
+        self.insn.fields.sort_by_key(|f| f.mask.leading_zeros());
+        self.insn
+    }
+}
+
+/// Decodes a 32-bit RV32IM/Zicsr instruction.
+fn riscv32(w: u32) -> Option<Builder> {
+    let opcode = bits(w, 6, 0);
+    let f3 = bits(w, 14, 12);
+    let f7 = bits(w, 31, 25);
+    let i_imm = sext(bits(w, 31, 20), 12);
+    let pick = |names: [&'static str; 8]| Some(names[f3 as usize]).filter(|n| !n.is_empty());
+
+    let mut b = match opcode {
+        0x37 | 0x17 => {
+            let mut b = Builder::new(w, 32, if opcode == 0x37 { "lui" } else { "auipc" });
+            let rd = b.xreg("rd", 11, 7, 0);
+            let imm = format!("{:#x}", bits(w, 31, 12));
+            b.field("imm[31:12]", mask(31, 12), &imm).op(rd).op(imm);
+            b
+        }
+        0x6f => {
+            let mut b = Builder::new(w, 32, "jal");
+            let rd = b.xreg("rd", 11, 7, 0);
+            let (imm, m) = gather(w, &[(31, 31, 20), (30, 21, 1), (20, 20, 11), (19, 12, 12)]);
+            let offset = sext(imm, 21);
+            b.field("imm[20|10:1|11|19:12]", m, offset)
+                .op(rd)
+                .op(relative(offset));
+            b
+        }
+        0x67 if f3 == 0 => {
+            let mut b = Builder::new(w, 32, "jalr");
+            let rd = b.xreg("rd", 11, 7, 0);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            b.field("imm[11:0]", mask(31, 20), i_imm)
+                .op(rd)
+                .op(format!("{}({})", i_imm, rs1));
+            b
+        }
+        0x63 => {
+            let name = pick(["beq", "bne", "", "", "blt", "bge", "bltu", "bgeu"])?;
+            let mut b = Builder::new(w, 32, name);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            let rs2 = b.xreg("rs2", 24, 20, 0);
+            let (imm, m) = gather(w, &[(31, 31, 12), (30, 25, 5), (11, 8, 1), (7, 7, 11)]);
+            let offset = sext(imm, 13);
+            b.field("imm[12|10:5|4:1|11]", m, offset)
+                .op(rs1)
+                .op(rs2)
+                .op(relative(offset));
+            b
+        }
+        0x03 => {
+            let name = pick(["lb", "lh", "lw", "", "lbu", "lhu", "", ""])?;
+            let mut b = Builder::new(w, 32, name);
+            let rd = b.xreg("rd", 11, 7, 0);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            b.field("imm[11:0]", mask(31, 20), i_imm)
+                .op(rd)
+                .op(format!("{}({})", i_imm, rs1));
+            b
+        }
+        0x23 => {
+            let name = pick(["sb", "sh", "sw", "", "", "", "", ""])?;
+            let mut b = Builder::new(w, 32, name);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            let rs2 = b.xreg("rs2", 24, 20, 0);
+            let (imm, m) = gather(w, &[(31, 25, 5), (11, 7, 0)]);
+            let offset = sext(imm, 12);
+            b.field("imm[11:5|4:0]", m, offset)
+                .op(rs2)
+                .op(format!("{}({})", offset, rs1));
+            b
+        }
+        0x13 => {
+            let shift = f3 == 1 || f3 == 5;
+            let name = match (f3, f7) {
+                (1, 0) => "slli",
+                (5, 0) => "srli",
+                (5, 0x20) => "srai",
+                (1 | 5, _) => return None,
+                _ => pick(["addi", "", "slti", "sltiu", "xori", "", "ori", "andi"])?,
+            };
+            let mut b = Builder::new(w, 32, name);
+            let rd = b.xreg("rd", 11, 7, 0);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            b.op(rd).op(rs1);
+            if shift {
+                let shamt = bits(w, 24, 20);
+                b.raw("funct7", 31, 25)
+                    .field("shamt", mask(24, 20), shamt)
+                    .op(shamt);
+            } else {
+                b.field("imm[11:0]", mask(31, 20), i_imm).op(i_imm);
+            }
+            b
+        }
+        0x33 => {
+            let name = match f7 {
+                0 => pick(["add", "sll", "slt", "sltu", "xor", "srl", "or", "and"])?,
+                0x20 => pick(["sub", "", "", "", "", "sra", "", ""])?,
+                1 => pick([
+                    "mul", "mulh", "mulhsu", "mulhu", "div", "divu", "rem", "remu",
+                ])?,
+                _ => return None,
+            };
+            let mut b = Builder::new(w, 32, name);
+            let rd = b.xreg("rd", 11, 7, 0);
+            let rs1 = b.xreg("rs1", 19, 15, 0);
+            let rs2 = b.xreg("rs2", 24, 20, 0);
+            b.raw("funct7", 31, 25).op(rd).op(rs1).op(rs2);
+            b
+        }
+        0x0f => {
+            let name = pick(["fence", "fence.i", "", "", "", "", "", ""])?;
+            let mut b = Builder::new(w, 32, name);
+            b.raw("rs1", 19, 15).raw("rd", 11, 7);
+            if f3 == 0 {
+                let flags = |set: u32| {
+                    let s: String = ['i', 'o', 'r', 'w']
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, _)| (set >> (3 - i)) & 1 == 1)
+                        .map(|(_, &c)| c)
+                        .collect();
+                    s
+                };
+                let (pred, succ) = (flags(bits(w, 27, 24)), flags(bits(w, 23, 20)));
+                b.raw("fm", 31, 28)
+                    .field("pred", mask(27, 24), &pred)
+                    .field("succ", mask(23, 20), &succ)
+                    .op(pred)
+                    .op(succ);
+            } else {
+                b.raw("imm[11:0]", 31, 20);
+            }
+            b
+        }
+        0x73 if f3 == 0 => {
+            let name = match w {
+                0x0000_0073 => "ecall",
+                0x0010_0073 => "ebreak",
+                0x3020_0073 => "mret",
+                0x1050_0073 => "wfi",
+                _ => return None,
+            };
+            let mut b = Builder::new(w, 32, name);
+            b.raw("funct12", 31, 20).raw("rs1", 19, 15).raw("rd", 11, 7);
+            b
+        }
+        0x73 => {
+            let name = pick([
+                "", "csrrw", "csrrs", "csrrc", "", "csrrwi", "csrrsi", "csrrci",
+            ])?;
+            let mut b = Builder::new(w, 32, name);
+            let rd = b.xreg("rd", 11, 7, 0);
+            let csr = format!("{:#x}", bits(w, 31, 20));
+            b.field("csr", mask(31, 20), &csr).op(rd).op(csr);
+            if f3 >= 5 {
+                let uimm = bits(w, 19, 15);
+                b.field("uimm", mask(19, 15), uimm).op(uimm);
+            } else {
+                let rs1 = b.xreg("rs1", 19, 15, 0);
+                b.op(rs1);
+            }
+            b
+        }
+        _ => return None,
+    };
+    b.raw("opcode", 6, 0);
+    if matches!(
+        opcode,
+        0x67 | 0x63 | 0x03 | 0x23 | 0x13 | 0x33 | 0x0f | 0x73
+    ) {
+        b.raw("funct3", 14, 12);
+    }
+    Some(b)
+}
+
+/// Decodes a 16-bit RV32C instruction.
+fn riscv16(w: u32) -> Option<Builder> {
+    if w == 0 {
+        return None;
+    }
+    let quadrant = bits(w, 1, 0);
+    let f3 = bits(w, 15, 13);
+    let rd = bits(w, 11, 7);
+    let rs2 = bits(w, 6, 2);
+    let imm6 = || gather(w, &[(12, 12, 5), (6, 2, 0)]);
+
+    let mut b = match (quadrant, f3) {
+        (0, 0b000) => {
+            let (imm, m) = gather(w, &[(12, 11, 4), (10, 7, 6), (6, 6, 2), (5, 5, 3)]);
+            if imm == 0 {
+                return None;
+            }
+            let mut b = Builder::new(w, 16, "c.addi4spn");
+            let rd = b.xreg("rd'", 4, 2, 8);
+            b.field("nzuimm[5:4|9:6|2|3]", m, imm)
+                .op(rd)
+                .op("sp")
+                .op(imm);
+            b
+        }
+        (0, 0b010) | (0, 0b110) => {
+            let load = f3 == 0b010;
+            let mut b = Builder::new(w, 16, if load { "c.lw" } else { "c.sw" });
+            let (imm, m) = gather(w, &[(12, 10, 3), (6, 6, 2), (5, 5, 6)]);
+            let rs1 = b.xreg("rs1'", 9, 7, 8);
+            let reg = b.xreg(if load { "rd'" } else { "rs2'" }, 4, 2, 8);
+            b.field("uimm[5:3|2|6]", m, imm)
+                .op(reg)
+                .op(format!("{}({})", imm, rs1));
+            b
+        }
+        (1, 0b000) | (1, 0b010) => {
+            let (imm, m) = imm6();
+            let imm = sext(imm, 6);
+            let name = match (f3, rd) {
+                (0b000, 0) => "c.nop",
+                (0b000, _) => "c.addi",
+                _ => "c.li",
+            };
+            let mut b = Builder::new(w, 16, name);
+            if name != "c.nop" {
+                let rd = b.xreg("rd", 11, 7, 0);
+                b.field("imm[5|4:0]", m, imm).op(rd).op(imm);
+            }
+            b
+        }
+        (1, 0b001) | (1, 0b101) => {
+            let mut b = Builder::new(w, 16, if f3 == 0b001 { "c.jal" } else { "c.j" });
+            let (imm, m) = gather(
+                w,
+                &[
+                    (12, 12, 11),
+                    (11, 11, 4),
+                    (10, 9, 8),
+                    (8, 8, 10),
+                    (7, 7, 6),
+                    (6, 6, 7),
+                    (5, 3, 1),
+                    (2, 2, 5),
+                ],
+            );
+            let offset = sext(imm, 12);
+            b.field("imm[11|4|9:8|10|6|7|3:1|5]", m, offset)
+                .op(relative(offset));
+            b
+        }
+        (1, 0b011) if rd == 2 => {
+            let (imm, m) = gather(
+                w,
+                &[(12, 12, 9), (6, 6, 4), (5, 5, 6), (4, 3, 7), (2, 2, 5)],
+            );
+            if imm == 0 {
+                return None;
+            }
+            let imm = sext(imm, 10);
+            let mut b = Builder::new(w, 16, "c.addi16sp");
+            b.field("rd", mask(11, 7), "sp")
+                .field("nzimm[9|4|6|8:7|5]", m, imm)
+                .op("sp")
+                .op(imm);
+            b
+        }
+        (1, 0b011) => {
+            let (imm, m) = imm6();
+            if imm == 0 || rd == 0 {
+                return None;
+            }
+            let imm = format!("{:#x}", sext(imm, 6) as u32 & 0xfffff);
+            let mut b = Builder::new(w, 16, "c.lui");
+            let rd = b.xreg("rd", 11, 7, 0);
+            b.field("nzimm[17|16:12]", m, &imm).op(rd).op(imm);
+            b
+        }
+        (1, 0b100) => {
+            let funct2 = bits(w, 11, 10);
+            let name = match (funct2, bits(w, 12, 12), bits(w, 6, 5)) {
+                (0b00, 0, _) => "c.srli",
+                (0b01, 0, _) => "c.srai",
+                (0b10, _, _) => "c.andi",
+                (0b11, 0, op) => ["c.sub", "c.xor", "c.or", "c.and"][op as usize],
+                _ => return None,
+            };
+            let mut b = Builder::new(w, 16, name);
+            let rd = b.xreg("rd'", 9, 7, 8);
+            b.op(rd);
+            if funct2 == 0b11 {
+                let rs2 = b.xreg("rs2'", 4, 2, 8);
+                b.raw("funct6", 15, 10).raw("funct2", 6, 5).op(rs2);
+            } else {
+                b.raw("funct2", 11, 10);
+                let (imm, m) = imm6();
+                let imm = if funct2 == 0b10 {
+                    sext(imm, 6)
+                } else {
+                    imm as i32
+                };
+                b.field(
+                    if funct2 == 0b10 {
+                        "imm[5|4:0]"
+                    } else {
+                        "shamt"
+                    },
+                    m,
+                    imm,
+                )
+                .op(imm);
+            }
+            b
+        }
+        (1, 0b110) | (1, 0b111) => {
+            let mut b = Builder::new(w, 16, if f3 == 0b110 { "c.beqz" } else { "c.bnez" });
+            let rs1 = b.xreg("rs1'", 9, 7, 8);
+            let (imm, m) = gather(
+                w,
+                &[(12, 12, 8), (11, 10, 3), (6, 5, 6), (4, 3, 1), (2, 2, 5)],
+            );
+            let offset = sext(imm, 9);
+            b.field("imm[8|4:3|7:6|2:1|5]", m, offset)
+                .op(rs1)
+                .op(relative(offset));
+            b
+        }
+        (2, 0b000) => {
+            let (imm, m) = imm6();
+            if imm >= 32 || rd == 0 {
+                return None;
+            }
+            let mut b = Builder::new(w, 16, "c.slli");
+            let rd = b.xreg("rd", 11, 7, 0);
+            b.field("shamt", m, imm).op(rd).op(imm);
+            b
+        }
+        (2, 0b010) => {
+            if rd == 0 {
+                return None;
+            }
+            let mut b = Builder::new(w, 16, "c.lwsp");
+            let rd = b.xreg("rd", 11, 7, 0);
+            let (imm, m) = gather(w, &[(12, 12, 5), (6, 4, 2), (3, 2, 6)]);
+            b.field("uimm[5|4:2|7:6]", m, imm)
+                .op(rd)
+                .op(format!("{}(sp)", imm));
+            b
+        }
+        (2, 0b100) => {
+            let name = match (bits(w, 12, 12), rd, rs2) {
+                (0, 0, _) => return None,
+                (0, _, 0) => "c.jr",
+                (0, _, _) => "c.mv",
+                (1, 0, 0) => "c.ebreak",
+                (1, _, 0) => "c.jalr",
+                _ => "c.add",
+            };
+            let mut b = Builder::new(w, 16, name);
+            b.raw("funct4", 15, 12);
+            if name == "c.ebreak" {
+                b.raw("rd", 11, 7);
+            } else {
+                let rd = b.xreg(if rs2 == 0 { "rs1" } else { "rd" }, 11, 7, 0);
+                b.op(rd);
+            }
+            if rs2 == 0 {
+                b.raw("rs2", 6, 2);
+            } else {
+                let rs2 = b.xreg("rs2", 6, 2, 0);
+                b.op(rs2);
+            }
+            b
+        }
+        (2, 0b110) => {
+            let mut b = Builder::new(w, 16, "c.swsp");
+            let rs2 = b.xreg("rs2", 6, 2, 0);
+            let (imm, m) = gather(w, &[(12, 9, 2), (8, 7, 6)]);
+            b.field("uimm[5:2|7:6]", m, imm)
+                .op(rs2)
+                .op(format!("{}(sp)", imm));
+            b
+        }
+        _ => return None,
+    };
+    if b.covered() & mask(15, 13) == 0 {
+        b.raw("funct3", 15, 13);
+    }
+    b.raw("op", 1, 0);
+    Some(b)
+}
+
+/// Formats a Thumb register list such as `{r4, r5, lr}`.
+fn reg_list(list: u32, extra: Option<&str>) -> String {
+    let regs: Vec<&str> = (0..8)
+        .filter(|i| (list >> i) & 1 == 1)
+        .map(|i| ARM_REGS[i])
+        .chain(extra)
+        .collect();
+    format!("{{{}}}", regs.join(", "))
+}
+
+/// Decodes a 16-bit Thumb instruction.
+fn thumb16(h: u32) -> Option<Builder> {
+    let imm = |v: u32| format!("#{}", v);
+    let b = match h >> 11 {
+        // add/sub register or 3-bit immediate
+        0b00011 => {
+            let sub = bits(h, 9, 9) == 1;
+            let mut b = Builder::new(h, 16, if sub { "subs" } else { "adds" });
+            let rd = b.areg("Rd", 2, 0);
+            let rn = b.areg("Rn", 5, 3);
+            b.raw("opcode", 15, 9).op(rd).op(rn);
+            if bits(h, 10, 10) == 1 {
+                let imm3 = bits(h, 8, 6);
+                b.field("imm3", mask(8, 6), imm3).op(imm(imm3));
+            } else {
+                let rm = b.areg("Rm", 8, 6);
+                b.op(rm);
+            }
+            b
+        }
+        // shift by immediate
+        0b00000..=0b00010 => {
+            let op = bits(h, 12, 11);
+            let shift = bits(h, 10, 6);
+            let name = match (op, shift) {
+                (0, 0) => "movs",
+                _ => ["lsls", "lsrs", "asrs"][op as usize],
+            };
+            let mut b = Builder::new(h, 16, name);
+            let rd = b.areg("Rd", 2, 0);
+            let rm = b.areg("Rm", 5, 3);
+            b.raw("opcode", 15, 11).op(rd).op(rm);
+            if name != "movs" {
+                let amount = if shift == 0 { 32 } else { shift };
+                b.field("imm5", mask(10, 6), amount).op(imm(amount));
+            }
+            b
+        }
+        // move/compare/add/subtract 8-bit immediate
+        0b00100..=0b00111 => {
+            let name = ["movs", "cmp", "adds", "subs"][bits(h, 12, 11) as usize];
+            let mut b = Builder::new(h, 16, name);
+            let rd = b.areg("Rd", 10, 8);
+            let imm8 = bits(h, 7, 0);
+            b.raw("opcode", 15, 11)
+                .field("imm8", mask(7, 0), imm8)
+                .op(rd)
+                .op(imm(imm8));
+            b
+        }
+        0b01000 if bits(h, 10, 10) == 0 => {
+            let op = bits(h, 9, 6);
+            let name = [
+                "ands", "eors", "lsls", "lsrs", "asrs", "adcs", "sbcs", "rors", "tst", "rsbs",
+                "cmp", "cmn", "orrs", "muls", "bics", "mvns",
+            ][op as usize];
+            let mut b = Builder::new(h, 16, name);
+            let rdn = b.areg("Rdn", 2, 0);
+            let rm = b.areg("Rm", 5, 3);
+            b.raw("opcode", 15, 6).op(rdn).op(rm);
+            match name {
+                "rsbs" => {
+                    b.op("#0");
+                }
+                "muls" => {
+                    b.op(rdn);
+                }
+                _ => {}
+            }
+            b
+        }
+        // high register operations and branch exchange
+        0b01000 => {
+            let op = bits(h, 9, 8);
+            let rm = ARM_REGS[bits(h, 6, 3) as usize];
+            let rdn = ARM_REGS[(bits(h, 7, 7) << 3 | bits(h, 2, 0)) as usize];
+            let name = match (op, bits(h, 7, 7)) {
+                (3, 0) => "bx",
+                (3, _) => "blx",
+                _ => ["add", "cmp", "mov"][op as usize],
+            };
+            let mut b = Builder::new(h, 16, name);
+            b.raw("opcode", 15, 8).field("Rm", mask(6, 3), rm);
+            if op == 3 {
+                b.raw("L", 7, 7).raw("(0)", 2, 0).op(rm);
+            } else {
+                b.field("Rdn", mask(7, 7) | mask(2, 0), rdn).op(rdn).op(rm);
+            }
+            b
+        }
+        0b01001 => {
+            let mut b = Builder::new(h, 16, "ldr");
+            let rt = b.areg("Rt", 10, 8);
+            let offset = bits(h, 7, 0) * 4;
+            b.raw("opcode", 15, 11)
+                .field("imm8", mask(7, 0), offset)
+                .op(rt)
+                .op(format!("[pc, #{}]", offset));
+            b
+        }
+        // load/store with register offset
+        0b01010 | 0b01011 => {
+            let name = [
+                "str", "strh", "strb", "ldrsb", "ldr", "ldrh", "ldrb", "ldrsh",
+            ][bits(h, 11, 9) as usize];
+            let mut b = Builder::new(h, 16, name);
+            let rt = b.areg("Rt", 2, 0);
+            let rn = b.areg("Rn", 5, 3);
+            let rm = b.areg("Rm", 8, 6);
+            b.raw("opcode", 15, 9)
+                .op(rt)
+                .op(format!("[{}, {}]", rn, rm));
+            b
+        }
+        // load/store with immediate offset
+        0b01100..=0b10001 => {
+            let (name, scale) = match h >> 11 {
+                0b01100 => ("str", 4),
+                0b01101 => ("ldr", 4),
+                0b01110 => ("strb", 1),
+                0b01111 => ("ldrb", 1),
+                0b10000 => ("strh", 2),
+                _ => ("ldrh", 2),
+            };
+            let mut b = Builder::new(h, 16, name);
+            let rt = b.areg("Rt", 2, 0);
+            let rn = b.areg("Rn", 5, 3);
+            let offset = bits(h, 10, 6) * scale;
+            b.raw("opcode", 15, 11)
+                .field("imm5", mask(10, 6), offset)
+                .op(rt)
+                .op(format!("[{}, #{}]", rn, offset));
+            b
+        }
+        0b10010 | 0b10011 => {
+            let mut b = Builder::new(h, 16, if h >> 11 == 0b10010 { "str" } else { "ldr" });
+            let rt = b.areg("Rt", 10, 8);
+            let offset = bits(h, 7, 0) * 4;
+            b.raw("opcode", 15, 11)
+                .field("imm8", mask(7, 0), offset)
+                .op(rt)
+                .op(format!("[sp, #{}]", offset));
+            b
+        }
+        0b10100 | 0b10101 => {
+            let sp = h >> 11 == 0b10101;
+            let mut b = Builder::new(h, 16, "add");
+            let rd = b.areg("Rd", 10, 8);
+            let offset = bits(h, 7, 0) * 4;
+            b.raw("opcode", 15, 11)
+                .field("imm8", mask(7, 0), offset)
+                .op(rd)
+                .op(if sp { "sp" } else { "pc" })
+                .op(imm(offset));
+            b
+        }
+        0b10110 | 0b10111 => return thumb_misc(h),
+        0b11000 | 0b11001 => {
+            let load = h >> 11 == 0b11001;
+            let mut b = Builder::new(h, 16, if load { "ldm" } else { "stm" });
+            let rn = b.areg("Rn", 10, 8);
+            let list = bits(h, 7, 0);
+            // ldm writes back unless the base register is in the list
+            let writeback = !load || (list >> bits(h, 10, 8)) & 1 == 0;
+            b.raw("opcode", 15, 11)
+                .field("register_list", mask(7, 0), reg_list(list, None))
+                .op(if writeback {
+                    format!("{}!", rn)
+                } else {
+                    rn.to_string()
+                })
+                .op(reg_list(list, None));
+            b
+        }
+        0b11010 | 0b11011 => {
+            let cond = bits(h, 11, 8);
+            let imm8 = bits(h, 7, 0);
+            let mut b = match cond {
+                0b1110 | 0b1111 => {
+                    let mut b = Builder::new(h, 16, if cond == 0b1110 { "udf" } else { "svc" });
+                    b.raw("cond", 11, 8)
+                        .field("imm8", mask(7, 0), imm8)
+                        .op(imm(imm8));
+                    b
+                }
+                _ => {
+                    let name = ARM_CONDS[cond as usize];
+                    let mut b = Builder::new(h, 16, format!("b{}", name));
+                    let offset = sext(imm8, 8) * 2;
+                    b.field("cond", mask(11, 8), name)
+                        .field("imm8", mask(7, 0), offset)
+                        .op(relative(offset + 4));
+                    b
+                }
+            };
+            b.raw("opcode", 15, 12);
+            b
+        }
+        0b11100 => {
+            let mut b = Builder::new(h, 16, "b");
+            let offset = sext(bits(h, 10, 0), 11) * 2;
+            b.raw("opcode", 15, 11)
+                .field("imm11", mask(10, 0), offset)
+                .op(relative(offset + 4));
+            b
+        }
+        _ => return None,
+    };
+    Some(b)
+}
+
+/// Decodes the miscellaneous 16-bit Thumb instructions (`1011 xxxx`).
+fn thumb_misc(h: u32) -> Option<Builder> {
+    let op = bits(h, 11, 8);
+    let mut b = match op {
+        0b0000 => {
+            let sub = bits(h, 7, 7) == 1;
+            let mut b = Builder::new(h, 16, if sub { "sub" } else { "add" });
+            let offset = bits(h, 6, 0) * 4;
+            b.raw("S", 7, 7)
+                .field("imm7", mask(6, 0), offset)
+                .op("sp")
+                .op("sp")
+                .op(format!("#{}", offset));
+            b
+        }
+        0b0010 | 0b1010 => {
+            let name = if op == 0b0010 {
+                ["sxth", "sxtb", "uxth", "uxtb"][bits(h, 7, 6) as usize]
+            } else {
+                ["rev", "rev16", "", "revsh"][bits(h, 7, 6) as usize]
+            };
+            if name.is_empty() {
+                return None;
+            }
+            let mut b = Builder::new(h, 16, name);
+            let rd = b.areg("Rd", 2, 0);
+            let rm = b.areg("Rm", 5, 3);
+            b.raw("opc", 7, 6).op(rd).op(rm);
+            b
+        }
+        0b0001 | 0b0011 | 0b1001 | 0b1011 => {
+            let nonzero = bits(h, 11, 11) == 1;
+            let mut b = Builder::new(h, 16, if nonzero { "cbnz" } else { "cbz" });
+            let rn = b.areg("Rn", 2, 0);
+            let (imm, m) = gather(h, &[(9, 9, 6), (7, 3, 1)]);
+            let (opcode, om) = gather(h, &[(15, 12, 2), (10, 10, 1), (8, 8, 0)]);
+            b.field("opcode", om, format!("{:#08b}", opcode))
+                .raw("op", 11, 11)
+                .field("i:imm5", m, imm)
+                .op(rn)
+                .op(relative(imm as i32 + 4));
+            b
+        }
+        0b0100 | 0b0101 | 0b1100 | 0b1101 => {
+            let pop = bits(h, 11, 11) == 1;
+            let extra = (bits(h, 8, 8) == 1).then_some(if pop { "pc" } else { "lr" });
+            let list = reg_list(bits(h, 7, 0), extra);
+            let mut b = Builder::new(h, 16, if pop { "pop" } else { "push" });
+            b.raw("opcode", 15, 9)
+                .field(
+                    if pop { "P" } else { "M" },
+                    mask(8, 8),
+                    extra.unwrap_or("-"),
+                )
+                .field("register_list", mask(7, 0), &list)
+                .op(list);
+            b
+        }
+        0b0110 if h & 0xffef == 0xb662 => {
+            let disable = bits(h, 4, 4) == 1;
+            let mut b = Builder::new(h, 16, if disable { "cpsid" } else { "cpsie" });
+            b.raw("opcode", 15, 5)
+                .raw("im", 4, 4)
+                .raw("flags", 3, 0)
+                .op("i");
+            b
+        }
+        0b1110 => {
+            let mut b = Builder::new(h, 16, "bkpt");
+            let imm8 = bits(h, 7, 0);
+            b.field("imm8", mask(7, 0), imm8).op(format!("#{}", imm8));
+            b
+        }
+        0b1111 if bits(h, 3, 0) == 0 => {
+            let name = ["nop", "yield", "wfe", "wfi", "sev"]
+                .get(bits(h, 7, 4) as usize)
+                .copied()?;
+            let mut b = Builder::new(h, 16, name);
+            b.raw("hint", 7, 4).raw("mask", 3, 0);
+            b
+        }
+        0b1111 => {
+            let first = bits(h, 7, 4);
+            let it_mask = bits(h, 3, 0);
+            if first == 0b1111 || (first == 0b1110 && it_mask.count_ones() != 1) {
+                return None;
+            }
+            // Each mask bit above the terminating 1 adds a then or else slot
+            let suffix: String = (it_mask.trailing_zeros() + 1..4)
+                .rev()
+                .map(|i| {
+                    if (it_mask >> i) & 1 == first & 1 {
+                        't'
+                    } else {
+                        'e'
+                    }
+                })
+                .collect();
+            let cond = ARM_CONDS[first as usize];
+            let mut b = Builder::new(h, 16, format!("it{}", suffix));
+            b.field("firstcond", mask(7, 4), cond)
+                .raw("mask", 3, 0)
+                .op(cond);
+            b
+        }
+        _ => return None,
+    };
+    if b.covered() & mask(15, 8) == 0 {
+        b.raw("opcode", 15, 8);
+    }
+    Some(b)
+}
+
+/// Decodes a 32-bit Thumb-2 `bl` or `blx` (immediate).
+fn thumb32(w: u32) -> Option<Builder> {
+    let (hw1, hw2) = (w & 0xffff, w >> 16);
+    if hw1 >> 11 != 0b11110 || bits(hw2, 15, 14) != 0b11 {
+        return None;
+    }
+    let link_exchange = bits(hw2, 12, 12) == 0;
+    if link_exchange && bits(hw2, 0, 0) == 1 {
+        return None;
+    }
+    let s = bits(hw1, 10, 10);
+    let i1 = !(bits(hw2, 13, 13) ^ s) & 1;
+    let i2 = !(bits(hw2, 11, 11) ^ s) & 1;
+    let imm = s << 24 | i1 << 23 | i2 << 22 | bits(hw1, 9, 0) << 12 | bits(hw2, 10, 0) << 1;
+    let offset = sext(imm, 25);
+    let mut b = Builder::new(w, 32, if link_exchange { "blx" } else { "bl" });
+    b.raw("opcode", 15, 11)
+        .raw("S", 10, 10)
+        .raw("op1", 31, 30)
+        .raw("J1", 29, 29)
+        .raw("op", 28, 28)
+        .raw("J2", 27, 27)
+        .field("imm10:imm11", mask(9, 0) | mask(26, 16), offset)
+        .op(relative(offset + 4));
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the assembler text of the instruction at the start of `word`
+    fn text(isa: Isa, word: u32) -> String {
+        decode(isa, word, 32).unwrap().to_string()
+    }
+
+    /// Returns the decoded value of the field named `name`
+    fn field(isa: Isa, word: u32, name: &str) -> String {
+        let insn = decode(isa, word, 32).unwrap();
+        insn.fields
+            .into_iter()
+            .find(|f| f.name == name)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn riscv_base_formats() {
+        // R
+        assert_eq!(text(Isa::RiscV, 0x00C5_8533), "add a0, a1, a2");
+        assert_eq!(text(Isa::RiscV, 0x40C5_8533), "sub a0, a1, a2");
+        // I, with a sign-extended immediate
+        assert_eq!(text(Isa::RiscV, 0xFFF5_0513), "addi a0, a0, -1");
+        assert_eq!(text(Isa::RiscV, 0xFFC1_2503), "lw a0, -4(sp)");
+        assert_eq!(text(Isa::RiscV, 0x4035_5513), "srai a0, a0, 3");
+        assert_eq!(text(Isa::RiscV, 0x0000_8067), "jalr zero, 0(ra)");
+        // S
+        assert_eq!(text(Isa::RiscV, 0xFEB1_2C23), "sw a1, -8(sp)");
+        assert_eq!(field(Isa::RiscV, 0xFEB1_2C23, "imm[11:5|4:0]"), "-8");
+        // B
+        assert_eq!(text(Isa::RiscV, 0xFEB5_0CE3), "beq a0, a1, .-8");
+        assert_eq!(text(Isa::RiscV, 0x00B5_1863), "bne a0, a1, .+16");
+        // U
+        assert_eq!(text(Isa::RiscV, 0x1234_5537), "lui a0, 0x12345");
+        assert_eq!(text(Isa::RiscV, 0x1234_5517), "auipc a0, 0x12345");
+        // J, including imm[11] and the sign bit
+        assert_eq!(text(Isa::RiscV, 0x0010_00EF), "jal ra, .+2048");
+        assert_eq!(text(Isa::RiscV, 0xFFDF_F06F), "jal zero, .-4");
+        // System and fence
+        assert_eq!(text(Isa::RiscV, 0x0000_0073), "ecall");
+        assert_eq!(text(Isa::RiscV, 0xF140_2573), "csrrs a0, 0xf14, zero");
+        assert_eq!(text(Isa::RiscV, 0x0FF0_000F), "fence iorw, iorw");
+    }
+
+    #[test]
+    fn riscv_m_extension() {
+        assert_eq!(text(Isa::RiscV, 0x02C5_8533), "mul a0, a1, a2");
+        assert_eq!(text(Isa::RiscV, 0x02C5_9533), "mulh a0, a1, a2");
+        assert_eq!(text(Isa::RiscV, 0x02C5_C533), "div a0, a1, a2");
+        assert_eq!(text(Isa::RiscV, 0x02C5_D533), "divu a0, a1, a2");
+        assert_eq!(text(Isa::RiscV, 0x02C5_F533), "remu a0, a1, a2");
+    }
+
+    #[test]
+    fn riscv_compressed_quadrants() {
+        // Quadrant 0
+        assert_eq!(text(Isa::RiscV, 0x0800), "c.addi4spn s0, sp, 16");
+        assert_eq!(text(Isa::RiscV, 0x41C8), "c.lw a0, 4(a1)");
+        // Quadrant 1
+        assert_eq!(text(Isa::RiscV, 0x0001), "c.nop");
+        assert_eq!(text(Isa::RiscV, 0x557D), "c.li a0, -1");
+        assert_eq!(text(Isa::RiscV, 0x7139), "c.addi16sp sp, -64");
+        assert_eq!(text(Isa::RiscV, 0x6505), "c.lui a0, 0x1");
+        assert_eq!(text(Isa::RiscV, 0x757D), "c.lui a0, 0xfffff");
+        assert_eq!(text(Isa::RiscV, 0x8505), "c.srai a0, 1");
+        assert_eq!(text(Isa::RiscV, 0x997D), "c.andi a0, -1");
+        assert_eq!(text(Isa::RiscV, 0x8D0D), "c.sub a0, a1");
+        assert_eq!(text(Isa::RiscV, 0xBFFD), "c.j .-2");
+        assert_eq!(text(Isa::RiscV, 0xC501), "c.beqz a0, .+8");
+        // Quadrant 2
+        assert_eq!(text(Isa::RiscV, 0x40B2), "c.lwsp ra, 12(sp)");
+        assert_eq!(text(Isa::RiscV, 0xC606), "c.swsp ra, 12(sp)");
+        assert_eq!(text(Isa::RiscV, 0x8082), "c.jr ra");
+        assert_eq!(text(Isa::RiscV, 0x852E), "c.mv a0, a1");
+        assert_eq!(text(Isa::RiscV, 0x952E), "c.add a0, a1");
+        assert_eq!(text(Isa::RiscV, 0x9002), "c.ebreak");
+        // Only the first halfword is read
+        let insn = decode(Isa::RiscV, 0xFFFF_41C8, 32).unwrap();
+        assert_eq!(insn.bits, 16);
+        assert_eq!(
+            decode(Isa::RiscV, 0x41C8, 16).unwrap().to_string(),
+            "c.lw a0, 4(a1)"
+        );
+    }
+
+    #[test]
+    fn riscv_illegal_and_reserved() {
+        let err = |word, bits| decode(Isa::RiscV, word, bits).unwrap_err();
+        assert_eq!(err(0x0000_0013, 16), DisasmError::Truncated);
+        // Opcode 0x7F is reserved for longer encodings
+        assert_eq!(err(0xFFFF_FFFF, 32), DisasmError::Unknown);
+        // funct3 without an instruction
+        assert_eq!(err(0x40C5_9533, 32), DisasmError::Unknown);
+        assert_eq!(err(0x0000_2063, 32), DisasmError::Unknown);
+        // Shift with a reserved funct7
+        assert_eq!(err(0x8035_5513, 32), DisasmError::Unknown);
+        // All-zero halfword, c.addi4spn with zero immediate, c.lwsp to x0
+        // and an RV32 shift amount of 32 or more
+        assert_eq!(err(0x0000, 16), DisasmError::Unknown);
+        assert_eq!(err(0x0008, 16), DisasmError::Unknown);
+        assert_eq!(err(0x4002, 16), DisasmError::Unknown);
+        assert_eq!(err(0x9505, 16), DisasmError::Unknown);
+    }
+
+    #[test]
+    fn thumb_16_bit() {
+        assert_eq!(text(Isa::Thumb, 0x2001), "movs r0, #1");
+        assert_eq!(text(Isa::Thumb, 0x1888), "adds r0, r1, r2");
+        assert_eq!(text(Isa::Thumb, 0x1E48), "subs r0, r1, #1");
+        assert_eq!(text(Isa::Thumb, 0x0088), "lsls r0, r1, #2");
+        // A shift of 0 encodes 32 for lsr and asr
+        assert_eq!(text(Isa::Thumb, 0x0808), "lsrs r0, r1, #32");
+        assert_eq!(text(Isa::Thumb, 0x0008), "movs r0, r1");
+        assert_eq!(text(Isa::Thumb, 0x4348), "muls r0, r1, r0");
+        assert_eq!(text(Isa::Thumb, 0x6841), "ldr r1, [r0, #4]");
+        assert_eq!(text(Isa::Thumb, 0x9802), "ldr r0, [sp, #8]");
+        assert_eq!(text(Isa::Thumb, 0xB510), "push {r4, lr}");
+        assert_eq!(text(Isa::Thumb, 0xBD10), "pop {r4, pc}");
+        assert_eq!(text(Isa::Thumb, 0x4770), "bx lr");
+        assert_eq!(text(Isa::Thumb, 0x4680), "mov r8, r0");
+        assert_eq!(text(Isa::Thumb, 0xDF00), "svc #0");
+        assert_eq!(text(Isa::Thumb, 0xBF00), "nop");
+        assert_eq!(text(Isa::Thumb, 0xBF06), "itte eq");
+        assert_eq!(text(Isa::Thumb, 0xBF08), "it eq");
+        // Branch targets are relative to the instruction, past the pipeline
+        assert_eq!(text(Isa::Thumb, 0xE000), "b .+4");
+        assert_eq!(text(Isa::Thumb, 0xD1FD), "bne .-2");
+        assert_eq!(text(Isa::Thumb, 0xB110), "cbz r0, .+8");
+    }
+
+    #[test]
+    fn thumb_32_bit() {
+        assert_eq!(text(Isa::Thumb, 0xF800_F000), "bl .+4");
+        assert_eq!(text(Isa::Thumb, 0xFFFC_F7FF), "bl .-4");
+        assert_eq!(text(Isa::Thumb, 0xE800_F000), "blx .+4");
+        assert_eq!(decode(Isa::Thumb, 0xF800_F000, 32).unwrap().bits, 32);
+    }
+
+    #[test]
+    fn thumb_illegal_and_reserved() {
+        let err = |word, bits| decode(Isa::Thumb, word, bits).unwrap_err();
+        assert_eq!(err(0xF000, 16), DisasmError::Truncated);
+        // 32-bit encodings other than bl/blx, and blx with H set
+        assert_eq!(err(0x0000_E800, 32), DisasmError::Unknown);
+        assert_eq!(err(0xE801_F000, 32), DisasmError::Unknown);
+        // Unallocated misc and hint encodings
+        assert_eq!(err(0xB600, 16), DisasmError::Unknown);
+        assert_eq!(err(0xBA80, 16), DisasmError::Unknown);
+        assert_eq!(err(0xBF50, 16), DisasmError::Unknown);
+    }
+}
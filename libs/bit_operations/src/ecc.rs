@@ -0,0 +1,267 @@
+//! Hamming SECDED Error Correction
+//!
+//! Extended Hamming codes that correct any single-bit error and detect any
+//! double-bit error (SECDED) in 8- to 64-bit values, e.g. Hamming(72,64) as
+//! used by ECC memory. Codewords are numbered the textbook way: Hamming
+//! parity bits sit at the power-of-two positions 1, 2, 4, ..., data bits
+//! fill the remaining positions from the least significant up, and position
+//! 0 holds the overall parity bit. Bit `i` of a codeword `u128` is position
+//! `i`.
+
+use crate::Width;
+
+/// A SECDED code for one data width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Secded {
+    data_bits: u32,
+    hamming_bits: u32,
+}
+
+/// Outcome of checking a codeword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EccStatus {
+    /// No error detected.
+    Clean,
+    /// A single-bit error at the given codeword position was corrected.
+    Corrected(u32),
+    /// Two (or an even number of) bits are wrong; the data cannot be
+    /// corrected.
+    Uncorrectable,
+}
+
+/// Result of decoding a codeword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EccResult {
+    /// The data bits, corrected if a single-bit error was found.
+    pub data: u64,
+    /// XOR of the positions of all set bits; the position of a single-bit
+    /// error, or 0.
+    pub syndrome: u32,
+    /// `true` if the overall parity of the codeword is odd.
+    pub parity_error: bool,
+    /// What the check concluded.
+    pub status: EccStatus,
+}
+
+impl Secded {
+    /// Creates the code for `width`, or `None` for widths beyond 64 bits.
+    pub fn new(width: Width) -> Option<Self> {
+        let data_bits = width.bits();
+        if data_bits > 64 {
+            return None;
+        }
+        let mut hamming_bits = 1;
+        while (1 << hamming_bits) < data_bits + hamming_bits + 1 {
+            hamming_bits += 1;
+        }
+        Some(Self {
+            data_bits,
+            hamming_bits,
+        })
+    }
+
+    /// Returns the number of data bits.
+    pub fn data_bits(&self) -> u32 {
+        self.data_bits
+    }
+
+    /// Returns the number of check bits, including the overall parity bit.
+    pub fn parity_bits(&self) -> u32 {
+        self.hamming_bits + 1
+    }
+
+    /// Returns the codeword length, e.g. 72 for 64 data bits.
+    pub fn code_bits(&self) -> u32 {
+        self.data_bits + self.parity_bits()
+    }
+
+    /// Returns `true` if `position` holds a check bit (position 0 or a power
+    /// of two).
+    pub fn is_parity_position(&self, position: u32) -> bool {
+        position == 0 || position.is_power_of_two()
+    }
+
+    /// Returns the codeword position of data bit `bit`.
+    ///
+    /// # Panics
+    /// Panics if `bit` is not below [`data_bits`](Self::data_bits).
+    pub fn data_position(&self, bit: u32) -> u32 {
+        assert!(bit < self.data_bits, "data bit {} out of range", bit);
+        (1..self.code_bits())
+            .filter(|&p| !self.is_parity_position(p))
+            .nth(bit as usize)
+            .unwrap_or(0)
+    }
+
+    /// Returns the data bit stored at codeword `position`, or `None` for
+    /// check bits and positions past the codeword.
+    pub fn data_bit(&self, position: u32) -> Option<u32> {
+        if self.is_parity_position(position) || position >= self.code_bits() {
+            return None;
+        }
+        Some(position - 1 - (32 - position.leading_zeros()))
+    }
+
+    /// Spreads the data bits over their codeword positions, leaving the
+    /// check bits clear.
+    pub fn scatter(&self, data: u64) -> u128 {
+        (0..self.data_bits)
+            .filter(|&bit| (data >> bit) & 1 == 1)
+            .fold(0, |code, bit| code | 1 << self.data_position(bit))
+    }
+
+    /// Collects the data bits from their codeword positions.
+    pub fn gather(&self, code: u128) -> u64 {
+        (0..self.data_bits)
+            .filter(|&bit| (code >> self.data_position(bit)) & 1 == 1)
+            .fold(0, |data, bit| data | 1 << bit)
+    }
+
+    /// Returns the XOR of the positions of all set bits of `code`.
+    fn syndrome(&self, code: u128) -> u32 {
+        (1..self.code_bits())
+            .filter(|&p| (code >> p) & 1 == 1)
+            .fold(0, |syndrome, p| syndrome ^ p)
+    }
+
+    /// Encodes the low [`data_bits`](Self::data_bits) of `data`.
+    ///
+    /// # Returns
+    /// The codeword: data bits plus check bits making the syndrome 0 and
+    /// the overall parity even.
+    pub fn encode(&self, data: u64) -> u128 {
+        let mut code = self.scatter(data);
+        let syndrome = self.syndrome(code);
+        for i in 0..self.hamming_bits {
+            if (syndrome >> i) & 1 == 1 {
+                code |= 1 << (1u32 << i);
+            }
+        }
+        if code.count_ones() % 2 == 1 {
+            code |= 1;
+        }
+        code
+    }
+
+    /// Checks a codeword, correcting a single-bit error.
+    ///
+    /// # Returns
+    /// The (corrected) data with the syndrome and the verdict: an odd
+    /// overall parity means a single error at the syndrome position (0 for
+    /// the overall parity bit itself), an even parity with a nonzero
+    /// syndrome a double error.
+    pub fn decode(&self, code: u128) -> EccResult {
+        let mask = u128::MAX >> (128 - self.code_bits());
+        let code = code & mask;
+        let syndrome = self.syndrome(code);
+        let parity_error = code.count_ones() % 2 == 1;
+        let (data, status) = match (syndrome, parity_error) {
+            (0, false) => (self.gather(code), EccStatus::Clean),
+            (s, true) if s < self.code_bits() => {
+                (self.gather(code ^ 1 << s), EccStatus::Corrected(s))
+            }
+            _ => (self.gather(code), EccStatus::Uncorrectable),
+        };
+        EccResult {
+            data,
+            syndrome,
+            parity_error,
+            status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTHS: [Width; 4] = [Width::W8, Width::W16, Width::W32, Width::W64];
+
+    /// Data values exercising all-zero, all-one and mixed patterns
+    fn samples(width: Width) -> [u64; 4] {
+        let mask = width.mask();
+        [
+            0,
+            mask,
+            0xA5A5_A5A5_A5A5_A5A5 & mask,
+            0x0123_4567_89AB_CDEF & mask,
+        ]
+    }
+
+    #[test]
+    fn code_sizes() {
+        let sizes = WIDTHS.map(|w| Secded::new(w).map(|c| c.code_bits()));
+        assert_eq!(sizes, [Some(13), Some(22), Some(39), Some(72)]);
+        assert!(Secded::new(Width::W128).is_none());
+    }
+
+    #[test]
+    fn data_positions_round_trip() {
+        for width in WIDTHS {
+            let code = Secded::new(width).unwrap();
+            for bit in 0..code.data_bits() {
+                assert_eq!(code.data_bit(code.data_position(bit)), Some(bit));
+            }
+        }
+    }
+
+    #[test]
+    fn clean_codewords_decode() {
+        for width in WIDTHS {
+            let code = Secded::new(width).unwrap();
+            for data in samples(width) {
+                let result = code.decode(code.encode(data));
+                assert_eq!(result.status, EccStatus::Clean);
+                assert_eq!(result.data, data);
+            }
+        }
+    }
+
+    #[test]
+    fn every_single_bit_error_is_corrected() {
+        for width in WIDTHS {
+            let code = Secded::new(width).unwrap();
+            for data in samples(width) {
+                let word = code.encode(data);
+                for position in 0..code.code_bits() {
+                    let result = code.decode(word ^ 1 << position);
+                    assert_eq!(
+                        result.status,
+                        EccStatus::Corrected(position),
+                        "{:?} data {:#X} position {}",
+                        width,
+                        data,
+                        position
+                    );
+                    assert_eq!(result.data, data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_double_bit_error_is_detected() {
+        for width in WIDTHS {
+            let code = Secded::new(width).unwrap();
+            for data in samples(width) {
+                let word = code.encode(data);
+                for first in 0..code.code_bits() {
+                    for second in first + 1..code.code_bits() {
+                        let result = code.decode(word ^ 1 << first ^ 1 << second);
+                        assert_eq!(
+                            result.status,
+                            EccStatus::Uncorrectable,
+                            "{:?} data {:#X} positions {} and {}",
+                            width,
+                            data,
+                            first,
+                            second
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
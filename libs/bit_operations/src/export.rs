@@ -0,0 +1,466 @@
+//! Bit Grid Image Export
+//!
+//! Renders a value's bit grid, with bit index labels, coloured fields and
+//! per-bit annotations, either as an SVG document or as an RGBA raster that
+//! can be encoded to PNG. Both outputs share one layout, so an SVG placed in
+//! a document and a PNG pasted into slides look the same.
+//!
+//! Raster text uses a built-in 5×7 pixel font covering digits, letters
+//! (lowercase is drawn as uppercase) and common punctuation.
+
+use crate::bitvec::BitVec;
+use crate::net::BitField;
+use crate::Width;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// A named run of bits coloured in the exported grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridField {
+    /// Name shown in the legend.
+    pub name: String,
+    /// Position of the least significant bit of the field.
+    pub shift: u32,
+    /// Width of the field in bits.
+    pub width: u32,
+}
+
+impl From<BitField> for GridField {
+    fn from(field: BitField) -> Self {
+        Self {
+            name: field.name.into(),
+            shift: field.shift,
+            width: field.width,
+        }
+    }
+}
+
+/// A note attached to a single bit; the bit is outlined in the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    /// Annotated bit position.
+    pub bit: u32,
+    /// Note shown in the legend.
+    pub text: String,
+}
+
+/// An RGBA image with 8 bits per channel, rows top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raster {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// `width * height * 4` bytes of RGBA data.
+    pub pixels: Vec<u8>,
+}
+
+/// Everything needed to render a bit grid image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridImage {
+    /// The value whose bits are drawn.
+    pub value: BitVec,
+    /// Number of bits drawn.
+    pub width: Width,
+    /// Optional heading above the grid.
+    pub title: String,
+    /// Coloured fields, listed in the legend in this order.
+    pub fields: Vec<GridField>,
+    /// Per-bit notes, listed in the legend after the fields.
+    pub annotations: Vec<Annotation>,
+}
+
+type Color = [u8; 3];
+
+const BACKGROUND: Color = [0xFF, 0xFF, 0xFF];
+const CELL: Color = [0xF4, 0xF4, 0xF4];
+const BORDER: Color = [0x44, 0x44, 0x44];
+const TEXT: Color = [0x11, 0x11, 0x11];
+const DIM: Color = [0x99, 0x99, 0x99];
+const MARK: Color = [0xD0, 0x30, 0x30];
+const PALETTE: [Color; 8] = [
+    [0xA6, 0xCE, 0xE3],
+    [0xB2, 0xDF, 0x8A],
+    [0xFB, 0x9A, 0x99],
+    [0xFD, 0xBF, 0x6F],
+    [0xCA, 0xB2, 0xD6],
+    [0xFF, 0xFF, 0x99],
+    [0x8D, 0xD3, 0xC7],
+    [0xD9, 0xD9, 0xD9],
+];
+
+const MARGIN: u32 = 16;
+const CELL_SIZE: u32 = 28;
+const GAP: u32 = 2;
+const COLUMNS: u32 = 16;
+const LABEL_HEIGHT: u32 = 12;
+const ROW_GAP: u32 = 8;
+const LINE_HEIGHT: u32 = 20;
+/// Text size multiplier for bit index labels.
+const SMALL: u32 = 1;
+/// Text size multiplier for bit values, title and legend.
+const LARGE: u32 = 2;
+
+/// Horizontal advance of one character at size multiplier `size`.
+fn text_width(text: &str, size: u32) -> u32 {
+    text.chars().count() as u32 * 6 * size
+}
+
+/// Drawing primitives shared by the SVG and raster back ends.
+trait Surface {
+    fn fill(&mut self, x: u32, y: u32, w: u32, h: u32, color: Color);
+    fn outline(&mut self, x: u32, y: u32, w: u32, h: u32, thickness: u32, color: Color);
+    /// Draws `text` with its top-left corner at `(x, y)`.
+    fn text(&mut self, x: u32, y: u32, size: u32, color: Color, text: &str);
+}
+
+impl GridImage {
+    /// Creates an image of `value` at `width` with no title, fields or
+    /// annotations.
+    pub fn new(value: BitVec, width: Width) -> Self {
+        Self {
+            value,
+            width,
+            title: String::new(),
+            fields: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    fn columns(&self) -> u32 {
+        self.width.bits().min(COLUMNS)
+    }
+
+    fn rows(&self) -> u32 {
+        self.width.bits().div_ceil(COLUMNS)
+    }
+
+    fn title_height(&self) -> u32 {
+        if self.title.is_empty() {
+            0
+        } else {
+            LINE_HEIGHT + ROW_GAP
+        }
+    }
+
+    /// Top-left corner of the cell drawing `bit`; the most significant bit
+    /// comes first.
+    fn cell_origin(&self, bit: u32) -> (u32, u32) {
+        let index = self.width.bits() - 1 - bit;
+        let (row, column) = (index / COLUMNS, index % COLUMNS);
+        (
+            MARGIN + column * (CELL_SIZE + GAP),
+            MARGIN
+                + self.title_height()
+                + row * (LABEL_HEIGHT + CELL_SIZE + ROW_GAP)
+                + LABEL_HEIGHT,
+        )
+    }
+
+    fn legend_top(&self) -> u32 {
+        MARGIN + self.title_height() + self.rows() * (LABEL_HEIGHT + CELL_SIZE + ROW_GAP)
+    }
+
+    /// Legend lines paired with the colour of their swatch, if any.
+    fn legend(&self) -> Vec<(Option<Color>, String)> {
+        let fields = self.fields.iter().enumerate().map(|(i, field)| {
+            let value = self
+                .value
+                .range(field.shift as usize, field.width as usize)
+                .to_str_radix(16)
+                .to_uppercase();
+            let range = if field.width <= 1 {
+                format!("[{}]", field.shift)
+            } else {
+                format!("[{}:{}]", field.shift + field.width - 1, field.shift)
+            };
+            (
+                Some(PALETTE[i % PALETTE.len()]),
+                format!("{} {} = 0x{}", field.name, range, value),
+            )
+        });
+        let notes = self
+            .annotations
+            .iter()
+            .map(|note| (None, format!("bit {}: {}", note.bit, note.text)));
+        fields.chain(notes).collect()
+    }
+
+    /// Returns the image size in unscaled pixels.
+    fn size(&self) -> (u32, u32) {
+        let grid = self.columns() * (CELL_SIZE + GAP) - GAP;
+        let widest = self
+            .legend()
+            .iter()
+            .map(|(_, line)| LINE_HEIGHT + text_width(line, LARGE))
+            .chain(core::iter::once(text_width(&self.title, LARGE)))
+            .max()
+            .unwrap_or(0);
+        let legend = self.legend().len() as u32 * LINE_HEIGHT;
+        (
+            2 * MARGIN + grid.max(widest),
+            self.legend_top() + legend + MARGIN - ROW_GAP,
+        )
+    }
+
+    fn field_color(&self, bit: u32) -> Option<Color> {
+        self.fields
+            .iter()
+            .position(|f| bit >= f.shift && bit - f.shift < f.width)
+            .map(|i| PALETTE[i % PALETTE.len()])
+    }
+
+    fn draw(&self, surface: &mut impl Surface) {
+        let (width, height) = self.size();
+        surface.fill(0, 0, width, height, BACKGROUND);
+        if !self.title.is_empty() {
+            surface.text(MARGIN, MARGIN, LARGE, TEXT, &self.title);
+        }
+
+        for bit in 0..self.width.bits() {
+            let (x, y) = self.cell_origin(bit);
+            let set = self.value.get_bit(bit as usize);
+            let label = format!("{}", bit);
+            let label_x = x + (CELL_SIZE - text_width(&label, SMALL)) / 2;
+            surface.text(label_x, y - LABEL_HEIGHT + 2, SMALL, DIM, &label);
+
+            surface.fill(
+                x,
+                y,
+                CELL_SIZE,
+                CELL_SIZE,
+                self.field_color(bit).unwrap_or(CELL),
+            );
+            let marked = self.annotations.iter().any(|note| note.bit == bit);
+            let (thickness, border) = if marked { (3, MARK) } else { (1, BORDER) };
+            surface.outline(x, y, CELL_SIZE, CELL_SIZE, thickness, border);
+            let (digit, color) = if set { ("1", TEXT) } else { ("0", DIM) };
+            surface.text(x + 9, y + 7, LARGE, color, digit);
+        }
+
+        let top = self.legend_top();
+        for (i, (swatch, line)) in self.legend().iter().enumerate() {
+            let y = top + i as u32 * LINE_HEIGHT;
+            match swatch {
+                Some(color) => {
+                    surface.fill(MARGIN, y + 2, 12, 12, *color);
+                    surface.outline(MARGIN, y + 2, 12, 12, 1, BORDER);
+                }
+                None => surface.outline(MARGIN, y + 2, 12, 12, 3, MARK),
+            }
+            surface.text(MARGIN + LINE_HEIGHT, y + 1, LARGE, TEXT, line);
+        }
+    }
+
+    /// Renders the image as a standalone SVG document.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = self.size();
+        let mut svg = SvgSurface(format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\" font-family=\"monospace\">\n",
+            w = width,
+            h = height
+        ));
+        self.draw(&mut svg);
+        svg.0.push_str("</svg>\n");
+        svg.0
+    }
+
+    /// Renders the image as RGBA pixels.
+    ///
+    /// # Arguments
+    /// - `scale`: Integer zoom factor, e.g. 2 for high-DPI slides; `0` is
+    ///   treated as 1.
+    pub fn to_raster(&self, scale: u32) -> Raster {
+        let scale = scale.max(1);
+        let (width, height) = self.size();
+        let mut raster = RasterSurface {
+            scale,
+            raster: Raster {
+                width: width * scale,
+                height: height * scale,
+                pixels: vec![0; (width * height * scale * scale * 4) as usize],
+            },
+        };
+        self.draw(&mut raster);
+        raster.raster
+    }
+}
+
+struct SvgSurface(String);
+
+fn hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+impl Surface for SvgSurface {
+    fn fill(&mut self, x: u32, y: u32, w: u32, h: u32, color: Color) {
+        let _ = writeln!(
+            self.0,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            x,
+            y,
+            w,
+            h,
+            hex(color)
+        );
+    }
+
+    fn outline(&mut self, x: u32, y: u32, w: u32, h: u32, thickness: u32, color: Color) {
+        // SVG strokes are centred on the path; inset so the stroke stays inside
+        let half = thickness as f32 / 2.0;
+        let _ = writeln!(
+            self.0,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>",
+            x as f32 + half,
+            y as f32 + half,
+            w as f32 - 2.0 * half,
+            h as f32 - 2.0 * half,
+            hex(color),
+            thickness
+        );
+    }
+
+    fn text(&mut self, x: u32, y: u32, size: u32, color: Color, text: &str) {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                c => escaped.push(c),
+            }
+        }
+        // A 10px monospace font advances about 6px per character, matching
+        // the raster font
+        let _ = writeln!(
+            self.0,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\">{}</text>",
+            x,
+            y + 7 * size,
+            10 * size,
+            hex(color),
+            escaped
+        );
+    }
+}
+
+struct RasterSurface {
+    scale: u32,
+    raster: Raster,
+}
+
+impl RasterSurface {
+    /// Fills a rectangle given in scaled pixels, clipped to the image.
+    fn fill_scaled(&mut self, x: u32, y: u32, w: u32, h: u32, color: Color) {
+        let (width, height) = (self.raster.width, self.raster.height);
+        for py in y.min(height)..(y + h).min(height) {
+            for px in x.min(width)..(x + w).min(width) {
+                let i = ((py * width + px) * 4) as usize;
+                self.raster.pixels[i..i + 4].copy_from_slice(&[color[0], color[1], color[2], 0xFF]);
+            }
+        }
+    }
+}
+
+impl Surface for RasterSurface {
+    fn fill(&mut self, x: u32, y: u32, w: u32, h: u32, color: Color) {
+        let s = self.scale;
+        self.fill_scaled(x * s, y * s, w * s, h * s, color);
+    }
+
+    fn outline(&mut self, x: u32, y: u32, w: u32, h: u32, thickness: u32, color: Color) {
+        let t = thickness.min(w / 2).min(h / 2);
+        self.fill(x, y, w, t, color);
+        self.fill(x, y + h - t, w, t, color);
+        self.fill(x, y, t, h, color);
+        self.fill(x + w - t, y, t, h, color);
+    }
+
+    fn text(&mut self, x: u32, y: u32, size: u32, color: Color, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            let left = x + i as u32 * 6 * size;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0x10 >> column) != 0 {
+                        let top = y + row as u32 * size;
+                        self.fill(left + column * size, top, size, size, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the 5×7 bitmap of `c`, one row per byte with the leftmost pixel
+/// in bit 4. Unsupported characters render as `?`.
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '[' => [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E],
+        ']' => [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E],
+        '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '*' => [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '&' => [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D],
+        '<' => [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02],
+        '>' => [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        '|' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        '\'' => [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        _ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+    }
+}
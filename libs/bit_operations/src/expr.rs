@@ -0,0 +1,579 @@
+//! Bitwise Expression Evaluation
+//!
+//! A small calculator language over unsigned integers of a given [`Width`],
+//! e.g. `(0xFF00 >> 4) | (1 << 13) & ~0b1010`. Literals are anything
+//! [`parse_u64`] accepts in radix 10, so `0x`, `0o` and `0b` prefixes, digit
+//! separators and type suffixes work; `x` stands for the current value.
+//!
+//! Operators follow C precedence, tightest first:
+//!
+//! | Operators   | Meaning                                        |
+//! |-------------|------------------------------------------------|
+//! | `~` `-` `+` | unary not, negate, plus                        |
+//! | `*` `/` `%` | multiply, unsigned divide, remainder           |
+//! | `+` `-`     | add, subtract                                  |
+//! | `<<` `>>`   | logical shifts; shifting by the width gives 0  |
+//! | `&`         | and                                            |
+//! | `^`         | exclusive or                                   |
+//! | `\|`        | or                                             |
+//!
+//! `+`, `-`, `*`, `/` and unary `-` go through [`arith::apply`] with the
+//! [`ArithMode`] passed to [`evaluate`], so intermediate results wrap as in
+//! C arithmetic on an unsigned type of that size, saturate at 0 and the
+//! maximum, or fail with [`ExprError::Overflow`]. Bitwise operators and
+//! shifts never overflow; their results are truncated to the width.
+//!
+//! The functions `popcount(e)`, `clz(e)`, `ctz(e)` and `parity(e)` count set
+//! bits, leading and trailing zeros within the width, and return 1 for an
+//! odd number of set bits.
+//!
+//! With the `alloc` feature, a [`Watch`] keeps an expression with the base
+//! its result is shown in, for watch lists re-evaluated on every change.
+
+use crate::arith::{self, ArithError, ArithMode, ArithOp, Overflow};
+use crate::parse::{parse_u64, NumberError};
+use crate::{BitArray, Width};
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+use core::fmt;
+
+/// Deepest parenthesis and unary operator nesting accepted.
+const MAX_DEPTH: u32 = 64;
+
+/// Error produced while evaluating an expression. Positions are byte
+/// offsets into the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExprError {
+    /// The input ended where an operand or `)` was expected.
+    UnexpectedEnd,
+    /// A character that cannot start or continue the expression.
+    Unexpected(char, usize),
+    /// A name other than `x` or a function.
+    UnknownName(usize),
+    /// A malformed or too large literal.
+    Number(NumberError, usize),
+    /// Division or remainder by zero.
+    DivisionByZero(usize),
+    /// An arithmetic result out of range in [`ArithMode::Checked`].
+    Overflow(Overflow, usize),
+    /// Parentheses or unary operators nested too deeply.
+    TooDeep,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprError::UnexpectedEnd => f.write_str("unexpected end of expression"),
+            ExprError::Unexpected(c, pos) => write!(f, "unexpected `{}` at {}", c, pos),
+            ExprError::UnknownName(pos) => {
+                write!(
+                    f,
+                    "unknown name at {}, only `x` and functions are defined",
+                    pos
+                )
+            }
+            ExprError::Number(e, pos) => write!(f, "{} in number at {}", e, pos),
+            ExprError::DivisionByZero(pos) => write!(f, "division by zero at {}", pos),
+            ExprError::Overflow(o, pos) => {
+                write!(f, "{} at {}", ArithError::Overflow(*o), pos)
+            }
+            ExprError::TooDeep => f.write_str("expression nested too deeply"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExprError {}
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Or,
+    Xor,
+    And,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    /// Operator tokens, two-character tokens first.
+    const TOKENS: [(&'static str, BinOp); 10] = [
+        ("<<", BinOp::Shl),
+        (">>", BinOp::Shr),
+        ("|", BinOp::Or),
+        ("^", BinOp::Xor),
+        ("&", BinOp::And),
+        ("+", BinOp::Add),
+        ("-", BinOp::Sub),
+        ("*", BinOp::Mul),
+        ("/", BinOp::Div),
+        ("%", BinOp::Rem),
+    ];
+
+    /// Binding strength; higher binds tighter.
+    fn precedence(self) -> u32 {
+        match self {
+            BinOp::Or => 1,
+            BinOp::Xor => 2,
+            BinOp::And => 3,
+            BinOp::Shl | BinOp::Shr => 4,
+            BinOp::Add | BinOp::Sub => 5,
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 6,
+        }
+    }
+}
+
+/// A one-argument function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Popcount,
+    Clz,
+    Ctz,
+    Parity,
+}
+
+impl Function {
+    /// Looks up a function by name, ignoring case.
+    fn find(name: &str) -> Option<Self> {
+        [
+            ("popcount", Function::Popcount),
+            ("clz", Function::Clz),
+            ("ctz", Function::Ctz),
+            ("parity", Function::Parity),
+        ]
+        .into_iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, f)| f)
+    }
+
+    /// Applies the function to a value already truncated to `width`.
+    fn apply(self, value: u64, width: Width) -> u64 {
+        let value = BitArray(value);
+        u64::from(match self {
+            Function::Popcount => value.count_ones(width),
+            Function::Clz => value.leading_zeros(width),
+            Function::Ctz => value.trailing_zeros(width),
+            Function::Parity => u32::from(value.parity(width)),
+        })
+    }
+}
+
+/// Evaluates `input`.
+///
+/// # Arguments
+/// - `input`: The expression, e.g. `x & ~(1 << 3)`.
+/// - `x`: The value of `x`.
+/// - `width`: The width results are computed at; at most 64 bits.
+/// - `mode`: What arithmetic does with out-of-range results.
+///
+/// # Returns
+/// - The value, or an [`ExprError`] for the first problem found.
+pub fn evaluate(input: &str, x: u64, width: Width, mode: ArithMode) -> Result<u64, ExprError> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        x: width.truncate(x),
+        width,
+        mode,
+        depth: 0,
+    };
+    let value = parser.binary(1)?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(ExprError::Unexpected(c, parser.pos)),
+    }
+}
+
+/// Recursive descent evaluator; values are computed while parsing.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    x: u64,
+    width: Width,
+    mode: ArithMode,
+    depth: u32,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `c` if it is the next character after whitespace.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Tracks nesting so pathological input cannot exhaust the stack.
+    fn nest(&mut self) -> Result<(), ExprError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            Err(ExprError::TooDeep)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Parses a chain of binary operators binding at least as tightly as
+    /// `min_precedence`.
+    fn binary(&mut self, min_precedence: u32) -> Result<u64, ExprError> {
+        let mut lhs = self.unary()?;
+        loop {
+            self.skip_whitespace();
+            let Some((token, op)) = BinOp::TOKENS
+                .into_iter()
+                .find(|(token, _)| self.rest().starts_with(token))
+            else {
+                return Ok(lhs);
+            };
+            if op.precedence() < min_precedence {
+                return Ok(lhs);
+            }
+            let at = self.pos;
+            self.pos += token.len();
+            let rhs = self.binary(op.precedence() + 1)?;
+            lhs = self.apply(op, lhs, rhs, at)?;
+        }
+    }
+
+    fn apply(&self, op: BinOp, a: u64, b: u64, at: usize) -> Result<u64, ExprError> {
+        let value = match op {
+            BinOp::Or => a | b,
+            BinOp::Xor => a ^ b,
+            BinOp::And => a & b,
+            BinOp::Shl | BinOp::Shr if b >= u64::from(self.width.bits().min(64)) => 0,
+            BinOp::Shl => a << b,
+            BinOp::Shr => a >> b,
+            BinOp::Add => self.arith(ArithOp::Add, a, b, at)?,
+            BinOp::Sub => self.arith(ArithOp::Sub, a, b, at)?,
+            BinOp::Mul => self.arith(ArithOp::Mul, a, b, at)?,
+            BinOp::Div => self.arith(ArithOp::Div, a, b, at)?,
+            BinOp::Rem => a.checked_rem(b).ok_or(ExprError::DivisionByZero(at))?,
+        };
+        Ok(self.width.truncate(value))
+    }
+
+    /// Applies an arithmetic operator with the evaluation's [`ArithMode`].
+    fn arith(&self, op: ArithOp, a: u64, b: u64, at: usize) -> Result<u64, ExprError> {
+        match arith::apply(op, a, b, self.width, self.mode) {
+            Ok(result) => Ok(result.value),
+            Err(ArithError::Overflow(o)) => Err(ExprError::Overflow(o, at)),
+            Err(ArithError::DivisionByZero) => Err(ExprError::DivisionByZero(at)),
+        }
+    }
+
+    fn unary(&mut self) -> Result<u64, ExprError> {
+        self.skip_whitespace();
+        let op = self.peek().filter(|c| matches!(c, '~' | '-' | '+'));
+        let Some(op) = op else {
+            return self.primary();
+        };
+        let at = self.pos;
+        self.pos += 1;
+        self.nest()?;
+        let value = self.unary()?;
+        self.depth -= 1;
+        Ok(self.width.truncate(match op {
+            '~' => !value,
+            '-' => self.arith(ArithOp::Sub, 0, value, at)?,
+            _ => value,
+        }))
+    }
+
+    fn primary(&mut self) -> Result<u64, ExprError> {
+        let start = self.pos;
+        if self.eat('(') {
+            self.nest()?;
+            let value = self.binary(1)?;
+            self.depth -= 1;
+            if !self.eat(')') {
+                return Err(self.unexpected());
+            }
+            return Ok(value);
+        }
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '\''))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.unexpected());
+        }
+        let token = &self.rest()[..len];
+        self.pos += len;
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_u64(token, 10)
+                .map(|v| self.width.truncate(v))
+                .map_err(|e| ExprError::Number(e, start))
+        } else if token.eq_ignore_ascii_case("x") {
+            Ok(self.x)
+        } else if let Some(function) = Function::find(token) {
+            if !self.eat('(') {
+                return Err(self.unexpected());
+            }
+            self.nest()?;
+            let value = self.binary(1)?;
+            self.depth -= 1;
+            if !self.eat(')') {
+                return Err(self.unexpected());
+            }
+            Ok(function.apply(value, self.width))
+        } else {
+            Err(ExprError::UnknownName(start))
+        }
+    }
+
+    /// Returns the error for the character at the current position.
+    fn unexpected(&self) -> ExprError {
+        match self.peek() {
+            Some(c) => ExprError::Unexpected(c, self.pos),
+            None => ExprError::UnexpectedEnd,
+        }
+    }
+}
+
+/// How a watch shows its result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WatchBase {
+    /// `0x`-prefixed hexadecimal.
+    #[default]
+    Hex,
+    /// Unsigned decimal.
+    Dec,
+    /// Two's complement signed decimal at the width.
+    Signed,
+    /// `0o`-prefixed octal.
+    Oct,
+    /// `0b`-prefixed binary.
+    Bin,
+}
+
+impl WatchBase {
+    /// Every base, in menu order.
+    pub const ALL: [WatchBase; 5] = [
+        WatchBase::Hex,
+        WatchBase::Dec,
+        WatchBase::Signed,
+        WatchBase::Oct,
+        WatchBase::Bin,
+    ];
+
+    /// Short label, e.g. `"HEX"`.
+    pub fn label(self) -> &'static str {
+        match self {
+            WatchBase::Hex => "HEX",
+            WatchBase::Dec => "DEC",
+            WatchBase::Signed => "DEC ±",
+            WatchBase::Oct => "OCT",
+            WatchBase::Bin => "BIN",
+        }
+    }
+
+    /// Formats a `width`-bit value in this base.
+    #[cfg(feature = "alloc")]
+    pub fn format(self, value: u64, width: Width) -> String {
+        match self {
+            WatchBase::Hex => format!("0x{:X}", value),
+            WatchBase::Dec => format!("{}", value),
+            WatchBase::Signed => format!("{}", BitArray(value).as_signed(width)),
+            WatchBase::Oct => format!("0o{:o}", value),
+            WatchBase::Bin => format!("0b{:b}", value),
+        }
+    }
+}
+
+/// An expression watched as the value changes, like a debugger watch.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Watch {
+    /// The expression, e.g. `(x >> 12) & 0xF`.
+    pub expression: String,
+    /// Base the result is shown in.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub base: WatchBase,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_at(input: &str, x: u64, width: Width) -> Result<u64, ExprError> {
+        evaluate(input, x, width, ArithMode::Wrapping)
+    }
+
+    fn eval(input: &str) -> Result<u64, ExprError> {
+        eval_at(input, 0, Width::W64)
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Ok(7));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9));
+        assert_eq!(eval("1 << 2 + 1"), Ok(8));
+        assert_eq!(eval("0xF0 | 0x0F & 0x3C"), Ok(0xFC));
+        assert_eq!(eval("6 ^ 3 & 1"), Ok(7));
+        assert_eq!(eval("1 | 2 ^ 3"), Ok(1));
+        assert_eq!(eval("0xFF00 >> 4 & 0xF0"), Ok(0xF0));
+        assert_eq!(eval("~0 & 0xFF"), Ok(0xFF));
+    }
+
+    #[test]
+    fn left_associativity() {
+        assert_eq!(eval("10 - 3 - 2"), Ok(5));
+        assert_eq!(eval("64 / 4 / 2"), Ok(8));
+        assert_eq!(eval("100 % 7 % 3"), Ok(2));
+        assert_eq!(eval("1 << 2 << 3"), Ok(32));
+        assert_eq!(eval("256 >> 2 >> 1"), Ok(32));
+    }
+
+    #[test]
+    fn unary_operators() {
+        assert_eq!(eval_at("-1", 0, Width::W8), Ok(0xFF));
+        assert_eq!(eval_at("~x", 0x0F, Width::W8), Ok(0xF0));
+        assert_eq!(eval("- -5"), Ok(5));
+        assert_eq!(eval("+5"), Ok(5));
+        assert_eq!(eval("~~7"), Ok(7));
+        assert_eq!(eval("-2 * 3"), Ok(6u64.wrapping_neg()));
+    }
+
+    #[test]
+    fn variable_and_literals() {
+        assert_eq!(eval_at("x & 0xFF", 0x1234, Width::W16), Ok(0x34));
+        assert_eq!(eval_at("X + 1", 41, Width::W32), Ok(42));
+        assert_eq!(eval("0b1010 + 0o17 + 1_000"), Ok(10 + 15 + 1000));
+        assert_eq!(eval_at("x", 0x1FF, Width::W8), Ok(0xFF));
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(eval_at("popcount(x)", 0xF0F0, Width::W16), Ok(8));
+        assert_eq!(eval_at("clz(x)", 1, Width::W16), Ok(15));
+        assert_eq!(eval_at("ctz(x)", 0x80, Width::W32), Ok(7));
+        assert_eq!(eval_at("clz(0)", 0, Width::W8), Ok(8));
+        assert_eq!(eval_at("parity(x)", 0b111, Width::W8), Ok(1));
+        assert_eq!(eval_at("PARITY(x)", 0b11, Width::W8), Ok(0));
+        assert_eq!(eval("popcount(0xFF) * 2 + ctz(4)"), Ok(18));
+        assert_eq!(eval("popcount 1"), Err(ExprError::Unexpected('1', 9)));
+        assert_eq!(eval("popcount(1"), Err(ExprError::UnexpectedEnd));
+    }
+
+    #[test]
+    fn width_truncation() {
+        assert_eq!(eval_at("0xFF + 1", 0, Width::W8), Ok(0));
+        assert_eq!(eval_at("0x1FF", 0, Width::W8), Ok(0xFF));
+        assert_eq!(eval_at("0 - 1", 0, Width::W16), Ok(0xFFFF));
+        assert_eq!(eval_at("0x80 * 2", 0, Width::W8), Ok(0));
+        assert_eq!(eval_at("1 << 7", 0, Width::W8), Ok(0x80));
+        assert_eq!(eval_at("1 << 8", 0, Width::W8), Ok(0));
+        assert_eq!(eval_at("0x80 >> 8", 0, Width::W8), Ok(0));
+        assert_eq!(eval("1 << 64"), Ok(0));
+        assert_eq!(eval("1 << 63"), Ok(1 << 63));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!(eval("1 / 0"), Err(ExprError::DivisionByZero(2)));
+        assert_eq!(eval("5 % (x & 0)"), Err(ExprError::DivisionByZero(2)));
+        assert_eq!(
+            eval_at("1 / (0x100)", 0, Width::W8),
+            Err(ExprError::DivisionByZero(2))
+        );
+        assert_eq!(eval("7 / 2"), Ok(3));
+        assert_eq!(eval("7 % 2"), Ok(1));
+    }
+
+    #[test]
+    fn arithmetic_follows_the_mode() {
+        let at = |input, mode| evaluate(input, 0, Width::W8, mode);
+        assert_eq!(at("0xFF + 1", ArithMode::Saturating), Ok(0xFF));
+        assert_eq!(at("0 - 1", ArithMode::Saturating), Ok(0));
+        assert_eq!(at("0x10 * 0x10", ArithMode::Saturating), Ok(0xFF));
+        assert_eq!(at("-1", ArithMode::Saturating), Ok(0));
+        assert_eq!(at("(0xFF + 1) - 1", ArithMode::Saturating), Ok(0xFE));
+        assert_eq!(at("(0xFF + 1) - 1", ArithMode::Wrapping), Ok(0xFF));
+        assert_eq!(
+            at("1 + 0xFF", ArithMode::Checked),
+            Err(ExprError::Overflow(Overflow::Above, 2))
+        );
+        assert_eq!(
+            at("2 * (1 - 2)", ArithMode::Checked),
+            Err(ExprError::Overflow(Overflow::Below, 7))
+        );
+        assert_eq!(at("-x", ArithMode::Checked), Ok(0));
+        assert_eq!(
+            at("~0 - -1", ArithMode::Checked),
+            Err(ExprError::Overflow(Overflow::Below, 5))
+        );
+        // Bitwise operators and shifts never overflow
+        assert_eq!(at("~0 | 1 << 9", ArithMode::Checked), Ok(0xFF));
+        assert_eq!(
+            at("7 % 0", ArithMode::Checked),
+            Err(ExprError::DivisionByZero(2))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn nesting_limit() {
+        use alloc::{format, vec};
+
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(eval(&nested(MAX_DEPTH as usize)), Ok(1));
+        assert_eq!(
+            eval(&nested(MAX_DEPTH as usize + 1)),
+            Err(ExprError::TooDeep)
+        );
+        let negations = "-".repeat(MAX_DEPTH as usize + 1) + "1";
+        assert_eq!(eval(&negations), Err(ExprError::TooDeep));
+        // Depth is released after each group, so long flat chains are fine
+        let chain = vec!["(1)"; 200].join(" + ");
+        assert_eq!(eval(&chain), Ok(200));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(eval(""), Err(ExprError::UnexpectedEnd));
+        assert_eq!(eval("1 +"), Err(ExprError::UnexpectedEnd));
+        assert_eq!(eval("(1"), Err(ExprError::UnexpectedEnd));
+        assert_eq!(eval("1 )"), Err(ExprError::Unexpected(')', 2)));
+        assert_eq!(eval("1 $ 2"), Err(ExprError::Unexpected('$', 2)));
+        assert_eq!(eval("y + 1"), Err(ExprError::UnknownName(0)));
+        assert!(matches!(eval("0xZZ"), Err(ExprError::Number(_, 0))));
+        assert!(matches!(
+            eval("1 + 99999999999999999999"),
+            Err(ExprError::Number(_, 4))
+        ));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn watch_bases() {
+        assert_eq!(WatchBase::Hex.format(0xAB, Width::W8), "0xAB");
+        assert_eq!(WatchBase::Dec.format(0xFF, Width::W8), "255");
+        assert_eq!(WatchBase::Signed.format(0xFF, Width::W8), "-1");
+        assert_eq!(WatchBase::Oct.format(8, Width::W8), "0o10");
+        assert_eq!(WatchBase::Bin.format(5, Width::W8), "0b101");
+    }
+}
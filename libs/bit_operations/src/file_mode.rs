@@ -0,0 +1,221 @@
+//! Unix File Mode Bits
+//!
+//! The `st_mode` word of `stat(2)`: the file type in bits 15-12, then the
+//! setuid, setgid and sticky bits, then read, write and execute permissions
+//! for the owner, the group and others. [`FileMode`] displays it the way
+//! `ls -l` does, e.g. `-rwxr-xr-x` for `0o100755`.
+
+use core::fmt;
+
+/// Mask of the file type bits.
+pub const TYPE_MASK: u16 = 0o170000;
+/// Set-user-ID on execution.
+pub const SETUID: u16 = 0o4000;
+/// Set-group-ID on execution.
+pub const SETGID: u16 = 0o2000;
+/// Sticky bit; restricts deletion in shared directories.
+pub const STICKY: u16 = 0o1000;
+
+/// A permission class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Class {
+    /// The file's owner (`u`).
+    Owner,
+    /// The file's group (`g`).
+    Group,
+    /// Everyone else (`o`).
+    Other,
+}
+
+impl Class {
+    /// All classes in mode order.
+    pub const ALL: [Class; 3] = [Class::Owner, Class::Group, Class::Other];
+
+    /// Returns the class's three permission bits.
+    pub fn mask(self) -> u16 {
+        0o700 >> (3 * self as u16)
+    }
+
+    /// Returns the special bit shown in the class's execute slot.
+    pub fn special(self) -> u16 {
+        match self {
+            Class::Owner => SETUID,
+            Class::Group => SETGID,
+            Class::Other => STICKY,
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Class::Owner => "owner",
+            Class::Group => "group",
+            Class::Other => "other",
+        })
+    }
+}
+
+/// A permission within a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Permission {
+    /// Read (`r`).
+    Read,
+    /// Write (`w`).
+    Write,
+    /// Execute, or search for directories (`x`).
+    Execute,
+}
+
+impl Permission {
+    /// All permissions in mode order.
+    pub const ALL: [Permission; 3] = [Permission::Read, Permission::Write, Permission::Execute];
+
+    /// Returns the `ls` letter of the permission.
+    pub fn symbol(self) -> char {
+        match self {
+            Permission::Read => 'r',
+            Permission::Write => 'w',
+            Permission::Execute => 'x',
+        }
+    }
+
+    /// Returns the mode bit of the permission for `class`.
+    pub fn bit(self, class: Class) -> u16 {
+        0o400 >> (3 * class as u16 + self as u16)
+    }
+}
+
+/// The type of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FileType {
+    /// Named pipe (`S_IFIFO`).
+    Fifo,
+    /// Character device (`S_IFCHR`).
+    CharDevice,
+    /// Directory (`S_IFDIR`).
+    Directory,
+    /// Block device (`S_IFBLK`).
+    BlockDevice,
+    /// Regular file (`S_IFREG`).
+    Regular,
+    /// Symbolic link (`S_IFLNK`).
+    Symlink,
+    /// Socket (`S_IFSOCK`).
+    Socket,
+}
+
+impl FileType {
+    /// All file types in order of their type bits.
+    pub const ALL: [FileType; 7] = [
+        FileType::Fifo,
+        FileType::CharDevice,
+        FileType::Directory,
+        FileType::BlockDevice,
+        FileType::Regular,
+        FileType::Symlink,
+        FileType::Socket,
+    ];
+
+    /// Returns the type bits, e.g. `0o040000` for a directory.
+    pub fn bits(self) -> u16 {
+        match self {
+            FileType::Fifo => 0o010000,
+            FileType::CharDevice => 0o020000,
+            FileType::Directory => 0o040000,
+            FileType::BlockDevice => 0o060000,
+            FileType::Regular => 0o100000,
+            FileType::Symlink => 0o120000,
+            FileType::Socket => 0o140000,
+        }
+    }
+
+    /// Returns the `ls` type letter, e.g. `d` for a directory.
+    pub fn symbol(self) -> char {
+        match self {
+            FileType::Fifo => 'p',
+            FileType::CharDevice => 'c',
+            FileType::Directory => 'd',
+            FileType::BlockDevice => 'b',
+            FileType::Regular => '-',
+            FileType::Symlink => 'l',
+            FileType::Socket => 's',
+        }
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FileType::Fifo => "FIFO",
+            FileType::CharDevice => "character device",
+            FileType::Directory => "directory",
+            FileType::BlockDevice => "block device",
+            FileType::Regular => "regular file",
+            FileType::Symlink => "symbolic link",
+            FileType::Socket => "socket",
+        })
+    }
+}
+
+/// A file mode word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FileMode(pub u16);
+
+impl FileMode {
+    /// Returns the file type, or `None` if the type bits are not a known
+    /// type (including zero, as in the mode argument of `chmod`).
+    pub fn file_type(self) -> Option<FileType> {
+        FileType::ALL
+            .into_iter()
+            .find(|t| t.bits() == self.0 & TYPE_MASK)
+    }
+
+    /// Returns the mode with its type bits replaced.
+    pub fn with_type(self, file_type: Option<FileType>) -> Self {
+        Self(self.0 & !TYPE_MASK | file_type.map_or(0, FileType::bits))
+    }
+
+    /// Returns the permission and special bits, as given to `chmod`.
+    pub fn permissions(self) -> u16 {
+        self.0 & 0o7777
+    }
+
+    /// Returns whether all bits of `mask` are set.
+    pub fn has(self, mask: u16) -> bool {
+        self.0 & mask == mask
+    }
+}
+
+impl fmt::Display for FileMode {
+    /// Formats the mode as `ls -l` does: the type letter (`?` if unknown)
+    /// and `rwx` for each class, with `s`/`S` and `t`/`T` in the execute
+    /// slot for the special bits.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use fmt::Write;
+        f.write_char(self.file_type().map_or('?', FileType::symbol))?;
+        for class in Class::ALL {
+            for perm in Permission::ALL {
+                let set = self.has(perm.bit(class));
+                let symbol = if perm == Permission::Execute && self.has(class.special()) {
+                    let letter = if class == Class::Other { 't' } else { 's' };
+                    if set {
+                        letter
+                    } else {
+                        letter.to_ascii_uppercase()
+                    }
+                } else if set {
+                    perm.symbol()
+                } else {
+                    '-'
+                };
+                f.write_char(symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
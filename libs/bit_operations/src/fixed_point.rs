@@ -0,0 +1,304 @@
+//! Fixed-Point (Q Format) Conversion
+//!
+//! Interprets integers as binary fixed-point numbers `raw × 2^-n` and
+//! converts real numbers back with a chosen [`RoundingMode`], saturating at
+//! the ends of the representable range. Formats use the ARM notation: a
+//! signed `Qm.n` value has `m` integer bits including the sign bit and `n`
+//! fraction bits, so Q16.16 is 32 bits wide; `UQm.n` is unsigned.
+
+use crate::rounding::{scale, RoundingMode};
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// A fixed-point format: integer and fraction bit counts and signedness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QFormat {
+    /// Integer bits, including the sign bit of signed formats.
+    pub int_bits: u32,
+    /// Fraction bits.
+    pub frac_bits: u32,
+    /// Two's complement when `true`, unsigned otherwise.
+    pub signed: bool,
+}
+
+impl fmt::Display for QFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = if self.signed { "Q" } else { "UQ" };
+        write!(f, "{}{}.{}", prefix, self.int_bits, self.frac_bits)
+    }
+}
+
+/// Result of converting a real number to fixed point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedValue {
+    /// Raw bits, right-aligned and zero above the format width.
+    pub raw: u64,
+    /// `true` if the input was outside the range and clamped to it.
+    pub saturated: bool,
+    /// `true` if the input was not exactly representable.
+    pub inexact: bool,
+}
+
+impl QFormat {
+    /// Creates a format, or `None` if it is empty, wider than 64 bits or
+    /// signed without a sign bit.
+    pub fn new(int_bits: u32, frac_bits: u32, signed: bool) -> Option<Self> {
+        let bits = int_bits.checked_add(frac_bits)?;
+        if bits == 0 || bits > 64 || (signed && int_bits == 0) {
+            return None;
+        }
+        Some(Self {
+            int_bits,
+            frac_bits,
+            signed,
+        })
+    }
+
+    /// Parses `Qm.n` or `UQm.n` (case-insensitive); a bare `Qn` means
+    /// `Q1.n` and `UQn` means `UQ0.n`.
+    ///
+    /// # Returns
+    /// - The format, or `None` if the text is malformed or [`QFormat::new`]
+    ///   rejects it.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (signed, rest) = if let Some(rest) = strip_prefix_ignore_case(text, "UQ") {
+            (false, rest)
+        } else {
+            (true, strip_prefix_ignore_case(text, "Q")?)
+        };
+        let (int_bits, frac_bits) = match rest.split_once('.') {
+            Some((m, n)) => (m.parse().ok()?, n.parse().ok()?),
+            None => (u32::from(signed), rest.parse().ok()?),
+        };
+        Self::new(int_bits, frac_bits, signed)
+    }
+
+    /// Returns the total width in bits.
+    pub fn bits(&self) -> u32 {
+        self.int_bits + self.frac_bits
+    }
+
+    /// Returns the value of one least significant bit, `2^-n`.
+    pub fn resolution(&self) -> f64 {
+        scale(1.0, -(self.frac_bits as i32))
+    }
+
+    /// Returns the smallest representable value.
+    pub fn min(&self) -> f64 {
+        self.to_f64(self.min_raw())
+    }
+
+    /// Returns the largest representable value.
+    pub fn max(&self) -> f64 {
+        self.to_f64(self.max_raw())
+    }
+
+    /// Returns the raw bits of the smallest representable value.
+    fn min_raw(&self) -> u64 {
+        if self.signed {
+            1 << (self.bits() - 1)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the raw bits of the largest representable value.
+    fn max_raw(&self) -> u64 {
+        self.mask() >> u32::from(self.signed)
+    }
+
+    /// Returns the mask of the format's bits.
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bits())
+    }
+
+    /// Returns the raw bits as a signed integer, sign-extended for signed
+    /// formats.
+    fn integer(&self, raw: u64) -> i128 {
+        let raw = raw & self.mask();
+        let shift = 64 - self.bits();
+        if self.signed {
+            i128::from(((raw << shift) as i64) >> shift)
+        } else {
+            i128::from(raw)
+        }
+    }
+
+    /// Interprets the low [`bits`](Self::bits) bits of `raw` as a
+    /// fixed-point number; values wider than 53 significant bits are
+    /// rounded to the nearest `f64`.
+    pub fn to_f64(&self, raw: u64) -> f64 {
+        self.integer(raw) as f64 * self.resolution()
+    }
+
+    /// Converts `x` to fixed point, rounding in the given direction and
+    /// saturating at [`min`](Self::min) and [`max`](Self::max).
+    ///
+    /// # Returns
+    /// - The raw bits with saturation and inexact flags, or `None` for NaN.
+    pub fn from_f64(&self, x: f64, mode: RoundingMode) -> Option<FixedValue> {
+        if x.is_nan() {
+            return None;
+        }
+        let scaled = scale(x, self.frac_bits as i32);
+        let rounded = match mode {
+            RoundingMode::NearestEven => scaled.round_ties_even(),
+            RoundingMode::TowardZero => scaled.trunc(),
+            RoundingMode::Up => scaled.ceil(),
+            RoundingMode::Down => scaled.floor(),
+        };
+        let (low, high) = (self.integer(self.min_raw()), self.integer(self.max_raw()));
+        // Compare against powers of two, which f64 holds exactly
+        let (raw, saturated) = if rounded < low as f64 {
+            (low, true)
+        } else if rounded >= (high + 1) as f64 {
+            (high, true)
+        } else {
+            (rounded as i128, false)
+        };
+        Some(FixedValue {
+            raw: raw as u64 & self.mask(),
+            saturated,
+            inexact: saturated || rounded != scaled,
+        })
+    }
+
+    /// Formats the low [`bits`](Self::bits) bits of `raw` as the exact
+    /// decimal value, with at most `n` fraction digits.
+    pub fn exact(&self, raw: u64) -> String {
+        let value = self.integer(raw);
+        let magnitude = value.unsigned_abs();
+        let mut out = String::new();
+        if value < 0 {
+            out.push('-');
+        }
+        let int = magnitude >> self.frac_bits;
+        out.push_str(&int.to_string());
+        let frac_mask = (1u128 << self.frac_bits) - 1;
+        let mut frac = magnitude & frac_mask;
+        if frac != 0 {
+            out.push('.');
+        }
+        while frac != 0 {
+            frac *= 10;
+            out.push(char::from(b'0' + (frac >> self.frac_bits) as u8));
+            frac &= frac_mask;
+        }
+        out
+    }
+}
+
+/// Strips an ASCII prefix regardless of case.
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(text: &str) -> QFormat {
+        QFormat::parse(text).unwrap()
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(q("Q16.16"), QFormat::new(16, 16, true).unwrap());
+        assert_eq!(q("q15"), QFormat::new(1, 15, true).unwrap());
+        assert_eq!(q("UQ8"), QFormat::new(0, 8, false).unwrap());
+        assert_eq!(q("uq4.4").to_string(), "UQ4.4");
+        assert_eq!(QFormat::parse("Q0.8"), None);
+        assert_eq!(QFormat::parse("Q40.40"), None);
+        assert_eq!(QFormat::parse("X1.7"), None);
+    }
+
+    #[test]
+    fn converts_in_range_values() {
+        let format = q("Q8.8");
+        assert_eq!(format.min(), -128.0);
+        assert_eq!(format.max(), 128.0 - 1.0 / 256.0);
+        let value = format.from_f64(-1.5, RoundingMode::NearestEven).unwrap();
+        assert_eq!(value.raw, 0xFE80);
+        assert!(!value.saturated && !value.inexact);
+        assert_eq!(format.to_f64(value.raw), -1.5);
+        assert_eq!(format.exact(0x0001), "0.00390625");
+        assert_eq!(format.exact(0xFE80), "-1.5");
+    }
+
+    #[test]
+    fn saturates_at_the_range_ends() {
+        let format = q("Q4.4");
+        let high = format.from_f64(100.0, RoundingMode::NearestEven).unwrap();
+        assert_eq!(high.raw, 0x7F);
+        assert!(high.saturated && high.inexact);
+        let low = format.from_f64(-8.5, RoundingMode::TowardZero).unwrap();
+        assert_eq!(low.raw, 0x80);
+        assert!(low.saturated);
+        let inf = format
+            .from_f64(f64::NEG_INFINITY, RoundingMode::Up)
+            .unwrap();
+        assert_eq!(inf.raw, 0x80);
+        assert!(format.from_f64(f64::NAN, RoundingMode::Up).is_none());
+
+        let unsigned = q("UQ4.4");
+        let negative = unsigned.from_f64(-0.01, RoundingMode::NearestEven).unwrap();
+        assert_eq!(negative.raw, 0);
+        assert!(negative.inexact);
+        assert!(!negative.saturated);
+        assert!(
+            unsigned
+                .from_f64(-1.0, RoundingMode::NearestEven)
+                .unwrap()
+                .saturated
+        );
+
+        // Rounding up past the top of the range saturates as well
+        let edge = format.from_f64(7.99, RoundingMode::Up).unwrap();
+        assert_eq!(edge.raw, 0x7F);
+        assert!(edge.saturated);
+    }
+
+    #[test]
+    fn rounds_in_each_direction() {
+        let format = q("Q8.0");
+        let cases = [
+            (2.5, [2, 2, 3, 2]),
+            (3.5, [4, 3, 4, 3]),
+            (-2.5, [-2, -2, -2, -3]),
+            (-2.2, [-2, -2, -2, -3]),
+            (2.7, [3, 2, 3, 2]),
+        ];
+        let modes = [
+            RoundingMode::NearestEven,
+            RoundingMode::TowardZero,
+            RoundingMode::Up,
+            RoundingMode::Down,
+        ];
+        for (x, expected) in cases {
+            for (mode, want) in modes.into_iter().zip(expected) {
+                let value = format.from_f64(x, mode).unwrap();
+                assert_eq!(value.raw, want as i8 as u8 as u64, "{} {:?}", x, mode);
+                assert!(value.inexact);
+            }
+        }
+    }
+
+    #[test]
+    fn handles_full_width_formats() {
+        let signed = q("Q64.0");
+        assert_eq!(signed.to_f64(u64::MAX), -1.0);
+        let max = signed.from_f64(1e30, RoundingMode::NearestEven).unwrap();
+        assert_eq!(max.raw, i64::MAX as u64);
+        let unsigned = q("UQ0.64");
+        assert_eq!(unsigned.to_f64(1 << 63), 0.5);
+        assert_eq!(
+            unsigned.exact(1),
+            "0.0000000000000000000542101086242752217003726400434970855712890625"
+        );
+    }
+}
@@ -0,0 +1,498 @@
+//! IEEE 754 Floating Point Decoding
+
+use crate::decimal::exact_parts;
+use crate::rounding::{scale, FloatFormat};
+
+/// IEEE 754 Format Enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum IEEEFormat {
+    /// binary16: 1 sign, 5 exponent, 10 mantissa bits.
+    Half,
+    /// binary32: 1 sign, 8 exponent, 23 mantissa bits.
+    Single,
+    /// binary64: 1 sign, 11 exponent, 52 mantissa bits.
+    Double,
+    /// bfloat16: 1 sign, 8 exponent, 7 mantissa bits; the top half of a
+    /// binary32 value.
+    BFloat16,
+    /// FP8 E4M3: 1 sign, 4 exponent, 3 mantissa bits, bias 7. Has no
+    /// infinities; only all-ones exponent and mantissa is NaN, so the
+    /// largest value is 448.
+    E4M3,
+    /// FP8 E5M2: 1 sign, 5 exponent, 2 mantissa bits; the top byte of a
+    /// binary16 value, with IEEE infinities and NaNs.
+    E5M2,
+}
+
+impl IEEEFormat {
+    /// Returns the field layout of formats that follow the IEEE 754
+    /// encoding rules.
+    ///
+    /// # Returns
+    /// - `Some(layout)`, or `None` for [`IEEEFormat::E4M3`], whose top
+    ///   exponent holds finite values instead of infinities.
+    pub fn layout(self) -> Option<FloatFormat> {
+        match self {
+            IEEEFormat::E4M3 => None,
+            IEEEFormat::E5M2 => Some(FloatFormat::E5M2),
+            IEEEFormat::Half => Some(FloatFormat::HALF),
+            IEEEFormat::BFloat16 => Some(FloatFormat::BFLOAT16),
+            IEEEFormat::Single => Some(FloatFormat::SINGLE),
+            IEEEFormat::Double => Some(FloatFormat::DOUBLE),
+        }
+    }
+
+    /// Returns the width of the exponent field.
+    pub fn exponent_width(self) -> usize {
+        match self {
+            IEEEFormat::E4M3 => 4,
+            IEEEFormat::Half | IEEEFormat::E5M2 => 5,
+            IEEEFormat::Single | IEEEFormat::BFloat16 => 8,
+            IEEEFormat::Double => 11,
+        }
+    }
+
+    /// Returns the width of the mantissa field.
+    pub fn mantissa_width(self) -> usize {
+        match self {
+            IEEEFormat::E5M2 => 2,
+            IEEEFormat::E4M3 => 3,
+            IEEEFormat::BFloat16 => 7,
+            IEEEFormat::Half => 10,
+            IEEEFormat::Single => 23,
+            IEEEFormat::Double => 52,
+        }
+    }
+}
+
+/// IEEE 754 Decoder structure
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IEEEDecoder {
+    /// Sign bit (1 for negative).
+    pub sign: u8,
+    /// Unbiased exponent.
+    pub exponent: i32,
+    /// Raw (biased) exponent field.
+    pub exponent_bits: i32,
+    /// Raw mantissa (fraction) field without the hidden bit.
+    pub mantissa: u64,
+    /// Decoded value widened to `f64`.
+    pub value: f64,
+    /// Classification: `Zero`, `Denormalized`, `Normalized`, `+Inf`, `-Inf` or `NaN`.
+    pub special: String,
+    /// Format the bits were decoded as.
+    pub format: IEEEFormat,
+}
+
+impl IEEEDecoder {
+    /// Decodes a raw bit pattern as an IEEE 754 floating point value.
+    ///
+    /// # Arguments
+    /// - `bits`: The raw bits; only the low `bit_size` bits are used.
+    /// - `bit_size`: 8 for FP8 E4M3, 16 for half, 32 for single, anything
+    ///   else for double precision.
+    ///
+    /// # Returns
+    /// The decoded sign, exponent, mantissa, value and classification.
+    pub fn new(bits: u64, bit_size: u64) -> Self {
+        match bit_size {
+            8 => Self::decode_e4m3(bits as u8),
+            16 => Self::decode_half(bits as u16),
+            32 => Self::decode_single(bits as u32),
+            _ => Self::decode_double(bits),
+        }
+    }
+
+    /// Decodes a raw bit pattern in an explicit format, e.g. to read 16 bits
+    /// as bfloat16 rather than half precision.
+    ///
+    /// # Arguments
+    /// - `bits`: The raw bits; only the low bits of the format's width are used.
+    /// - `format`: The format to decode as.
+    ///
+    /// # Returns
+    /// The decoded sign, exponent, mantissa, value and classification.
+    pub fn with_format(bits: u64, format: IEEEFormat) -> Self {
+        match format {
+            IEEEFormat::Half => Self::decode_half(bits as u16),
+            IEEEFormat::Single => Self::decode_single(bits as u32),
+            IEEEFormat::Double => Self::decode_double(bits),
+            IEEEFormat::BFloat16 => Self::decode_bfloat16(bits as u16),
+            IEEEFormat::E4M3 => Self::decode_e4m3(bits as u8),
+            IEEEFormat::E5M2 => Self::decode_e5m2(bits as u8),
+        }
+    }
+
+    fn decode_e4m3(bits: u8) -> Self {
+        let sign = bits >> 7;
+        let exponent_bits = ((bits >> 3) & 0xF) as i32;
+        let exponent = exponent_bits - 7;
+        let mantissa = u64::from(bits & 0x7);
+        let sign_mult = if sign == 1 { -1.0 } else { 1.0 };
+
+        let (special, value) = match (exponent_bits, mantissa) {
+            (0xF, 0x7) => ("NaN", f64::NAN),
+            (0, 0) => ("Zero", 0.0),
+            // Denormalized: exp = -6, no hidden bit
+            (0, _) => (
+                "Denormalized",
+                sign_mult * mantissa as f64 * 2.0f64.powi(-9),
+            ),
+            _ => (
+                "Normalized",
+                sign_mult * (8 + mantissa) as f64 * 2.0f64.powi(exponent - 3),
+            ),
+        };
+
+        Self {
+            sign,
+            exponent,
+            exponent_bits,
+            mantissa,
+            value,
+            special: special.to_string(),
+            format: IEEEFormat::E4M3,
+        }
+    }
+
+    fn decode_e5m2(bits: u8) -> Self {
+        // E5M2 is binary16 with the low 8 mantissa bits dropped
+        let half = Self::decode_half(u16::from(bits) << 8);
+        Self {
+            mantissa: half.mantissa >> 8,
+            format: IEEEFormat::E5M2,
+            ..half
+        }
+    }
+
+    fn decode_half(bits: u16) -> Self {
+        let sign = ((bits >> 15) & 1) as u8;
+        let exponent_bits = ((bits >> 10) & 0x1F) as i32;
+        let exponent = exponent_bits - 15;
+        let mantissa = (bits & 0x03FF) as u64;
+
+        let (special, value) = match (exponent_bits, mantissa) {
+            (0x1F, 0) => (
+                if sign == 0 { "+Inf" } else { "-Inf" },
+                if sign == 0 {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                },
+            ),
+            (0x1F, _) => ("NaN", f64::NAN),
+            (0, 0) => ("Zero", 0.0),
+            (0, _) => ("Denormalized", Self::half_to_f64(sign, 0, mantissa)),
+            _ => (
+                "Normalized",
+                Self::half_to_f64(sign, exponent_bits, mantissa),
+            ),
+        };
+
+        Self {
+            sign,
+            exponent,
+            exponent_bits,
+            mantissa,
+            value,
+            special: special.to_string(),
+            format: IEEEFormat::Half,
+        }
+    }
+
+    fn decode_single(bits: u32) -> Self {
+        let sign = ((bits >> 31) & 1) as u8;
+        let exponent_bits = ((bits >> 23) & 0xFF) as i32;
+        let exponent = exponent_bits - 127;
+        let mantissa = (bits & 0x007F_FFFF) as u64;
+
+        let (special, value) = match (exponent_bits, mantissa) {
+            (0xFF, 0) => (
+                if sign == 0 { "+Inf" } else { "-Inf" },
+                if sign == 0 {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                },
+            ),
+            (0xFF, _) => ("NaN", f64::NAN),
+            (0, 0) => ("Zero", f32::from_bits(bits) as f64),
+            (0, _) => ("Denormalized", f32::from_bits(bits) as f64),
+            _ => ("Normalized", f32::from_bits(bits) as f64),
+        };
+
+        Self {
+            sign,
+            exponent,
+            exponent_bits,
+            mantissa,
+            value,
+            special: special.to_string(),
+            format: IEEEFormat::Single,
+        }
+    }
+
+    fn decode_bfloat16(bits: u16) -> Self {
+        // bfloat16 is binary32 with the low 16 mantissa bits dropped
+        let single = Self::decode_single(u32::from(bits) << 16);
+        Self {
+            mantissa: single.mantissa >> 16,
+            format: IEEEFormat::BFloat16,
+            ..single
+        }
+    }
+
+    fn decode_double(bits: u64) -> Self {
+        let sign = ((bits >> 63) & 1) as u8;
+        let exponent_bits = ((bits >> 52) & 0x7FF) as i32;
+        let exponent = exponent_bits - 1023;
+        let mantissa = bits & 0x000F_FFFF_FFFF_FFFF;
+
+        let (special, value) = match (exponent_bits, mantissa) {
+            (0x7FF, 0) => (
+                if sign == 0 { "+Inf" } else { "-Inf" },
+                if sign == 0 {
+                    f64::INFINITY
+                } else {
+                    f64::NEG_INFINITY
+                },
+            ),
+            (0x7FF, _) => ("NaN", f64::NAN),
+            (0, 0) => ("Zero", f64::from_bits(bits)),
+            (0, _) => ("Denormalized", f64::from_bits(bits)),
+            _ => ("Normalized", f64::from_bits(bits)),
+        };
+
+        Self {
+            sign,
+            exponent,
+            exponent_bits,
+            mantissa,
+            value,
+            special: special.to_string(),
+            format: IEEEFormat::Double,
+        }
+    }
+
+    fn half_to_f64(sign: u8, exponent: i32, mantissa: u64) -> f64 {
+        let sign_mult = if sign == 1 { -1.0 } else { 1.0 };
+
+        if exponent == 0 {
+            // Denormalized numbers: exp = -14, no hidden leading 1 in the mantissa
+            sign_mult * (mantissa as f64) * 2.0f64.powi(-24) // 2^(-14 - 10)
+        } else {
+            // Normal numbers: exp - 15, add the hidden leading 1 to the mantissa
+            sign_mult * (1.0 + (mantissa as f64) / 1024.0) * 2.0f64.powi(exponent - 15)
+        }
+    }
+
+    /// Returns the width of the exponent field for the decoded format.
+    pub fn exponent_bits_count(&self) -> usize {
+        self.format.exponent_width()
+    }
+
+    /// Returns the width of the mantissa field for the decoded format.
+    pub fn mantissa_bits(&self) -> usize {
+        self.format.mantissa_width()
+    }
+
+    /// Assembles a bit pattern from its sign, biased exponent and mantissa
+    /// fields, the inverse of [`IEEEDecoder::with_format`].
+    ///
+    /// # Arguments
+    /// - `format`: The format to encode in.
+    /// - `sign`: The sign bit; only 0 and 1 are accepted.
+    /// - `exponent_bits`: The raw (biased) exponent field.
+    /// - `mantissa`: The raw mantissa field without the hidden bit.
+    ///
+    /// # Returns
+    /// - `Some(bits)`, or `None` when a field does not fit its width.
+    pub fn encode(format: IEEEFormat, sign: u8, exponent_bits: u64, mantissa: u64) -> Option<u64> {
+        let exponent_width = format.exponent_width();
+        let mantissa_width = format.mantissa_width();
+        if sign > 1 || exponent_bits >> exponent_width != 0 || mantissa >> mantissa_width != 0 {
+            return None;
+        }
+        Some(
+            u64::from(sign) << (exponent_width + mantissa_width)
+                | exponent_bits << mantissa_width
+                | mantissa,
+        )
+    }
+}
+
+/// binary128 (quad precision) decoder: 1 sign, 15 exponent, 112 mantissa bits.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadDecoder {
+    /// Sign bit (1 for negative).
+    pub sign: u8,
+    /// Unbiased exponent.
+    pub exponent: i32,
+    /// Raw (biased) exponent field.
+    pub exponent_bits: i32,
+    /// Raw mantissa (fraction) field without the hidden bit.
+    pub mantissa: u128,
+    /// Decoded value rounded to `f64` (may overflow to infinity or flush to
+    /// zero outside the `f64` range).
+    pub value: f64,
+    /// Classification: `Zero`, `Denormalized`, `Normalized`, `+Inf`, `-Inf` or `NaN`.
+    pub special: String,
+}
+
+impl QuadDecoder {
+    /// Width of the exponent field.
+    pub const EXPONENT_BITS: usize = 15;
+    /// Width of the mantissa field.
+    pub const MANTISSA_BITS: usize = 112;
+    const BIAS: i32 = 16383;
+
+    /// Decodes a raw 128-bit pattern.
+    pub fn new(bits: u128) -> Self {
+        let sign = (bits >> 127) as u8;
+        let exponent_bits = ((bits >> 112) & 0x7FFF) as i32;
+        let exponent = exponent_bits - Self::BIAS;
+        let mantissa = bits & ((1 << 112) - 1);
+        let sign_mult = if sign == 1 { -1.0 } else { 1.0 };
+
+        let (special, value) = match (exponent_bits, mantissa) {
+            (0x7FFF, 0) => (
+                if sign == 0 { "+Inf" } else { "-Inf" },
+                sign_mult * f64::INFINITY,
+            ),
+            (0x7FFF, _) => ("NaN", f64::NAN),
+            (0, 0) => ("Zero", sign_mult * 0.0),
+            (0, _) => (
+                "Denormalized",
+                sign_mult * scale(mantissa as f64, 1 - Self::BIAS - 112),
+            ),
+            _ => (
+                "Normalized",
+                sign_mult * scale((mantissa | 1 << 112) as f64, exponent - 112),
+            ),
+        };
+
+        Self {
+            sign,
+            exponent,
+            exponent_bits,
+            mantissa,
+            value,
+            special: special.to_string(),
+        }
+    }
+
+    /// Returns the exact decimal value, or the classification for
+    /// infinities and NaN.
+    pub fn exact(&self) -> String {
+        match self.special.as_str() {
+            "Normalized" => exact_parts(
+                self.sign == 1,
+                self.mantissa | 1 << 112,
+                self.exponent - 112,
+            ),
+            "Denormalized" | "Zero" => {
+                exact_parts(self.sign == 1, self.mantissa, 1 - Self::BIAS - 112)
+            }
+            special => special.to_string(),
+        }
+    }
+}
+
+/// Encodes an `f64` as binary128. Every `f64` value is exactly representable;
+/// NaN payloads are preserved in the high mantissa bits.
+pub fn f64_to_quad(x: f64) -> u128 {
+    let bits = x.to_bits();
+    let sign = u128::from(bits >> 63) << 127;
+    let exp_field = ((bits >> 52) & 0x7FF) as i32;
+    let fraction = u128::from(bits & ((1 << 52) - 1));
+
+    match exp_field {
+        0x7FF => sign | 0x7FFF << 112 | fraction << 60,
+        0 if fraction == 0 => sign,
+        0 => {
+            // Normalize the subnormal so its leading one becomes the hidden bit
+            let shift = fraction.leading_zeros() - (128 - 53);
+            let exponent = -1022 - shift as i32;
+            let mantissa = (fraction << shift) & ((1 << 52) - 1);
+            sign | ((exponent + QuadDecoder::BIAS) as u128) << 112 | mantissa << 60
+        }
+        _ => sign | ((exp_field - 1023 + QuadDecoder::BIAS) as u128) << 112 | fraction << 60,
+    }
+}
+
+/// Encodes an `f64` as FP8 E4M3, rounding to nearest with ties to even.
+/// NaN and magnitudes beyond the largest value's rounding range (448) encode
+/// as NaN, since the format has no infinities.
+pub fn f64_to_e4m3(x: f64) -> u8 {
+    let sign = if x.is_sign_negative() { 0x80 } else { 0 };
+    // Halfway between 448 and the next step of 32 above it
+    if x.is_nan() || x.abs() >= 464.0 {
+        return sign | 0x7F;
+    }
+    let magnitude = |code: u8| IEEEDecoder::with_format(code.into(), IEEEFormat::E4M3).value;
+    // The finite codes are ordered by magnitude; take the nearest, preferring
+    // an even mantissa on a tie
+    let nearest = (0..=0x7E)
+        .min_by(|&a: &u8, &b: &u8| {
+            let (da, db) = (
+                (magnitude(a) - x.abs()).abs(),
+                (magnitude(b) - x.abs()).abs(),
+            );
+            da.total_cmp(&db).then((a & 1).cmp(&(b & 1)))
+        })
+        .unwrap_or(0);
+    sign | nearest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_subnormals() {
+        let smallest = IEEEDecoder::new(0x0000_0001, 32);
+        assert_eq!(smallest.special, "Denormalized");
+        assert_eq!(smallest.value, f64::from(f32::from_bits(1)));
+        assert!((smallest.value - 1.401_298_464e-45).abs() < 1e-54);
+
+        let negative = IEEEDecoder::new(0x8000_0001, 32);
+        assert_eq!(negative.sign, 1);
+        assert_eq!(negative.value, -smallest.value);
+
+        let largest = IEEEDecoder::new(0x007F_FFFF, 32);
+        assert_eq!(largest.value, f64::from(f32::from_bits(0x007F_FFFF)));
+    }
+
+    #[test]
+    fn double_subnormals() {
+        let smallest = IEEEDecoder::new(1, 64);
+        assert_eq!(smallest.special, "Denormalized");
+        assert_eq!(smallest.value, f64::from_bits(1));
+        assert_eq!(smallest.value, 5e-324);
+
+        let negative = IEEEDecoder::new(0x8000_0000_0000_0001, 64);
+        assert_eq!(negative.value, -f64::from_bits(1));
+    }
+
+    #[test]
+    fn bfloat16_subnormals() {
+        let smallest = IEEEDecoder::with_format(0x0001, IEEEFormat::BFloat16);
+        assert_eq!(smallest.special, "Denormalized");
+        assert_eq!(smallest.mantissa, 1);
+        assert_eq!(smallest.value, f64::from(f32::from_bits(0x0001_0000)));
+        assert!((smallest.value - 9.183_549_615e-41).abs() < 1e-50);
+
+        let negative = IEEEDecoder::with_format(0x8001, IEEEFormat::BFloat16);
+        assert_eq!(negative.value, -smallest.value);
+    }
+
+    #[test]
+    fn negative_zero_keeps_its_sign() {
+        assert!(IEEEDecoder::new(0x8000_0000, 32).value.is_sign_negative());
+        assert!(IEEEDecoder::new(1 << 63, 64).value.is_sign_negative());
+    }
+}
@@ -0,0 +1,145 @@
+//! Interval Arithmetic
+//!
+//! A closed interval `[lo, hi]` of `f64` values whose operations round the
+//! lower bound down and the upper bound up, so the exact real result is always
+//! contained. Chaining operations shows how uncertainty accumulates.
+
+use crate::rounding::{self, RoundingMode};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Sub};
+
+/// Closed interval guaranteed to contain an exact real value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    /// Lower bound (rounded toward negative infinity).
+    pub lo: f64,
+    /// Upper bound (rounded toward positive infinity).
+    pub hi: f64,
+}
+
+impl Interval {
+    /// The interval containing every real number.
+    pub const ENTIRE: Interval = Interval {
+        lo: f64::NEG_INFINITY,
+        hi: f64::INFINITY,
+    };
+
+    /// Creates an interval from its bounds, swapping them if reversed.
+    pub fn new(lo: f64, hi: f64) -> Self {
+        if lo <= hi {
+            Self { lo, hi }
+        } else {
+            Self { lo: hi, hi: lo }
+        }
+    }
+
+    /// Creates the degenerate interval `[x, x]` for an exactly known value.
+    pub fn point(x: f64) -> Self {
+        Self { lo: x, hi: x }
+    }
+
+    /// Returns `true` if `x` lies within the interval.
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    /// Returns `hi - lo`, rounded up.
+    pub fn width(&self) -> f64 {
+        rounding::sub(self.hi, self.lo, RoundingMode::Up)
+    }
+
+    /// Returns the midpoint of the interval.
+    pub fn midpoint(&self) -> f64 {
+        self.lo / 2.0 + self.hi / 2.0
+    }
+
+    /// Widens the bounds outward to the nearest `f32` values, giving the
+    /// tightest single-precision interval containing this one.
+    pub fn round_out_f32(self) -> Interval {
+        Interval {
+            lo: rounding::f64_to_f32(self.lo, RoundingMode::Down) as f64,
+            hi: rounding::f64_to_f32(self.hi, RoundingMode::Up) as f64,
+        }
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+
+    /// Interval sum.
+    fn add(self, other: Interval) -> Interval {
+        Interval {
+            lo: rounding::add(self.lo, other.lo, RoundingMode::Down),
+            hi: rounding::add(self.hi, other.hi, RoundingMode::Up),
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+
+    /// Interval difference.
+    fn sub(self, other: Interval) -> Interval {
+        Interval {
+            lo: rounding::sub(self.lo, other.hi, RoundingMode::Down),
+            hi: rounding::sub(self.hi, other.lo, RoundingMode::Up),
+        }
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+
+    /// Interval product.
+    fn mul(self, other: Interval) -> Interval {
+        let pairs = [
+            (self.lo, other.lo),
+            (self.lo, other.hi),
+            (self.hi, other.lo),
+            (self.hi, other.hi),
+        ];
+        let lo = pairs
+            .iter()
+            .map(|&(a, b)| rounding::mul(a, b, RoundingMode::Down))
+            .fold(f64::INFINITY, f64::min);
+        let hi = pairs
+            .iter()
+            .map(|&(a, b)| rounding::mul(a, b, RoundingMode::Up))
+            .fold(f64::NEG_INFINITY, f64::max);
+        Interval { lo, hi }
+    }
+}
+
+impl Div for Interval {
+    type Output = Interval;
+
+    /// Interval quotient; division by an interval containing zero yields
+    /// [`Interval::ENTIRE`].
+    fn div(self, other: Interval) -> Interval {
+        if other.contains(0.0) {
+            return Interval::ENTIRE;
+        }
+        let pairs = [
+            (self.lo, other.lo),
+            (self.lo, other.hi),
+            (self.hi, other.lo),
+            (self.hi, other.hi),
+        ];
+        let lo = pairs
+            .iter()
+            .map(|&(a, b)| rounding::div(a, b, RoundingMode::Down))
+            .fold(f64::INFINITY, f64::min);
+        let hi = pairs
+            .iter()
+            .map(|&(a, b)| rounding::div(a, b, RoundingMode::Up))
+            .fold(f64::NEG_INFINITY, f64::max);
+        Interval { lo, hi }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{:e}, {:e}]", self.lo, self.hi)
+    }
+}
@@ -0,0 +1,443 @@
+//! C Struct Layouts
+//!
+//! Parses a C-like struct description and computes where each field lies,
+//! following the usual C rules: every field is aligned to its own size,
+//! capped by the packing (`#pragma pack(n)`), and the struct is padded at
+//! the end to a multiple of its largest field alignment so arrays of it stay
+//! aligned.
+//!
+//! ```text
+//! struct header {
+//!     uint8_t  flags;
+//!     uint16_t len;      // offset 2 with natural packing, 1 with pack(1)
+//!     uint32_t crc;
+//!     char     tag[3], kind;
+//! };
+//! ```
+//!
+//! Accepted types are `u8`..`u64`, `i8`..`i64`, `f32`, `f64`, the
+//! `<stdint.h>` names, `char`, `bool`, `short`, `int`, `long long`, `float`
+//! and `double` with `signed`/`unsigned` variants. The `struct name { ... };`
+//! wrapper is optional and `//` starts a comment.
+
+use crate::template::Endian;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// How a field's bytes are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScalarKind {
+    /// Unsigned integer.
+    Unsigned,
+    /// Two's complement integer.
+    Signed,
+    /// IEEE 754 binary32 or binary64.
+    Float,
+}
+
+/// A scalar C type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scalar {
+    /// Size in bytes: 1, 2, 4 or 8.
+    pub size: usize,
+    /// How the bytes are read.
+    pub kind: ScalarKind,
+}
+
+impl Scalar {
+    /// Looks up a type name, with its words separated by single spaces.
+    fn parse(name: &str) -> Option<Self> {
+        use ScalarKind::*;
+        let (size, kind) = match name {
+            "u8" | "uint8_t" | "unsigned char" | "bool" | "_Bool" => (1, Unsigned),
+            "i8" | "int8_t" | "char" | "signed char" => (1, Signed),
+            "u16" | "uint16_t" | "unsigned short" => (2, Unsigned),
+            "i16" | "int16_t" | "short" | "signed short" => (2, Signed),
+            "u32" | "uint32_t" | "unsigned int" | "unsigned" => (4, Unsigned),
+            "i32" | "int32_t" | "int" | "signed int" | "signed" => (4, Signed),
+            "u64" | "uint64_t" | "unsigned long long" => (8, Unsigned),
+            "i64" | "int64_t" | "long long" | "signed long long" => (8, Signed),
+            "f32" | "float" => (4, Float),
+            "f64" | "double" => (8, Float),
+            _ => return None,
+        };
+        Some(Self { size, kind })
+    }
+}
+
+/// Error produced while parsing a layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutError {
+    /// 1-based source line the error refers to.
+    pub line: usize,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {}
+
+/// A field placed in a layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutField {
+    /// Field name.
+    pub name: String,
+    /// Type name as written, e.g. `uint16_t`.
+    pub type_name: String,
+    /// Element type.
+    pub scalar: Scalar,
+    /// Element count of an array field, `None` for a scalar.
+    pub count: Option<usize>,
+    /// Byte offset from the start of the struct.
+    pub offset: usize,
+}
+
+impl LayoutField {
+    /// Returns the field's size in bytes.
+    pub fn size(&self) -> usize {
+        self.scalar.size * self.count.unwrap_or(1)
+    }
+}
+
+/// A field element read from a byte buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldValue {
+    /// Field name; array elements are named `name[i]`.
+    pub name: String,
+    /// Byte offset of the element.
+    pub offset: usize,
+    /// Element type.
+    pub scalar: Scalar,
+    /// Raw bits, or `None` if the buffer ends before the element does.
+    pub bits: Option<u64>,
+}
+
+impl FieldValue {
+    /// Formats the value for its type: decimal and hex for unsigned
+    /// integers, signed decimal, or the float value.
+    pub fn display(&self) -> String {
+        let Some(bits) = self.bits else {
+            return "past end".to_string();
+        };
+        let size = self.scalar.size;
+        match self.scalar.kind {
+            ScalarKind::Unsigned => format!("{} (0x{:0w$X})", bits, bits, w = size * 2),
+            ScalarKind::Signed => {
+                let shift = 64 - size * 8;
+                ((bits << shift) as i64 >> shift).to_string()
+            }
+            ScalarKind::Float if size == 4 => format!("{:?}", f32::from_bits(bits as u32)),
+            ScalarKind::Float => format!("{:?}", f64::from_bits(bits)),
+        }
+    }
+}
+
+/// A struct layout with every field's offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    /// Fields in declaration order.
+    pub fields: Vec<LayoutField>,
+    /// Padding byte ranges inserted for alignment, including the tail.
+    pub padding: Vec<Range<usize>>,
+    /// Total size in bytes, including tail padding.
+    pub size: usize,
+    /// Alignment of the whole struct.
+    pub align: usize,
+}
+
+/// Returns the 1-based line of byte `pos` in `src`.
+fn line_at(src: &str, pos: usize) -> usize {
+    src[..pos].matches('\n').count() + 1
+}
+
+/// Replaces `//` comments with spaces, keeping byte positions and lines.
+fn strip_comments(src: &str) -> String {
+    src.lines()
+        .map(|line| match line.find("//") {
+            Some(i) => format!("{}{}", &line[..i], " ".repeat(line.len() - i)),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns `true` for identifiers: a letter or `_`, then alphanumerics.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Layout {
+    /// Parses a struct description and lays it out.
+    ///
+    /// # Arguments
+    /// - `src`: Field declarations such as `u8 flags; u16 len[2];`,
+    ///   optionally inside `struct name { ... };`.
+    /// - `pack`: Largest alignment of any field, as `#pragma pack(n)`: 1
+    ///   for a packed struct, 8 for natural alignment.
+    ///
+    /// # Returns
+    /// - The layout, or the first error: an unknown type, a bad name or
+    ///   array size, a duplicate field or a packing that is not 1, 2, 4 or
+    ///   8.
+    pub fn parse(src: &str, pack: usize) -> Result<Self, LayoutError> {
+        if ![1, 2, 4, 8].contains(&pack) {
+            return Err(LayoutError {
+                line: 1,
+                message: format!("packing {} is not 1, 2, 4 or 8", pack),
+            });
+        }
+        let text = strip_comments(src);
+        // Only the body of a `struct name { ... }` wrapper is read
+        let (start, end) = match (text.find('{'), text.rfind('}')) {
+            (Some(open), Some(close)) if open < close => (open + 1, close),
+            _ => (0, text.len()),
+        };
+
+        let mut layout = Layout {
+            fields: Vec::new(),
+            padding: Vec::new(),
+            size: 0,
+            align: 1,
+        };
+        let mut pos = start;
+        for decl in text[start..end].split(';') {
+            let line = line_at(&text, pos + (decl.len() - decl.trim_start().len()));
+            pos += decl.len() + 1;
+            let decl = decl.trim();
+            if decl.is_empty() {
+                continue;
+            }
+            let error = |message: String| LayoutError { line, message };
+
+            // The type is the longest run of leading words naming one, so
+            // `unsigned long long` is not read as `unsigned`
+            let words: Vec<&str> = decl.split_whitespace().collect();
+            let split = (1..words.len())
+                .rev()
+                .find(|&i| Scalar::parse(&words[..i].join(" ")).is_some())
+                .ok_or_else(|| error(format!("unknown type in `{}`", decl)))?;
+            let type_name = words[..split].join(" ");
+            let scalar = Scalar::parse(&type_name)
+                .ok_or_else(|| error(format!("unknown type `{}`", type_name)))?;
+
+            for declarator in words[split..].join(" ").split(',') {
+                let declarator = declarator.trim();
+                let (name, count) = match declarator.split_once('[') {
+                    Some((name, rest)) => {
+                        let count = rest
+                            .strip_suffix(']')
+                            .and_then(|n| n.trim().parse::<usize>().ok())
+                            .filter(|&n| n > 0)
+                            .ok_or_else(|| error(format!("bad array size in `{}`", declarator)))?;
+                        (name.trim(), Some(count))
+                    }
+                    None => (declarator, None),
+                };
+                if !is_identifier(name) {
+                    return Err(error(format!("bad field name `{}`", name)));
+                }
+                if layout.fields.iter().any(|f| f.name == name) {
+                    return Err(error(format!("duplicate field `{}`", name)));
+                }
+                layout.push(
+                    LayoutField {
+                        name: name.to_string(),
+                        type_name: type_name.clone(),
+                        scalar,
+                        count,
+                        offset: 0,
+                    },
+                    pack,
+                );
+            }
+        }
+
+        // Tail padding keeps arrays of the struct aligned
+        let tail = layout.size.next_multiple_of(layout.align);
+        if tail > layout.size {
+            layout.padding.push(layout.size..tail);
+            layout.size = tail;
+        }
+        Ok(layout)
+    }
+
+    /// Places `field` after the fields so far, aligned to its element size
+    /// capped by `pack`.
+    fn push(&mut self, mut field: LayoutField, pack: usize) {
+        let align = field.scalar.size.min(pack);
+        let offset = self.size.next_multiple_of(align);
+        if offset > self.size {
+            self.padding.push(self.size..offset);
+        }
+        field.offset = offset;
+        self.size = offset + field.size();
+        self.align = self.align.max(align);
+        self.fields.push(field);
+    }
+
+    /// Reads every field element from `data`, which starts at the struct's
+    /// first byte.
+    ///
+    /// # Arguments
+    /// - `data`: The struct's bytes; elements past its end have no value.
+    /// - `endian`: Byte order of multi-byte elements.
+    ///
+    /// # Returns
+    /// The elements in layout order, array elements one by one.
+    pub fn decode(&self, data: &[u8], endian: Endian) -> Vec<FieldValue> {
+        let mut values = Vec::new();
+        for field in &self.fields {
+            for i in 0..field.count.unwrap_or(1) {
+                let offset = field.offset + i * field.scalar.size;
+                let bits = data.get(offset..offset + field.scalar.size).map(|bytes| {
+                    let fold = |acc: u64, &b: &u8| acc << 8 | u64::from(b);
+                    match endian {
+                        Endian::Big => bytes.iter().fold(0, fold),
+                        Endian::Little => bytes.iter().rev().fold(0, fold),
+                    }
+                });
+                let name = match field.count {
+                    Some(_) => format!("{}[{}]", field.name, i),
+                    None => field.name.clone(),
+                };
+                values.push(FieldValue {
+                    name,
+                    offset,
+                    scalar: field.scalar,
+                    bits,
+                });
+            }
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Returns each field's name and offset
+    fn offsets(layout: &Layout) -> Vec<(&str, usize)> {
+        layout
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset))
+            .collect()
+    }
+
+    #[test]
+    fn natural_alignment_of_mixed_sizes() {
+        let layout = Layout::parse(
+            "struct s {
+                uint8_t  a;   // 0, then 1 byte of padding
+                uint16_t b;   // 2
+                uint8_t  c;   // 4, then 3 bytes of padding
+                uint32_t d;   // 8
+                char     e;   // 12, then 3 bytes of padding
+                double   f;   // 16
+            };",
+            8,
+        )
+        .unwrap();
+        assert_eq!(
+            offsets(&layout),
+            [("a", 0), ("b", 2), ("c", 4), ("d", 8), ("e", 12), ("f", 16)]
+        );
+        assert_eq!(layout.padding, [1..2, 5..8, 13..16]);
+        assert_eq!(layout.size, 24);
+        assert_eq!(layout.align, 8);
+    }
+
+    #[test]
+    fn trailing_padding() {
+        // Rounded up to the 4-byte alignment of `a`
+        let layout = Layout::parse("uint32_t a; uint8_t b;", 8).unwrap();
+        assert_eq!(layout.size, 8);
+        assert_eq!(layout.padding, vec![5..8]);
+        // Only byte fields: no padding at all
+        let bytes = Layout::parse("u8 a; u8 b[3];", 8).unwrap();
+        assert_eq!(bytes.size, 4);
+        assert_eq!(bytes.align, 1);
+        assert!(bytes.padding.is_empty());
+    }
+
+    #[test]
+    fn packed_layouts() {
+        let src = "u8 a; u32 b; u16 c; u64 d;";
+        let packed = Layout::parse(src, 1).unwrap();
+        assert_eq!(offsets(&packed), [("a", 0), ("b", 1), ("c", 5), ("d", 7)]);
+        assert_eq!(packed.size, 15);
+        assert!(packed.padding.is_empty());
+
+        let pack2 = Layout::parse(src, 2).unwrap();
+        assert_eq!(offsets(&pack2), [("a", 0), ("b", 2), ("c", 6), ("d", 8)]);
+        assert_eq!(pack2.size, 16);
+        assert_eq!(pack2.align, 2);
+
+        let pack4 = Layout::parse(src, 4).unwrap();
+        assert_eq!(offsets(&pack4), [("a", 0), ("b", 4), ("c", 8), ("d", 12)]);
+        assert_eq!(pack4.size, 20);
+        assert_eq!(pack4.padding, [1..4, 10..12]);
+    }
+
+    #[test]
+    fn arrays_and_multi_word_types() {
+        let layout =
+            Layout::parse("char tag[3], kind; unsigned long long n; short s[2];", 8).unwrap();
+        assert_eq!(
+            offsets(&layout),
+            [("tag", 0), ("kind", 3), ("n", 8), ("s", 16)]
+        );
+        assert_eq!(layout.fields[2].scalar.size, 8);
+        assert_eq!(layout.fields[2].type_name, "unsigned long long");
+        assert_eq!(layout.fields[3].size(), 4);
+        assert_eq!(layout.size, 24);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Layout::parse("u8 a;", 3).unwrap_err().line, 1);
+        assert_eq!(Layout::parse("u8 a;\nfoo b;", 8).unwrap_err().line, 2);
+        assert!(Layout::parse("u8 a; u8 a;", 8).is_err());
+        assert!(Layout::parse("u8 a[0];", 8).is_err());
+        assert!(Layout::parse("u8 1a;", 8).is_err());
+    }
+
+    #[test]
+    fn decodes_fields_in_both_byte_orders() {
+        let layout = Layout::parse("u8 a; u16 b; i8 c; f32 d;", 8).unwrap();
+        let data = [
+            0xFF, 0x00, 0x34, 0x12, 0x80, 0, 0, 0, 0x00, 0x00, 0x80, 0x3F,
+        ];
+        let values = layout.decode(&data, Endian::Little);
+        let bits: Vec<Option<u64>> = values.iter().map(|v| v.bits).collect();
+        assert_eq!(
+            bits,
+            [Some(0xFF), Some(0x1234), Some(0x80), Some(0x3F80_0000)]
+        );
+        assert_eq!(values[2].display(), "-128");
+        assert_eq!(values[3].display(), "1.0");
+        assert_eq!(layout.decode(&data, Endian::Big)[1].bits, Some(0x3412));
+        // Elements past the end of the buffer have no value
+        assert_eq!(layout.decode(&data[..6], Endian::Little)[3].bits, None);
+    }
+}
@@ -1,7 +1,97 @@
-use std::fmt;
+//! Bit manipulation primitives for the bitwise viewer.
+//!
+//! The crate is `no_std` compatible. Optional functionality is gated behind
+//! cargo features:
+//!
+//! - `std` (default): standard library support, implies `alloc`.
+//! - `alloc`: helpers that return heap-allocated collections, the
+//!   multi-word [`bitvec::BitVec`] for widths beyond 64 bits, the binary
+//!   [`template`] language, [`magic`] file identification, bit grid
+//!   image [`export`], [`base_encoding`] (Base64/Base32), [`disasm`]
+//!   instruction decoding, struct [`layout`]s, [`register_map`]s, saved
+//!   [`session`]s, viewer [`settings`] and source code [`snippet`]s.
+//! - `float` (default): the [`ieee754`] decoder, directed [`rounding`],
+//!   [`fixed_point`] Q-format conversion, [`interval`] arithmetic,
+//!   [`accumulate`] error tracking, [`decimal`] string conversion and
+//!   [`precision`] loss measurement.
+//! - `serde`: `Serialize`/`Deserialize` implementations.
+//! - `rand`: random [`BitArray`] generation via `rand::distr::StandardUniform`,
+//!   random bit flips with [`BitArray::mutate`] and the random [`patterns`]
+//!   generators.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "float")]
+pub mod accumulate;
+pub mod arith;
+#[cfg(feature = "alloc")]
+pub mod base_encoding;
+#[cfg(feature = "alloc")]
+pub mod bitvec;
+pub mod checksum;
+pub mod color;
+#[cfg(feature = "float")]
+pub mod decimal;
+#[cfg(feature = "alloc")]
+pub mod disasm;
+pub mod ecc;
+#[cfg(feature = "alloc")]
+pub mod export;
+pub mod expr;
+pub mod file_mode;
+#[cfg(feature = "float")]
+pub mod fixed_point;
+#[cfg(feature = "float")]
+pub mod ieee754;
+#[cfg(feature = "float")]
+pub mod interval;
+#[cfg(feature = "alloc")]
+pub mod layout;
+#[cfg(feature = "alloc")]
+pub mod magic;
+pub mod net;
+pub mod net_repr;
+pub mod parse;
+pub mod patterns;
+pub mod permute;
+#[cfg(feature = "float")]
+pub mod precision;
+#[cfg(feature = "alloc")]
+pub mod register_map;
+#[cfg(feature = "float")]
+pub mod rounding;
+pub mod script;
+pub mod search;
+#[cfg(feature = "alloc")]
+pub mod session;
+#[cfg(feature = "alloc")]
+pub mod settings;
+pub mod sized;
+#[cfg(feature = "alloc")]
+pub mod snippet;
+#[cfg(feature = "alloc")]
+pub mod template;
+pub mod timestamp;
+pub mod unicode;
+pub mod varint;
+pub mod width;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{self, Range};
+pub use width::Width;
 
 /// A struct representing a 64-bit array of bits stored in a u64.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Positions and fields are not checked against a bit size; use
+/// [`BitArray::with_width`] for checked access that reports errors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BitArray(pub u64);
 
 impl fmt::Display for BitArray {
@@ -66,10 +156,22 @@ impl BitArray {
     ///
     /// # Returns
     /// A vector of `bool` values representing the bits in the `BitArray`.
+    #[cfg(feature = "alloc")]
     pub fn get_all_bits(&self) -> Vec<bool> {
         (0..64).map(|i| self.get_bit(i)).collect()
     }
 
+    /// Returns the positions of the set bits, lowest first.
+    pub fn iter_ones(&self) -> Positions {
+        Positions(self.0)
+    }
+
+    /// Returns the positions of the clear bits among all 64, lowest first;
+    /// filter by position to stay within a width.
+    pub fn iter_zeros(&self) -> Positions {
+        Positions(!self.0)
+    }
+
     /// Retrieves the raw `u64` value representing the `BitArray`.
     ///
     /// # Returns
@@ -77,4 +179,758 @@ impl BitArray {
     pub fn get_raw(&self) -> u64 {
         self.0
     }
+
+    /// Interprets the low bits as a two's complement signed integer.
+    ///
+    /// # Arguments
+    /// - `width`: The bit size; its top bit is the sign bit. Widths beyond
+    ///   64 bits interpret the whole `u64`.
+    ///
+    /// # Returns
+    /// The sign-extended value, e.g. `0xFF` at 8 bits is `-1`.
+    pub fn as_signed(&self, width: Width) -> i64 {
+        let unused = 64 - width.bits().min(64);
+        ((self.0 << unused) as i64) >> unused
+    }
+
+    /// Encodes a signed integer in two's complement at the given width.
+    ///
+    /// # Arguments
+    /// - `value`: The signed value; bits beyond `width` are discarded, so
+    ///   out-of-range values wrap.
+    /// - `width`: The bit size.
+    ///
+    /// # Returns
+    /// A `BitArray` whose [`as_signed`](Self::as_signed) equals `value`
+    /// whenever `value` fits in `width` bits.
+    pub fn from_signed(value: i64, width: Width) -> Self {
+        Self(width.truncate(value as u64))
+    }
+
+    /// Sign-extends a `from`-bit value to `to` bits: the bits between the
+    /// two widths copy bit `from - 1`, so `0x80` from 8 to 32 bits becomes
+    /// `0xFFFF_FF80`. A narrower `to` truncates instead.
+    pub fn sign_extend(&self, from: Width, to: Width) -> Self {
+        Self::from_signed(self.as_signed(from), to)
+    }
+
+    /// Zero-extends a `from`-bit value to `to` bits, clearing every bit from
+    /// `from` up. A narrower `to` truncates instead.
+    pub fn zero_extend(&self, from: Width, to: Width) -> Self {
+        Self(to.truncate(from.truncate(self.0)))
+    }
+
+    /// Returns the mask selecting the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    fn field_mask(range: &Range<u8>) -> u64 {
+        assert!(
+            range.start <= range.end && range.end <= 64,
+            "bit range {}..{} out of bounds for 64 bits",
+            range.start,
+            range.end
+        );
+        let width = range.end - range.start;
+        if width == 64 {
+            u64::MAX
+        } else {
+            ((1u64 << width) - 1)
+                .checked_shl(range.start.into())
+                .unwrap_or(0)
+        }
+    }
+
+    /// Extracts the bits in `range` (least significant bit first, end
+    /// exclusive), e.g. `12..20` for an 8-bit field starting at bit 12.
+    ///
+    /// # Returns
+    /// The field value shifted down to bit 0.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn get_field(&self, range: Range<u8>) -> u64 {
+        let mask = Self::field_mask(&range);
+        (self.0 & mask).checked_shr(range.start.into()).unwrap_or(0)
+    }
+
+    /// Replaces the bits in `range` with the low bits of `value`; higher
+    /// bits of `value` are discarded.
+    ///
+    /// # Arguments
+    /// - `range`: The bit positions to write, end exclusive.
+    /// - `value`: The new field value, aligned to bit 0.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn set_field(&mut self, range: Range<u8>, value: u64) {
+        let mask = Self::field_mask(&range);
+        let shifted = value.checked_shl(range.start.into()).unwrap_or(0);
+        self.0 = (self.0 & !mask) | (shifted & mask);
+    }
+
+    /// Clears the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn clear_field(&mut self, range: Range<u8>) {
+        self.0 &= !Self::field_mask(&range);
+    }
+
+    /// Sets the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn fill_field(&mut self, range: Range<u8>) {
+        self.0 |= Self::field_mask(&range);
+    }
+
+    /// Inverts the bits in `range`.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn invert_field(&mut self, range: Range<u8>) {
+        self.0 ^= Self::field_mask(&range);
+    }
+
+    /// Shifts the bits in `range` towards the top of the range by `amount`,
+    /// filling with zeros; bits outside the range are unchanged.
+    ///
+    /// # Arguments
+    /// - `range`: The bit positions to shift, end exclusive.
+    /// - `amount`: The shift count; counts of the range's width or more
+    ///   clear the range.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn shl_field(&mut self, range: Range<u8>, amount: u32) {
+        let value = self.get_field(range.clone());
+        self.set_field(range, value.checked_shl(amount).unwrap_or(0));
+    }
+
+    /// Shifts the bits in `range` towards the bottom of the range by
+    /// `amount`, filling with zeros; bits outside the range are unchanged.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or ends past bit 64.
+    pub fn shr_field(&mut self, range: Range<u8>, amount: u32) {
+        let value = self.get_field(range.clone());
+        self.set_field(range, value.checked_shr(amount).unwrap_or(0));
+    }
+
+    /// Retrieves byte `index`, counting from the least significant byte, so
+    /// index 0 is the first byte in little-endian memory order.
+    ///
+    /// # Panics
+    /// Panics if `index` is 8 or more.
+    pub fn get_byte(&self, index: u8) -> u8 {
+        assert!(index < 8, "byte index {} out of bounds for 8 bytes", index);
+        self.get_field(index * 8..index * 8 + 8) as u8
+    }
+
+    /// Replaces byte `index`, counting from the least significant byte.
+    ///
+    /// # Arguments
+    /// - `index`: The byte position (0-7).
+    /// - `value`: The new byte.
+    ///
+    /// # Panics
+    /// Panics if `index` is 8 or more.
+    pub fn set_byte(&mut self, index: u8, value: u8) {
+        assert!(index < 8, "byte index {} out of bounds for 8 bytes", index);
+        self.set_field(index * 8..index * 8 + 8, value.into());
+    }
+
+    /// Encodes the value as reflected binary (Gray) code, in which
+    /// consecutive integers differ in exactly one bit, as read from rotary
+    /// encoders. Bits above the value's width stay clear.
+    ///
+    /// # Returns
+    /// The Gray code `value ^ (value >> 1)`.
+    pub fn to_gray(&self) -> Self {
+        Self(self.0 ^ (self.0 >> 1))
+    }
+
+    /// Decodes a reflected binary (Gray) code back to binary; the inverse of
+    /// [`to_gray`](Self::to_gray).
+    ///
+    /// # Returns
+    /// The binary value, each bit the XOR of all Gray bits at or above it.
+    pub fn from_gray(&self) -> Self {
+        let mut value = self.0;
+        for shift in [1, 2, 4, 8, 16, 32] {
+            value ^= value >> shift;
+        }
+        Self(value)
+    }
+
+    /// Encodes the value as packed binary-coded decimal, one decimal digit
+    /// per nibble with the least significant digit in the low nibble, e.g.
+    /// 1234 becomes `0x1234`.
+    ///
+    /// # Returns
+    /// - `Some(bcd)`, or `None` if the value has more than 16 decimal digits.
+    pub fn to_bcd(&self) -> Option<Self> {
+        let mut value = self.0;
+        let mut bcd = 0u64;
+        let mut shift = 0;
+        while value != 0 {
+            if shift == 64 {
+                return None;
+            }
+            bcd |= (value % 10) << shift;
+            value /= 10;
+            shift += 4;
+        }
+        Some(Self(bcd))
+    }
+
+    /// Decodes packed binary-coded decimal back to binary; the inverse of
+    /// [`to_bcd`](Self::to_bcd).
+    ///
+    /// # Returns
+    /// - `Ok(value)`, or `Err(nibble)` with the index of the lowest nibble
+    ///   holding a value above 9.
+    pub fn from_bcd(&self) -> Result<Self, u32> {
+        let digit = |nibble: u32| (self.0 >> (nibble * 4)) & 0xf;
+        if let Some(nibble) = (0..16).find(|&n| digit(n) > 9) {
+            return Err(nibble);
+        }
+        Ok(Self(
+            (0..16).rev().fold(0, |value, n| value * 10 + digit(n)),
+        ))
+    }
+
+    /// Counts the set bits among the low `width` bits.
+    pub fn count_ones(&self, width: Width) -> u32 {
+        width.truncate(self.0).count_ones()
+    }
+
+    /// Counts the zero bits among the low `width` bits.
+    pub fn count_zeros(&self, width: Width) -> u32 {
+        width.bits().min(64) - self.count_ones(width)
+    }
+
+    /// Counts the zero bits above the highest set bit, starting at the top
+    /// of the width rather than at bit 63.
+    ///
+    /// # Returns
+    /// The count, or the full width for a zero value.
+    pub fn leading_zeros(&self, width: Width) -> u32 {
+        let unused = 64 - width.bits().min(64);
+        width.truncate(self.0).leading_zeros() - unused
+    }
+
+    /// Counts the zero bits below the lowest set bit.
+    ///
+    /// # Returns
+    /// The count, or the full width for a zero value.
+    pub fn trailing_zeros(&self, width: Width) -> u32 {
+        width
+            .truncate(self.0)
+            .trailing_zeros()
+            .min(width.bits().min(64))
+    }
+
+    /// Returns the index of the highest set bit within the width, or `None`
+    /// for a zero value.
+    pub fn highest_set_bit(&self, width: Width) -> Option<u32> {
+        let value = width.truncate(self.0);
+        (value != 0).then(|| 63 - value.leading_zeros())
+    }
+
+    /// Returns the index of the lowest set bit within the width, or `None`
+    /// for a zero value.
+    pub fn lowest_set_bit(&self, width: Width) -> Option<u32> {
+        let value = width.truncate(self.0);
+        (value != 0).then(|| value.trailing_zeros())
+    }
+
+    /// Returns the parity of the low `width` bits: `true` if an odd number
+    /// of them are set.
+    pub fn parity(&self, width: Width) -> bool {
+        self.count_ones(width) % 2 == 1
+    }
+
+    /// Shifts the low `width` bits towards the most significant bit by
+    /// `amount`, discarding bits that leave the width and filling with zeros.
+    ///
+    /// # Arguments
+    /// - `amount`: The shift count; counts of `width` or more clear the value.
+    /// - `width`: The bit size. Widths beyond 64 bits shift the whole `u64`.
+    pub fn shl(&mut self, amount: u32, width: Width) {
+        self.0 = width.truncate(self.0.checked_shl(amount).unwrap_or(0));
+    }
+
+    /// Shifts the low `width` bits towards the least significant bit by
+    /// `amount`, filling with zeros (logical shift).
+    ///
+    /// # Arguments
+    /// - `amount`: The shift count; counts of `width` or more clear the value.
+    /// - `width`: The bit size.
+    pub fn shr(&mut self, amount: u32, width: Width) {
+        self.0 = width.truncate(self.0).checked_shr(amount).unwrap_or(0);
+    }
+
+    /// Shifts the low `width` bits towards the least significant bit by
+    /// `amount`, filling with copies of the sign bit (arithmetic shift), so
+    /// `0xF0` at 8 bits shifted by 2 is `0xFC`.
+    ///
+    /// # Arguments
+    /// - `amount`: The shift count; counts of `width` or more leave only
+    ///   copies of the sign bit.
+    /// - `width`: The bit size; its top bit is the sign bit.
+    pub fn sar(&mut self, amount: u32, width: Width) {
+        let signed = self.as_signed(width) >> amount.min(63);
+        *self = Self::from_signed(signed, width);
+    }
+
+    /// Rotates the low `width` bits towards the most significant bit by
+    /// `amount`; bits leaving the top of the width re-enter at bit 0.
+    ///
+    /// # Arguments
+    /// - `amount`: The rotate count, taken modulo the width.
+    /// - `width`: The bit size.
+    pub fn rotl(&mut self, amount: u32, width: Width) {
+        let bits = width.bits().min(64);
+        let value = width.truncate(self.0);
+        self.0 = match amount % bits {
+            0 => value,
+            n => width.truncate(value << n | value >> (bits - n)),
+        };
+    }
+
+    /// Rotates the low `width` bits towards the least significant bit by
+    /// `amount`; bits leaving bit 0 re-enter at the top of the width.
+    ///
+    /// # Arguments
+    /// - `amount`: The rotate count, taken modulo the width.
+    /// - `width`: The bit size.
+    pub fn rotr(&mut self, amount: u32, width: Width) {
+        let bits = width.bits().min(64);
+        self.rotl(bits - amount % bits, width);
+    }
+
+    /// Reverses the byte order within each `width`-bit lane, so `W16` swaps
+    /// the bytes of every halfword and `W32` those of every word; at the
+    /// value's own width this converts between little- and big-endian.
+    /// Widths beyond 64 bits reverse the whole `u64`.
+    ///
+    /// # Arguments
+    /// - `width`: The lane size.
+    pub fn swap_bytes(&mut self, width: Width) {
+        let bits = width.bits().min(64);
+        let mut swapped = 0;
+        for shift in (0..64).step_by(bits as usize) {
+            let lane = (self.0 >> shift) & width.mask();
+            swapped |= (lane.swap_bytes() >> (64 - bits)) << shift;
+        }
+        self.0 = swapped;
+    }
+
+    /// Reverses the order of the low `width` bits, so bit 0 trades places
+    /// with the top bit of the width; higher bits are cleared. Widths beyond
+    /// 64 bits reverse the whole `u64`.
+    ///
+    /// # Arguments
+    /// - `width`: The bit size.
+    pub fn reverse_bits(&mut self, width: Width) {
+        self.0 = (self.0 & width.mask()).reverse_bits() >> (64 - width.bits().min(64));
+    }
+
+    /// Returns the low `width` bits as little-endian bytes. Widths beyond 64
+    /// bits are zero-extended.
+    ///
+    /// # Returns
+    /// A vector of `width.bytes()` bytes, least significant first.
+    #[cfg(feature = "alloc")]
+    pub fn to_le_bytes(&self, width: Width) -> Vec<u8> {
+        let mut bytes = (self.0 & width.mask()).to_le_bytes().to_vec();
+        bytes.resize(width.bytes(), 0);
+        bytes
+    }
+
+    /// Returns the low `width` bits as big-endian bytes. Widths beyond 64
+    /// bits are zero-extended.
+    ///
+    /// # Returns
+    /// A vector of `width.bytes()` bytes, most significant first.
+    #[cfg(feature = "alloc")]
+    pub fn to_be_bytes(&self, width: Width) -> Vec<u8> {
+        let mut bytes = self.to_le_bytes(width);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Creates a BitArray from exactly `width.bytes()` little-endian bytes.
+    ///
+    /// # Returns
+    /// - `Some(array)`, or `None` if the length does not match the width or
+    ///   the width is beyond 64 bits.
+    pub fn from_le_bytes(bytes: &[u8], width: Width) -> Option<Self> {
+        if bytes.len() != width.bytes() || width.bits() > 64 {
+            return None;
+        }
+        let value = bytes
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+        Some(Self(value))
+    }
+
+    /// Creates a BitArray from exactly `width.bytes()` big-endian bytes.
+    ///
+    /// # Returns
+    /// - `Some(array)`, or `None` if the length does not match the width or
+    ///   the width is beyond 64 bits.
+    pub fn from_be_bytes(bytes: &[u8], width: Width) -> Option<Self> {
+        if bytes.len() != width.bytes() || width.bits() > 64 {
+            return None;
+        }
+        let value = bytes.iter().fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+        Some(Self(value))
+    }
+
+    /// Flips each bit selected by `mask` independently with probability
+    /// `rate`, simulating random bit errors such as single-event upsets.
+    ///
+    /// # Arguments
+    /// - `mask`: The bits that may flip.
+    /// - `rate`: Per-bit flip probability in `[0, 1]`.
+    /// - `rng`: The random number generator.
+    ///
+    /// # Returns
+    /// The mask of bits that were flipped.
+    #[cfg(feature = "rand")]
+    pub fn mutate<R: rand::Rng + ?Sized>(&mut self, mask: u64, rate: f64, rng: &mut R) -> u64 {
+        let flipped = (0..64)
+            .filter(|i| (mask >> i) & 1 == 1 && rng.random::<f64>() < rate)
+            .fold(0u64, |acc, i| acc | 1 << i);
+        self.0 ^= flipped;
+        flipped
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand::distr::Distribution<BitArray> for rand::distr::StandardUniform {
+    /// Samples a `BitArray` with every bit chosen uniformly at random.
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> BitArray {
+        BitArray(rng.random())
+    }
+}
+
+impl From<u8> for BitArray {
+    fn from(value: u8) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u16> for BitArray {
+    fn from(value: u16) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u32> for BitArray {
+    fn from(value: u32) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<u64> for BitArray {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<BitArray> for u64 {
+    fn from(value: BitArray) -> Self {
+        value.0
+    }
+}
+
+impl core::str::FromStr for BitArray {
+    type Err = parse::NumberError;
+
+    /// Parses a decimal value, or hex, octal or binary with a `0x`, `0o` or
+    /// `0b` prefix, as accepted by [`parse::parse_u64`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse_u64(s, 10).map(Self)
+    }
+}
+
+impl ops::BitAnd for BitArray {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl ops::BitOr for BitArray {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitXor for BitArray {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl ops::BitAndAssign for BitArray {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl ops::BitOrAssign for BitArray {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl ops::BitXorAssign for BitArray {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl ops::Not for BitArray {
+    type Output = Self;
+
+    /// Inverts all 64 bits; mask with [`Width::mask`] to stay within a width.
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl ops::Shl<u32> for BitArray {
+    type Output = Self;
+
+    /// Shifts left, giving 0 for amounts of 64 or more.
+    fn shl(self, amount: u32) -> Self {
+        Self(self.0.checked_shl(amount).unwrap_or(0))
+    }
+}
+
+impl ops::Shr<u32> for BitArray {
+    type Output = Self;
+
+    /// Shifts right logically, giving 0 for amounts of 64 or more.
+    fn shr(self, amount: u32) -> Self {
+        Self(self.0.checked_shr(amount).unwrap_or(0))
+    }
+}
+
+impl ops::ShlAssign<u32> for BitArray {
+    fn shl_assign(&mut self, amount: u32) {
+        *self = *self << amount;
+    }
+}
+
+impl ops::ShrAssign<u32> for BitArray {
+    fn shr_assign(&mut self, amount: u32) {
+        *self = *self >> amount;
+    }
+}
+
+/// Iterator over the 64 bits of a [`BitArray`], least significant first.
+#[derive(Debug, Clone)]
+pub struct Bits {
+    value: u64,
+    range: Range<u8>,
+}
+
+impl Iterator for Bits {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.range.next().map(|pos| (self.value >> pos) & 1 == 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Bits {
+    fn next_back(&mut self) -> Option<bool> {
+        self.range
+            .next_back()
+            .map(|pos| (self.value >> pos) & 1 == 1)
+    }
+}
+
+impl ExactSizeIterator for Bits {}
+
+impl IntoIterator for BitArray {
+    type Item = bool;
+    type IntoIter = Bits;
+
+    fn into_iter(self) -> Bits {
+        Bits {
+            value: self.0,
+            range: 0..64,
+        }
+    }
+}
+
+/// Iterator over the positions of the set bits of a `u64`, lowest first;
+/// returned by [`BitArray::iter_ones`] and [`BitArray::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct Positions(u64);
+
+impl Iterator for Positions {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.0 == 0 {
+            return None;
+        }
+        let pos = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(pos)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Positions {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gets_fields() {
+        let bits = BitArray(0x1234_5678_9ABC_DEF0);
+        assert_eq!(bits.get_field(0..4), 0x0);
+        assert_eq!(bits.get_field(4..12), 0xEF);
+        assert_eq!(bits.get_field(60..64), 0x1);
+        assert_eq!(bits.get_field(0..64), 0x1234_5678_9ABC_DEF0);
+        assert_eq!(bits.get_field(8..8), 0);
+        assert_eq!(bits.get_field(64..64), 0);
+    }
+
+    #[test]
+    fn sets_fields_and_discards_high_bits() {
+        let mut bits = BitArray(0xFFFF);
+        bits.set_field(4..8, 0x5);
+        assert_eq!(bits.0, 0xFF5F);
+        bits.set_field(4..8, 0x1A);
+        assert_eq!(bits.0, 0xFFAF);
+        bits.set_field(0..64, 7);
+        assert_eq!(bits.0, 7);
+        bits.set_field(63..64, 1);
+        assert_eq!(bits.0, 0x8000_0000_0000_0007);
+    }
+
+    #[test]
+    fn clears_fills_and_inverts_fields() {
+        let mut bits = BitArray(0xFF);
+        bits.clear_field(2..6);
+        assert_eq!(bits.0, 0xC3);
+        bits.fill_field(8..12);
+        assert_eq!(bits.0, 0xFC3);
+        bits.invert_field(0..8);
+        assert_eq!(bits.0, 0xF3C);
+        bits.clear_field(0..64);
+        assert_eq!(bits.0, 0);
+        bits.fill_field(0..64);
+        assert_eq!(bits.0, u64::MAX);
+    }
+
+    #[test]
+    fn shifts_within_fields() {
+        let mut bits = BitArray(0xF0F6);
+        bits.shl_field(0..8, 2);
+        assert_eq!(bits.0, 0xF0D8);
+        bits.shr_field(0..8, 3);
+        assert_eq!(bits.0, 0xF01B);
+        bits.shl_field(0..8, 8);
+        assert_eq!(bits.0, 0xF000);
+        bits.shr_field(8..16, 100);
+        assert_eq!(bits.0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn rejects_fields_past_bit_64() {
+        BitArray(0).get_field(60..65);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn rejects_reversed_fields() {
+        BitArray(0).set_field(8..4, 1);
+    }
+
+    #[test]
+    fn counts_bits_within_the_width() {
+        let bits = BitArray(0xF0F0_0000_0001_8001);
+        assert_eq!(bits.count_ones(Width::W8), 1);
+        assert_eq!(bits.count_ones(Width::W16), 2);
+        assert_eq!(bits.count_ones(Width::W32), 3);
+        assert_eq!(bits.count_ones(Width::W64), 11);
+        assert_eq!(bits.count_zeros(Width::W8), 7);
+        assert_eq!(bits.count_zeros(Width::W16), 14);
+        assert_eq!(bits.count_zeros(Width::W32), 29);
+        assert_eq!(bits.count_zeros(Width::W64), 53);
+    }
+
+    #[test]
+    fn counts_leading_and_trailing_zeros_per_width() {
+        let one = BitArray(1);
+        assert_eq!(one.leading_zeros(Width::W8), 7);
+        assert_eq!(one.leading_zeros(Width::W16), 15);
+        assert_eq!(one.leading_zeros(Width::W32), 31);
+        assert_eq!(one.leading_zeros(Width::W64), 63);
+
+        let zero = BitArray(0);
+        for width in [Width::W8, Width::W16, Width::W32, Width::W64] {
+            assert_eq!(zero.leading_zeros(width), width.bits());
+            assert_eq!(zero.trailing_zeros(width), width.bits());
+            assert_eq!(zero.highest_set_bit(width), None);
+            assert_eq!(zero.lowest_set_bit(width), None);
+        }
+
+        // Bits above the width are ignored
+        let high = BitArray(0x0100);
+        assert_eq!(high.trailing_zeros(Width::W8), 8);
+        assert_eq!(high.trailing_zeros(Width::W16), 8);
+        assert_eq!(high.leading_zeros(Width::W16), 7);
+        assert_eq!(high.highest_set_bit(Width::W8), None);
+    }
+
+    #[test]
+    fn finds_highest_and_lowest_set_bits() {
+        let bits = BitArray(0x8001_0420);
+        assert_eq!(bits.highest_set_bit(Width::W8), Some(5));
+        assert_eq!(bits.lowest_set_bit(Width::W8), Some(5));
+        assert_eq!(bits.highest_set_bit(Width::W16), Some(10));
+        assert_eq!(bits.highest_set_bit(Width::W32), Some(31));
+        assert_eq!(bits.lowest_set_bit(Width::W32), Some(5));
+        assert_eq!(BitArray(u64::MAX).highest_set_bit(Width::W64), Some(63));
+    }
+
+    #[test]
+    fn computes_parity_per_width() {
+        let bits = BitArray(0x0107);
+        assert!(bits.parity(Width::W8));
+        assert!(!bits.parity(Width::W16));
+        assert!(!bits.parity(Width::W32));
+        assert!(!BitArray(0).parity(Width::W64));
+        assert!(BitArray(1 << 63).parity(Width::W64));
+        assert!(!BitArray(1 << 63).parity(Width::W32));
+    }
 }
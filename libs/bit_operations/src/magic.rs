@@ -0,0 +1,167 @@
+//! File Magic-Number Identification
+//!
+//! Matches the leading bytes of a buffer against a database of file
+//! signatures. The built-in database lives in `data/magic.txt`; additional
+//! signatures in the same line format can be parsed with [`MagicDb::parse`]
+//! and merged with [`MagicDb::extend`].
+//!
+//! ```text
+//! # <offset> <hex pattern> <name>
+//! 0   89504E470D0A1A0A          PNG image
+//! 0   52494646????????57415645  WAV audio
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Source of the built-in signature database.
+const BUILTIN: &str = include_str!("../data/magic.txt");
+
+/// Splits off the first whitespace-separated field, returning it and the
+/// trimmed remainder.
+fn split_field(s: &str) -> (&str, &str) {
+    match s.find(char::is_whitespace) {
+        Some(i) => (&s[..i], s[i..].trim()),
+        None => (s, ""),
+    }
+}
+
+/// A single file signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MagicSignature {
+    /// Human-readable format name.
+    pub name: String,
+    /// Byte offset at which the pattern must appear.
+    pub offset: usize,
+    /// Bytes to match; `None` matches any byte.
+    pub pattern: Vec<Option<u8>>,
+}
+
+impl MagicSignature {
+    /// Returns `true` if `data` contains the pattern at the signature offset.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        let Some(window) = data.get(self.offset..self.offset + self.pattern.len()) else {
+            return false;
+        };
+        self.pattern
+            .iter()
+            .zip(window)
+            .all(|(expected, actual)| expected.is_none() || *expected == Some(*actual))
+    }
+}
+
+/// Error produced while parsing a signature database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MagicError {
+    /// 1-based source line the error refers to.
+    pub line: usize,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for MagicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MagicError {}
+
+/// A collection of file signatures.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MagicDb {
+    /// The signatures in source order.
+    pub signatures: Vec<MagicSignature>,
+}
+
+impl MagicDb {
+    /// Returns the built-in database.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("built-in magic database is valid")
+    }
+
+    /// Parses a signature database.
+    ///
+    /// Each non-empty line not starting with `#` holds a decimal offset, a
+    /// hex pattern (`??` for any byte) and the format name, separated by
+    /// whitespace.
+    ///
+    /// # Arguments
+    /// - `src`: The database source.
+    ///
+    /// # Returns
+    /// - `Ok(db)` on success, `Err(MagicError)` for the first malformed line.
+    pub fn parse(src: &str) -> Result<Self, MagicError> {
+        let mut signatures = Vec::new();
+        for (index, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| MagicError {
+                line: index + 1,
+                message,
+            };
+
+            let (offset, rest) = split_field(line);
+            let (hex, name) = split_field(rest);
+            let offset = offset
+                .parse()
+                .map_err(|_| error(format!("invalid offset `{}`", offset)))?;
+            if hex.is_empty() {
+                return Err(error("missing pattern".to_string()));
+            }
+            if name.is_empty() {
+                return Err(error("missing name".to_string()));
+            }
+            if hex.len() % 2 != 0 {
+                return Err(error(format!("invalid pattern `{}`", hex)));
+            }
+
+            let pattern = hex
+                .as_bytes()
+                .chunks(2)
+                .map(|pair| match pair {
+                    b"??" => Ok(None),
+                    _ => core::str::from_utf8(pair)
+                        .ok()
+                        .and_then(|s| u8::from_str_radix(s, 16).ok())
+                        .map(Some)
+                        .ok_or_else(|| error(format!("invalid pattern `{}`", hex))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            signatures.push(MagicSignature {
+                name: name.to_string(),
+                offset,
+                pattern,
+            });
+        }
+        Ok(Self { signatures })
+    }
+
+    /// Appends the signatures of `other`.
+    pub fn extend(&mut self, other: MagicDb) {
+        self.signatures.extend(other.signatures);
+    }
+
+    /// Identifies the format of `data`.
+    ///
+    /// # Returns
+    /// - The matching signature with the longest pattern (the first one on a
+    ///   tie), or `None` if nothing matches.
+    pub fn identify(&self, data: &[u8]) -> Option<&MagicSignature> {
+        self.signatures.iter().filter(|sig| sig.matches(data)).fold(
+            None,
+            |best: Option<&MagicSignature>, sig| match best {
+                Some(best) if best.pattern.len() >= sig.pattern.len() => Some(best),
+                _ => Some(sig),
+            },
+        )
+    }
+}
@@ -0,0 +1,321 @@
+//! Network Header Decoding
+//!
+//! Fixed-layout protocol headers decoded from big-endian (network order)
+//! bytes, with per-field bit positions so views can highlight them.
+
+use core::fmt;
+
+/// A bit field inside a header word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BitField {
+    /// Short field name as used in the protocol specification.
+    pub name: &'static str,
+    /// Position of the least significant bit of the field.
+    pub shift: u32,
+    /// Field width in bits.
+    pub width: u32,
+}
+
+impl BitField {
+    /// Creates a field descriptor.
+    pub const fn new(name: &'static str, shift: u32, width: u32) -> Self {
+        Self { name, shift, width }
+    }
+
+    /// Returns the mask of the field's bits within the word.
+    pub fn mask(&self) -> u64 {
+        ((1u64 << self.width) - 1) << self.shift
+    }
+
+    /// Extracts the field value from `word`.
+    pub fn extract(&self, word: u64) -> u64 {
+        (word & self.mask()) >> self.shift
+    }
+}
+
+/// Fields of the 16-bit DNS flags word, most significant first (RFC 1035,
+/// with the AD and CD bits of RFC 4035).
+pub const DNS_FLAG_FIELDS: [BitField; 10] = [
+    BitField::new("QR", 15, 1),
+    BitField::new("Opcode", 11, 4),
+    BitField::new("AA", 10, 1),
+    BitField::new("TC", 9, 1),
+    BitField::new("RD", 8, 1),
+    BitField::new("RA", 7, 1),
+    BitField::new("Z", 6, 1),
+    BitField::new("AD", 5, 1),
+    BitField::new("CD", 4, 1),
+    BitField::new("RCODE", 0, 4),
+];
+
+/// The fixed 12-byte DNS message header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DnsHeader {
+    /// Transaction identifier.
+    pub id: u16,
+    /// Flags word; see [`DNS_FLAG_FIELDS`].
+    pub flags: u16,
+    /// Number of entries in the question section.
+    pub qdcount: u16,
+    /// Number of resource records in the answer section.
+    pub ancount: u16,
+    /// Number of name server records in the authority section.
+    pub nscount: u16,
+    /// Number of resource records in the additional section.
+    pub arcount: u16,
+}
+
+impl DnsHeader {
+    /// Length of the header in bytes.
+    pub const LEN: usize = 12;
+
+    /// Decodes a header from the first 12 bytes of `bytes`.
+    ///
+    /// # Returns
+    /// - `Some(header)`, or `None` if fewer than 12 bytes are given.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::LEN {
+            return None;
+        }
+        let word = |i: usize| u16::from_be_bytes([bytes[i], bytes[i + 1]]);
+        Some(Self {
+            id: word(0),
+            flags: word(2),
+            qdcount: word(4),
+            ancount: word(6),
+            nscount: word(8),
+            arcount: word(10),
+        })
+    }
+
+    /// Returns `true` for a response, `false` for a query.
+    pub fn is_response(&self) -> bool {
+        self.flags & 0x8000 != 0
+    }
+
+    /// Returns the 4-bit opcode.
+    pub fn opcode(&self) -> u8 {
+        ((self.flags >> 11) & 0xF) as u8
+    }
+
+    /// Returns the 4-bit response code.
+    pub fn rcode(&self) -> u8 {
+        (self.flags & 0xF) as u8
+    }
+}
+
+/// Returns the mnemonic of a DNS opcode.
+pub fn dns_opcode_name(opcode: u8) -> &'static str {
+    match opcode {
+        0 => "QUERY",
+        1 => "IQUERY",
+        2 => "STATUS",
+        4 => "NOTIFY",
+        5 => "UPDATE",
+        6 => "DSO",
+        _ => "unassigned",
+    }
+}
+
+/// Returns the mnemonic of a DNS response code.
+pub fn dns_rcode_name(rcode: u8) -> &'static str {
+    match rcode {
+        0 => "NOERROR",
+        1 => "FORMERR",
+        2 => "SERVFAIL",
+        3 => "NXDOMAIN",
+        4 => "NOTIMP",
+        5 => "REFUSED",
+        6 => "YXDOMAIN",
+        7 => "YXRRSET",
+        8 => "NXRRSET",
+        9 => "NOTAUTH",
+        10 => "NOTZONE",
+        11 => "DSOTYPENI",
+        _ => "unassigned",
+    }
+}
+
+/// A 48-bit IEEE 802 MAC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacAddress(pub [u8; 6]);
+
+impl MacAddress {
+    /// The broadcast address `ff:ff:ff:ff:ff:ff`.
+    pub const BROADCAST: MacAddress = MacAddress([0xFF; 6]);
+
+    /// Bit of the address as a `u64` marking group (multicast) addresses.
+    pub const GROUP_BIT: u32 = 40;
+    /// Bit of the address as a `u64` marking locally administered addresses.
+    pub const LOCAL_BIT: u32 = 41;
+
+    /// Creates an address from the low 48 bits of `value`, first octet most
+    /// significant.
+    pub fn from_u64(value: u64) -> Self {
+        let bytes = value.to_be_bytes();
+        Self([bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]])
+    }
+
+    /// Returns the address as the low 48 bits of a `u64`.
+    pub fn to_u64(self) -> u64 {
+        self.0.iter().fold(0, |acc, &b| acc << 8 | u64::from(b))
+    }
+
+    /// Returns `true` for group (multicast or broadcast) addresses.
+    pub fn is_multicast(self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Returns `true` for the broadcast address.
+    pub fn is_broadcast(self) -> bool {
+        self == Self::BROADCAST
+    }
+
+    /// Returns `true` for locally administered addresses.
+    pub fn is_local(self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+/// Fields of the 16-bit VLAN tag control information word (IEEE 802.1Q).
+pub const VLAN_TCI_FIELDS: [BitField; 3] = [
+    BitField::new("PCP", 13, 3),
+    BitField::new("DEI", 12, 1),
+    BitField::new("VID", 0, 12),
+];
+
+/// An IEEE 802.1Q VLAN tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VlanTag {
+    /// Tag protocol identifier (0x8100, or 0x88A8 for an outer QinQ tag).
+    pub tpid: u16,
+    /// Tag control information; see [`VLAN_TCI_FIELDS`].
+    pub tci: u16,
+}
+
+impl VlanTag {
+    /// Returns the 3-bit priority code point.
+    pub fn pcp(&self) -> u8 {
+        (self.tci >> 13) as u8
+    }
+
+    /// Returns the drop eligible indicator.
+    pub fn dei(&self) -> bool {
+        self.tci & 0x1000 != 0
+    }
+
+    /// Returns the 12-bit VLAN identifier.
+    pub fn vid(&self) -> u16 {
+        self.tci & 0x0FFF
+    }
+}
+
+/// An Ethernet II header with up to two VLAN tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EthernetHeader {
+    /// Destination address.
+    pub destination: MacAddress,
+    /// Source address.
+    pub source: MacAddress,
+    /// Outer VLAN tag, if present.
+    pub vlan: Option<VlanTag>,
+    /// Inner VLAN tag of a double-tagged (QinQ) frame, if present.
+    pub inner_vlan: Option<VlanTag>,
+    /// EtherType of the payload (or length, when below 0x0600).
+    pub ethertype: u16,
+}
+
+impl EthernetHeader {
+    /// Decodes a header from the start of a frame.
+    ///
+    /// # Returns
+    /// - `Some(header)`, or `None` if the frame is too short.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let word = |i: usize| -> Option<u16> {
+            Some(u16::from_be_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]))
+        };
+        let mac = |i: usize| -> Option<MacAddress> {
+            Some(MacAddress(bytes.get(i..i + 6)?.try_into().ok()?))
+        };
+
+        let destination = mac(0)?;
+        let source = mac(6)?;
+        let mut offset = 12;
+        let mut tags = [None; 2];
+        for tag in tags.iter_mut() {
+            let tpid = word(offset)?;
+            if !is_vlan_tpid(tpid) {
+                break;
+            }
+            *tag = Some(VlanTag {
+                tpid,
+                tci: word(offset + 2)?,
+            });
+            offset += 4;
+        }
+        Some(Self {
+            destination,
+            source,
+            vlan: tags[0],
+            inner_vlan: tags[1],
+            ethertype: word(offset)?,
+        })
+    }
+
+    /// Returns the header length in bytes, including VLAN tags.
+    pub fn header_len(&self) -> usize {
+        14 + 4 * (self.vlan.is_some() as usize + self.inner_vlan.is_some() as usize)
+    }
+}
+
+/// Returns `true` for EtherTypes that introduce a VLAN tag.
+fn is_vlan_tpid(ethertype: u16) -> bool {
+    matches!(ethertype, 0x8100 | 0x88A8 | 0x9100)
+}
+
+/// Returns the protocol name of a well-known EtherType.
+pub fn ethertype_name(ethertype: u16) -> Option<&'static str> {
+    Some(match ethertype {
+        0x0000..=0x05DC => "802.3 length",
+        0x0800 => "IPv4",
+        0x0806 => "ARP",
+        0x0842 => "Wake-on-LAN",
+        0x22F0 => "AVTP",
+        0x22F3 => "TRILL",
+        0x8035 => "RARP",
+        0x809B => "AppleTalk",
+        0x8100 => "VLAN (802.1Q)",
+        0x8137 => "IPX",
+        0x86DD => "IPv6",
+        0x8808 => "Ethernet flow control",
+        0x8809 => "Slow protocols (LACP)",
+        0x8847 => "MPLS unicast",
+        0x8848 => "MPLS multicast",
+        0x8863 => "PPPoE discovery",
+        0x8864 => "PPPoE session",
+        0x888E => "EAPOL (802.1X)",
+        0x88A8 => "QinQ (802.1ad)",
+        0x88CC => "LLDP",
+        0x88E5 => "MACsec",
+        0x88F7 => "PTP",
+        0x8906 => "FCoE",
+        0x9100 => "QinQ (legacy)",
+        _ => return None,
+    })
+}
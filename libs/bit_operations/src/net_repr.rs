@@ -0,0 +1,113 @@
+//! Address Representations
+//!
+//! Reading a value as a network address and back: IPv4 in dotted-quad form
+//! at 32 bits, a 48-bit MAC address in the low bits of a 64-bit value, and
+//! IPv6 in RFC 5952 form at 128 bits.
+
+use crate::net::MacAddress;
+use crate::Width;
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+/// The kind of address a value of a given width is read as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressKind {
+    /// 32-bit IPv4 address.
+    Ipv4,
+    /// 48-bit IEEE 802 MAC address.
+    Mac,
+    /// 128-bit IPv6 address.
+    Ipv6,
+}
+
+impl AddressKind {
+    /// Returns the address kind shown at `width`: IPv4 at 32 bits, MAC at
+    /// 64 bits (the low 48 bits) and IPv6 at 128 bits.
+    pub fn for_width(width: Width) -> Option<Self> {
+        match width {
+            Width::W32 => Some(AddressKind::Ipv4),
+            Width::W64 => Some(AddressKind::Mac),
+            Width::W128 => Some(AddressKind::Ipv6),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of address bits.
+    pub fn bits(self) -> u32 {
+        match self {
+            AddressKind::Ipv4 => 32,
+            AddressKind::Mac => 48,
+            AddressKind::Ipv6 => 128,
+        }
+    }
+}
+
+impl fmt::Display for AddressKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            AddressKind::Ipv4 => "IPv4",
+            AddressKind::Mac => "MAC",
+            AddressKind::Ipv6 => "IPv6",
+        })
+    }
+}
+
+/// Reads the low 32 bits of `value` as an IPv4 address, first octet most
+/// significant.
+pub fn ipv4(value: u64) -> Ipv4Addr {
+    Ipv4Addr::from(value as u32)
+}
+
+/// Parses a dotted-quad IPv4 address such as `192.168.0.1`.
+///
+/// # Returns
+/// - The address as a `u64`, or `None` if the text is not an IPv4 address.
+pub fn parse_ipv4(text: &str) -> Option<u64> {
+    text.trim()
+        .parse::<Ipv4Addr>()
+        .ok()
+        .map(|addr| u64::from(u32::from(addr)))
+}
+
+/// Reads the low 48 bits of `value` as a MAC address.
+pub fn mac(value: u64) -> MacAddress {
+    MacAddress::from_u64(value)
+}
+
+/// Parses a MAC address written as six colon- or dash-separated octets
+/// (`00:1a:2b:3c:4d:5e`, `00-1A-2B-3C-4D-5E`), Cisco-style dotted groups
+/// (`001a.2b3c.4d5e`) or 12 bare hex digits.
+///
+/// # Returns
+/// - The address, or `None` if the text does not hold exactly 12 hex digits
+///   apart from separators.
+pub fn parse_mac(text: &str) -> Option<MacAddress> {
+    let mut octets = [0u8; 6];
+    let mut count = 0;
+    for c in text
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.'))
+    {
+        let digit = c.to_digit(16)? as u8;
+        let octet = octets.get_mut(count / 2)?;
+        *octet = *octet << 4 | digit;
+        count += 1;
+    }
+    (count == 12).then_some(MacAddress(octets))
+}
+
+/// Reads `value` as an IPv6 address, first group most significant.
+pub fn ipv6(value: u128) -> Ipv6Addr {
+    Ipv6Addr::from(value)
+}
+
+/// Parses an IPv6 address in any standard form, e.g. `2001:db8::1` or
+/// `::ffff:192.0.2.1`.
+///
+/// # Returns
+/// - The address as a `u128`, or `None` if the text is not an IPv6 address.
+pub fn parse_ipv6(text: &str) -> Option<u128> {
+    text.trim().parse::<Ipv6Addr>().ok().map(u128::from)
+}
@@ -0,0 +1,390 @@
+//! Number Input Parsing
+//!
+//! Lenient parsing of numbers pasted from source code or documents. Digit
+//! group separators (`_`, `'` and whitespace) are ignored, radix prefixes
+//! (`0x`, `0o`, `0b`) override the field's radix, and type suffixes such as
+//! `u64`, `ULL` or `f` are dropped, so `0xDEAD_BEEF`, `1'000'000` and
+//! `255u8` all parse. [`parse_any`] goes further and works out which of
+//! several formats pasted text is in.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Integer type suffixes, longest first so `u64` wins over `u`.
+const INT_SUFFIXES: [&str; 19] = [
+    "usize", "isize", "u128", "i128", "u64", "i64", "u32", "i32", "u16", "i16", "u8", "i8", "ull",
+    "llu", "ul", "lu", "ll", "u", "l",
+];
+
+/// Float type suffixes, longest first.
+#[cfg(feature = "alloc")]
+const FLOAT_SUFFIXES: [&str; 5] = ["f32", "f64", "f", "d", "l"];
+
+/// Error produced while parsing a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberError {
+    /// The input contains no digits.
+    Empty,
+    /// The input contains a character that is not a digit of the radix.
+    InvalidDigit(char),
+    /// The value does not fit in the target type.
+    Overflow,
+}
+
+impl fmt::Display for NumberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NumberError::Empty => f.write_str("no digits"),
+            NumberError::InvalidDigit(c) => write!(f, "invalid digit `{}`", c),
+            NumberError::Overflow => f.write_str("value too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NumberError {}
+
+/// Returns `true` for characters accepted as digit group separators.
+pub fn is_separator(c: char) -> bool {
+    c == '_' || c == '\'' || c.is_whitespace()
+}
+
+/// Strips `suffix` from the end of `s`, ignoring ASCII case.
+fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = s.len().checked_sub(suffix.len())?;
+    let (body, tail) = (s.get(..split)?, s.get(split..)?);
+    tail.eq_ignore_ascii_case(suffix).then_some(body)
+}
+
+/// Splits an integer input into its radix and digit text.
+///
+/// Removes surrounding whitespace, a leading `+`, a radix prefix and an
+/// integer type suffix; separators inside the digits are left in place.
+fn split_integer(input: &str, radix: u32) -> (u32, &str) {
+    let s = input.trim();
+    let s = s.strip_prefix('+').unwrap_or(s);
+    let prefix = match s.as_bytes() {
+        [b'0', p, ..] => p.to_ascii_lowercase(),
+        _ => 0,
+    };
+    let (radix, s) = match prefix {
+        b'x' => (16, &s[2..]),
+        b'o' => (8, &s[2..]),
+        b'b' if radix != 16 => (2, &s[2..]),
+        _ => (radix, s),
+    };
+    let s = s.trim_end();
+    let body = INT_SUFFIXES
+        .iter()
+        .find_map(|suffix| strip_suffix_ignore_case(s, suffix))
+        .unwrap_or(s);
+    (radix, body)
+}
+
+/// Parses an unsigned integer.
+///
+/// # Arguments
+/// - `input`: The text, e.g. `0xDEAD_BEEF`, `1'000'000` or `255u8`.
+/// - `radix`: The radix used when the input has no prefix (2, 8, 10 or 16).
+///   A `0b` prefix is only recognised outside radix 16, where it reads as
+///   hex digits.
+///
+/// # Returns
+/// - `Ok(value)`, or a [`NumberError`] describing the first problem.
+pub fn parse_u64(input: &str, radix: u32) -> Result<u64, NumberError> {
+    let (radix, body) = split_integer(input, radix);
+    let mut value: u64 = 0;
+    let mut any = false;
+    for c in body.chars().filter(|&c| !is_separator(c)) {
+        let digit = c.to_digit(radix).ok_or(NumberError::InvalidDigit(c))?;
+        value = value
+            .checked_mul(u64::from(radix))
+            .and_then(|v| v.checked_add(u64::from(digit)))
+            .ok_or(NumberError::Overflow)?;
+        any = true;
+    }
+    if any {
+        Ok(value)
+    } else {
+        Err(NumberError::Empty)
+    }
+}
+
+/// Parses a signed integer.
+///
+/// Accepts an optional leading `-` followed by anything [`parse_u64`]
+/// accepts, so `-0x80` and `-1'000i32` parse.
+///
+/// # Returns
+/// - `Ok(value)`, or a [`NumberError`]; magnitudes beyond the `i64` range
+///   are [`NumberError::Overflow`].
+pub fn parse_i64(input: &str, radix: u32) -> Result<i64, NumberError> {
+    let s = input.trim_start();
+    match s.strip_prefix('-') {
+        Some(rest) => {
+            let magnitude = parse_u64(rest, radix)?;
+            if magnitude > 1 << 63 {
+                Err(NumberError::Overflow)
+            } else {
+                Ok((magnitude as i64).wrapping_neg())
+            }
+        }
+        None => i64::try_from(parse_u64(s, radix)?).map_err(|_| NumberError::Overflow),
+    }
+}
+
+/// Returns the bare digits of an unsigned integer input.
+///
+/// Applies the same leniency as [`parse_u64`] without limiting the value's
+/// size, for byte strings and arbitrary-precision values.
+///
+/// # Returns
+/// - `Ok((radix, digits))` with the effective radix and its digits, or a
+///   [`NumberError`].
+#[cfg(feature = "alloc")]
+pub fn normalize_digits(input: &str, radix: u32) -> Result<(u32, String), NumberError> {
+    let (radix, body) = split_integer(input, radix);
+    let mut digits = String::with_capacity(body.len());
+    for c in body.chars().filter(|&c| !is_separator(c)) {
+        if !c.is_digit(radix) {
+            return Err(NumberError::InvalidDigit(c));
+        }
+        digits.push(c);
+    }
+    if digits.is_empty() {
+        Err(NumberError::Empty)
+    } else {
+        Ok((radix, digits))
+    }
+}
+
+/// Parses a floating point number.
+///
+/// Ignores separators and a trailing `f`, `f32`, `f64`, `d` or `l` suffix,
+/// so `1'000.5f` parses as `1000.5`. `inf`, `infinity` and `nan` are
+/// accepted in any case.
+///
+/// # Returns
+/// - `Ok(value)`, or [`NumberError::Empty`] / [`NumberError::InvalidDigit`].
+#[cfg(feature = "alloc")]
+pub fn parse_f64(input: &str) -> Result<f64, NumberError> {
+    let s: String = input.chars().filter(|&c| !is_separator(c)).collect();
+    let unsigned = s.trim_start_matches(['+', '-']);
+    let special = ["inf", "infinity", "nan"]
+        .iter()
+        .any(|name| unsigned.eq_ignore_ascii_case(name));
+    let body = if special {
+        s.as_str()
+    } else {
+        FLOAT_SUFFIXES
+            .iter()
+            .find_map(|suffix| strip_suffix_ignore_case(&s, suffix))
+            .unwrap_or(&s)
+    };
+    if body.is_empty() {
+        return Err(NumberError::Empty);
+    }
+    body.parse().map_err(|_| {
+        let bad = body
+            .chars()
+            .find(|c| !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')))
+            .unwrap_or_else(|| body.chars().last().unwrap_or(' '));
+        NumberError::InvalidDigit(bad)
+    })
+}
+
+/// Inserts `separator` between groups of `size` digits, counting from the
+/// right.
+///
+/// # Arguments
+/// - `digits`: Bare digits without prefix.
+/// - `size`: Digits per group; `0` disables grouping.
+/// - `separator`: The character placed between groups.
+#[cfg(feature = "alloc")]
+pub fn group_digits(digits: &str, size: usize, separator: char) -> String {
+    if size == 0 {
+        return digits.into();
+    }
+    let count = digits.chars().count();
+    let mut out = String::with_capacity(digits.len() + count / size);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (count - i) % size == 0 {
+            out.push(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// A value recognised by [`parse_any`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyNumber {
+    /// An integer of any size.
+    Integer {
+        /// The radix the digits are in.
+        radix: u32,
+        /// Bare digits without prefix or separators.
+        digits: String,
+        /// Whether the integer had a leading `-`.
+        negative: bool,
+    },
+    /// Bytes in the order they were written.
+    Bytes(Vec<u8>),
+    /// A floating point number.
+    Float(f64),
+}
+
+/// Parses one byte of a byte list: `0x` followed by one or two hex digits,
+/// or exactly two hex digits.
+#[cfg(feature = "alloc")]
+fn parse_byte(token: &str) -> Option<u8> {
+    let digits = match token.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("0x") => &token[2..],
+        _ if token.len() == 2 => token,
+        _ => return None,
+    };
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+    u8::from_str_radix(digits, 16).ok()
+}
+
+/// Parses a byte list such as `DE AD BE EF`, `[0xde, 0xad]` or `\xde\xad`.
+///
+/// Needs at least two bytes unless the list is bracketed or escaped.
+#[cfg(feature = "alloc")]
+fn parse_byte_list(s: &str) -> Option<Vec<u8>> {
+    let inner = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .or_else(|| s.strip_prefix('{').and_then(|s| s.strip_suffix('}')));
+    if let Some(rest) = s.strip_prefix("\\x") {
+        return rest
+            .split("\\x")
+            .map(|b| parse_byte(&["0x", b.trim()].concat()))
+            .collect();
+    }
+    let list = inner.unwrap_or(s);
+    let bytes: Vec<u8> = list
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(parse_byte)
+        .collect::<Option<_>>()?;
+    (bytes.len() >= 2 || (inner.is_some() && !bytes.is_empty())).then_some(bytes)
+}
+
+/// Parses pasted text in whichever format it is written in.
+///
+/// Formats are tried in this order:
+/// 1. A byte list: two-digit hex bytes separated by spaces or commas
+///    (`DE AD BE EF`), `0x` bytes in brackets or braces
+///    (`[0xDE, 0xAD]`, `{0xde,0xad}`) or `\x` escapes (`\xDE\xAD`).
+///    Space-separated two-digit groups therefore read as bytes, not as a
+///    grouped decimal number.
+/// 2. An integer with a radix prefix, e.g. `0xDEAD_BEEF` or `-0b1010`.
+/// 3. A decimal integer, with the leniency of [`parse_u64`].
+/// 4. A float, e.g. `-1.5`, `6.02e23`, `3f` or `inf`.
+/// 5. Bare hex digits with at least one letter, e.g. `DEADBEEF` or `cafe`.
+///    Text that also reads as a float, such as `1e5` or `3f`, is taken as
+///    the float; hex needs the `0x` prefix then.
+///
+/// # Returns
+/// - The recognised value, or the [`NumberError`] from reading the text as
+///   a float when no format matches.
+#[cfg(feature = "alloc")]
+pub fn parse_any(input: &str) -> Result<AnyNumber, NumberError> {
+    let s = input.trim();
+    if s.is_empty() {
+        return Err(NumberError::Empty);
+    }
+    if let Some(bytes) = parse_byte_list(s) {
+        return Ok(AnyNumber::Bytes(bytes));
+    }
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, s),
+    };
+    if let Ok((radix, digits)) = normalize_digits(unsigned, 10) {
+        return Ok(AnyNumber::Integer {
+            radix,
+            digits,
+            negative,
+        });
+    }
+    let float = parse_f64(s);
+    if float.is_ok() {
+        return float.map(AnyNumber::Float);
+    }
+    let hex: String = unsigned.chars().filter(|&c| !is_separator(c)).collect();
+    if !hex.is_empty()
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+        && hex.chars().any(|c| c.is_ascii_alphabetic())
+    {
+        return Ok(AnyNumber::Integer {
+            radix: 16,
+            digits: hex,
+            negative,
+        });
+    }
+    float.map(AnyNumber::Float)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn integer(radix: u32, digits: &str, negative: bool) -> AnyNumber {
+        AnyNumber::Integer {
+            radix,
+            digits: digits.into(),
+            negative,
+        }
+    }
+
+    #[test]
+    fn reads_byte_lists() {
+        let bytes = AnyNumber::Bytes(alloc::vec![0xDE, 0xAD]);
+        assert_eq!(parse_any("DE AD"), Ok(bytes.clone()));
+        assert_eq!(parse_any("[0xde, 0xad]"), Ok(bytes.clone()));
+        assert_eq!(parse_any("\\xDE\\xAD"), Ok(bytes));
+        assert_eq!(parse_any("{0x7}"), Ok(AnyNumber::Bytes(alloc::vec![7])));
+    }
+
+    #[test]
+    fn reads_integers() {
+        assert_eq!(parse_any("0xDEAD_BEEF"), Ok(integer(16, "DEADBEEF", false)));
+        assert_eq!(parse_any("-0b1010"), Ok(integer(2, "1010", true)));
+        assert_eq!(parse_any("1'000'000"), Ok(integer(10, "1000000", false)));
+        assert_eq!(parse_any("255u8"), Ok(integer(10, "255", false)));
+        assert_eq!(parse_any("0x1E5"), Ok(integer(16, "1E5", false)));
+    }
+
+    #[test]
+    fn prefers_floats_over_bare_hex() {
+        assert_eq!(parse_any("1e5"), Ok(AnyNumber::Float(1e5)));
+        assert_eq!(parse_any("3f"), Ok(AnyNumber::Float(3.0)));
+        assert_eq!(parse_any("-1.5"), Ok(AnyNumber::Float(-1.5)));
+        assert_eq!(parse_any("6.02e23"), Ok(AnyNumber::Float(6.02e23)));
+        assert_eq!(parse_any("-inf"), Ok(AnyNumber::Float(f64::NEG_INFINITY)));
+    }
+
+    #[test]
+    fn reads_bare_hex_that_is_not_a_float() {
+        assert_eq!(parse_any("DEADBEEF"), Ok(integer(16, "DEADBEEF", false)));
+        assert_eq!(parse_any("cafe"), Ok(integer(16, "cafe", false)));
+        assert_eq!(parse_any("-ff"), Ok(integer(16, "ff", true)));
+        assert_eq!(parse_any("1e"), Ok(integer(16, "1e", false)));
+    }
+
+    #[test]
+    fn reports_unrecognised_text() {
+        assert_eq!(parse_any("   "), Err(NumberError::Empty));
+        assert_eq!(parse_any("12g"), Err(NumberError::InvalidDigit('g')));
+        assert!(parse_any("0xZZ").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Test Value Generators
+//!
+//! Integer test patterns at a given width: alternating bits, walking ones
+//! and zeros, powers of two and, with the `rand` feature, uniformly random
+//! values and random values with a fixed number of set bits. Widths beyond
+//! 64 bits are generated over their low 64 bits.
+
+use crate::width::Width;
+
+/// Returns the number of bits the generators fill at `width`.
+fn bit_count(width: Width) -> u32 {
+    width.bits().min(64)
+}
+
+/// Returns alternating bits across the width: `0xAA…` with the top bit set,
+/// or `0x55…` with the bottom bit set.
+pub fn alternating(width: Width, top_set: bool) -> u64 {
+    let pattern = if top_set {
+        0xAAAA_AAAA_AAAA_AAAA
+    } else {
+        0x5555_5555_5555_5555
+    };
+    pattern & width.mask()
+}
+
+/// Returns step `step` of a walking-one sequence: a single set bit starting
+/// at bit 0 and moving up one bit per step, wrapping at the width.
+pub fn walking_one(width: Width, step: u32) -> u64 {
+    1 << (step % bit_count(width))
+}
+
+/// Returns step `step` of a walking-zero sequence, the inverse of
+/// [`walking_one`] within the width.
+pub fn walking_zero(width: Width, step: u32) -> u64 {
+    !walking_one(width, step) & width.mask()
+}
+
+/// Returns `2^exponent`.
+///
+/// # Returns
+/// - `None` if the power does not fit the width.
+pub fn power_of_two(width: Width, exponent: u32) -> Option<u64> {
+    (exponent < bit_count(width)).then(|| 1 << exponent)
+}
+
+/// Returns a value chosen uniformly over all values of the width.
+#[cfg(feature = "rand")]
+pub fn random<R: rand::Rng + ?Sized>(width: Width, rng: &mut R) -> u64 {
+    rng.random::<u64>() & width.mask()
+}
+
+/// Returns a value with exactly `ones` set bits, chosen uniformly over all
+/// such values of the width.
+///
+/// # Returns
+/// - `None` if `ones` exceeds the number of bits.
+#[cfg(feature = "rand")]
+pub fn random_with_popcount<R: rand::Rng + ?Sized>(
+    width: Width,
+    ones: u32,
+    rng: &mut R,
+) -> Option<u64> {
+    let bits = bit_count(width);
+    if ones > bits {
+        return None;
+    }
+    // Floyd's sampling: each step sets one new bit out of 0..=j, taking bit
+    // j itself when the drawn one is already set
+    let mut value = 0u64;
+    for j in bits - ones..bits {
+        let t = rng.random_range(0..=j);
+        value |= if value >> t & 1 == 1 { 1 << j } else { 1 << t };
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alternating_fills_the_width() {
+        assert_eq!(alternating(Width::W8, true), 0xAA);
+        assert_eq!(alternating(Width::W8, false), 0x55);
+        assert_eq!(alternating(Width::W16, true), 0xAAAA);
+        assert_eq!(alternating(Width::W32, false), 0x5555_5555);
+        assert_eq!(alternating(Width::W256, true), 0xAAAA_AAAA_AAAA_AAAA);
+        for width in Width::ALL {
+            let (top, bottom) = (alternating(width, true), alternating(width, false));
+            assert_eq!(top ^ bottom, width.mask());
+            assert_eq!(top & bottom, 0);
+        }
+    }
+
+    #[test]
+    fn walking_bits_wrap_at_the_width() {
+        let ones: [u64; 10] = core::array::from_fn(|step| walking_one(Width::W8, step as u32));
+        assert_eq!(ones, [1, 2, 4, 8, 16, 32, 64, 128, 1, 2]);
+        assert_eq!(walking_zero(Width::W8, 0), 0xFE);
+        assert_eq!(walking_zero(Width::W8, 7), 0x7F);
+        assert_eq!(walking_zero(Width::W16, 17), 0xFFFD);
+        assert_eq!(walking_one(Width::W64, 63), 1 << 63);
+        // Wide values walk over their low 64 bits
+        assert_eq!(walking_one(Width::W128, 70), 1 << 6);
+        assert_eq!(walking_zero(Width::W256, 64), u64::MAX - 1);
+    }
+
+    #[test]
+    fn powers_of_two_fit_the_width() {
+        assert_eq!(power_of_two(Width::W8, 0), Some(1));
+        assert_eq!(power_of_two(Width::W8, 7), Some(0x80));
+        assert_eq!(power_of_two(Width::W8, 8), None);
+        assert_eq!(power_of_two(Width::W32, 31), Some(1 << 31));
+        assert_eq!(power_of_two(Width::W128, 63), Some(1 << 63));
+        assert_eq!(power_of_two(Width::W128, 64), None);
+    }
+
+    /// Generator returning a fixed sequence of words
+    #[cfg(feature = "rand")]
+    struct Sequence(u64);
+
+    #[cfg(feature = "rand")]
+    impl rand::RngCore for Sequence {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let value = self.0;
+            self.0 = self.0.wrapping_add(0x1111_1111_1111_1111);
+            value
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(8) {
+                let bytes = self.next_u64().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_masks_to_the_width() {
+        let mut rng = Sequence(0x0123_4567_89AB_CDEF);
+        assert_eq!(random(Width::W8, &mut rng), 0xEF);
+        assert_eq!(random(Width::W16, &mut rng), 0xDF00);
+        assert_eq!(random(Width::W32, &mut rng), 0xABCD_F011);
+        assert_eq!(random(Width::W256, &mut rng), 0x3456_789A_BCDF_0122);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_with_popcount_sets_exactly_that_many_bits() {
+        use rand::{rngs::SmallRng, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(7);
+        for width in [Width::W8, Width::W16, Width::W64, Width::W256] {
+            let bits = width.bits().min(64);
+            for ones in [0, 1, bits / 2, bits - 1, bits] {
+                let value = random_with_popcount(width, ones, &mut rng).unwrap();
+                assert_eq!(value.count_ones(), ones);
+                assert_eq!(value & !width.mask(), 0);
+            }
+            assert_eq!(random_with_popcount(width, bits + 1, &mut rng), None);
+        }
+        assert_eq!(random_with_popcount(Width::W8, 8, &mut rng), Some(0xFF));
+
+        // The same seed gives the same values
+        let draw = |seed| {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            [3, 3, 3].map(|ones| random_with_popcount(Width::W32, ones, &mut rng))
+        };
+        assert_eq!(draw(42), draw(42));
+        assert_ne!(draw(42), draw(43));
+    }
+}
@@ -0,0 +1,375 @@
+//! Bit Permutations
+//!
+//! A [`Permutation`] moves every bit of the low `len` bits of a value to a
+//! new position: output bit `i` takes input bit `sources()[i]`. Bits at or
+//! above `len` are left in place. Built-ins cover bit reversal within each
+//! byte and Morton (Z-order) interleaving of the two halves of a value;
+//! arbitrary tables are parsed from text.
+//!
+//! Construction precomputes, for each of the eight input bytes and each of
+//! its 256 values, the output bits it produces, so [`BitArray::permute`]
+//! costs eight table lookups whatever the permutation.
+
+use crate::parse::{parse_u64, NumberError};
+use crate::{BitArray, Width};
+use core::fmt;
+
+/// Error produced while building a [`Permutation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermutationError {
+    /// A length of zero or more than 64 bits.
+    InvalidLength(u32),
+    /// A table with a different number of entries than bits.
+    Length {
+        /// The number of bits permuted.
+        expected: u32,
+        /// The number of entries given.
+        found: u32,
+    },
+    /// A source bit at or beyond the length.
+    OutOfRange {
+        /// The source bit given.
+        bit: u64,
+        /// The number of bits permuted.
+        len: u32,
+    },
+    /// A source bit used by more than one output bit.
+    Duplicate(u32),
+    /// A table entry that is not a number.
+    Parse(NumberError),
+}
+
+impl fmt::Display for PermutationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PermutationError::InvalidLength(len) => {
+                write!(f, "cannot permute {} bits", len)
+            }
+            PermutationError::Length { expected, found } => {
+                write!(f, "expected {} entries, found {}", expected, found)
+            }
+            PermutationError::OutOfRange { bit, len } => {
+                write!(f, "bit {} is outside the {} bits", bit, len)
+            }
+            PermutationError::Duplicate(bit) => write!(f, "bit {} is used twice", bit),
+            PermutationError::Parse(e) => write!(f, "not a number: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PermutationError {}
+
+impl From<NumberError> for PermutationError {
+    fn from(e: NumberError) -> Self {
+        PermutationError::Parse(e)
+    }
+}
+
+/// A permutation of the low bits of a value.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Permutation {
+    len: u32,
+    /// Input bit taken by each output bit
+    sources: [u8; 64],
+    /// Output bits produced by each value of each input byte
+    lookup: [[u64; 256]; 8],
+}
+
+impl fmt::Debug for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Permutation")
+            .field("sources", &self.sources())
+            .finish()
+    }
+}
+
+impl Permutation {
+    /// Creates a permutation of the low `sources.len()` bits where output
+    /// bit `i` takes input bit `sources[i]`.
+    ///
+    /// # Returns
+    /// - The permutation, or an error if there are more than 64 entries or
+    ///   the entries are not each of `0..len` exactly once.
+    pub fn new(sources: &[u32]) -> Result<Self, PermutationError> {
+        let len = u32::try_from(sources.len()).unwrap_or(u32::MAX);
+        if !(1..=64).contains(&len) {
+            return Err(PermutationError::InvalidLength(len));
+        }
+        let mut table = [0u8; 64];
+        let mut used = 0u64;
+        for (slot, &bit) in table.iter_mut().zip(sources) {
+            if bit >= len {
+                return Err(PermutationError::OutOfRange {
+                    bit: bit.into(),
+                    len,
+                });
+            }
+            if used >> bit & 1 == 1 {
+                return Err(PermutationError::Duplicate(bit));
+            }
+            used |= 1 << bit;
+            *slot = bit as u8;
+        }
+        Ok(Self::from_table(len, table))
+    }
+
+    /// Builds the lookup tables for a checked source table.
+    fn from_table(len: u32, sources: [u8; 64]) -> Self {
+        // Output position of every input bit; bits from `len` up stay put
+        let mut dest: [u8; 64] = core::array::from_fn(|i| i as u8);
+        for (out, &src) in sources.iter().enumerate().take(len as usize) {
+            dest[src as usize] = out as u8;
+        }
+        let mut lookup = [[0u64; 256]; 8];
+        for (byte, table) in lookup.iter_mut().enumerate() {
+            for (value, entry) in table.iter_mut().enumerate() {
+                *entry = (0..8)
+                    .filter(|b| value >> b & 1 == 1)
+                    .fold(0, |acc, b| acc | 1 << dest[byte * 8 + b]);
+            }
+        }
+        Self {
+            len,
+            sources,
+            lookup,
+        }
+    }
+
+    /// Builds a permutation of `len` bits from a source function, which
+    /// must be a bijection on `0..len`.
+    fn from_fn(len: u32, source: impl Fn(u32) -> u32) -> Self {
+        let mut sources = [0u8; 64];
+        for (i, slot) in sources.iter_mut().enumerate().take(len as usize) {
+            *slot = source(i as u32) as u8;
+        }
+        Self::from_table(len, sources)
+    }
+
+    /// Returns the permutation that leaves the low `width` bits in place.
+    pub fn identity(width: Width) -> Self {
+        Self::from_fn(width.bits().min(64), |i| i)
+    }
+
+    /// Returns the permutation reversing the bit order within each byte.
+    pub fn reverse_bits_in_bytes(width: Width) -> Self {
+        Self::from_fn(width.bits().min(64), |i| i ^ 7)
+    }
+
+    /// Returns the Morton encoding of the two halves of the value: the low
+    /// half's bits go to the even output bits and the high half's to the
+    /// odd ones, as in a Z-order curve index.
+    pub fn morton_encode(width: Width) -> Self {
+        let half = width.bits().min(64) / 2;
+        Self::from_fn(half * 2, |i| if i % 2 == 0 { i / 2 } else { half + i / 2 })
+    }
+
+    /// Returns the inverse of [`Permutation::morton_encode`]: even bits to
+    /// the low half and odd bits to the high half.
+    pub fn morton_decode(width: Width) -> Self {
+        Self::morton_encode(width).inverse()
+    }
+
+    /// Parses a table of source bits for output bits 0, 1, 2, … in order,
+    /// separated by whitespace or commas, e.g. `7 6 5 4 3 2 1 0` to reverse
+    /// a byte.
+    ///
+    /// # Arguments
+    /// - `text`: The source bits, decimal or with a `0x`/`0b`/`0o` prefix.
+    /// - `width`: The width permuted; the table needs one entry per bit.
+    pub fn parse(text: &str, width: Width) -> Result<Self, PermutationError> {
+        let expected = width.bits().min(64);
+        let mut sources = [0u32; 64];
+        let mut found = 0u32;
+        for entry in text.split(|c: char| c == ',' || c.is_whitespace()) {
+            if entry.is_empty() {
+                continue;
+            }
+            let bit = parse_u64(entry, 10)?;
+            if found < 64 {
+                sources[found as usize] = u32::try_from(bit)
+                    .map_err(|_| PermutationError::OutOfRange { bit, len: expected })?;
+            }
+            found += 1;
+        }
+        if found != expected {
+            return Err(PermutationError::Length { expected, found });
+        }
+        Self::new(&sources[..found as usize])
+    }
+
+    /// Returns the number of bits permuted.
+    pub fn bits(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns the input bit taken by each output bit, from bit 0 up.
+    pub fn sources(&self) -> &[u8] {
+        &self.sources[..self.len as usize]
+    }
+
+    /// Returns the permutation undoing this one.
+    pub fn inverse(&self) -> Self {
+        let mut sources = [0u8; 64];
+        for (out, &src) in self.sources().iter().enumerate() {
+            sources[src as usize] = out as u8;
+        }
+        Self::from_table(self.len, sources)
+    }
+
+    /// Applies the permutation to a raw value.
+    pub fn apply(&self, value: u64) -> u64 {
+        self.lookup
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (byte, table)| {
+                acc | table[(value >> (byte * 8)) as usize & 0xFF]
+            })
+    }
+}
+
+impl BitArray {
+    /// Moves every bit to its position under `permutation`; bits above its
+    /// length are unchanged.
+    pub fn permute(&mut self, permutation: &Permutation) {
+        self.0 = permutation.apply(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [u64; 5] = [
+        0,
+        u64::MAX,
+        0x0123_4567_89AB_CDEF,
+        0xDEAD_BEEF_0BAD_F00D,
+        1 << 63 | 1,
+    ];
+
+    /// Returns a shuffled table of `len` source bits from a fixed seed
+    fn shuffled(len: u32, mut seed: u64) -> [u32; 64] {
+        let mut sources: [u32; 64] = core::array::from_fn(|i| i as u32);
+        for i in (1..len as usize).rev() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            sources.swap(i, (seed % (i as u64 + 1)) as usize);
+        }
+        sources
+    }
+
+    fn assert_round_trip(permutation: &Permutation) {
+        let inverse = permutation.inverse();
+        for value in VALUES {
+            assert_eq!(inverse.apply(permutation.apply(value)), value);
+            assert_eq!(permutation.apply(inverse.apply(value)), value);
+        }
+    }
+
+    #[test]
+    fn inverse_undoes_permutation() {
+        for width in Width::ALL {
+            assert_round_trip(&Permutation::identity(width));
+            assert_round_trip(&Permutation::reverse_bits_in_bytes(width));
+            assert_round_trip(&Permutation::morton_encode(width));
+        }
+        for (len, seed) in [(8, 1), (13, 2), (32, 3), (64, 4), (64, 5)] {
+            let permutation = Permutation::new(&shuffled(len, seed)[..len as usize]).unwrap();
+            assert_round_trip(&permutation);
+            assert_eq!(permutation.inverse().inverse(), permutation);
+        }
+        let mut ba = BitArray(0x0123_4567_89AB_CDEF);
+        let morton = Permutation::morton_encode(Width::W64);
+        ba.permute(&morton);
+        ba.permute(&Permutation::morton_decode(Width::W64));
+        assert_eq!(ba, BitArray(0x0123_4567_89AB_CDEF));
+    }
+
+    #[test]
+    fn built_ins_move_the_expected_bits() {
+        let reverse = Permutation::reverse_bits_in_bytes(Width::W16);
+        assert_eq!(reverse.apply(0x0102), 0x8040);
+        assert_eq!(reverse.apply(reverse.apply(0xBEEF)), 0xBEEF);
+        let morton = Permutation::morton_encode(Width::W8);
+        assert_eq!(morton.apply(0x0F), 0x55);
+        assert_eq!(morton.apply(0xF0), 0xAA);
+        assert_eq!(morton.apply(0b0001_0001), 0b0000_0011);
+        assert_eq!(morton.apply(0b0010_0000), 0b0000_1000);
+        // Bits at or above the length stay put
+        let swap = Permutation::new(&[1, 0]).unwrap();
+        assert_eq!(swap.apply(0xFF01), 0xFF02);
+        assert_eq!(Permutation::identity(Width::W32).apply(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn parses_tables() {
+        let parsed = Permutation::parse("0x7,6,5, 4 3 2 1 0b0", Width::W8).unwrap();
+        assert_eq!(parsed, Permutation::reverse_bits_in_bytes(Width::W8));
+        assert_eq!(parsed.sources(), [7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(parsed.bits(), 8);
+    }
+
+    #[test]
+    fn rejects_invalid_tables() {
+        assert_eq!(
+            Permutation::new(&[]),
+            Err(PermutationError::InvalidLength(0))
+        );
+        assert_eq!(
+            Permutation::new(&[0; 65]),
+            Err(PermutationError::InvalidLength(65))
+        );
+        assert_eq!(
+            Permutation::new(&[0, 2]),
+            Err(PermutationError::OutOfRange { bit: 2, len: 2 })
+        );
+        assert_eq!(
+            Permutation::new(&[1, 0, 1]),
+            Err(PermutationError::Duplicate(1))
+        );
+        assert_eq!(
+            Permutation::parse("0 1 2", Width::W8),
+            Err(PermutationError::Length {
+                expected: 8,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Permutation::parse("7 6 5 4 3 2 1 0 0", Width::W8),
+            Err(PermutationError::Length {
+                expected: 8,
+                found: 9
+            })
+        );
+        assert_eq!(
+            Permutation::parse("7 6 5 4 3 2 1 8", Width::W8),
+            Err(PermutationError::OutOfRange { bit: 8, len: 8 })
+        );
+        assert_eq!(
+            Permutation::parse("7 6 5 4 3 2 1 4294967296", Width::W8),
+            Err(PermutationError::OutOfRange {
+                bit: 1 << 32,
+                len: 8
+            })
+        );
+        assert_eq!(
+            Permutation::parse("7 6 5 4 3 2 1 1", Width::W8),
+            Err(PermutationError::Duplicate(1))
+        );
+        assert_eq!(
+            Permutation::parse("7 6 5 4 3 2 1 x", Width::W8),
+            Err(PermutationError::Parse(NumberError::InvalidDigit('x')))
+        );
+        // More entries than any permutation holds are counted, not stored
+        let long = "0 ".repeat(70);
+        assert_eq!(
+            Permutation::parse(&long, Width::W64),
+            Err(PermutationError::Length {
+                expected: 64,
+                found: 70
+            })
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Precision Loss
+//!
+//! How a decimal number rounds into each binary float format: the nearest
+//! bit pattern, the exact value it stores, the error against the decimal as
+//! typed and whether the stored value lies above or below it. Errors are
+//! computed from the exact decimal digits, so they are not themselves
+//! distorted by a detour through binary64.
+
+use crate::decimal;
+use crate::rounding::FloatFormat;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Formats compared by [`convert_all`], with their display names.
+pub const FORMATS: [(FloatFormat, &str); 4] = [
+    (FloatFormat::HALF, "binary16"),
+    (FloatFormat::BFLOAT16, "bfloat16"),
+    (FloatFormat::SINGLE, "binary32"),
+    (FloatFormat::DOUBLE, "binary64"),
+];
+
+/// Which side of the decimal the stored value lies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// The decimal is stored exactly.
+    Exact,
+    /// Rounded towards -Inf: the stored value is smaller.
+    Down,
+    /// Rounded towards +Inf: the stored value is larger.
+    Up,
+}
+
+impl Direction {
+    /// Describes the direction relative to zero, e.g. `"toward zero"` for
+    /// a positive value rounded down.
+    pub fn relative_to_zero(self, negative: bool) -> &'static str {
+        match (self, negative) {
+            (Direction::Exact, _) => "exact",
+            (Direction::Down, false) | (Direction::Up, true) => "toward zero",
+            (Direction::Up, false) | (Direction::Down, true) => "away from zero",
+        }
+    }
+}
+
+/// A decimal number rounded into one float format.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conversion {
+    /// The target format.
+    pub format: FloatFormat,
+    /// The nearest bit pattern, ties to even.
+    pub bits: u64,
+    /// The stored value widened to `f64`.
+    pub value: f64,
+    /// Exact decimal expansion of the stored value.
+    pub exact: String,
+    /// Stored value minus the decimal; infinite when the decimal overflows
+    /// the format.
+    pub error: f64,
+    /// `error` divided by the decimal; -1 when the decimal underflows to
+    /// zero.
+    pub relative_error: f64,
+    /// Which way the decimal was rounded.
+    pub direction: Direction,
+}
+
+/// Rounds the decimal `input` into `format` and measures the error.
+///
+/// # Arguments
+/// - `format`: The target format (at most binary64).
+/// - `input`: A decimal such as `0.1` or `-6.02e23`, or `inf`/`nan`.
+///
+/// # Returns
+/// - `None` if `input` is not a number. NaN and infinities convert with no
+///   error.
+pub fn convert(format: FloatFormat, input: &str) -> Option<Conversion> {
+    let bits = decimal::parse(format, input)?;
+    let value = format.decode(bits);
+    let exact = decimal::exact(value);
+    let typed: f64 = input.trim().parse().ok()?;
+
+    // NaN and infinities carry over unchanged
+    let (error, direction) = if !decimal::is_decimal(input) || decimal::same_value(&exact, input) {
+        (0.0, Direction::Exact)
+    } else {
+        let error = if value.is_finite() {
+            decimal::difference(&exact, input)?
+        } else {
+            value
+        };
+        // The sign survives an error too small for f64
+        let direction = if error.is_sign_negative() {
+            Direction::Down
+        } else {
+            Direction::Up
+        };
+        (error, direction)
+    };
+    let relative_error = if direction == Direction::Exact {
+        0.0
+    } else if value == 0.0 {
+        // Underflow to zero loses the whole value
+        -1.0
+    } else if !value.is_finite() {
+        error
+    } else {
+        error / typed
+    };
+    Some(Conversion {
+        format,
+        bits,
+        value,
+        exact,
+        error,
+        relative_error,
+        direction,
+    })
+}
+
+/// Rounds `input` into each of the [`FORMATS`].
+///
+/// # Returns
+/// - `None` if `input` is not a number.
+pub fn convert_all(input: &str) -> Option<Vec<Conversion>> {
+    FORMATS
+        .iter()
+        .map(|(format, _)| convert(*format, input))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_rounding_error() {
+        let half = convert(FloatFormat::HALF, "0.1").unwrap();
+        assert_eq!(half.bits, 0x2E66);
+        assert_eq!(half.exact, "0.0999755859375");
+        assert_eq!(half.error, -2.44140625e-5);
+        assert!((half.relative_error + 2.44140625e-4).abs() < 1e-18);
+        assert_eq!(half.direction, Direction::Down);
+        assert_eq!(half.direction.relative_to_zero(false), "toward zero");
+
+        // The binary64 error is below binary64 resolution at 0.1 but still
+        // computed from the exact digits
+        let double = convert(FloatFormat::DOUBLE, "0.1").unwrap();
+        assert_eq!(double.direction, Direction::Up);
+        assert!((double.error - 5.551115123125783e-18).abs() < 1e-30);
+
+        let negative = convert(FloatFormat::SINGLE, "-0.1").unwrap();
+        assert_eq!(negative.direction, Direction::Down);
+        assert_eq!(negative.direction.relative_to_zero(true), "away from zero");
+    }
+
+    #[test]
+    fn reports_exact_values() {
+        for input in ["0.5", "-2", "1.5e0", "0", "nan", "-inf"] {
+            for (format, name) in FORMATS {
+                let conversion = convert(format, input).unwrap();
+                assert_eq!(conversion.direction, Direction::Exact, "{} {}", input, name);
+                assert_eq!(conversion.error, 0.0);
+                assert_eq!(conversion.relative_error, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn handles_overflow_and_underflow() {
+        let overflow = convert(FloatFormat::HALF, "70000").unwrap();
+        assert_eq!(overflow.value, f64::INFINITY);
+        assert_eq!(overflow.error, f64::INFINITY);
+        assert_eq!(overflow.direction, Direction::Up);
+
+        let underflow = convert(FloatFormat::HALF, "1e-10").unwrap();
+        assert_eq!(underflow.bits, 0);
+        assert_eq!(underflow.error, -1e-10);
+        assert_eq!(underflow.relative_error, -1.0);
+        assert_eq!(underflow.direction, Direction::Down);
+
+        let negative = convert(FloatFormat::HALF, "-1e-10").unwrap();
+        assert_eq!(negative.bits, 0x8000);
+        assert_eq!(negative.direction, Direction::Up);
+        assert_eq!(negative.relative_error, -1.0);
+    }
+
+    #[test]
+    fn converts_into_every_format() {
+        let all = convert_all("1.2345").unwrap();
+        let bits: Vec<u64> = all.iter().map(|c| c.bits).collect();
+        assert_eq!(bits, [0x3CF0, 0x3F9E, 0x3F9E_0419, 1.2345f64.to_bits()]);
+        assert!(all.iter().all(|c| c.direction != Direction::Exact));
+        assert!(convert_all("pi").is_none());
+        assert!(convert(FloatFormat::HALF, "1,5").is_none());
+    }
+}
@@ -0,0 +1,308 @@
+//! Named Register Maps
+//!
+//! A register description names the bit fields of one register and,
+//! optionally, the symbolic values a field can take, so a raw value can be
+//! shown and edited field by field. Maps are written in a small line-based
+//! format:
+//!
+//! ```text
+//! register CTRL 32           // name and width in bits
+//! field EN    0    "Enable"  // single bit, optional description
+//! field MODE  5:4  off=0 slow=1 fast=2
+//! field DIV   15..8
+//! ```
+//!
+//! Bit ranges are `hi:lo` or `hi..lo`, both inclusive, or a single bit.
+//! Enum values are decimal or `0x`/`0b`/`0o` prefixed. Fields may not
+//! overlap or extend past the register width. `//` starts a comment. With
+//! the `serde` feature a [`RegisterMap`] can also be (de)serialized directly,
+//! e.g. from JSON.
+
+use crate::parse::parse_u64;
+use crate::{BitArray, Width};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+use core::ops::Range;
+
+/// Error produced while parsing a register map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterMapError {
+    /// 1-based source line the error refers to.
+    pub line: usize,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for RegisterMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RegisterMapError {}
+
+/// A symbolic name for one value of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumValue {
+    /// Symbol shown in place of the raw value.
+    pub name: String,
+    /// Raw field value, aligned to bit 0.
+    pub value: u64,
+}
+
+/// A named bit field of a register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterField {
+    /// Field name.
+    pub name: String,
+    /// Position of the least significant bit of the field.
+    pub shift: u32,
+    /// Field width in bits.
+    pub width: u32,
+    /// Free-form description; may be empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: String,
+    /// Symbolic values; may be empty.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub values: Vec<EnumValue>,
+}
+
+impl RegisterField {
+    /// Returns the bit positions of the field, end exclusive.
+    pub fn range(&self) -> Range<u8> {
+        self.shift as u8..(self.shift + self.width) as u8
+    }
+
+    /// Returns the mask of the field's bits within the register.
+    pub fn mask(&self) -> u64 {
+        let mut bits = BitArray::new();
+        bits.set_field(self.range(), u64::MAX);
+        bits.0
+    }
+
+    /// Returns `true` if `bit` belongs to the field.
+    pub fn contains(&self, bit: u32) -> bool {
+        (self.shift..self.shift + self.width).contains(&bit)
+    }
+
+    /// Extracts the field value from a register value.
+    pub fn extract(&self, value: u64) -> u64 {
+        BitArray(value).get_field(self.range())
+    }
+
+    /// Returns `value` with the field replaced by `field`.
+    ///
+    /// # Returns
+    /// - `Some(value)`, or `None` if `field` does not fit in the field width.
+    pub fn insert(&self, value: u64, field: u64) -> Option<u64> {
+        if self.width < 64 && field >> self.width != 0 {
+            return None;
+        }
+        let mut bits = BitArray(value);
+        bits.set_field(self.range(), field);
+        Some(bits.0)
+    }
+
+    /// Returns the symbol for a raw field value, if one is defined.
+    pub fn symbol(&self, field: u64) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|v| v.value == field)
+            .map(|v| v.name.as_str())
+    }
+
+    /// Resolves a symbol or a number (see [`parse_u64`]) to a raw field
+    /// value.
+    ///
+    /// # Returns
+    /// - `Some(field)`, or `None` for an unknown symbol, invalid number or a
+    ///   value wider than the field.
+    pub fn resolve(&self, text: &str) -> Option<u64> {
+        let text = text.trim();
+        let field = match self.values.iter().find(|v| v.name == text) {
+            Some(v) => v.value,
+            None => parse_u64(text, 10).ok()?,
+        };
+        (self.width >= 64 || field >> self.width == 0).then_some(field)
+    }
+}
+
+/// A register description: its width and named fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterMap {
+    /// Register name.
+    pub name: String,
+    /// Register width.
+    pub width: Width,
+    /// Fields, most significant first.
+    pub fields: Vec<RegisterField>,
+}
+
+impl RegisterMap {
+    /// Parses a register map in the line-based format described in the
+    /// module documentation.
+    ///
+    /// # Returns
+    /// - The map with fields sorted most significant first, or the first
+    ///   syntax or layout error.
+    pub fn parse(src: &str) -> Result<Self, RegisterMapError> {
+        let mut map: Option<Self> = None;
+        for (index, raw) in src.lines().enumerate() {
+            let line = index + 1;
+            let err = |message: String| RegisterMapError { line, message };
+            let text = raw.split("//").next().unwrap_or("");
+            let tokens = tokenize(text).map_err(err)?;
+            let Some((keyword, args)) = tokens.split_first() else {
+                continue;
+            };
+            match keyword.as_str() {
+                "register" => {
+                    if map.is_some() {
+                        return Err(err("duplicate `register` line".to_string()));
+                    }
+                    let [name, bits] = args else {
+                        return Err(err("expected `register NAME BITS`".to_string()));
+                    };
+                    let width = parse_u64(bits, 10)
+                        .ok()
+                        .and_then(|b| u32::try_from(b).ok())
+                        .and_then(Width::from_bits)
+                        .filter(|w| w.bits() <= 64)
+                        .ok_or_else(|| err(format!("unsupported register width `{}`", bits)))?;
+                    map = Some(Self {
+                        name: name.clone(),
+                        width,
+                        fields: Vec::new(),
+                    });
+                }
+                "field" => {
+                    let map = map
+                        .as_mut()
+                        .ok_or_else(|| err("`field` before `register`".to_string()))?;
+                    map.fields.push(parse_field(args, map.width).map_err(err)?);
+                    map.validate().map_err(err)?;
+                }
+                other => return Err(err(format!("unknown keyword `{}`", other))),
+            }
+        }
+        let mut map = map.ok_or(RegisterMapError {
+            line: 1,
+            message: "missing `register` line".to_string(),
+        })?;
+        map.fields.sort_by_key(|f| Reverse(f.shift));
+        Ok(map)
+    }
+
+    /// Checks that every field lies inside the register, no two fields
+    /// overlap or share a name, and enum values fit their field; useful for
+    /// maps built by hand or deserialized.
+    ///
+    /// # Returns
+    /// - `Ok(())`, or a description of the first problem.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut used = 0u64;
+        for (i, field) in self.fields.iter().enumerate() {
+            if field.width == 0 || field.shift + field.width > self.width.bits().min(64) {
+                return Err(format!(
+                    "field `{}` does not fit in {} bits",
+                    field.name, self.width
+                ));
+            }
+            if self.fields[..i].iter().any(|f| f.name == field.name) {
+                return Err(format!("duplicate field `{}`", field.name));
+            }
+            let mask = field.mask();
+            if used & mask != 0 {
+                return Err(format!("field `{}` overlaps another field", field.name));
+            }
+            used |= mask;
+            let fits = |v: &&EnumValue| field.width >= 64 || v.value >> field.width == 0;
+            if let Some(v) = field.values.iter().find(|v| !fits(v)) {
+                return Err(format!(
+                    "value `{}` of field `{}` does not fit in {} bits",
+                    v.name, field.name, field.width
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up a field by name.
+    pub fn field(&self, name: &str) -> Option<&RegisterField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Returns the field covering `bit`, if any.
+    pub fn field_at(&self, bit: u32) -> Option<&RegisterField> {
+        self.fields.iter().find(|f| f.contains(bit))
+    }
+}
+
+/// Splits a line into whitespace-separated tokens, keeping `"quoted text"`
+/// together (without the quotes).
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| "unterminated string".to_string())?;
+            tokens.push(quoted[..end].to_string());
+            rest = quoted[end + 1..].trim_start();
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            tokens.push(rest[..end].to_string());
+            rest = rest[end..].trim_start();
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses the arguments of a `field` line.
+fn parse_field(args: &[String], width: Width) -> Result<RegisterField, String> {
+    let [name, bits, rest @ ..] = args else {
+        return Err("expected `field NAME BITS [\"description\"] [name=value ...]`".to_string());
+    };
+    let bit = |s: &str| {
+        parse_u64(s, 10)
+            .ok()
+            .and_then(|b| u32::try_from(b).ok())
+            .filter(|&b| b < width.bits().min(64))
+            .ok_or_else(|| format!("bit `{}` is outside the {}-bit register", s, width))
+    };
+    let (hi, lo) = match bits.split_once(':').or_else(|| bits.split_once("..")) {
+        Some((a, b)) => (bit(a)?, bit(b)?),
+        None => (bit(bits)?, bit(bits)?),
+    };
+    let (hi, lo) = (hi.max(lo), hi.min(lo));
+    let mut field = RegisterField {
+        name: name.clone(),
+        shift: lo,
+        width: hi - lo + 1,
+        description: String::new(),
+        values: Vec::new(),
+    };
+    for arg in rest {
+        match arg.split_once('=') {
+            Some((symbol, value)) => {
+                let value = parse_u64(value, 10)
+                    .map_err(|e| format!("value `{}` of `{}`: {}", value, symbol, e))?;
+                field.values.push(EnumValue {
+                    name: symbol.to_string(),
+                    value,
+                });
+            }
+            None if field.description.is_empty() => field.description = arg.clone(),
+            None => return Err(format!("unexpected `{}`", arg)),
+        }
+    }
+    Ok(field)
+}
@@ -0,0 +1,544 @@
+//! Directed Rounding
+//!
+//! Rust floats always round to nearest-even, so other IEEE 754 rounding modes
+//! are emulated: the round-to-nearest result is computed, the exact rounding
+//! error is recovered with an error-free transformation (TwoSum or FMA), and
+//! the result is nudged one ULP in the requested direction when needed.
+
+/// IEEE 754 rounding direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even (the hardware default).
+    #[default]
+    NearestEven,
+    /// Round toward zero (truncate).
+    TowardZero,
+    /// Round toward positive infinity.
+    Up,
+    /// Round toward negative infinity.
+    Down,
+}
+
+/// Returns the smallest `f64` greater than `x`.
+///
+/// NaN and `+Inf` are returned unchanged; `-0.0` and `+0.0` step to the
+/// smallest positive subnormal.
+pub fn next_up_f64(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        return x;
+    }
+    if x == 0.0 {
+        return f64::from_bits(1);
+    }
+    let bits = x.to_bits();
+    f64::from_bits(if x > 0.0 { bits + 1 } else { bits - 1 })
+}
+
+/// Returns the largest `f64` less than `x`.
+pub fn next_down_f64(x: f64) -> f64 {
+    -next_up_f64(-x)
+}
+
+/// Returns the smallest `f32` greater than `x`.
+pub fn next_up_f32(x: f32) -> f32 {
+    if x.is_nan() || x == f32::INFINITY {
+        return x;
+    }
+    if x == 0.0 {
+        return f32::from_bits(1);
+    }
+    let bits = x.to_bits();
+    f32::from_bits(if x > 0.0 { bits + 1 } else { bits - 1 })
+}
+
+/// Returns the largest `f32` less than `x`.
+pub fn next_down_f32(x: f32) -> f32 {
+    -next_up_f32(-x)
+}
+
+/// Adjusts a round-to-nearest `result` given the sign of the exact error
+/// (`exact - result`). An overflow to infinity is signalled with an error of
+/// the opposite sign so directed modes fall back to the largest finite value.
+fn adjust(result: f64, error: f64, mode: RoundingMode) -> f64 {
+    if result.is_nan() || error == 0.0 || error.is_nan() {
+        return result;
+    }
+    match mode {
+        RoundingMode::NearestEven => result,
+        RoundingMode::Up if error > 0.0 => next_up_f64(result),
+        RoundingMode::Down if error < 0.0 => next_down_f64(result),
+        RoundingMode::TowardZero if result > 0.0 && error < 0.0 => next_down_f64(result),
+        RoundingMode::TowardZero if result < 0.0 && error > 0.0 => next_up_f64(result),
+        _ => result,
+    }
+}
+
+/// Computes `a + b` rounded in the given direction.
+pub fn add(a: f64, b: f64, mode: RoundingMode) -> f64 {
+    let s = a + b;
+    if s.is_infinite() && a.is_finite() && b.is_finite() {
+        return adjust(s, -s, mode);
+    }
+    // TwoSum: exact error of the rounded sum
+    let bb = s - a;
+    let error = (a - (s - bb)) + (b - bb);
+    adjust(s, error, mode)
+}
+
+/// Computes `a - b` rounded in the given direction.
+pub fn sub(a: f64, b: f64, mode: RoundingMode) -> f64 {
+    add(a, -b, mode)
+}
+
+/// Computes `a * b` rounded in the given direction.
+pub fn mul(a: f64, b: f64, mode: RoundingMode) -> f64 {
+    let p = a * b;
+    if p.is_infinite() && a.is_finite() && b.is_finite() {
+        return adjust(p, -p, mode);
+    }
+    adjust(p, a.mul_add(b, -p), mode)
+}
+
+/// Computes `a / b` rounded in the given direction.
+pub fn div(a: f64, b: f64, mode: RoundingMode) -> f64 {
+    let q = a / b;
+    if q.is_infinite() && a.is_finite() && b != 0.0 {
+        return adjust(q, -q, mode);
+    }
+    // remainder a - q*b is exact; its sign relative to b gives the error sign
+    let remainder = (-q).mul_add(b, a);
+    adjust(q, remainder / b, mode)
+}
+
+/// Converts `x` to `f32` rounded in the given direction.
+pub fn f64_to_f32(x: f64, mode: RoundingMode) -> f32 {
+    let r = x as f32;
+    if x.is_nan() || r.is_infinite() && x.is_infinite() {
+        return r;
+    }
+    let error = x - r as f64;
+    match mode {
+        RoundingMode::NearestEven => r,
+        RoundingMode::Up if error > 0.0 => next_up_f32(r),
+        RoundingMode::Down if error < 0.0 => next_down_f32(r),
+        RoundingMode::TowardZero if r > 0.0 && error < 0.0 => next_down_f32(r),
+        RoundingMode::TowardZero if r < 0.0 && error > 0.0 => next_up_f32(r),
+        _ => r,
+    }
+}
+
+/// Computes `x * 2^exp` with a single rounding, stepping through normal
+/// powers of two so intermediate factors never underflow.
+pub(crate) fn scale(mut x: f64, mut exp: i32) -> f64 {
+    while exp > 1000 {
+        x *= 2f64.powi(1000);
+        exp -= 1000;
+    }
+    while exp < -1000 {
+        x *= 2f64.powi(-1000);
+        exp += 1000;
+    }
+    x * 2f64.powi(exp)
+}
+
+/// Layout of a binary IEEE 754 interchange format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatFormat {
+    /// Width of the exponent field.
+    pub exp_bits: u32,
+    /// Width of the mantissa (fraction) field.
+    pub man_bits: u32,
+}
+
+impl FloatFormat {
+    /// FP8 E5M2.
+    pub const E5M2: FloatFormat = FloatFormat {
+        exp_bits: 5,
+        man_bits: 2,
+    };
+    /// binary16.
+    pub const HALF: FloatFormat = FloatFormat {
+        exp_bits: 5,
+        man_bits: 10,
+    };
+    /// bfloat16.
+    pub const BFLOAT16: FloatFormat = FloatFormat {
+        exp_bits: 8,
+        man_bits: 7,
+    };
+    /// binary32.
+    pub const SINGLE: FloatFormat = FloatFormat {
+        exp_bits: 8,
+        man_bits: 23,
+    };
+    /// binary64.
+    pub const DOUBLE: FloatFormat = FloatFormat {
+        exp_bits: 11,
+        man_bits: 52,
+    };
+
+    /// Total width in bits, including the sign.
+    pub fn bits(self) -> u32 {
+        1 + self.exp_bits + self.man_bits
+    }
+
+    fn bias(self) -> i32 {
+        (1 << (self.exp_bits - 1)) - 1
+    }
+
+    fn exp_max_field(self) -> u64 {
+        (1 << self.exp_bits) - 1
+    }
+
+    /// Decodes a bit pattern of this format to `f64`.
+    ///
+    /// Exact for every format whose values fit in binary64.
+    pub fn decode(self, bits: u64) -> f64 {
+        let sign = if (bits >> (self.exp_bits + self.man_bits)) & 1 == 1 {
+            -1.0
+        } else {
+            1.0
+        };
+        let exp_field = (bits >> self.man_bits) & self.exp_max_field();
+        let mantissa = bits & ((1u64 << self.man_bits) - 1);
+
+        let magnitude = if exp_field == self.exp_max_field() {
+            if mantissa == 0 {
+                f64::INFINITY
+            } else {
+                f64::NAN
+            }
+        } else if exp_field == 0 {
+            scale(mantissa as f64, 1 - self.bias() - self.man_bits as i32)
+        } else {
+            scale(
+                (mantissa | 1 << self.man_bits) as f64,
+                exp_field as i32 - self.bias() - self.man_bits as i32,
+            )
+        };
+        sign * magnitude
+    }
+
+    /// Largest finite bit pattern of this format with the given sign.
+    fn max_finite(self, sign: u64) -> u64 {
+        sign | (self.exp_max_field() - 1) << self.man_bits | ((1u64 << self.man_bits) - 1)
+    }
+
+    /// Encodes `x` in this format, rounding in the given direction.
+    ///
+    /// # Arguments
+    /// - `x`: The value to encode.
+    /// - `mode`: The rounding direction.
+    ///
+    /// # Returns
+    /// The bit pattern in the low `bits()` bits. NaN becomes a quiet NaN.
+    pub fn encode(self, x: f64, mode: RoundingMode) -> u64 {
+        let sign = if x.is_sign_negative() {
+            1u64 << (self.exp_bits + self.man_bits)
+        } else {
+            0
+        };
+        let inf = sign | self.exp_max_field() << self.man_bits;
+        if x.is_nan() {
+            return inf | 1 << (self.man_bits - 1);
+        }
+        if x.is_infinite() {
+            return inf;
+        }
+        if x == 0.0 {
+            return sign;
+        }
+
+        // |x| = m * 2^e exactly
+        let raw = x.abs().to_bits();
+        let raw_exp = (raw >> 52) as i32;
+        let (m, e) = if raw_exp == 0 {
+            (raw & ((1 << 52) - 1), -1074)
+        } else {
+            (raw & ((1 << 52) - 1) | 1 << 52, raw_exp - 1075)
+        };
+        let msb = 63 - m.leading_zeros() as i32 + e;
+
+        // Quantum of the target at this magnitude
+        let emin = 1 - self.bias();
+        let q = msb.max(emin) - self.man_bits as i32;
+        let (mut n, remainder, half, exact) = if e >= q {
+            ((m as u128) << (e - q), 0u128, 1u128, true)
+        } else {
+            let shift = (q - e) as u32;
+            if shift >= 127 {
+                (0, 1, 2, false)
+            } else {
+                let rem = (m as u128) & ((1u128 << shift) - 1);
+                ((m as u128) >> shift, rem, 1u128 << (shift - 1), rem == 0)
+            }
+        };
+
+        let negative = sign != 0;
+        let round_away = !exact
+            && match mode {
+                RoundingMode::NearestEven => remainder > half || (remainder == half && n & 1 == 1),
+                RoundingMode::TowardZero => false,
+                RoundingMode::Up => !negative,
+                RoundingMode::Down => negative,
+            };
+        if round_away {
+            n += 1;
+        }
+
+        let mut q = q;
+        if n >> (self.man_bits + 1) != 0 {
+            n >>= 1;
+            q += 1;
+        }
+
+        if n >> self.man_bits == 0 {
+            // Subnormal (or rounded to zero)
+            return sign | n as u64;
+        }
+        let exp_field = (q + self.man_bits as i32 + self.bias()) as u64;
+        if exp_field >= self.exp_max_field() {
+            let to_inf = match mode {
+                RoundingMode::NearestEven => true,
+                RoundingMode::TowardZero => false,
+                RoundingMode::Up => !negative,
+                RoundingMode::Down => negative,
+            };
+            return if to_inf { inf } else { self.max_finite(sign) };
+        }
+        sign | exp_field << self.man_bits | (n as u64 & ((1u64 << self.man_bits) - 1))
+    }
+
+    /// Rounds the exact value `s + e` (with `|e|` below half an ULP of `s` in
+    /// binary64) to this format with a single rounding.
+    ///
+    /// `s` is first rounded to odd in binary64, which keeps the sticky
+    /// information needed for a correct second rounding into any format at
+    /// least two bits narrower than binary64.
+    fn round_exact_sum(self, s: f64, e: f64) -> u64 {
+        let odd = if e != 0.0 && e.is_finite() && s.to_bits() & 1 == 0 {
+            if e > 0.0 {
+                next_up_f64(s)
+            } else {
+                next_down_f64(s)
+            }
+        } else {
+            s
+        };
+        self.encode(odd, RoundingMode::NearestEven)
+    }
+
+    /// Negates a bit pattern of this format by flipping its sign bit.
+    pub fn neg(self, bits: u64) -> u64 {
+        bits ^ (1 << (self.bits() - 1))
+    }
+
+    /// Returns the bit pattern of the smallest value of this format greater
+    /// than the one `bits` encodes.
+    ///
+    /// NaN and `+Inf` are returned unchanged; both zeros step to the smallest
+    /// positive subnormal, the largest negative subnormal steps to `-0` and
+    /// the largest finite value steps to `+Inf`.
+    pub fn next_up(self, bits: u64) -> u64 {
+        let sign_bit = 1u64 << (self.bits() - 1);
+        let magnitude = bits & (sign_bit - 1);
+        let inf = self.exp_max_field() << self.man_bits;
+        if magnitude > inf || bits == inf {
+            return bits;
+        }
+        if magnitude == 0 {
+            return 1;
+        }
+        if bits & sign_bit == 0 {
+            bits + 1
+        } else {
+            bits - 1
+        }
+    }
+
+    /// Returns the bit pattern of the largest value of this format less than
+    /// the one `bits` encodes; the mirror image of [`next_up`](Self::next_up).
+    pub fn next_down(self, bits: u64) -> u64 {
+        self.neg(self.next_up(self.neg(bits)))
+    }
+
+    /// Returns the unit in the last place of the value `bits` encodes: the
+    /// gap to the next value of larger magnitude, or to the next smaller one
+    /// for the largest finite value.
+    ///
+    /// # Returns
+    /// The gap as `f64`, or NaN for NaN and infinities.
+    pub fn ulp(self, bits: u64) -> f64 {
+        let magnitude = bits & ((1u64 << (self.bits() - 1)) - 1);
+        let up = self.next_up(magnitude);
+        let inf = self.exp_max_field() << self.man_bits;
+        let (low, high) = if up == inf && magnitude != inf {
+            (magnitude - 1, magnitude)
+        } else {
+            (magnitude, up)
+        };
+        self.decode(high) - self.decode(low)
+    }
+
+    /// Adds two bit patterns of this format with round-to-nearest-even.
+    pub fn add(self, a: u64, b: u64) -> u64 {
+        let (a, b) = (self.decode(a), self.decode(b));
+        if self == FloatFormat::DOUBLE {
+            return (a + b).to_bits();
+        }
+        let s = a + b;
+        let bb = s - a;
+        self.round_exact_sum(s, (a - (s - bb)) + (b - bb))
+    }
+
+    /// Multiplies two bit patterns of this format with round-to-nearest-even.
+    pub fn mul(self, a: u64, b: u64) -> u64 {
+        let (a, b) = (self.decode(a), self.decode(b));
+        if self == FloatFormat::DOUBLE {
+            return (a * b).to_bits();
+        }
+        // exact in binary64 for formats with at most 26 significand bits
+        self.encode(a * b, RoundingMode::NearestEven)
+    }
+
+    /// Computes `a * b + c` on bit patterns of this format with a single
+    /// round-to-nearest-even, as a hardware FMA does.
+    pub fn fused_mul_add(self, a: u64, b: u64, c: u64) -> u64 {
+        let (a, b, c) = (self.decode(a), self.decode(b), self.decode(c));
+        if self == FloatFormat::DOUBLE {
+            return a.mul_add(b, c).to_bits();
+        }
+        // a * b is exact in binary64; TwoSum recovers the error of adding c
+        let p = a * b;
+        let s = p + c;
+        let bb = s - p;
+        self.round_exact_sum(s, (p - (s - bb)) + (c - bb))
+    }
+
+    /// Computes `a * b + c` as a rounded multiply followed by a rounded add.
+    pub fn separate_mul_add(self, a: u64, b: u64, c: u64) -> u64 {
+        self.add(self.mul(a, b), c)
+    }
+
+    /// Encodes `x` with stochastic rounding.
+    ///
+    /// The result is the round-up neighbour with probability proportional to
+    /// the distance of `x` from the round-down neighbour, which makes the
+    /// rounding unbiased in expectation.
+    ///
+    /// # Arguments
+    /// - `x`: The value to encode.
+    /// - `u`: A uniform random sample in `[0, 1)`.
+    pub fn encode_stochastic(self, x: f64, u: f64) -> u64 {
+        let down = self.encode(x, RoundingMode::Down);
+        let up = self.encode(x, RoundingMode::Up);
+        if down == up {
+            return down;
+        }
+        let (lo, hi) = (self.decode(down), self.decode(up));
+        let p_up = (x - lo) / (hi - lo);
+        if u < p_up {
+            up
+        } else {
+            down
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: [FloatFormat; 5] = [
+        FloatFormat::E5M2,
+        FloatFormat::HALF,
+        FloatFormat::BFLOAT16,
+        FloatFormat::SINGLE,
+        FloatFormat::DOUBLE,
+    ];
+
+    #[test]
+    fn steps_match_native_floats() {
+        for x in [1.0f32, -1.0, 0.1, -3.5e-40, f32::MAX, f32::MIN_POSITIVE] {
+            let bits = x.to_bits() as u64;
+            assert_eq!(
+                FloatFormat::SINGLE.next_up(bits),
+                next_up_f32(x).to_bits() as u64
+            );
+            assert_eq!(
+                FloatFormat::SINGLE.next_down(bits),
+                next_down_f32(x).to_bits() as u64
+            );
+        }
+        for x in [1.0f64, -1.0, 0.1, -5e-324, f64::MAX, f64::MIN_POSITIVE] {
+            assert_eq!(
+                FloatFormat::DOUBLE.next_up(x.to_bits()),
+                next_up_f64(x).to_bits()
+            );
+            assert_eq!(
+                FloatFormat::DOUBLE.next_down(x.to_bits()),
+                next_down_f64(x).to_bits()
+            );
+        }
+    }
+
+    #[test]
+    fn steps_across_zero() {
+        for format in FORMATS {
+            let sign = 1u64 << (format.bits() - 1);
+            assert_eq!(format.next_up(0), 1);
+            assert_eq!(format.next_up(sign), 1);
+            assert_eq!(format.next_down(0), sign | 1);
+            assert_eq!(format.next_down(sign), sign | 1);
+            assert_eq!(format.next_up(sign | 1), sign);
+            assert_eq!(format.next_down(1), 0);
+        }
+        assert_eq!(next_up_f64(-0.0), 5e-324);
+        assert_eq!(next_down_f64(0.0), -5e-324);
+        assert_eq!(next_up_f32(0.0).to_bits(), 1);
+    }
+
+    #[test]
+    fn steps_at_infinities_and_nan() {
+        for format in FORMATS {
+            let sign = 1u64 << (format.bits() - 1);
+            let inf = format.encode(f64::INFINITY, RoundingMode::NearestEven);
+            let max = inf - 1;
+            assert_eq!(format.next_up(max), inf);
+            assert_eq!(format.next_up(inf), inf);
+            assert_eq!(format.next_down(inf), max);
+            assert_eq!(format.next_up(sign | inf), sign | max);
+            assert_eq!(format.next_down(sign | inf), sign | inf);
+            assert_eq!(format.next_up(inf + 1), inf + 1);
+        }
+        assert_eq!(next_up_f64(f64::MAX), f64::INFINITY);
+        assert_eq!(next_up_f64(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_down_f64(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        assert_eq!(next_up_f64(f64::NEG_INFINITY), f64::MIN);
+        assert!(next_up_f64(f64::NAN).is_nan());
+        assert!(next_down_f32(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn ulp_of_normals_subnormals_and_extremes() {
+        let double = FloatFormat::DOUBLE;
+        assert_eq!(double.ulp(1.0f64.to_bits()), f64::EPSILON);
+        assert_eq!(double.ulp((-1.0f64).to_bits()), f64::EPSILON);
+        assert_eq!(double.ulp(0), 5e-324);
+        assert_eq!(double.ulp(f64::MIN_POSITIVE.to_bits()), 5e-324);
+        assert_eq!(double.ulp(f64::MAX.to_bits()), 2f64.powi(971));
+        assert!(double.ulp(f64::INFINITY.to_bits()).is_nan());
+        assert!(double.ulp(f64::NAN.to_bits()).is_nan());
+
+        let half = FloatFormat::HALF;
+        assert_eq!(half.ulp(0x3C00), 2f64.powi(-10));
+        assert_eq!(half.ulp(0x0001), 2f64.powi(-24));
+        assert_eq!(half.ulp(0x7BFF), 32.0);
+        assert_eq!(
+            FloatFormat::SINGLE.ulp(1.0f32.to_bits() as u64),
+            f32::EPSILON as f64
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Scripted Bit Operations
+//!
+//! [`BitOp`] names one of the transformations offered by the main panel's
+//! buttons, so a sequence of them can be recorded, stored, sent to the
+//! backend in one call and replayed at any width. Operations are applied
+//! through [`SizedBitArray`], so a position or mask outside the width is an
+//! error rather than a silent no-op.
+
+#[cfg(feature = "alloc")]
+use crate::bitvec::BitVec;
+use crate::sized::{BitError, SizedBitArray};
+use crate::Width;
+use core::fmt;
+
+/// A single transformation of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitOp {
+    /// Sets the bit at a position to 1.
+    Set(u32),
+    /// Clears the bit at a position.
+    Clear(u32),
+    /// Flips the bit at a position.
+    Toggle(u32),
+    /// Logical shift towards the most significant bit.
+    Shl(u32),
+    /// Logical shift towards the least significant bit.
+    Shr(u32),
+    /// Arithmetic shift towards the least significant bit.
+    Sar(u32),
+    /// Rotate towards the most significant bit.
+    Rotl(u32),
+    /// Rotate towards the least significant bit.
+    Rotr(u32),
+    /// Bitwise AND with a mask.
+    And(u64),
+    /// Bitwise OR with a mask.
+    Or(u64),
+    /// Bitwise XOR with a mask.
+    Xor(u64),
+    /// Inverts every bit of the width.
+    Not,
+    /// Clears every bit.
+    ClearAll,
+    /// Sets every bit of the width.
+    SetAll,
+    /// Reverses the bytes within each lane of the given size.
+    SwapBytes(Width),
+    /// Reverses the bytes of the whole width.
+    ReverseBytes,
+    /// Reverses the bits of the whole width.
+    ReverseBits,
+}
+
+impl fmt::Display for BitOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitOp::Set(pos) => write!(f, "set {}", pos),
+            BitOp::Clear(pos) => write!(f, "clear {}", pos),
+            BitOp::Toggle(pos) => write!(f, "toggle {}", pos),
+            BitOp::Shl(n) => write!(f, "shl {}", n),
+            BitOp::Shr(n) => write!(f, "shr {}", n),
+            BitOp::Sar(n) => write!(f, "sar {}", n),
+            BitOp::Rotl(n) => write!(f, "rotl {}", n),
+            BitOp::Rotr(n) => write!(f, "rotr {}", n),
+            BitOp::And(mask) => write!(f, "and {:#X}", mask),
+            BitOp::Or(mask) => write!(f, "or {:#X}", mask),
+            BitOp::Xor(mask) => write!(f, "xor {:#X}", mask),
+            BitOp::Not => f.write_str("not"),
+            BitOp::ClearAll => f.write_str("clear all"),
+            BitOp::SetAll => f.write_str("set all"),
+            BitOp::SwapBytes(lane) => write!(f, "swap{}", lane.bits()),
+            BitOp::ReverseBytes => f.write_str("reverse bytes"),
+            BitOp::ReverseBits => f.write_str("reverse bits"),
+        }
+    }
+}
+
+/// Checks that a mask has no bits set above `bits`.
+fn check_mask(mask: u64, bits: u32) -> Result<u64, BitError> {
+    if bits < 64 && mask >> bits != 0 {
+        Err(BitError::Overflow { bits })
+    } else {
+        Ok(mask)
+    }
+}
+
+/// Checks that a byte-swap lane fits in `bits`.
+fn check_lane(lane: Width, bits: u32) -> Result<Width, BitError> {
+    if lane.bits() <= bits {
+        Ok(lane)
+    } else {
+        Err(BitError::InvalidWidth(lane.bits()))
+    }
+}
+
+impl SizedBitArray {
+    /// Applies `op` to the value.
+    ///
+    /// # Returns
+    /// - [`BitError::OutOfRange`] for a bit position outside the width,
+    ///   [`BitError::Overflow`] for a mask wider than it, or
+    ///   [`BitError::InvalidWidth`] for a swap lane wider than it; the
+    ///   value is left unchanged.
+    pub fn apply(&mut self, op: BitOp) -> Result<(), BitError> {
+        let width = self.width();
+        let mut bits = self.value();
+        match op {
+            BitOp::Set(pos) => return self.set_bit(pos),
+            BitOp::Clear(pos) => return self.clear_bit(pos),
+            BitOp::Toggle(pos) => return self.toggle_bit(pos),
+            BitOp::Shl(n) => bits.shl(n, width),
+            BitOp::Shr(n) => bits.shr(n, width),
+            BitOp::Sar(n) => bits.sar(n, width),
+            BitOp::Rotl(n) => bits.rotl(n, width),
+            BitOp::Rotr(n) => bits.rotr(n, width),
+            BitOp::And(mask) => bits.0 &= check_mask(mask, width.bits())?,
+            BitOp::Or(mask) => bits.0 |= check_mask(mask, width.bits())?,
+            BitOp::Xor(mask) => bits.0 ^= check_mask(mask, width.bits())?,
+            BitOp::Not => bits.0 = !bits.0 & width.mask(),
+            BitOp::ClearAll => bits.0 = 0,
+            BitOp::SetAll => bits.0 = width.mask(),
+            BitOp::SwapBytes(lane) => bits.swap_bytes(check_lane(lane, width.bits())?),
+            BitOp::ReverseBytes => bits.swap_bytes(width),
+            BitOp::ReverseBits => bits.reverse_bits(width),
+        }
+        self.set(bits.0)
+    }
+
+    /// Applies `ops` in order.
+    ///
+    /// # Returns
+    /// - The index of the first operation that failed and its error; the
+    ///   operations before it stay applied.
+    pub fn apply_all(&mut self, ops: &[BitOp]) -> Result<(), (usize, BitError)> {
+        ops.iter()
+            .enumerate()
+            .try_for_each(|(i, &op)| self.apply(op).map_err(|e| (i, e)))
+    }
+}
+
+/// Applies `op` to a value wider than 64 bits; masks act on its least
+/// significant word.
+///
+/// # Returns
+/// - The same errors as [`SizedBitArray::apply`], with the value unchanged.
+#[cfg(feature = "alloc")]
+pub fn apply_wide(value: &mut BitVec, op: BitOp) -> Result<(), BitError> {
+    let len = value.len();
+    let check_pos = |pos: u32| {
+        if (pos as usize) < len {
+            Ok(pos as usize)
+        } else {
+            Err(BitError::OutOfRange {
+                pos,
+                width: len as u32,
+            })
+        }
+    };
+    match op {
+        BitOp::Set(pos) => value.set_bit(check_pos(pos)?),
+        BitOp::Clear(pos) => value.clear_bit(check_pos(pos)?),
+        BitOp::Toggle(pos) => value.toggle_bit(check_pos(pos)?),
+        BitOp::Shl(n) => value.shift_left(n as usize),
+        BitOp::Shr(n) => value.shift_right(n as usize),
+        BitOp::Sar(n) => value.shift_right_arithmetic(n as usize),
+        BitOp::Rotl(n) => value.rotate_left(n as usize),
+        BitOp::Rotr(n) => value.rotate_right(n as usize),
+        BitOp::And(mask) => value.set_word(0, value.word(0) & mask),
+        BitOp::Or(mask) => value.set_word(0, value.word(0) | mask),
+        BitOp::Xor(mask) => value.set_word(0, value.word(0) ^ mask),
+        BitOp::Not => value.invert(),
+        BitOp::ClearAll => value.clear(),
+        BitOp::SetAll => value.set_all(),
+        BitOp::SwapBytes(lane) => {
+            let lane = check_lane(lane, len as u32)?;
+            value.swap_bytes(lane.bits() as usize)
+        }
+        BitOp::ReverseBytes => value.swap_bytes(len),
+        BitOp::ReverseBits => value.reverse_bits(),
+    }
+    Ok(())
+}
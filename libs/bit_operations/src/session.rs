@@ -0,0 +1,90 @@
+//! Saved Sessions
+//!
+//! The state of a viewer session in a form both the viewer and the desktop
+//! backend understand: the current value, the workspace slots, the loaded
+//! register map, the undo history and the watch expressions. With the
+//! `serde` feature a [`Session`] can be written to and read from a JSON file;
+//! fields added in later versions default when missing, so older files still
+//! load.
+
+use crate::bitvec::BitVec;
+use crate::expr::Watch;
+use crate::register_map::RegisterMap;
+use crate::Width;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A value with the bit size and input mode it was viewed at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedValue {
+    /// The value; its length is the bit size's width.
+    pub value: BitVec,
+    /// Selected bit size.
+    pub bit_size: Width,
+    /// Whether the value was entered and shown as a float.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub float: bool,
+}
+
+/// A named workspace slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedSlot {
+    /// Slot name, e.g. `"A"`.
+    pub name: String,
+    /// The value kept in the slot, or `None` for an empty slot.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub value: Option<SavedValue>,
+}
+
+/// A saved viewer session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    /// Format version; see [`Session::VERSION`].
+    pub version: u32,
+    /// The value being edited.
+    pub current: SavedValue,
+    /// Workspace slots in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slots: Vec<SavedSlot>,
+    /// Index of the slot being edited.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub active_slot: usize,
+    /// Register description labelling the grid, if one was loaded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub register_map: Option<RegisterMap>,
+    /// Undo history, oldest first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub history: Vec<SavedValue>,
+    /// Index of the current entry in `history`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub history_cursor: usize,
+    /// Whether the session is saved automatically when the app exits.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub autosave: bool,
+    /// Watch expressions in display order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub watches: Vec<Watch>,
+}
+
+impl Session {
+    /// Current format version.
+    pub const VERSION: u32 = 1;
+
+    /// Creates a session holding only `current`.
+    pub fn new(current: SavedValue) -> Self {
+        Self {
+            version: Self::VERSION,
+            current,
+            slots: Vec::new(),
+            active_slot: 0,
+            register_map: None,
+            history: Vec::new(),
+            history_cursor: 0,
+            autosave: false,
+            watches: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Viewer Settings
+//!
+//! Preferences kept between runs of the viewer: the colour theme, the order
+//! and visibility of its panel sections, the bit size and input mode it
+//! starts at, the case of hex digits and the watch list. With the `serde`
+//! feature [`Settings`] are stored as JSON; fields added in later versions
+//! default when missing, so older settings still load.
+
+use crate::expr::Watch;
+use crate::Width;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Colour theme of the viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Theme {
+    /// Light text on a dark background.
+    #[default]
+    Dark,
+    /// Dark text on a light background.
+    Light,
+}
+
+impl Theme {
+    /// All themes in menu order.
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    /// Returns the theme's name, e.g. `"dark"`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+        }
+    }
+}
+
+/// Position and visibility of one panel section, in display order within
+/// [`Settings::sections`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section {
+    /// Identifier of the section, e.g. `"ieee754"`.
+    pub id: String,
+    /// Whether the section is shown.
+    pub visible: bool,
+}
+
+/// Saved viewer preferences.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Settings {
+    /// Colour theme.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub theme: Theme,
+    /// Panel sections in display order; sections missing from the list are
+    /// shown after the listed ones.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sections: Vec<Section>,
+    /// Bit size selected on startup.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bit_size: Width,
+    /// Whether the viewer starts in float mode.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub float: bool,
+    /// Whether hex digits are shown in lowercase.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lowercase_hex: bool,
+    /// Watch expressions, in list order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub watches: Vec<Watch>,
+}
+
+impl Settings {
+    /// Returns the sections named in `ids` in display order: the saved ones
+    /// first, keeping their order and visibility, then the rest of `ids`,
+    /// shown, in the order given. Saved sections not in `ids` are dropped.
+    pub fn arrange(&self, ids: &[&str]) -> Vec<Section> {
+        let mut sections: Vec<Section> = Vec::with_capacity(ids.len());
+        for section in &self.sections {
+            if ids.contains(&section.id.as_str()) && !sections.iter().any(|s| s.id == section.id) {
+                sections.push(section.clone());
+            }
+        }
+        for &id in ids {
+            if !sections.iter().any(|s| s.id == id) {
+                sections.push(Section {
+                    id: id.into(),
+                    visible: true,
+                });
+            }
+        }
+        sections
+    }
+
+    /// Moves the section at index `from` to index `to`, shifting the
+    /// sections in between; out-of-range indices leave the order unchanged.
+    pub fn move_section(&mut self, from: usize, to: usize) {
+        if from < self.sections.len() && to < self.sections.len() {
+            let section = self.sections.remove(from);
+            self.sections.insert(to, section);
+        }
+    }
+}
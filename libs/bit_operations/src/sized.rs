@@ -0,0 +1,335 @@
+//! Width-Aware Bit Arrays
+//!
+//! [`SizedBitArray`] pairs a [`BitArray`] with its [`Width`] and checks every
+//! bit position, field and value against it. Where the plain `BitArray`
+//! methods panic on a position past bit 63 or silently drop bits above the
+//! width, these return a [`BitError`] that can be shown to the user.
+
+use crate::parse::{parse_i64, parse_u64, NumberError};
+use crate::{BitArray, Width};
+use core::fmt;
+use core::ops::Range;
+
+/// Error produced by a [`SizedBitArray`] operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitError {
+    /// The width is not 8, 16, 32 or 64 bits.
+    InvalidWidth(u32),
+    /// A bit position at or beyond the width.
+    OutOfRange {
+        /// The position asked for.
+        pos: u32,
+        /// The width in bits.
+        width: u32,
+    },
+    /// A field that is empty, reversed or ends past the width.
+    InvalidField {
+        /// First bit of the field.
+        start: u32,
+        /// One past the last bit of the field.
+        end: u32,
+        /// The width in bits.
+        width: u32,
+    },
+    /// A value with bits set above the `bits` it has to fit in.
+    Overflow {
+        /// The number of bits available.
+        bits: u32,
+    },
+    /// Text that is not a number.
+    Parse(NumberError),
+}
+
+impl fmt::Display for BitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitError::InvalidWidth(bits) => write!(f, "unsupported bit size {}", bits),
+            BitError::OutOfRange { pos, width } => {
+                write!(f, "bit {} is outside the {}-bit value", pos, width)
+            }
+            BitError::InvalidField { start, end, width } => write!(
+                f,
+                "field {}..{} is not within the {}-bit value",
+                start, end, width
+            ),
+            BitError::Overflow { bits } => write!(f, "value does not fit in {} bits", bits),
+            BitError::Parse(e) => write!(f, "not a number: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BitError {}
+
+impl From<NumberError> for BitError {
+    fn from(e: NumberError) -> Self {
+        BitError::Parse(e)
+    }
+}
+
+/// A [`BitArray`] whose bits above its width are always clear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizedBitArray {
+    value: BitArray,
+    width: Width,
+}
+
+impl BitArray {
+    /// Checks the value against a width of `bits` bits.
+    ///
+    /// # Returns
+    /// - `Ok(sized)`, [`BitError::InvalidWidth`] for widths other than 8,
+    ///   16, 32 and 64 bits, or [`BitError::Overflow`] if bits above the
+    ///   width are set.
+    pub fn with_width(self, bits: u32) -> Result<SizedBitArray, BitError> {
+        let width = Width::from_bits(bits)
+            .filter(|w| w.bits() <= 64)
+            .ok_or(BitError::InvalidWidth(bits))?;
+        let mut sized = SizedBitArray {
+            value: BitArray::new(),
+            width,
+        };
+        sized.set(self.0)?;
+        Ok(sized)
+    }
+}
+
+impl SizedBitArray {
+    /// Parses an unsigned value, as [`parse_u64`] does, into a width of
+    /// `bits` bits.
+    ///
+    /// # Arguments
+    /// - `input`: The text, e.g. `0xFF` or `255`.
+    /// - `radix`: The radix used when the input has no prefix.
+    /// - `bits`: The width in bits.
+    pub fn from_str_radix(input: &str, radix: u32, bits: u32) -> Result<Self, BitError> {
+        let mut sized = BitArray::new().with_width(bits)?;
+        sized.set(parse_u64(input, radix)?)?;
+        Ok(sized)
+    }
+
+    /// Parses a signed decimal value, as [`parse_i64`] does, into its two's
+    /// complement encoding at a width of `bits` bits.
+    pub fn from_signed_str(input: &str, bits: u32) -> Result<Self, BitError> {
+        let mut sized = BitArray::new().with_width(bits)?;
+        sized.set_signed(parse_i64(input, 10)?)?;
+        Ok(sized)
+    }
+
+    /// Returns the bits.
+    pub fn value(&self) -> BitArray {
+        self.value
+    }
+
+    /// Returns the width.
+    pub fn width(&self) -> Width {
+        self.width
+    }
+
+    fn bits(&self) -> u32 {
+        self.width.bits()
+    }
+
+    fn check_bit(&self, pos: u32) -> Result<u8, BitError> {
+        if pos < self.bits() {
+            Ok(pos as u8)
+        } else {
+            Err(BitError::OutOfRange {
+                pos,
+                width: self.bits(),
+            })
+        }
+    }
+
+    fn check_field(&self, range: &Range<u32>) -> Result<Range<u8>, BitError> {
+        if range.start < range.end && range.end <= self.bits() {
+            Ok(range.start as u8..range.end as u8)
+        } else {
+            Err(BitError::InvalidField {
+                start: range.start,
+                end: range.end,
+                width: self.bits(),
+            })
+        }
+    }
+
+    /// Replaces the value.
+    ///
+    /// # Returns
+    /// - [`BitError::Overflow`] if `value` has bits set above the width;
+    ///   the value is left unchanged.
+    pub fn set(&mut self, value: u64) -> Result<(), BitError> {
+        if value & !self.width.mask() != 0 {
+            return Err(BitError::Overflow { bits: self.bits() });
+        }
+        self.value = BitArray(value);
+        Ok(())
+    }
+
+    /// Replaces the value with the two's complement encoding of `value`.
+    ///
+    /// # Returns
+    /// - [`BitError::Overflow`] if `value` is outside the signed range of
+    ///   the width.
+    pub fn set_signed(&mut self, value: i64) -> Result<(), BitError> {
+        let bits = BitArray::from_signed(value, self.width);
+        if bits.as_signed(self.width) != value {
+            return Err(BitError::Overflow { bits: self.bits() });
+        }
+        self.value = bits;
+        Ok(())
+    }
+
+    /// Returns the value as a two's complement signed integer.
+    pub fn as_signed(&self) -> i64 {
+        self.value.as_signed(self.width)
+    }
+
+    /// Returns the bit at `pos`.
+    pub fn get_bit(&self, pos: u32) -> Result<bool, BitError> {
+        Ok(self.value.get_bit(self.check_bit(pos)?))
+    }
+
+    /// Sets the bit at `pos` to 1.
+    pub fn set_bit(&mut self, pos: u32) -> Result<(), BitError> {
+        let pos = self.check_bit(pos)?;
+        self.value.set_bit(pos);
+        Ok(())
+    }
+
+    /// Clears the bit at `pos`.
+    pub fn clear_bit(&mut self, pos: u32) -> Result<(), BitError> {
+        let pos = self.check_bit(pos)?;
+        self.value.clear_bit(pos);
+        Ok(())
+    }
+
+    /// Flips the bit at `pos`.
+    pub fn toggle_bit(&mut self, pos: u32) -> Result<(), BitError> {
+        let pos = self.check_bit(pos)?;
+        self.value.toggle_bit(pos);
+        Ok(())
+    }
+
+    /// Extracts the bits in `range`, end exclusive, shifted down to bit 0.
+    pub fn get_field(&self, range: Range<u32>) -> Result<u64, BitError> {
+        Ok(self.value.get_field(self.check_field(&range)?))
+    }
+
+    /// Replaces the bits in `range`, end exclusive, with `value`.
+    ///
+    /// # Returns
+    /// - [`BitError::Overflow`] if `value` is wider than the field, or
+    ///   [`BitError::InvalidField`] for a field outside the width.
+    pub fn set_field(&mut self, range: Range<u32>, value: u64) -> Result<(), BitError> {
+        let field = self.check_field(&range)?;
+        let len = range.end - range.start;
+        if len < 64 && value >> len != 0 {
+            return Err(BitError::Overflow { bits: len });
+        }
+        self.value.set_field(field, value);
+        Ok(())
+    }
+}
+
+impl From<SizedBitArray> for BitArray {
+    fn from(sized: SizedBitArray) -> Self {
+        sized.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_within_width() {
+        let mut sized = BitArray(0xAB).with_width(8).unwrap();
+        assert_eq!(sized.width(), Width::W8);
+        assert_eq!(sized.value(), BitArray(0xAB));
+        sized.set_bit(7).unwrap();
+        sized.clear_bit(0).unwrap();
+        sized.toggle_bit(2).unwrap();
+        assert_eq!(sized.value(), BitArray(0xAE));
+        assert_eq!(sized.get_bit(3), Ok(true));
+        sized.set_field(4..8, 0x5).unwrap();
+        assert_eq!(sized.get_field(4..8), Ok(0x5));
+        assert_eq!(BitArray::from(sized), BitArray(0x5E));
+
+        let mut full = BitArray(u64::MAX).with_width(64).unwrap();
+        full.set_field(0..64, 0x0123_4567_89AB_CDEF).unwrap();
+        assert_eq!(full.get_field(0..64), Ok(0x0123_4567_89AB_CDEF));
+
+        let parsed = SizedBitArray::from_str_radix("0xBEEF", 16, 16).unwrap();
+        assert_eq!(parsed.value(), BitArray(0xBEEF));
+        let negative = SizedBitArray::from_signed_str("-128", 8).unwrap();
+        assert_eq!(negative.value(), BitArray(0x80));
+        assert_eq!(negative.as_signed(), -128);
+    }
+
+    #[test]
+    fn rejects_positions_outside_width() {
+        let mut sized = BitArray(0).with_width(16).unwrap();
+        let out = BitError::OutOfRange { pos: 16, width: 16 };
+        assert_eq!(sized.get_bit(16), Err(out));
+        assert_eq!(sized.set_bit(16), Err(out));
+        assert_eq!(sized.clear_bit(16), Err(out));
+        assert_eq!(sized.toggle_bit(16), Err(out));
+        assert_eq!(
+            sized.get_bit(u32::MAX),
+            Err(BitError::OutOfRange {
+                pos: u32::MAX,
+                width: 16
+            })
+        );
+        assert_eq!(
+            sized.get_field(8..17),
+            Err(BitError::InvalidField {
+                start: 8,
+                end: 17,
+                width: 16
+            })
+        );
+        assert_eq!(
+            sized.set_field(4..4, 0),
+            Err(BitError::InvalidField {
+                start: 4,
+                end: 4,
+                width: 16
+            })
+        );
+        assert_eq!(sized.value(), BitArray(0));
+    }
+
+    #[test]
+    fn rejects_values_wider_than_width() {
+        assert_eq!(
+            BitArray(0x100).with_width(8),
+            Err(BitError::Overflow { bits: 8 })
+        );
+        assert_eq!(BitArray(0).with_width(12), Err(BitError::InvalidWidth(12)));
+        assert_eq!(
+            BitArray(0).with_width(128),
+            Err(BitError::InvalidWidth(128))
+        );
+
+        let mut sized = BitArray(0x12).with_width(8).unwrap();
+        assert_eq!(sized.set(0x1FF), Err(BitError::Overflow { bits: 8 }));
+        assert_eq!(sized.set_signed(128), Err(BitError::Overflow { bits: 8 }));
+        assert_eq!(sized.set_signed(-129), Err(BitError::Overflow { bits: 8 }));
+        assert_eq!(
+            sized.set_field(0..4, 0x10),
+            Err(BitError::Overflow { bits: 4 })
+        );
+        assert_eq!(sized.value(), BitArray(0x12));
+
+        assert_eq!(
+            SizedBitArray::from_str_radix("0x1_0000", 16, 16),
+            Err(BitError::Overflow { bits: 16 })
+        );
+        assert_eq!(
+            SizedBitArray::from_str_radix("0xZZ", 16, 16),
+            Err(BitError::Parse(NumberError::InvalidDigit('Z')))
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Code Snippets
+//!
+//! Source code literals for a value, ready to paste into C, Rust or Python:
+//! an integer constant of the value's width, a float constant at the sizes
+//! the language has a float type for, and byte array initializers in both
+//! byte orders. Widths without a native integer type (256 bits in all
+//! languages but Python) are written as arrays of 64-bit words, least
+//! significant first.
+
+use crate::bitvec::BitVec;
+use crate::parse::group_digits;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Language a snippet is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    /// C99 with `<stdint.h>`.
+    C,
+    /// Rust.
+    Rust,
+    /// Python 3.
+    Python,
+}
+
+impl Language {
+    /// All languages in display order.
+    pub const ALL: [Language; 3] = [Language::C, Language::Rust, Language::Python];
+
+    /// Returns the language's display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::C => "C",
+            Language::Rust => "Rust",
+            Language::Python => "Python",
+        }
+    }
+}
+
+/// A generated snippet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snippet {
+    /// What the snippet declares, e.g. `"Bytes (big-endian)"`.
+    pub label: String,
+    /// The source code.
+    pub code: String,
+}
+
+/// Returns the value's hex digits, zero-padded to its full width.
+fn hex(value: &BitVec) -> String {
+    let digits = value.to_str_radix(16).to_uppercase();
+    format!("{:0>width$}", digits, width = value.len().div_ceil(4))
+}
+
+/// Formats a list of `0x` items separated by commas.
+fn hex_list<T: core::fmt::UpperHex>(items: impl Iterator<Item = T>, digits: usize) -> String {
+    let items: Vec<String> = items
+        .map(|item| format!("0x{:0digits$X}", item, digits = digits))
+        .collect();
+    items.join(", ")
+}
+
+/// Returns the unsigned integer type name of a `bits`-wide value, if the
+/// language has one.
+fn int_type(language: Language, bits: usize) -> Option<String> {
+    match (language, bits) {
+        (Language::C, 8 | 16 | 32 | 64) => Some(format!("uint{}_t", bits)),
+        (Language::C, 128) => Some("unsigned __int128".into()),
+        (Language::Rust, 8 | 16 | 32 | 64 | 128) => Some(format!("u{}", bits)),
+        _ => None,
+    }
+}
+
+/// Writes an integer constant holding `value`.
+///
+/// # Arguments
+/// - `name`: Identifier for the constant; uppercased for C macros and Rust
+///   constants.
+pub fn integer(language: Language, value: &BitVec, name: &str) -> String {
+    let upper = name.to_uppercase();
+    let bits = value.len();
+    let words = hex_list(value.words().iter(), 16);
+    match language {
+        Language::C => match int_type(language, bits) {
+            Some(ty) if bits == 128 => {
+                let literal = format!(
+                    "(((unsigned __int128)0x{:016X}ull << 64) | 0x{:016X}ull)",
+                    value.word(1),
+                    value.word(0)
+                );
+                format!(
+                    "#define {} {}\nstatic const {} {} = {};",
+                    upper, literal, ty, name, literal
+                )
+            }
+            Some(ty) => {
+                let suffix = if bits == 64 { "ull" } else { "u" };
+                let literal = format!("0x{}{}", hex(value), suffix);
+                format!(
+                    "#define {} {}\nstatic const {} {} = {};",
+                    upper, literal, ty, name, literal
+                )
+            }
+            None => format!(
+                "/* least significant word first */\nstatic const uint64_t {}[{}] = {{ {} }};",
+                name,
+                value.words().len(),
+                words
+            ),
+        },
+        Language::Rust => match int_type(language, bits) {
+            Some(ty) => format!(
+                "const {}: {} = 0x{};",
+                upper,
+                ty,
+                group_digits(&hex(value), 4, '_')
+            ),
+            None => format!(
+                "// least significant word first\nconst {}: [u64; {}] = [{}];",
+                upper,
+                value.words().len(),
+                words
+            ),
+        },
+        Language::Python => format!("{} = 0x{}", name, group_digits(&hex(value), 4, '_')),
+    }
+}
+
+/// Writes a float constant with the bit pattern of `value`.
+///
+/// # Returns
+/// - `None` when the language has no float type of the value's width: C
+///   and Rust cover 32 and 64 bits, Python also 16 bits through `struct`.
+pub fn float(language: Language, value: &BitVec, name: &str) -> Option<String> {
+    let bits = value.len();
+    let word = value.word(0);
+    let (ty, text) = match bits {
+        32 => ("f32", format!("{:?}", f32::from_bits(word as u32))),
+        64 => ("f64", format!("{:?}", f64::from_bits(word))),
+        16 if language == Language::Python => ("f16", String::new()),
+        _ => return None,
+    };
+    Some(match language {
+        Language::C => {
+            let (c_ty, suffix) = if bits == 32 {
+                ("float", "f")
+            } else {
+                ("double", "")
+            };
+            let literal = match text.as_str() {
+                "NaN" => "NAN /* <math.h>, payload not kept */".into(),
+                "inf" => "INFINITY /* <math.h> */".into(),
+                "-inf" => "-INFINITY /* <math.h> */".into(),
+                _ => format!("{}{}", text, suffix),
+            };
+            format!("static const {} {}_f = {};", c_ty, name, literal)
+        }
+        Language::Rust => format!(
+            "const {}_F: {} = {}::from_bits(0x{}); // {}",
+            name.to_uppercase(),
+            ty,
+            ty,
+            group_digits(&hex(value), 4, '_'),
+            text
+        ),
+        Language::Python => {
+            let format = match bits {
+                16 => 'e',
+                32 => 'f',
+                _ => 'd',
+            };
+            let comment = if text.is_empty() {
+                String::new()
+            } else {
+                format!("  # {}", text)
+            };
+            format!(
+                "import struct\n{}_f = struct.unpack('>{}', bytes.fromhex('{}'))[0]{}",
+                name,
+                format,
+                hex(value),
+                comment
+            )
+        }
+    })
+}
+
+/// Writes a byte array initializer holding `value`.
+///
+/// # Arguments
+/// - `big_endian`: Whether the most significant byte comes first; the
+///   array is named with a `_be` or `_le` suffix to match.
+pub fn byte_array(language: Language, value: &BitVec, name: &str, big_endian: bool) -> String {
+    let bytes = if big_endian {
+        value.to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    };
+    let order = if big_endian { "be" } else { "le" };
+    let list = hex_list(bytes.iter(), 2);
+    match language {
+        Language::C => format!(
+            "static const uint8_t {}_{}[{}] = {{ {} }};",
+            name,
+            order,
+            bytes.len(),
+            list
+        ),
+        Language::Rust => format!(
+            "const {}_{}: [u8; {}] = [{}];",
+            name.to_uppercase(),
+            order.to_uppercase(),
+            bytes.len(),
+            list
+        ),
+        Language::Python => format!("{}_{} = bytes([{}])", name, order, list),
+    }
+}
+
+/// Writes every snippet available for `value` in `language`: the integer,
+/// the float where there is one, and both byte orders.
+pub fn snippets(language: Language, value: &BitVec, name: &str) -> Vec<Snippet> {
+    let snippet = |label: &str, code: String| Snippet {
+        label: label.into(),
+        code,
+    };
+    let mut out = Vec::with_capacity(4);
+    out.push(snippet("Integer", integer(language, value, name)));
+    if let Some(code) = float(language, value, name) {
+        out.push(snippet("Float", code));
+    }
+    out.push(snippet(
+        "Bytes (big-endian)",
+        byte_array(language, value, name, true),
+    ));
+    out.push(snippet(
+        "Bytes (little-endian)",
+        byte_array(language, value, name, false),
+    ));
+    out
+}
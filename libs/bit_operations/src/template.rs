@@ -0,0 +1,942 @@
+//! Binary Template Language
+//!
+//! A small 010-Editor-style language describing a byte layout as a sequence
+//! of typed fields. Templates are parsed once and then decoded against any
+//! byte buffer into a tree of [`Node`]s.
+//!
+//! ```text
+//! endian big;            // default byte order for multi-byte fields
+//! u8    kind;
+//! u16le length;          // explicit byte order suffix
+//! u8    payload[length]; // array sized by an earlier field
+//! u8    version : 4;     // bitfields share one storage unit
+//! u8    ihl     : 4;
+//! if (kind == 2) {
+//!     u32 crc;
+//! } else {
+//!     u8 pad[3];
+//! }
+//! ```
+//!
+//! Supported types are `u8`..`u64` and `i8`..`i64`, optionally suffixed with
+//! `le`/`be`. A scalar field followed by `: n` is an `n`-bit bitfield:
+//! consecutive bitfields of the same type and byte order are taken from one
+//! value of that type while they fit, starting at its most significant bit
+//! in big endian order and at its least significant bit in little endian
+//! order, as in 010 Editor. Conditions compare an earlier field or a literal
+//! with `==`, `!=`, `<`, `<=`, `>`, `>=` or test bits with `&`; a bare
+//! operand is true when non-zero. Field values are compared as their raw
+//! unsigned bits. `//` starts a comment.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// Byte order of a multi-byte field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Endian {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+/// Error produced while parsing or decoding a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    /// 1-based source line the error refers to.
+    pub line: usize,
+    /// 1-based source column (in characters) the error refers to; decoding
+    /// errors point at the start of the field or `if`.
+    pub column: usize,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl TemplateError {
+    fn at(pos: Pos, message: impl Into<String>) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// 1-based source position of a token or item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TemplateError {}
+
+/// Decoded value of a template field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeValue {
+    /// Value of an unsigned scalar field.
+    Unsigned(u64),
+    /// Value of a signed scalar field.
+    Signed(i64),
+    /// Elements of an array field.
+    Array(Vec<Node>),
+}
+
+/// A decoded field and its location in the input buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    /// Field name; array elements are named `name[i]`.
+    pub name: String,
+    /// Byte offset of the field.
+    pub offset: usize,
+    /// Size of the field in bytes; for a bitfield, of its storage unit.
+    pub size: usize,
+    /// Bits of the storage unit's value holding a bitfield, counted from
+    /// its least significant bit; `None` for whole fields.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bits: Option<Range<u32>>,
+    /// Decoded value.
+    pub value: NodeValue,
+}
+
+/// Scalar field type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldType {
+    bytes: usize,
+    signed: bool,
+    endian: Option<Endian>,
+}
+
+impl FieldType {
+    fn parse(name: &str) -> Option<Self> {
+        let (base, endian) = if let Some(base) = name.strip_suffix("le") {
+            (base, Some(Endian::Little))
+        } else if let Some(base) = name.strip_suffix("be") {
+            (base, Some(Endian::Big))
+        } else {
+            (name, None)
+        };
+        let signed = match base.as_bytes().first()? {
+            b'u' => false,
+            b'i' => true,
+            _ => return None,
+        };
+        let bytes = match &base[1..] {
+            "8" => 1,
+            "16" => 2,
+            "32" => 4,
+            "64" => 8,
+            _ => return None,
+        };
+        Some(Self {
+            bytes,
+            signed,
+            endian,
+        })
+    }
+}
+
+/// Literal or reference to an earlier field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operand {
+    Literal(u64),
+    Field(String),
+}
+
+/// Comparison operator in a condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    lhs: Operand,
+    rhs: Option<(CmpOp, Operand)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Endian(Endian),
+    Field {
+        pos: Pos,
+        ty: FieldType,
+        name: String,
+        count: Option<Operand>,
+        bits: Option<u32>,
+    },
+    If {
+        pos: Pos,
+        cond: Condition,
+        then: Vec<Item>,
+        otherwise: Vec<Item>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Punct(&'static str),
+}
+
+/// Splits the source into `(position, token)` pairs, followed by the
+/// position just past the last token.
+fn tokenize(src: &str) -> Result<(Vec<(Pos, Token)>, Pos), TemplateError> {
+    const PUNCT: [&str; 15] = [
+        "==", "!=", "<=", ">=", "<", ">", "&", ";", ":", "[", "]", "(", ")", "{", "}",
+    ];
+    let mut tokens = Vec::new();
+    let mut end = Pos { line: 1, column: 1 };
+
+    for (idx, raw_line) in src.lines().enumerate() {
+        let line = idx + 1;
+        let text = raw_line.split("//").next().unwrap_or("");
+        let mut rest = text.trim_start();
+        let pos = |rest: &str| Pos {
+            line,
+            column: text[..text.len() - rest.len()].chars().count() + 1,
+        };
+
+        while !rest.is_empty() {
+            let start = pos(rest);
+            if let Some(p) = PUNCT.iter().find(|p| rest.starts_with(**p)) {
+                tokens.push((start, Token::Punct(p)));
+                rest = &rest[p.len()..];
+            } else {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    let c = rest.chars().next().unwrap();
+                    return Err(TemplateError::at(
+                        start,
+                        format!("unexpected character '{}'", c),
+                    ));
+                }
+                let word = &rest[..len];
+                let token = if word.as_bytes()[0].is_ascii_digit() {
+                    Token::Number(parse_number(word).ok_or_else(|| {
+                        TemplateError::at(start, format!("invalid number '{}'", word))
+                    })?)
+                } else {
+                    Token::Ident(word.to_string())
+                };
+                tokens.push((start, token));
+                rest = &rest[len..];
+            }
+            end = pos(rest);
+            rest = rest.trim_start();
+        }
+    }
+    Ok((tokens, end))
+}
+
+/// Parses a decimal, `0x`, `0b` or `0o` literal.
+fn parse_number(word: &str) -> Option<u64> {
+    let word = word.replace('_', "");
+    let (digits, radix) = match word.get(..2) {
+        Some("0x") | Some("0X") => (&word[2..], 16),
+        Some("0b") | Some("0B") => (&word[2..], 2),
+        Some("0o") | Some("0O") => (&word[2..], 8),
+        _ => (&word[..], 10),
+    };
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Recursive-descent parser over the token stream.
+struct Parser {
+    tokens: Vec<(Pos, Token)>,
+    /// Position just past the last token, where running out of input is
+    /// reported.
+    end: Pos,
+    pos: usize,
+}
+
+impl Parser {
+    /// Returns the position of the next token.
+    fn here(&self) -> Pos {
+        self.tokens.get(self.pos).map_or(self.end, |(pos, _)| *pos)
+    }
+
+    fn error(&self, message: impl Into<String>) -> TemplateError {
+        TemplateError::at(self.here(), message)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, t)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: &'static str) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &'static str) -> Result<(), TemplateError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", punct)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, TemplateError> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(name),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected identifier"))
+            }
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, TemplateError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Operand::Literal(n)),
+            Some(Token::Ident(name)) => Ok(Operand::Field(name)),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected number or field name"))
+            }
+        }
+    }
+
+    fn items(&mut self, in_block: bool) -> Result<Vec<Item>, TemplateError> {
+        let mut items = Vec::new();
+        loop {
+            match self.peek() {
+                None if in_block => return Err(self.error("unterminated block, expected '}'")),
+                None => return Ok(items),
+                Some(Token::Punct("}")) if in_block => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => items.push(self.item()?),
+            }
+        }
+    }
+
+    fn block(&mut self) -> Result<Vec<Item>, TemplateError> {
+        self.expect("{")?;
+        self.items(true)
+    }
+
+    fn item(&mut self) -> Result<Item, TemplateError> {
+        let pos = self.here();
+        let keyword = self.ident()?;
+
+        match keyword.as_str() {
+            "endian" => {
+                let endian = match self.ident()?.as_str() {
+                    "little" => Endian::Little,
+                    "big" => Endian::Big,
+                    other => return Err(self.error(format!("unknown endianness '{}'", other))),
+                };
+                self.expect(";")?;
+                Ok(Item::Endian(endian))
+            }
+            "if" => {
+                self.expect("(")?;
+                let lhs = self.operand()?;
+                let op = [
+                    ("==", CmpOp::Eq),
+                    ("!=", CmpOp::Ne),
+                    ("<=", CmpOp::Le),
+                    (">=", CmpOp::Ge),
+                    ("<", CmpOp::Lt),
+                    (">", CmpOp::Gt),
+                    ("&", CmpOp::And),
+                ]
+                .into_iter()
+                .find(|(p, _)| self.eat(p))
+                .map(|(_, op)| op);
+                let rhs = match op {
+                    Some(op) => Some((op, self.operand()?)),
+                    None => None,
+                };
+                self.expect(")")?;
+                let then = self.block()?;
+                let otherwise = if self.peek() == Some(&Token::Ident("else".to_string())) {
+                    self.pos += 1;
+                    if self.peek() == Some(&Token::Ident("if".to_string())) {
+                        alloc::vec![self.item()?]
+                    } else {
+                        self.block()?
+                    }
+                } else {
+                    Vec::new()
+                };
+                Ok(Item::If {
+                    pos,
+                    cond: Condition { lhs, rhs },
+                    then,
+                    otherwise,
+                })
+            }
+            type_name => {
+                let ty = FieldType::parse(type_name).ok_or_else(|| {
+                    TemplateError::at(pos, format!("unknown type '{}'", type_name))
+                })?;
+                let name = self.ident()?;
+                let count = if self.eat("[") {
+                    let count = self.operand()?;
+                    self.expect("]")?;
+                    Some(count)
+                } else {
+                    None
+                };
+                let bits = if self.eat(":") {
+                    if count.is_some() {
+                        return Err(self.error(format!("array '{}' cannot be a bitfield", name)));
+                    }
+                    let max = ty.bytes as u64 * 8;
+                    match self.peek() {
+                        Some(&Token::Number(n)) if (1..=max).contains(&n) => {
+                            self.pos += 1;
+                            Some(n as u32)
+                        }
+                        _ => {
+                            return Err(self
+                                .error(format!("expected a bitfield width of 1 to {} bits", max)))
+                        }
+                    }
+                } else {
+                    None
+                };
+                self.expect(";")?;
+                Ok(Item::Field {
+                    pos,
+                    ty,
+                    name,
+                    count,
+                    bits,
+                })
+            }
+        }
+    }
+}
+
+/// Value the current run of bitfields is taken from.
+struct Unit {
+    bytes: usize,
+    endian: Endian,
+    offset: usize,
+    raw: u64,
+    /// Bits already handed out.
+    used: u32,
+}
+
+/// Decoding state: cursor, current byte order and field values seen so far.
+struct Decoder<'a> {
+    data: &'a [u8],
+    offset: usize,
+    endian: Endian,
+    values: BTreeMap<String, u64>,
+    unit: Option<Unit>,
+}
+
+/// Sign-extends the low `bits` bits of `raw` into a [`NodeValue`].
+fn scalar_value(raw: u64, bits: u32, signed: bool) -> NodeValue {
+    if signed {
+        let shift = 64 - bits;
+        NodeValue::Signed(((raw << shift) as i64) >> shift)
+    } else {
+        NodeValue::Unsigned(raw)
+    }
+}
+
+impl Decoder<'_> {
+    fn resolve(&self, operand: &Operand, pos: Pos) -> Result<u64, TemplateError> {
+        match operand {
+            Operand::Literal(n) => Ok(*n),
+            Operand::Field(name) => self
+                .values
+                .get(name)
+                .copied()
+                .ok_or_else(|| TemplateError::at(pos, format!("unknown field '{}'", name))),
+        }
+    }
+
+    /// Reads `ty.bytes` bytes in the field's byte order, returning their
+    /// offset and value.
+    fn read(&mut self, ty: FieldType, name: &str, pos: Pos) -> Result<(usize, u64), TemplateError> {
+        let end = self.offset + ty.bytes;
+        let bytes = self.data.get(self.offset..end).ok_or_else(|| {
+            TemplateError::at(
+                pos,
+                format!(
+                    "field '{}' needs {} bytes at offset {}, only {} available",
+                    name,
+                    ty.bytes,
+                    self.offset,
+                    self.data.len().saturating_sub(self.offset)
+                ),
+            )
+        })?;
+        let raw = match ty.endian.unwrap_or(self.endian) {
+            Endian::Little => bytes
+                .iter()
+                .rev()
+                .fold(0u64, |acc, &b| (acc << 8) | b as u64),
+            Endian::Big => bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64),
+        };
+        let offset = self.offset;
+        self.offset = end;
+        Ok((offset, raw))
+    }
+
+    /// Reads one scalar field, returning its node and raw value.
+    fn scalar(
+        &mut self,
+        ty: FieldType,
+        name: String,
+        pos: Pos,
+    ) -> Result<(Node, u64), TemplateError> {
+        let (offset, raw) = self.read(ty, &name, pos)?;
+        let node = Node {
+            name,
+            offset,
+            size: ty.bytes,
+            bits: None,
+            value: scalar_value(raw, ty.bytes as u32 * 8, ty.signed),
+        };
+        Ok((node, raw))
+    }
+
+    /// Takes a `bits`-wide bitfield from the current storage unit, starting
+    /// a new unit when the type or byte order differs or the bits do not
+    /// fit, and returns its node and raw value.
+    fn bitfield(
+        &mut self,
+        ty: FieldType,
+        name: String,
+        bits: u32,
+        pos: Pos,
+    ) -> Result<(Node, u64), TemplateError> {
+        let endian = ty.endian.unwrap_or(self.endian);
+        let total = ty.bytes as u32 * 8;
+        let mut unit = match self.unit.take() {
+            Some(unit)
+                if unit.bytes == ty.bytes && unit.endian == endian && unit.used + bits <= total =>
+            {
+                unit
+            }
+            _ => {
+                let (offset, raw) = self.read(ty, &name, pos)?;
+                Unit {
+                    bytes: ty.bytes,
+                    endian,
+                    offset,
+                    raw,
+                    used: 0,
+                }
+            }
+        };
+        let shift = match endian {
+            Endian::Little => unit.used,
+            Endian::Big => total - unit.used - bits,
+        };
+        let raw = (unit.raw >> shift) & (u64::MAX >> (64 - bits));
+        let node = Node {
+            name,
+            offset: unit.offset,
+            size: unit.bytes,
+            bits: Some(shift..shift + bits),
+            value: scalar_value(raw, bits, ty.signed),
+        };
+        unit.used += bits;
+        self.unit = Some(unit);
+        Ok((node, raw))
+    }
+
+    fn run(&mut self, items: &[Item], out: &mut Vec<Node>) -> Result<(), TemplateError> {
+        for item in items {
+            match item {
+                Item::Endian(endian) => self.endian = *endian,
+                Item::Field {
+                    pos,
+                    ty,
+                    name,
+                    count: None,
+                    bits,
+                } => {
+                    let (node, raw) = match bits {
+                        Some(bits) => self.bitfield(*ty, name.clone(), *bits, *pos)?,
+                        None => {
+                            self.unit = None;
+                            self.scalar(*ty, name.clone(), *pos)?
+                        }
+                    };
+                    self.values.insert(name.clone(), raw);
+                    out.push(node);
+                }
+                Item::Field {
+                    pos,
+                    ty,
+                    name,
+                    count: Some(count),
+                    ..
+                } => {
+                    self.unit = None;
+                    let count = self.resolve(count, *pos)? as usize;
+                    let remaining = self.data.len().saturating_sub(self.offset);
+                    if count.saturating_mul(ty.bytes) > remaining {
+                        return Err(TemplateError::at(
+                            *pos,
+                            format!(
+                                "array '{}' of {} elements exceeds the {} remaining bytes",
+                                name, count, remaining
+                            ),
+                        ));
+                    }
+                    let offset = self.offset;
+                    let elements = (0..count)
+                        .map(|i| {
+                            self.scalar(*ty, format!("{}[{}]", name, i), *pos)
+                                .map(|(node, _)| node)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    out.push(Node {
+                        name: name.clone(),
+                        offset,
+                        size: self.offset - offset,
+                        bits: None,
+                        value: NodeValue::Array(elements),
+                    });
+                }
+                Item::If {
+                    pos,
+                    cond,
+                    then,
+                    otherwise,
+                } => {
+                    let lhs = self.resolve(&cond.lhs, *pos)?;
+                    let taken = match &cond.rhs {
+                        None => lhs != 0,
+                        Some((op, rhs)) => {
+                            let rhs = self.resolve(rhs, *pos)?;
+                            match op {
+                                CmpOp::Eq => lhs == rhs,
+                                CmpOp::Ne => lhs != rhs,
+                                CmpOp::Lt => lhs < rhs,
+                                CmpOp::Le => lhs <= rhs,
+                                CmpOp::Gt => lhs > rhs,
+                                CmpOp::Ge => lhs >= rhs,
+                                CmpOp::And => lhs & rhs != 0,
+                            }
+                        }
+                    };
+                    self.run(if taken { then } else { otherwise }, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A parsed binary template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    items: Vec<Item>,
+}
+
+impl Template {
+    /// Parses template source text.
+    ///
+    /// # Arguments
+    /// - `src`: The template definition.
+    ///
+    /// # Returns
+    /// - The parsed template, or the first syntax error.
+    pub fn parse(src: &str) -> Result<Self, TemplateError> {
+        let (tokens, end) = tokenize(src)?;
+        let mut parser = Parser {
+            tokens,
+            end,
+            pos: 0,
+        };
+        Ok(Self {
+            items: parser.items(false)?,
+        })
+    }
+
+    /// Decodes `data` according to the template.
+    ///
+    /// # Arguments
+    /// - `data`: The bytes to decode, starting at offset 0.
+    ///
+    /// # Returns
+    /// - The top-level fields in declaration order, or the first decoding error
+    ///   (missing data, unknown field reference).
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<Node>, TemplateError> {
+        let mut decoder = Decoder {
+            data,
+            offset: 0,
+            endian: Endian::Little,
+            values: BTreeMap::new(),
+            unit: None,
+        };
+        let mut nodes = Vec::new();
+        decoder.run(&self.items, &mut nodes)?;
+        Ok(nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn decode(src: &str, data: &[u8]) -> Vec<Node> {
+        Template::parse(src).unwrap().decode(data).unwrap()
+    }
+
+    /// Returns the value of the top-level field `name`
+    fn value<'a>(nodes: &'a [Node], name: &str) -> &'a NodeValue {
+        &nodes.iter().find(|n| n.name == name).unwrap().value
+    }
+
+    /// Returns the line, column and message of a parse error
+    fn parse_error(src: &str) -> (usize, usize, String) {
+        let err = Template::parse(src).unwrap_err();
+        (err.line, err.column, err.message)
+    }
+
+    #[test]
+    fn parses_valid_templates() {
+        let src = "endian big;            // default byte order
+u8    kind;
+u16le length;
+u8    payload[length];
+u8    version : 4;
+u8    ihl     : 4;
+if (kind == 2) {
+    u32 crc;
+} else if (kind & 0x80) {
+    i64be stamp;
+} else {
+    u8 pad[0b11];
+}";
+        assert!(Template::parse(src).is_ok());
+        assert!(Template::parse("").unwrap().decode(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reports_parse_errors_with_position() {
+        assert_eq!(
+            parse_error("u8 a;\nu17 b;"),
+            (2, 1, "unknown type 'u17'".into())
+        );
+        assert_eq!(
+            parse_error("u8 a;\nu8 $b;"),
+            (2, 4, "unexpected character '$'".into())
+        );
+        assert_eq!(
+            parse_error("u8 a[0xZZ];"),
+            (1, 6, "invalid number '0xZZ'".into())
+        );
+        // Running out of input points just past the last token
+        assert_eq!(
+            parse_error("u8 a;\n  u8 b   // comment"),
+            (2, 7, "expected ';'".into())
+        );
+        assert_eq!(
+            parse_error("if (a == 1) {\n  u8 b;\n"),
+            (2, 8, "unterminated block, expected '}'".into())
+        );
+        assert_eq!(
+            parse_error("u8 a[2] : 3;"),
+            (1, 11, "array 'a' cannot be a bitfield".into())
+        );
+        assert_eq!(
+            parse_error("u8 a : 9;"),
+            (1, 8, "expected a bitfield width of 1 to 8 bits".into())
+        );
+        assert_eq!(
+            Template::parse("\tu8 a;\n\tu8 1;").unwrap_err().to_string(),
+            "line 2, column 5: expected identifier"
+        );
+    }
+
+    #[test]
+    fn decodes_both_byte_orders() {
+        let nodes = decode(
+            "u16 a;\nu16be b;\nendian big;\nu32 c;\ni16le d;",
+            &[0x34, 0x12, 0x12, 0x34, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xFF],
+        );
+        assert_eq!(value(&nodes, "a"), &NodeValue::Unsigned(0x1234));
+        assert_eq!(value(&nodes, "b"), &NodeValue::Unsigned(0x1234));
+        assert_eq!(value(&nodes, "c"), &NodeValue::Unsigned(0xDEAD_BEEF));
+        assert_eq!(value(&nodes, "d"), &NodeValue::Signed(-2));
+        let c = &nodes[2];
+        assert_eq!((c.offset, c.size, c.bits.clone()), (4, 4, None));
+    }
+
+    #[test]
+    fn decodes_arrays_sized_by_fields() {
+        let nodes = decode("u8 n;\nu16 items[n];\nu8 tail;", &[2, 1, 0, 2, 0, 9]);
+        let items = &nodes[1];
+        assert_eq!((items.offset, items.size), (1, 4));
+        let NodeValue::Array(elements) = &items.value else {
+            panic!("items is not an array");
+        };
+        let decoded: Vec<_> = elements
+            .iter()
+            .map(|e| (e.name.as_str(), e.offset, e.value.clone()))
+            .collect();
+        assert_eq!(
+            decoded,
+            vec![
+                ("items[0]", 1, NodeValue::Unsigned(1)),
+                ("items[1]", 3, NodeValue::Unsigned(2)),
+            ]
+        );
+        assert_eq!(nodes[2].offset, 5);
+        assert_eq!(value(&nodes, "tail"), &NodeValue::Unsigned(9));
+
+        let empty = decode("u8 n;\nu32 items[n];", &[0]);
+        assert_eq!(value(&empty, "items"), &NodeValue::Array(Vec::new()));
+    }
+
+    #[test]
+    fn follows_conditions() {
+        let src = "u8 kind;\nif (kind == 1) {\n  u16 crc;\n} else if (kind & 0x80) {\n  u8 flag;\n} else {\n  u8 pad[2];\n}";
+        let names = |data: &[u8]| {
+            decode(src, data)
+                .into_iter()
+                .map(|n| n.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&[1, 0, 0]), ["kind", "crc"]);
+        assert_eq!(names(&[0x81, 0]), ["kind", "flag"]);
+        assert_eq!(names(&[2, 0, 0]), ["kind", "pad"]);
+    }
+
+    #[test]
+    fn decodes_big_endian_bitfields_from_the_top() {
+        // IPv4 version/IHL byte, then flags and fragment offset
+        let src = "endian big;
+u8  version : 4;
+u8  ihl     : 4;
+u16 flags   : 3;
+u16 frag    : 13;
+u8  ttl;
+if (version == 4) {
+    u8 ipv4;
+}";
+        let nodes = decode(src, &[0x45, 0x40, 0x01, 64, 0]);
+        let fields: Vec<_> = nodes
+            .iter()
+            .map(|n| (n.name.as_str(), n.offset, n.size, n.bits.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("version", 0, 1, Some(4..8)),
+                ("ihl", 0, 1, Some(0..4)),
+                ("flags", 1, 2, Some(13..16)),
+                ("frag", 1, 2, Some(0..13)),
+                ("ttl", 3, 1, None),
+                ("ipv4", 4, 1, None),
+            ]
+        );
+        assert_eq!(value(&nodes, "version"), &NodeValue::Unsigned(4));
+        assert_eq!(value(&nodes, "ihl"), &NodeValue::Unsigned(5));
+        assert_eq!(value(&nodes, "flags"), &NodeValue::Unsigned(2));
+        assert_eq!(value(&nodes, "frag"), &NodeValue::Unsigned(1));
+        assert_eq!(value(&nodes, "ttl"), &NodeValue::Unsigned(64));
+    }
+
+    #[test]
+    fn decodes_little_endian_bitfields_from_the_bottom() {
+        let src = "u8 lo : 3;\nu8 hi : 5;\ni8 s : 4;\nu8 next : 5;\nu16 w : 12;";
+        let nodes = decode(src, &[0b1010_1101, 0x0F, 0b0001_0011, 0x34, 0x12]);
+        assert_eq!(value(&nodes, "lo"), &NodeValue::Unsigned(0b101));
+        assert_eq!(value(&nodes, "hi"), &NodeValue::Unsigned(0b10101));
+        // A full unit starts a new one; signed bitfields are sign-extended
+        assert_eq!(nodes[2].offset, 1);
+        assert_eq!(value(&nodes, "s"), &NodeValue::Signed(-1));
+        // Bits that do not fit the rest of the unit start the next one
+        assert_eq!((nodes[3].offset, nodes[3].bits.clone()), (2, Some(0..5)));
+        assert_eq!(value(&nodes, "next"), &NodeValue::Unsigned(0b10011));
+        // A different type starts its own unit
+        assert_eq!((nodes[4].offset, nodes[4].size), (3, 2));
+        assert_eq!(value(&nodes, "w"), &NodeValue::Unsigned(0x234));
+
+        // Whole fields end the run of bitfields
+        let nodes = decode("u8 a : 4;\nu8 b;\nu8 c : 4;", &[0xAB, 0xCD, 0xEF]);
+        let offsets: Vec<_> = nodes.iter().map(|n| n.offset).collect();
+        assert_eq!(offsets, [0, 1, 2]);
+        assert_eq!(value(&nodes, "c"), &NodeValue::Unsigned(0xF));
+    }
+
+    #[test]
+    fn reports_data_shorter_than_the_template() {
+        let decode_error = |src: &str, data: &[u8]| {
+            let err = Template::parse(src).unwrap().decode(data).unwrap_err();
+            (err.line, err.column, err.message)
+        };
+        assert_eq!(
+            decode_error("u8 a;\nu32 b;", &[1, 2, 3]),
+            (
+                2,
+                1,
+                "field 'b' needs 4 bytes at offset 1, only 2 available".into()
+            )
+        );
+        assert_eq!(
+            decode_error("u8 n;\n  u8 data[n];", &[5, 1, 2]),
+            (
+                2,
+                3,
+                "array 'data' of 5 elements exceeds the 2 remaining bytes".into()
+            )
+        );
+        assert_eq!(
+            decode_error("u16 a : 4;", &[1]),
+            (
+                1,
+                1,
+                "field 'a' needs 2 bytes at offset 0, only 1 available".into()
+            )
+        );
+        assert_eq!(
+            decode_error("if (missing) {\n  u8 a;\n}", &[1]),
+            (1, 1, "unknown field 'missing'".into())
+        );
+    }
+}
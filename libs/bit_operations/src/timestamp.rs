@@ -0,0 +1,358 @@
+//! Unix Timestamps
+//!
+//! Conversion between Unix time (seconds or milliseconds since
+//! 1970-01-01T00:00:00Z, ignoring leap seconds) and proleptic Gregorian UTC
+//! dates, for reading raw time fields such as those in packet captures.
+//! Windows `FILETIME` and NTP seconds are converted the same way from their
+//! own epochs.
+
+use core::fmt;
+
+/// Resolution and epoch of a timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeUnit {
+    /// Whole seconds, as in `time_t`.
+    #[default]
+    Seconds,
+    /// Milliseconds, as in JavaScript and Java timestamps.
+    Millis,
+    /// 100-nanosecond intervals since 1601-01-01, as in Windows `FILETIME`.
+    FileTime,
+    /// Seconds since 1900-01-01, as in the integer part of an NTP timestamp.
+    Ntp,
+}
+
+/// Number of 100-nanosecond ticks per millisecond.
+const TICKS_PER_MILLI: i128 = 10_000;
+
+impl TimeUnit {
+    /// All units in display order.
+    pub const ALL: [TimeUnit; 4] = [
+        TimeUnit::Seconds,
+        TimeUnit::Millis,
+        TimeUnit::FileTime,
+        TimeUnit::Ntp,
+    ];
+
+    /// Returns whether values in this unit are stored signed; `FILETIME`
+    /// and NTP seconds are unsigned counts.
+    pub fn is_signed(self) -> bool {
+        matches!(self, TimeUnit::Seconds | TimeUnit::Millis)
+    }
+
+    /// Returns the number of 100-nanosecond ticks per unit.
+    fn ticks(self) -> i128 {
+        match self {
+            TimeUnit::Seconds | TimeUnit::Ntp => 10_000_000,
+            TimeUnit::Millis => TICKS_PER_MILLI,
+            TimeUnit::FileTime => 1,
+        }
+    }
+
+    /// Returns the unit's epoch in ticks relative to the Unix epoch.
+    fn epoch(self) -> i128 {
+        match self {
+            TimeUnit::Seconds | TimeUnit::Millis => 0,
+            TimeUnit::FileTime => -116_444_736_000_000_000,
+            TimeUnit::Ntp => -2_208_988_800 * 10_000_000,
+        }
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TimeUnit::Seconds => "s",
+            TimeUnit::Millis => "ms",
+            TimeUnit::FileTime => "FILETIME",
+            TimeUnit::Ntp => "NTP",
+        })
+    }
+}
+
+/// A UTC date and time with millisecond resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DateTime {
+    /// Year; 0 is 1 BC and negative years go further back.
+    pub year: i64,
+    /// Month, 1 to 12.
+    pub month: u8,
+    /// Day of the month, starting at 1.
+    pub day: u8,
+    /// Hour, 0 to 23.
+    pub hour: u8,
+    /// Minute, 0 to 59.
+    pub minute: u8,
+    /// Second, 0 to 59.
+    pub second: u8,
+    /// Millisecond, 0 to 999.
+    pub millis: u16,
+}
+
+/// Largest year magnitude accepted by [`DateTime::parse`], well beyond the
+/// range of 64-bit millisecond timestamps.
+const MAX_YEAR: u64 = 1_000_000_000_000;
+
+/// Returns the number of days in `month` of `year`.
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date, following
+/// Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a date to days since 1970-01-01; the inverse of
+/// [`civil_from_days`].
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+impl DateTime {
+    /// Converts a timestamp in the given unit, truncating below
+    /// milliseconds towards negative infinity.
+    pub fn from_unix(value: i64, unit: TimeUnit) -> Self {
+        let ticks = i128::from(value) * unit.ticks() + unit.epoch();
+        let millis = ticks.div_euclid(TICKS_PER_MILLI);
+        let days = millis.div_euclid(86_400_000) as i64;
+        let rest = millis.rem_euclid(86_400_000) as u32;
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            hour: (rest / 3_600_000) as u8,
+            minute: (rest / 60_000 % 60) as u8,
+            second: (rest / 1000 % 60) as u8,
+            millis: (rest % 1000) as u16,
+        }
+    }
+
+    /// Converts to a timestamp in the given unit, truncating milliseconds
+    /// towards negative infinity for whole seconds.
+    ///
+    /// # Returns
+    /// - `Some(value)`, or `None` if it does not fit in an `i64`.
+    pub fn to_unix(&self, unit: TimeUnit) -> Option<i64> {
+        let days = i128::from(days_from_civil(self.year, self.month, self.day));
+        let millis = days * 86_400_000
+            + i128::from(self.hour) * 3_600_000
+            + i128::from(self.minute) * 60_000
+            + i128::from(self.second) * 1000
+            + i128::from(self.millis);
+        let ticks = millis * TICKS_PER_MILLI - unit.epoch();
+        i64::try_from(ticks.div_euclid(unit.ticks())).ok()
+    }
+
+    /// Parses an ISO 8601 style UTC date such as `2024-02-29`,
+    /// `2024-02-29T13:45`, or `2024-02-29 13:45:07.250Z`. The date and time
+    /// may be separated by `T` or a space; the trailing `Z` is optional.
+    /// Years beyond ±10^12 are rejected.
+    ///
+    /// # Returns
+    /// - The date, or `None` for malformed text or out-of-range fields.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_suffix(['Z', 'z']).unwrap_or(text);
+        let (date, time) = match text.split_once(['T', 't', ' ']) {
+            Some((date, time)) => (date, Some(time.trim())),
+            None => (text, None),
+        };
+
+        // A leading `-` belongs to the year, not the separator
+        let (negative, date) = match date.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, date.strip_prefix('+').unwrap_or(date)),
+        };
+        let mut parts = date.splitn(3, '-');
+        let year: i64 = parts.next()?.parse().ok()?;
+        let month: u8 = parts.next()?.parse().ok()?;
+        let day: u8 = parts.next()?.parse().ok()?;
+        let year = if negative { -year } else { year };
+        if year.unsigned_abs() > MAX_YEAR
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+        {
+            return None;
+        }
+
+        let (mut hour, mut minute, mut second, mut millis) = (0, 0, 0, 0);
+        if let Some(time) = time {
+            let (hms, fraction) = match time.split_once('.') {
+                Some((hms, fraction)) => (hms, Some(fraction)),
+                None => (time, None),
+            };
+            let mut fields = hms.split(':');
+            hour = fields.next()?.parse().ok()?;
+            minute = fields.next()?.parse().ok()?;
+            if let Some(s) = fields.next() {
+                second = s.parse().ok()?;
+            }
+            if fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                return None;
+            }
+            if let Some(fraction) = fraction {
+                if fraction.is_empty() || fraction.len() > 3 {
+                    return None;
+                }
+                let digits: u16 = fraction.parse().ok()?;
+                millis = digits * 10u16.pow(3 - fraction.len() as u32);
+            }
+        }
+
+        Some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            millis,
+        })
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Formats as `YYYY-MM-DDTHH:MM:SSZ`, with `.mmm` before the `Z` when
+    /// the milliseconds are not zero.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}", -self.year)?;
+        } else {
+            write!(f, "{:04}", self.year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.millis != 0 {
+            write!(f, ".{:03}", self.millis)?;
+        }
+        f.write_str("Z")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> DateTime {
+        DateTime::parse(text).unwrap()
+    }
+
+    /// Checks that `value` in `unit` is `text` and converts back
+    fn assert_vector(value: i64, unit: TimeUnit, text: &str) {
+        let converted = DateTime::from_unix(value, unit);
+        assert_eq!(converted, date(text), "{} {}", value, unit);
+        assert_eq!(converted.to_unix(unit), Some(value), "{}", text);
+    }
+
+    #[test]
+    fn unix_epoch() {
+        let epoch = DateTime {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            millis: 0,
+        };
+        assert_eq!(DateTime::from_unix(0, TimeUnit::Seconds), epoch);
+        assert_eq!(DateTime::from_unix(0, TimeUnit::Millis), epoch);
+        assert_eq!(date("1970-01-01T00:00:00Z"), epoch);
+        assert_eq!(epoch.to_unix(TimeUnit::Seconds), Some(0));
+        assert_eq!(epoch.to_unix(TimeUnit::Millis), Some(0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn display() {
+        use alloc::string::ToString;
+
+        let show = |value, unit| DateTime::from_unix(value, unit).to_string();
+        assert_eq!(show(0, TimeUnit::Seconds), "1970-01-01T00:00:00Z");
+        assert_eq!(show(-1, TimeUnit::Millis), "1969-12-31T23:59:59.999Z");
+        assert_eq!(show(0, TimeUnit::FileTime), "1601-01-01T00:00:00Z");
+        assert_eq!(
+            show(-62_167_219_201, TimeUnit::Seconds),
+            "-0001-12-31T23:59:59Z"
+        );
+    }
+
+    #[test]
+    fn leap_days() {
+        assert_vector(951_782_400, TimeUnit::Seconds, "2000-02-29");
+        assert_vector(951_868_800, TimeUnit::Seconds, "2000-03-01");
+        assert_vector(
+            1_709_214_307_250,
+            TimeUnit::Millis,
+            "2024-02-29T13:45:07.250Z",
+        );
+        // 1900 is not a leap year, so February has 28 days
+        assert_eq!(DateTime::parse("1900-02-29"), None);
+        assert_vector(-2_203_977_600, TimeUnit::Seconds, "1900-02-28");
+        assert_vector(-2_203_891_200, TimeUnit::Seconds, "1900-03-01");
+        assert_eq!(DateTime::parse("2100-02-29"), None);
+        assert!(DateTime::parse("2400-02-29").is_some());
+    }
+
+    #[test]
+    fn negative_timestamps() {
+        assert_vector(-1, TimeUnit::Seconds, "1969-12-31T23:59:59");
+        assert_vector(-1, TimeUnit::Millis, "1969-12-31T23:59:59.999");
+        assert_vector(-86_400, TimeUnit::Seconds, "1969-12-31");
+        assert_vector(i32::MIN.into(), TimeUnit::Seconds, "1901-12-13T20:45:52");
+        assert_vector(i32::MAX.into(), TimeUnit::Seconds, "2038-01-19T03:14:07");
+        // Whole seconds round towards negative infinity
+        assert_eq!(
+            date("1969-12-31T23:59:59.500").to_unix(TimeUnit::Seconds),
+            Some(-1)
+        );
+        assert_eq!(DateTime::parse("-0001-01-01").map(|d| d.year), Some(-1));
+    }
+
+    #[test]
+    fn filetime_and_ntp_epochs() {
+        assert_vector(0, TimeUnit::FileTime, "1601-01-01");
+        assert_vector(116_444_736_000_000_000, TimeUnit::FileTime, "1970-01-01");
+        assert_vector(125_962_560_000_000_000, TimeUnit::FileTime, "2000-02-29");
+        // Ticks below a millisecond are truncated
+        assert_eq!(
+            DateTime::from_unix(116_444_736_000_009_999, TimeUnit::FileTime),
+            date("1970-01-01")
+        );
+        assert_vector(0, TimeUnit::Ntp, "1900-01-01");
+        assert_vector(2_208_988_800, TimeUnit::Ntp, "1970-01-01");
+        assert_vector(3_160_771_200, TimeUnit::Ntp, "2000-02-29");
+        assert_vector(u32::MAX.into(), TimeUnit::Ntp, "2036-02-07T06:28:15");
+        assert!(!TimeUnit::FileTime.is_signed() && !TimeUnit::Ntp.is_signed());
+        assert!(TimeUnit::Seconds.is_signed() && TimeUnit::Millis.is_signed());
+    }
+}
@@ -0,0 +1,150 @@
+//! UTF-16 and UTF-32 Text
+//!
+//! Decoding and encoding of byte sequences as UTF-16 or UTF-32 in either
+//! byte order. Decoding never fails: unpaired surrogates and values that are
+//! not Unicode scalar values come out as `Err(unit)` so callers can show a
+//! replacement character and flag the text as invalid.
+
+use core::fmt;
+
+/// A Unicode encoding with fixed-size code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Encoding {
+    /// UTF-16, least significant byte of each unit first.
+    Utf16Le,
+    /// UTF-16, most significant byte of each unit first.
+    Utf16Be,
+    /// UTF-32, least significant byte first.
+    Utf32Le,
+    /// UTF-32, most significant byte first.
+    Utf32Be,
+}
+
+impl Encoding {
+    /// All encodings in display order.
+    pub const ALL: [Encoding; 4] = [
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Utf32Le,
+        Encoding::Utf32Be,
+    ];
+
+    /// Returns the size of a code unit in bytes.
+    pub fn unit_bytes(self) -> usize {
+        match self {
+            Encoding::Utf16Le | Encoding::Utf16Be => 2,
+            Encoding::Utf32Le | Encoding::Utf32Be => 4,
+        }
+    }
+
+    fn is_big_endian(self) -> bool {
+        matches!(self, Encoding::Utf16Be | Encoding::Utf32Be)
+    }
+
+    /// Decodes `bytes`, ignoring a trailing partial code unit.
+    ///
+    /// # Returns
+    /// An iterator yielding `Ok(char)` for each character and `Err(unit)`
+    /// for each unpaired surrogate or out-of-range UTF-32 value.
+    pub fn decode(self, bytes: &[u8]) -> Decode<'_> {
+        Decode {
+            encoding: self,
+            bytes,
+        }
+    }
+
+    /// Encodes `text` into the start of `out`.
+    ///
+    /// # Returns
+    /// - `Some(len)` with the number of bytes written, or `None` if the
+    ///   encoded text does not fit in `out`.
+    pub fn encode(self, text: &str, out: &mut [u8]) -> Option<usize> {
+        let mut len = 0;
+        let mut units = [0u16; 2];
+        for c in text.chars() {
+            let mut write = |unit: u32| {
+                let bytes = self.unit_bytes();
+                let slot = out.get_mut(len..len + bytes)?;
+                for (i, b) in slot.iter_mut().enumerate() {
+                    let shift = if self.is_big_endian() {
+                        bytes - 1 - i
+                    } else {
+                        i
+                    };
+                    *b = (unit >> (8 * shift)) as u8;
+                }
+                len += bytes;
+                Some(())
+            };
+            match self {
+                Encoding::Utf16Le | Encoding::Utf16Be => {
+                    for &unit in c.encode_utf16(&mut units).iter() {
+                        write(u32::from(unit))?;
+                    }
+                }
+                Encoding::Utf32Le | Encoding::Utf32Be => write(u32::from(c))?,
+            }
+        }
+        Some(len)
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Utf32Le => "UTF-32LE",
+            Encoding::Utf32Be => "UTF-32BE",
+        })
+    }
+}
+
+/// Iterator over the characters of an encoded byte sequence, returned by
+/// [`Encoding::decode`].
+#[derive(Debug, Clone)]
+pub struct Decode<'a> {
+    encoding: Encoding,
+    bytes: &'a [u8],
+}
+
+impl Decode<'_> {
+    /// Reads the code unit at the front without consuming it.
+    fn peek(&self) -> Option<u32> {
+        let unit = self.bytes.get(..self.encoding.unit_bytes())?;
+        Some(if self.encoding.is_big_endian() {
+            unit.iter().fold(0, |acc, &b| acc << 8 | u32::from(b))
+        } else {
+            unit.iter().rev().fold(0, |acc, &b| acc << 8 | u32::from(b))
+        })
+    }
+
+    fn advance(&mut self) {
+        self.bytes = &self.bytes[self.encoding.unit_bytes()..];
+    }
+}
+
+impl Iterator for Decode<'_> {
+    type Item = Result<char, u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let unit = self.peek()?;
+        self.advance();
+        if self.encoding.unit_bytes() == 4 {
+            return Some(char::from_u32(unit).ok_or(unit));
+        }
+        Some(match unit {
+            0xD800..=0xDBFF => match self.peek() {
+                Some(low @ 0xDC00..=0xDFFF) => {
+                    self.advance();
+                    let c = 0x10000 + ((unit - 0xD800) << 10 | (low - 0xDC00));
+                    char::from_u32(c).ok_or(unit)
+                }
+                _ => Err(unit),
+            },
+            0xDC00..=0xDFFF => Err(unit),
+            _ => char::from_u32(unit).ok_or(unit),
+        })
+    }
+}
@@ -0,0 +1,250 @@
+//! LEB128 Variable-Length Integers
+//!
+//! Unsigned and signed LEB128 as used by DWARF, WebAssembly and Protocol
+//! Buffers: seven value bits per byte, least significant group first, with
+//! the top bit set on every byte but the last. Protocol Buffers' `sint`
+//! types first map signed values to unsigned with [`zigzag`] so small
+//! negative numbers stay short.
+
+use core::fmt;
+
+/// Longest encoding of a 64-bit value, in bytes.
+pub const MAX_LEN: usize = 10;
+
+/// An encoded varint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Varint {
+    bytes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl Varint {
+    /// Returns the encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+}
+
+/// Error produced while decoding a varint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VarintError {
+    /// The input ended before a byte without the continuation bit.
+    Truncated,
+    /// The encoded value does not fit in 64 bits.
+    Overflow,
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            VarintError::Truncated => "truncated varint",
+            VarintError::Overflow => "varint exceeds 64 bits",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {}
+
+/// Maps a signed value to unsigned so that small magnitudes stay small:
+/// 0, -1, 1, -2, ... become 0, 1, 2, 3, ...
+pub fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Reverses [`zigzag`].
+pub fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Encodes `value` as unsigned LEB128, e.g. 300 becomes `AC 02`.
+pub fn encode_unsigned(mut value: u64) -> Varint {
+    let mut varint = Varint {
+        bytes: [0; MAX_LEN],
+        len: 0,
+    };
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            varint.push(byte);
+            return varint;
+        }
+        varint.push(byte | 0x80);
+    }
+}
+
+/// Encodes `value` as signed LEB128, e.g. -2 becomes `7E`.
+pub fn encode_signed(mut value: i64) -> Varint {
+    let mut varint = Varint {
+        bytes: [0; MAX_LEN],
+        len: 0,
+    };
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let sign_bit = byte & 0x40 != 0;
+        if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+            varint.push(byte);
+            return varint;
+        }
+        varint.push(byte | 0x80);
+    }
+}
+
+/// Decodes unsigned LEB128 from the start of `bytes`.
+///
+/// # Returns
+/// - `Ok((value, len))` with the number of bytes read, or an error if the
+///   input ends mid-value or the value needs more than 64 bits.
+pub fn decode_unsigned(bytes: &[u8]) -> Result<(u64, usize), VarintError> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let payload = u64::from(byte & 0x7f);
+        if i >= MAX_LEN || (i == MAX_LEN - 1 && payload > 1) {
+            return Err(VarintError::Overflow);
+        }
+        value |= payload << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+/// Decodes signed LEB128 from the start of `bytes`.
+///
+/// # Returns
+/// - `Ok((value, len))` with the number of bytes read, or an error if the
+///   input ends mid-value or the value does not fit in an `i64`.
+pub fn decode_signed(bytes: &[u8]) -> Result<(i64, usize), VarintError> {
+    let mut value = 0i64;
+    for (i, &byte) in bytes.iter().enumerate() {
+        let payload = byte & 0x7f;
+        // The tenth byte carries bit 63; its other bits must repeat it
+        if i >= MAX_LEN || (i == MAX_LEN - 1 && payload != 0 && payload != 0x7f) {
+            return Err(VarintError::Overflow);
+        }
+        value |= i64::from(payload) << (7 * i);
+        if byte & 0x80 == 0 {
+            let shift = 7 * (i as u32 + 1);
+            if shift < 64 && payload & 0x40 != 0 {
+                value |= -1 << shift;
+            }
+            return Ok((value, i + 1));
+        }
+    }
+    Err(VarintError::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_round_trips() {
+        assert_eq!(encode_unsigned(0).as_bytes(), [0x00]);
+        assert_eq!(encode_unsigned(127).as_bytes(), [0x7F]);
+        assert_eq!(encode_unsigned(300).as_bytes(), [0xAC, 0x02]);
+        for value in [0, 1, 127, 128, 300, 1 << 35, u64::MAX - 1, u64::MAX] {
+            let varint = encode_unsigned(value);
+            assert_eq!(
+                decode_unsigned(varint.as_bytes()),
+                Ok((value, varint.as_bytes().len()))
+            );
+        }
+    }
+
+    #[test]
+    fn unsigned_max() {
+        let max = encode_unsigned(u64::MAX);
+        assert_eq!(
+            max.as_bytes(),
+            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+        );
+        assert_eq!(max.as_bytes().len(), MAX_LEN);
+    }
+
+    #[test]
+    fn signed_round_trips() {
+        assert_eq!(encode_signed(-2).as_bytes(), [0x7E]);
+        assert_eq!(encode_signed(63).as_bytes(), [0x3F]);
+        assert_eq!(encode_signed(64).as_bytes(), [0xC0, 0x00]);
+        assert_eq!(encode_signed(-64).as_bytes(), [0x40]);
+        assert_eq!(encode_signed(-65).as_bytes(), [0xBF, 0x7F]);
+        for value in [0, 1, -1, 63, -64, 64, -65, i64::MIN, i64::MAX] {
+            let varint = encode_signed(value);
+            assert_eq!(
+                decode_signed(varint.as_bytes()),
+                Ok((value, varint.as_bytes().len()))
+            );
+        }
+        assert_eq!(encode_signed(i64::MIN).as_bytes().len(), MAX_LEN);
+        assert_eq!(encode_signed(i64::MAX).as_bytes().len(), MAX_LEN);
+    }
+
+    #[test]
+    fn overflow_past_64_bits() {
+        // Tenth byte with more than bit 63 set
+        let mut bytes = [0xFF; 10];
+        bytes[9] = 0x02;
+        assert_eq!(decode_unsigned(&bytes), Err(VarintError::Overflow));
+        // Eleven bytes
+        let mut long = [0x80; 11];
+        long[10] = 0x00;
+        assert_eq!(decode_unsigned(&long), Err(VarintError::Overflow));
+        assert_eq!(decode_signed(&long), Err(VarintError::Overflow));
+        // Signed tenth byte whose bits do not all repeat the sign
+        let mut signed = [0x80; 10];
+        signed[9] = 0x01;
+        assert_eq!(decode_signed(&signed), Err(VarintError::Overflow));
+    }
+
+    #[test]
+    fn truncated_input() {
+        assert_eq!(decode_unsigned(&[]), Err(VarintError::Truncated));
+        assert_eq!(decode_unsigned(&[0x80]), Err(VarintError::Truncated));
+        assert_eq!(decode_unsigned(&[0xAC]), Err(VarintError::Truncated));
+        assert_eq!(decode_signed(&[0xFF, 0xFF]), Err(VarintError::Truncated));
+    }
+
+    #[test]
+    fn trailing_bytes_are_not_consumed() {
+        assert_eq!(decode_unsigned(&[0xAC, 0x02, 0xFF]), Ok((300, 2)));
+        assert_eq!(decode_signed(&[0x7E, 0x01]), Ok((-2, 1)));
+    }
+
+    #[test]
+    fn non_canonical_encodings_decode() {
+        // Padding with zero-payload continuation bytes is valid LEB128
+        assert_eq!(decode_unsigned(&[0x80, 0x00]), Ok((0, 2)));
+        assert_eq!(decode_unsigned(&[0xFF, 0x80, 0x00]), Ok((127, 3)));
+        assert_eq!(decode_signed(&[0xFF, 0x7F]), Ok((-1, 2)));
+        assert_eq!(decode_signed(&[0x80, 0x80, 0x00]), Ok((0, 3)));
+        // Ten-byte padding of a small value still fits
+        let mut padded = [0x80; 10];
+        padded[0] = 0x81;
+        padded[9] = 0x00;
+        assert_eq!(decode_unsigned(&padded), Ok((1, 10)));
+    }
+
+    #[test]
+    fn zigzag_mapping() {
+        assert_eq!([0, -1, 1, -2, 2].map(zigzag), [0, 1, 2, 3, 4]);
+        assert_eq!(zigzag(i64::MAX), u64::MAX - 1);
+        assert_eq!(zigzag(i64::MIN), u64::MAX);
+        for value in [0, -1, 1, i64::MIN, i64::MAX, i64::MIN + 1] {
+            assert_eq!(unzigzag(zigzag(value)), value);
+        }
+        assert_eq!(
+            decode_unsigned(encode_unsigned(zigzag(i64::MIN)).as_bytes()).map(|(v, _)| unzigzag(v)),
+            Ok(i64::MIN)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Bit Width Abstraction
+//!
+//! All width-dependent arithmetic (masking, digit counts, byte slicing) goes
+//! through [`Width`] so callers never hand-roll per-size match arms.
+//!
+//! Widths beyond 64 bits hold their full value in a
+//! [`BitVec`](crate::bitvec::BitVec); the `u64` helpers here then act on its
+//! least significant word.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Supported value widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Width {
+    /// 8-bit value.
+    W8,
+    /// 16-bit value.
+    W16,
+    /// 32-bit value.
+    W32,
+    /// 64-bit value.
+    #[default]
+    W64,
+    /// 128-bit value.
+    W128,
+    /// 256-bit value.
+    W256,
+}
+
+impl Width {
+    /// All widths in ascending order.
+    pub const ALL: [Width; 6] = [
+        Width::W8,
+        Width::W16,
+        Width::W32,
+        Width::W64,
+        Width::W128,
+        Width::W256,
+    ];
+
+    /// Looks up the width with the given number of bits.
+    ///
+    /// # Arguments
+    /// - `bits`: The width in bits.
+    ///
+    /// # Returns
+    /// - `Some(width)` for 8, 16, 32, 64, 128 or 256, `None` otherwise.
+    pub fn from_bits(bits: u32) -> Option<Self> {
+        match bits {
+            8 => Some(Width::W8),
+            16 => Some(Width::W16),
+            32 => Some(Width::W32),
+            64 => Some(Width::W64),
+            128 => Some(Width::W128),
+            256 => Some(Width::W256),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bits.
+    pub fn bits(self) -> u32 {
+        match self {
+            Width::W8 => 8,
+            Width::W16 => 16,
+            Width::W32 => 32,
+            Width::W64 => 64,
+            Width::W128 => 128,
+            Width::W256 => 256,
+        }
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(self) -> usize {
+        self.bits() as usize / 8
+    }
+
+    /// Returns the number of 64-bit words holding a full-width value.
+    pub fn word_count(self) -> usize {
+        self.bits().div_ceil(64) as usize
+    }
+
+    /// Returns the mask with the low `bits()` bits set, saturating at all 64
+    /// bits for wider widths.
+    pub fn mask(self) -> u64 {
+        u64::MAX >> (64 - self.bits().min(64))
+    }
+
+    /// Returns the number of hexadecimal digits needed for a full-width value.
+    pub fn hex_digits(self) -> usize {
+        self.bits() as usize / 4
+    }
+
+    /// Masks `value` down to this width.
+    pub fn truncate(self, value: u64) -> u64 {
+        value & self.mask()
+    }
+
+    /// Returns the active bytes of `value` in little-endian order,
+    /// zero-extended for widths beyond 64 bits.
+    #[cfg(feature = "alloc")]
+    pub fn le_bytes(self, value: u64) -> Vec<u8> {
+        let mut bytes = value.to_le_bytes().to_vec();
+        bytes.resize(self.bytes(), 0);
+        bytes
+    }
+
+    /// Returns the active bytes of `value` in big-endian order,
+    /// zero-extended for widths beyond 64 bits.
+    #[cfg(feature = "alloc")]
+    pub fn be_bytes(self, value: u64) -> Vec<u8> {
+        let mut bytes = self.le_bytes(value);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Assembles a value from little-endian bytes.
+    ///
+    /// # Returns
+    /// - `Some(value)` if exactly `bytes()` bytes were supplied and the value
+    ///   fits in a `u64`, `None` otherwise.
+    pub fn from_le_bytes(self, bytes: &[u8]) -> Option<u64> {
+        let (low, high) = bytes.split_at(bytes.len().min(8));
+        (bytes.len() == self.bytes() && high.iter().all(|&b| b == 0))
+            .then(|| low.iter().rev().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+    }
+
+    /// Assembles a value from big-endian bytes.
+    ///
+    /// # Returns
+    /// - `Some(value)` if exactly `bytes()` bytes were supplied and the value
+    ///   fits in a `u64`, `None` otherwise.
+    pub fn from_be_bytes(self, bytes: &[u8]) -> Option<u64> {
+        let (high, low) = bytes.split_at(bytes.len().saturating_sub(8));
+        (bytes.len() == self.bytes() && high.iter().all(|&b| b == 0))
+            .then(|| low.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+    }
+}
+
+impl fmt::Display for Width {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
@@ -0,0 +1,289 @@
+//! Headless `bitwise` Command
+//!
+//! Prints the viewer's conversions of a value as text, for CI servers and
+//! SSH sessions where the desktop app cannot run:
+//!
+//! ```text
+//! bitwise 0x7FC00000 --width 32 --float --field 12..20 --crc crc32
+//! echo 255 4096 | bitwise -w 16
+//! ```
+//!
+//! Values are read from the arguments, or as whitespace-separated words
+//! from stdin when there are none. Each is parsed at the chosen width, so a
+//! value that does not fit is an error rather than being truncated.
+
+use bit_operations::checksum::Crc;
+use bit_operations::decimal;
+use bit_operations::ieee754::{f64_to_e4m3, IEEEDecoder, IEEEFormat};
+use bit_operations::parse::group_digits;
+use bit_operations::sized::{BitError, SizedBitArray};
+use bit_operations::{BitArray, Width};
+use std::io::{self, Read};
+use std::ops::Range;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: bitwise [OPTIONS] [VALUE]...
+
+Prints binary, hex, octal, decimal, bit counts and bytes of each VALUE.
+Values take a 0x, 0b or 0o prefix or are decimal; a leading - gives the
+two's complement encoding. Without values, they are read from stdin.
+
+Options:
+  -w, --width BITS      Bit size: 8, 16, 32 or 64 (default 64)
+  -f, --float           Also decode as an IEEE 754 float of the bit size,
+                        and accept decimals such as 1.5 or nan as input
+      --field START..END
+                        Print the bits START up to END (exclusive);
+                        may be repeated
+      --crc NAME        Print a CRC of the value's bytes: crc8,
+                        crc16-ccitt, crc16-modbus or crc32
+      --little-endian   Feed the CRC the bytes least significant first
+  -h, --help            Print this help";
+
+/// Command line options.
+struct Options {
+    width: Width,
+    float: bool,
+    fields: Vec<Range<u32>>,
+    crc: Option<Crc>,
+    little_endian: bool,
+    values: Vec<String>,
+}
+
+/// Returns the value following option `name`.
+fn option_value(name: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("{} needs a value", name))
+}
+
+/// Parses a field such as `12..20`.
+fn parse_field(text: &str) -> Result<Range<u32>, String> {
+    let (start, end) = text
+        .split_once("..")
+        .ok_or_else(|| format!("field `{}` is not START..END", text))?;
+    let bound = |s: &str| {
+        s.trim()
+            .parse::<u32>()
+            .map_err(|_| format!("field `{}` is not START..END", text))
+    };
+    Ok(bound(start)?..bound(end)?)
+}
+
+/// Finds a CRC preset by name, ignoring case and punctuation, so `crc32`
+/// and `CRC-16/MODBUS` both match.
+fn find_crc(name: &str) -> Option<Crc> {
+    let key = |s: &str| {
+        s.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    Crc::PRESETS
+        .into_iter()
+        .find(|crc| key(crc.name) == key(name))
+}
+
+/// Parses the command line, without the program name.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        width: Width::W64,
+        float: false,
+        fields: Vec::new(),
+        crc: None,
+        little_endian: false,
+        values: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-w" | "--width" => {
+                let bits = option_value(&arg, &mut args)?;
+                options.width = bits
+                    .parse()
+                    .ok()
+                    .and_then(Width::from_bits)
+                    .filter(|w| w.bits() <= 64)
+                    .ok_or_else(|| format!("unsupported bit size `{}`", bits))?;
+            }
+            "-f" | "--float" => options.float = true,
+            "--field" => options
+                .fields
+                .push(parse_field(&option_value(&arg, &mut args)?)?),
+            "--crc" => {
+                let name = option_value(&arg, &mut args)?;
+                options.crc =
+                    Some(find_crc(&name).ok_or_else(|| format!("unknown CRC `{}`", name))?);
+            }
+            "--little-endian" => options.little_endian = true,
+            "--" => options.values.extend(args.by_ref()),
+            // Negative values are not options
+            s if s.starts_with('-') && !s[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                return Err(format!("unknown option `{}`", s));
+            }
+            _ => options.values.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+/// Returns the float format decoded at a bit size, as the viewer does.
+fn float_format(width: Width) -> IEEEFormat {
+    match width {
+        Width::W8 => IEEEFormat::E4M3,
+        Width::W16 => IEEEFormat::Half,
+        Width::W32 => IEEEFormat::Single,
+        _ => IEEEFormat::Double,
+    }
+}
+
+/// Returns the IEEE 754 name of a format.
+fn format_name(format: IEEEFormat) -> &'static str {
+    match format {
+        IEEEFormat::Half => "binary16",
+        IEEEFormat::Single => "binary32",
+        IEEEFormat::Double => "binary64",
+        IEEEFormat::BFloat16 => "bfloat16",
+        IEEEFormat::E4M3 => "FP8 E4M3",
+        IEEEFormat::E5M2 => "FP8 E5M2",
+        _ => "float",
+    }
+}
+
+/// Parses a value at the chosen width; with `--float`, text that is not an
+/// integer is rounded to the nearest float of the bit size.
+fn parse_value(input: &str, options: &Options) -> Result<SizedBitArray, BitError> {
+    let bits = options.width.bits();
+    let integer = if input.starts_with('-') {
+        SizedBitArray::from_signed_str(input, bits)
+    } else {
+        SizedBitArray::from_str_radix(input, 10, bits)
+    };
+    match integer {
+        Err(BitError::Parse(e)) if options.float => {
+            let float = match float_format(options.width).layout() {
+                Some(layout) => decimal::parse(layout, input),
+                // E4M3 has no IEEE layout; round through f64
+                None => input.parse().ok().map(|x| u64::from(f64_to_e4m3(x))),
+            }
+            .ok_or(BitError::Parse(e))?;
+            BitArray(float).with_width(bits)
+        }
+        result => result,
+    }
+}
+
+/// Formats the views of a value, one per line.
+fn report(sized: SizedBitArray, options: &Options) -> Result<String, BitError> {
+    let (value, width) = (sized.value(), sized.width());
+    let bits = width.bits();
+    let bytes = |bytes: Vec<u8>| {
+        bytes
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut lines = vec![
+        format!("hex      0x{:0w$X}", value.0, w = width.hex_digits()),
+        format!("dec      {}", value.0),
+        format!("signed   {}", sized.as_signed()),
+        format!("oct      0o{:o}", value.0),
+        format!(
+            "bin      0b{}",
+            group_digits(&format!("{:0w$b}", value.0, w = bits as usize), 4, '_')
+        ),
+        format!(
+            "popcount {} ones, {} zeros",
+            value.count_ones(width),
+            value.count_zeros(width)
+        ),
+        format!("bytes BE {}", bytes(width.be_bytes(value.0))),
+        format!("bytes LE {}", bytes(width.le_bytes(value.0))),
+    ];
+
+    if options.float {
+        let decoder = IEEEDecoder::new(value.0, u64::from(bits));
+        let shown = match decoder.format.layout() {
+            Some(layout) => decimal::shortest(layout, value.0),
+            None => decoder.value.to_string(),
+        };
+        lines.push(format!(
+            "float    {} ({}, {})",
+            shown,
+            decoder.special,
+            format_name(decoder.format)
+        ));
+        lines.push(format!(
+            "         sign {}, exponent {} (field {:#X}), mantissa {:#X}",
+            decoder.sign, decoder.exponent, decoder.exponent_bits, decoder.mantissa
+        ));
+    }
+
+    for field in &options.fields {
+        let part = sized.get_field(field.clone())?;
+        lines.push(format!(
+            "field    {}..{} = {:#X} ({})",
+            field.start, field.end, part, part
+        ));
+    }
+
+    if let Some(crc) = options.crc {
+        let data = if options.little_endian {
+            width.le_bytes(value.0)
+        } else {
+            width.be_bytes(value.0)
+        };
+        lines.push(format!(
+            "crc      {} = {:#0w$X}",
+            crc.name,
+            crc.checksum(&data),
+            w = crc.width.div_ceil(4) as usize + 2
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let mut options = match parse_args(args.into_iter()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("bitwise: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if options.values.is_empty() {
+        let mut input = String::new();
+        if let Err(e) = io::stdin().read_to_string(&mut input) {
+            eprintln!("bitwise: cannot read stdin: {}", e);
+            return ExitCode::FAILURE;
+        }
+        options.values = input.split_whitespace().map(String::from).collect();
+    }
+
+    // Every value is reported; any failure sets the exit status
+    let mut status = ExitCode::SUCCESS;
+    let mut first = true;
+    for input in &options.values {
+        match parse_value(input, &options).and_then(|sized| report(sized, &options)) {
+            Ok(text) => {
+                if !first {
+                    println!();
+                }
+                first = false;
+                if options.values.len() > 1 {
+                    println!("value    {}", input);
+                }
+                println!("{}", text);
+            }
+            Err(e) => {
+                eprintln!("bitwise: `{}`: {}", input, e);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
@@ -0,0 +1,26 @@
+//! Generates `bitwise.h` from the `extern "C"` API into `OUT_DIR`, and
+//! refreshes the checked-in `include/bitwise.h` when `BITWISE_UPDATE_HEADER`
+//! is set.
+
+use std::path::PathBuf;
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("invalid cbindgen.toml");
+
+    let bindings = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("unable to generate C bindings");
+    bindings.write_to_file(out_dir.join("bitwise.h"));
+    if std::env::var_os("BITWISE_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(format!("{}/include/bitwise.h", crate_dir));
+    }
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=BITWISE_UPDATE_HEADER");
+}
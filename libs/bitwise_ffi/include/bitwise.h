@@ -0,0 +1,244 @@
+#ifndef BITWISE_H
+#define BITWISE_H
+
+/* Generated by cbindgen from libs/bitwise_ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Classification of a decoded IEEE 754 value.
+ */
+typedef enum BitwiseFloatClass {
+  BITWISE_FLOAT_CLASS_ZERO,
+  BITWISE_FLOAT_CLASS_DENORMALIZED,
+  BITWISE_FLOAT_CLASS_NORMALIZED,
+  BITWISE_FLOAT_CLASS_POS_INF,
+  BITWISE_FLOAT_CLASS_NEG_INF,
+  BITWISE_FLOAT_CLASS_NAN,
+} BitwiseFloatClass;
+
+/**
+ * IEEE 754 interchange format of a decoded value.
+ */
+typedef enum BitwiseFloatFormat {
+  BITWISE_FLOAT_FORMAT_HALF,
+  BITWISE_FLOAT_FORMAT_SINGLE,
+  BITWISE_FLOAT_FORMAT_DOUBLE,
+  BITWISE_FLOAT_FORMAT_B_FLOAT16,
+  BITWISE_FLOAT_FORMAT_E4M3,
+  BITWISE_FLOAT_FORMAT_E5M2,
+  /**
+   * A format added to the library after this binding was written.
+   */
+  BITWISE_FLOAT_FORMAT_UNKNOWN,
+} BitwiseFloatFormat;
+
+/**
+ * Outcome of parsing a number.
+ */
+typedef enum BitwiseParseStatus {
+  BITWISE_PARSE_STATUS_OK,
+  /**
+   * The text contains no digits.
+   */
+  BITWISE_PARSE_STATUS_EMPTY,
+  /**
+   * The text contains a character that is not a digit of the radix, or
+   * is not valid UTF-8.
+   */
+  BITWISE_PARSE_STATUS_INVALID_DIGIT,
+  /**
+   * The value does not fit in 64 bits.
+   */
+  BITWISE_PARSE_STATUS_OVERFLOW,
+  /**
+   * A pointer is null or the radix is not between 2 and 36.
+   */
+  BITWISE_PARSE_STATUS_INVALID_ARGUMENT,
+} BitwiseParseStatus;
+
+/**
+ * Built-in CRC parameter sets.
+ */
+typedef enum BitwiseCrcPreset {
+  BITWISE_CRC_PRESET_CRC8,
+  BITWISE_CRC_PRESET_CRC16_CCITT,
+  BITWISE_CRC_PRESET_CRC16_MODBUS,
+  BITWISE_CRC_PRESET_CRC32,
+} BitwiseCrcPreset;
+
+/**
+ * Fields of a bit pattern decoded as an IEEE 754 float.
+ */
+typedef struct BitwiseFloatDecoding {
+  uint8_t sign;
+  int32_t exponent;
+  int32_t exponent_bits;
+  uint64_t mantissa;
+  double value;
+  enum BitwiseFloatClass kind;
+  enum BitwiseFloatFormat format;
+} BitwiseFloatDecoding;
+
+/**
+ * Parameters of a CRC of up to 64 bits, in the Rocksoft model.
+ */
+typedef struct BitwiseCrc {
+  /**
+   * CRC width in bits, 1 to 64.
+   */
+  uint32_t width;
+  /**
+   * Generator polynomial without the implicit top bit, not reflected.
+   */
+  uint64_t poly;
+  /**
+   * Register value before the first byte, not reflected.
+   */
+  uint64_t init;
+  /**
+   * Process each input byte least significant bit first.
+   */
+  bool refin;
+  /**
+   * Reflect the register before the final XOR.
+   */
+  bool refout;
+  /**
+   * Value XORed into the result.
+   */
+  uint64_t xorout;
+} BitwiseCrc;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns `value` with the bit at `pos` set to 1.
+ */
+uint64_t bitwise_set_bit(uint64_t value, uint8_t pos);
+
+/**
+ * Returns `value` with the bit at `pos` cleared to 0.
+ */
+uint64_t bitwise_clear_bit(uint64_t value, uint8_t pos);
+
+/**
+ * Returns `value` with the bit at `pos` flipped.
+ */
+uint64_t bitwise_toggle_bit(uint64_t value, uint8_t pos);
+
+/**
+ * Returns the bit of `value` at `pos`.
+ */
+bool bitwise_get_bit(uint64_t value, uint8_t pos);
+
+/**
+ * Decodes `bits` as an FP8 E4M3 (8), half (16), single (32) or double (any
+ * other size) float.
+ */
+struct BitwiseFloatDecoding bitwise_decode_float(uint64_t bits, uint64_t bit_size);
+
+/**
+ * Shifts the low `bit_size` bits of `value` left by `amount`; counts of
+ * `bit_size` or more give 0.
+ */
+uint64_t bitwise_shl(uint64_t value, uint32_t amount, uint32_t bit_size);
+
+/**
+ * Shifts the low `bit_size` bits of `value` right by `amount`, filling with
+ * zeros.
+ */
+uint64_t bitwise_shr(uint64_t value, uint32_t amount, uint32_t bit_size);
+
+/**
+ * Shifts the low `bit_size` bits of `value` right by `amount`, filling with
+ * copies of bit `bit_size - 1`.
+ */
+uint64_t bitwise_sar(uint64_t value, uint32_t amount, uint32_t bit_size);
+
+/**
+ * Rotates the low `bit_size` bits of `value` left by `amount`.
+ */
+uint64_t bitwise_rotl(uint64_t value, uint32_t amount, uint32_t bit_size);
+
+/**
+ * Rotates the low `bit_size` bits of `value` right by `amount`.
+ */
+uint64_t bitwise_rotr(uint64_t value, uint32_t amount, uint32_t bit_size);
+
+/**
+ * Parses an unsigned integer such as `0xDEAD_BEEF`, `0b1010` or `255u8`.
+ *
+ * `radix` applies when the text has no `0x`/`0o`/`0b` prefix. `out` is only
+ * written on success.
+ *
+ * # Safety
+ * `text` must be null or point to a NUL-terminated string, and `out` must
+ * be null or valid for writing a `uint64_t`.
+ */
+enum BitwiseParseStatus bitwise_parse_u64(const char *text, uint32_t radix, uint64_t *out);
+
+/**
+ * Parses a signed integer such as `-0x80` or `-128i8`.
+ *
+ * `radix` applies when the text has no `0x`/`0o`/`0b` prefix. `out` is only
+ * written on success.
+ *
+ * # Safety
+ * `text` must be null or point to a NUL-terminated string, and `out` must
+ * be null or valid for writing an `int64_t`.
+ */
+enum BitwiseParseStatus bitwise_parse_i64(const char *text, uint32_t radix, int64_t *out);
+
+/**
+ * Returns the parameters of `preset`, e.g. to adjust before calling
+ * [`bitwise_crc`].
+ */
+struct BitwiseCrc bitwise_crc_preset(enum BitwiseCrcPreset preset);
+
+/**
+ * Computes the CRC described by `params` over `len` bytes at `data`.
+ *
+ * Returns 0 when `params.width` is not between 1 and 64.
+ *
+ * # Safety
+ * `data` must be null or valid for reading `len` bytes.
+ */
+uint64_t bitwise_crc(struct BitwiseCrc params, const uint8_t *data, size_t len);
+
+/**
+ * Sums `len` bytes at `data` modulo 256.
+ *
+ * # Safety
+ * `data` must be null or valid for reading `len` bytes.
+ */
+uint8_t bitwise_sum8(const uint8_t *data, size_t len);
+
+/**
+ * Returns the byte that makes the sum of `len` bytes at `data` zero, as in
+ * Intel HEX records.
+ *
+ * # Safety
+ * `data` must be null or valid for reading `len` bytes.
+ */
+uint8_t bitwise_sum8_complement(const uint8_t *data, size_t len);
+
+/**
+ * XORs `len` bytes at `data` together.
+ *
+ * # Safety
+ * `data` must be null or valid for reading `len` bytes.
+ */
+uint8_t bitwise_xor8(const uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BITWISE_H */
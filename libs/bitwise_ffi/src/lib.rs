@@ -0,0 +1,481 @@
+//! C FFI for `bit_operations`
+//!
+//! A plain `extern "C"` surface over the library so C/C++ test harnesses link
+//! against the same bit manipulation code as the viewer. `build.rs` generates
+//! the header with cbindgen into `OUT_DIR`; the checked-in copy in
+//! `include/bitwise.h` is only rewritten when `BITWISE_UPDATE_HEADER` is set.
+//!
+//! Bit positions outside `0..64` are ignored: setters return the value
+//! unchanged and `bitwise_get_bit` returns `false`. Likewise shifts and
+//! rotates at a bit size other than 8, 16, 32 or 64 return the value
+//! unchanged.
+
+use core::ffi::{c_char, CStr};
+
+use bit_operations::checksum::{self, Crc};
+use bit_operations::ieee754::{IEEEDecoder, IEEEFormat};
+use bit_operations::parse::{self, NumberError};
+use bit_operations::{BitArray, Width};
+
+/// IEEE 754 interchange format of a decoded value.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseFloatFormat {
+    Half,
+    Single,
+    Double,
+    BFloat16,
+    E4M3,
+    E5M2,
+    /// A format added to the library after this binding was written.
+    Unknown,
+}
+
+/// Classification of a decoded IEEE 754 value.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseFloatClass {
+    Zero,
+    Denormalized,
+    Normalized,
+    PosInf,
+    NegInf,
+    Nan,
+}
+
+/// Fields of a bit pattern decoded as an IEEE 754 float.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BitwiseFloatDecoding {
+    pub sign: u8,
+    pub exponent: i32,
+    pub exponent_bits: i32,
+    pub mantissa: u64,
+    pub value: f64,
+    pub kind: BitwiseFloatClass,
+    pub format: BitwiseFloatFormat,
+}
+
+impl From<IEEEDecoder> for BitwiseFloatDecoding {
+    fn from(decoder: IEEEDecoder) -> Self {
+        let kind = match decoder.special.as_str() {
+            "Zero" => BitwiseFloatClass::Zero,
+            "Denormalized" => BitwiseFloatClass::Denormalized,
+            "+Inf" => BitwiseFloatClass::PosInf,
+            "-Inf" => BitwiseFloatClass::NegInf,
+            "NaN" => BitwiseFloatClass::Nan,
+            _ => BitwiseFloatClass::Normalized,
+        };
+        let format = match decoder.format {
+            IEEEFormat::Half => BitwiseFloatFormat::Half,
+            IEEEFormat::Single => BitwiseFloatFormat::Single,
+            IEEEFormat::Double => BitwiseFloatFormat::Double,
+            IEEEFormat::BFloat16 => BitwiseFloatFormat::BFloat16,
+            IEEEFormat::E4M3 => BitwiseFloatFormat::E4M3,
+            IEEEFormat::E5M2 => BitwiseFloatFormat::E5M2,
+            // `IEEEFormat` is non-exhaustive; never pass a new format off as
+            // one of the above
+            _ => BitwiseFloatFormat::Unknown,
+        };
+
+        Self {
+            sign: decoder.sign,
+            exponent: decoder.exponent,
+            exponent_bits: decoder.exponent_bits,
+            mantissa: decoder.mantissa,
+            value: decoder.value,
+            kind,
+            format,
+        }
+    }
+}
+
+/// Applies `op` to `value` when `pos` is a valid bit position.
+fn with_bit(value: u64, pos: u8, op: fn(&mut BitArray, u8)) -> u64 {
+    let mut ba = BitArray(value);
+    if pos < 64 {
+        op(&mut ba, pos);
+    }
+    ba.get_raw()
+}
+
+/// Returns `value` with the bit at `pos` set to 1.
+#[no_mangle]
+pub extern "C" fn bitwise_set_bit(value: u64, pos: u8) -> u64 {
+    with_bit(value, pos, BitArray::set_bit)
+}
+
+/// Returns `value` with the bit at `pos` cleared to 0.
+#[no_mangle]
+pub extern "C" fn bitwise_clear_bit(value: u64, pos: u8) -> u64 {
+    with_bit(value, pos, BitArray::clear_bit)
+}
+
+/// Returns `value` with the bit at `pos` flipped.
+#[no_mangle]
+pub extern "C" fn bitwise_toggle_bit(value: u64, pos: u8) -> u64 {
+    with_bit(value, pos, BitArray::toggle_bit)
+}
+
+/// Returns the bit of `value` at `pos`.
+#[no_mangle]
+pub extern "C" fn bitwise_get_bit(value: u64, pos: u8) -> bool {
+    pos < 64 && BitArray(value).get_bit(pos)
+}
+
+/// Decodes `bits` as an FP8 E4M3 (8), half (16), single (32) or double (any
+/// other size) float.
+#[no_mangle]
+pub extern "C" fn bitwise_decode_float(bits: u64, bit_size: u64) -> BitwiseFloatDecoding {
+    IEEEDecoder::new(bits, bit_size).into()
+}
+
+/// Applies the shift or rotate `op` to `value` when `bit_size` is 8, 16, 32
+/// or 64.
+fn with_width(value: u64, amount: u32, bit_size: u32, op: fn(&mut BitArray, u32, Width)) -> u64 {
+    let mut ba = BitArray(value);
+    if let Some(width) = Width::from_bits(bit_size).filter(|w| w.bits() <= 64) {
+        op(&mut ba, amount, width);
+    }
+    ba.get_raw()
+}
+
+/// Shifts the low `bit_size` bits of `value` left by `amount`; counts of
+/// `bit_size` or more give 0.
+#[no_mangle]
+pub extern "C" fn bitwise_shl(value: u64, amount: u32, bit_size: u32) -> u64 {
+    with_width(value, amount, bit_size, BitArray::shl)
+}
+
+/// Shifts the low `bit_size` bits of `value` right by `amount`, filling with
+/// zeros.
+#[no_mangle]
+pub extern "C" fn bitwise_shr(value: u64, amount: u32, bit_size: u32) -> u64 {
+    with_width(value, amount, bit_size, BitArray::shr)
+}
+
+/// Shifts the low `bit_size` bits of `value` right by `amount`, filling with
+/// copies of bit `bit_size - 1`.
+#[no_mangle]
+pub extern "C" fn bitwise_sar(value: u64, amount: u32, bit_size: u32) -> u64 {
+    with_width(value, amount, bit_size, BitArray::sar)
+}
+
+/// Rotates the low `bit_size` bits of `value` left by `amount`.
+#[no_mangle]
+pub extern "C" fn bitwise_rotl(value: u64, amount: u32, bit_size: u32) -> u64 {
+    with_width(value, amount, bit_size, BitArray::rotl)
+}
+
+/// Rotates the low `bit_size` bits of `value` right by `amount`.
+#[no_mangle]
+pub extern "C" fn bitwise_rotr(value: u64, amount: u32, bit_size: u32) -> u64 {
+    with_width(value, amount, bit_size, BitArray::rotr)
+}
+
+/// Outcome of parsing a number.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseParseStatus {
+    Ok,
+    /// The text contains no digits.
+    Empty,
+    /// The text contains a character that is not a digit of the radix, or
+    /// is not valid UTF-8.
+    InvalidDigit,
+    /// The value does not fit in 64 bits.
+    Overflow,
+    /// A pointer is null or the radix is not between 2 and 36.
+    InvalidArgument,
+}
+
+impl From<NumberError> for BitwiseParseStatus {
+    fn from(err: NumberError) -> Self {
+        match err {
+            NumberError::Empty => BitwiseParseStatus::Empty,
+            NumberError::InvalidDigit(_) => BitwiseParseStatus::InvalidDigit,
+            NumberError::Overflow => BitwiseParseStatus::Overflow,
+        }
+    }
+}
+
+/// Parses the C string `text` with `parse` and stores the value in `out`.
+///
+/// # Safety
+/// As for [`bitwise_parse_u64`].
+unsafe fn parse_into<T>(
+    text: *const c_char,
+    radix: u32,
+    out: *mut T,
+    parse: fn(&str, u32) -> Result<T, NumberError>,
+) -> BitwiseParseStatus {
+    if text.is_null() || out.is_null() || !(2..=36).contains(&radix) {
+        return BitwiseParseStatus::InvalidArgument;
+    }
+    let Ok(text) = CStr::from_ptr(text).to_str() else {
+        return BitwiseParseStatus::InvalidDigit;
+    };
+    match parse(text, radix) {
+        Ok(value) => {
+            out.write(value);
+            BitwiseParseStatus::Ok
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// Parses an unsigned integer such as `0xDEAD_BEEF`, `0b1010` or `255u8`.
+///
+/// `radix` applies when the text has no `0x`/`0o`/`0b` prefix. `out` is only
+/// written on success.
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string, and `out` must
+/// be null or valid for writing a `uint64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_parse_u64(
+    text: *const c_char,
+    radix: u32,
+    out: *mut u64,
+) -> BitwiseParseStatus {
+    parse_into(text, radix, out, parse::parse_u64)
+}
+
+/// Parses a signed integer such as `-0x80` or `-128i8`.
+///
+/// `radix` applies when the text has no `0x`/`0o`/`0b` prefix. `out` is only
+/// written on success.
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string, and `out` must
+/// be null or valid for writing an `int64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_parse_i64(
+    text: *const c_char,
+    radix: u32,
+    out: *mut i64,
+) -> BitwiseParseStatus {
+    parse_into(text, radix, out, parse::parse_i64)
+}
+
+/// Parameters of a CRC of up to 64 bits, in the Rocksoft model.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitwiseCrc {
+    /// CRC width in bits, 1 to 64.
+    pub width: u32,
+    /// Generator polynomial without the implicit top bit, not reflected.
+    pub poly: u64,
+    /// Register value before the first byte, not reflected.
+    pub init: u64,
+    /// Process each input byte least significant bit first.
+    pub refin: bool,
+    /// Reflect the register before the final XOR.
+    pub refout: bool,
+    /// Value XORed into the result.
+    pub xorout: u64,
+}
+
+impl From<Crc> for BitwiseCrc {
+    fn from(crc: Crc) -> Self {
+        Self {
+            width: crc.width,
+            poly: crc.poly,
+            init: crc.init,
+            refin: crc.refin,
+            refout: crc.refout,
+            xorout: crc.xorout,
+        }
+    }
+}
+
+/// Built-in CRC parameter sets.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseCrcPreset {
+    Crc8,
+    Crc16Ccitt,
+    Crc16Modbus,
+    Crc32,
+}
+
+/// Returns the parameters of `preset`, e.g. to adjust before calling
+/// [`bitwise_crc`].
+#[no_mangle]
+pub extern "C" fn bitwise_crc_preset(preset: BitwiseCrcPreset) -> BitwiseCrc {
+    match preset {
+        BitwiseCrcPreset::Crc8 => Crc::CRC8,
+        BitwiseCrcPreset::Crc16Ccitt => Crc::CRC16_CCITT,
+        BitwiseCrcPreset::Crc16Modbus => Crc::CRC16_MODBUS,
+        BitwiseCrcPreset::Crc32 => Crc::CRC32,
+    }
+    .into()
+}
+
+/// Views `len` bytes at `data` as a slice; a null `data` reads as empty.
+///
+/// # Safety
+/// A non-null `data` must be valid for reading `len` bytes.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        core::slice::from_raw_parts(data, len)
+    }
+}
+
+/// Computes the CRC described by `params` over `len` bytes at `data`.
+///
+/// Returns 0 when `params.width` is not between 1 and 64.
+///
+/// # Safety
+/// `data` must be null or valid for reading `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_crc(params: BitwiseCrc, data: *const u8, len: usize) -> u64 {
+    if !(1..=64).contains(&params.width) {
+        return 0;
+    }
+    let crc = Crc {
+        name: "",
+        width: params.width,
+        poly: params.poly,
+        init: params.init,
+        refin: params.refin,
+        refout: params.refout,
+        xorout: params.xorout,
+    };
+    crc.checksum(bytes(data, len))
+}
+
+/// Sums `len` bytes at `data` modulo 256.
+///
+/// # Safety
+/// `data` must be null or valid for reading `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_sum8(data: *const u8, len: usize) -> u8 {
+    checksum::sum8(bytes(data, len))
+}
+
+/// Returns the byte that makes the sum of `len` bytes at `data` zero, as in
+/// Intel HEX records.
+///
+/// # Safety
+/// `data` must be null or valid for reading `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_sum8_complement(data: *const u8, len: usize) -> u8 {
+    checksum::sum8_complement(bytes(data, len))
+}
+
+/// XORs `len` bytes at `data` together.
+///
+/// # Safety
+/// `data` must be null or valid for reading `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn bitwise_xor8(data: *const u8, len: usize) -> u8 {
+    checksum::xor8(bytes(data, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifts_and_rotates_respect_bit_size() {
+        assert_eq!(bitwise_shl(0x81, 1, 8), 0x02);
+        assert_eq!(bitwise_shl(0x81, 8, 8), 0);
+        assert_eq!(bitwise_shr(0xFF00, 4, 16), 0x0FF0);
+        assert_eq!(bitwise_sar(0xF0, 2, 8), 0xFC);
+        assert_eq!(bitwise_sar(0x8000_0000, 31, 32), 0xFFFF_FFFF);
+        assert_eq!(bitwise_rotl(0x81, 1, 8), 0x03);
+        assert_eq!(bitwise_rotr(0x01, 1, 16), 0x8000);
+        assert_eq!(bitwise_rotl(1 << 63, 1, 64), 1);
+        // Unsupported sizes leave the value alone
+        assert_eq!(bitwise_shl(0x81, 1, 12), 0x81);
+        assert_eq!(bitwise_rotl(0x81, 1, 128), 0x81);
+    }
+
+    #[test]
+    fn parse_reports_status() {
+        let mut out = 0u64;
+        unsafe {
+            assert_eq!(
+                bitwise_parse_u64(c"0xDEAD_BEEF".as_ptr(), 10, &mut out),
+                BitwiseParseStatus::Ok
+            );
+            assert_eq!(out, 0xDEAD_BEEF);
+            assert_eq!(
+                bitwise_parse_u64(c"12z".as_ptr(), 10, &mut out),
+                BitwiseParseStatus::InvalidDigit
+            );
+            assert_eq!(
+                bitwise_parse_u64(c"".as_ptr(), 10, &mut out),
+                BitwiseParseStatus::Empty
+            );
+            assert_eq!(
+                bitwise_parse_u64(c"0x1_0000_0000_0000_0000".as_ptr(), 10, &mut out),
+                BitwiseParseStatus::Overflow
+            );
+            assert_eq!(
+                bitwise_parse_u64(c"1".as_ptr(), 37, &mut out),
+                BitwiseParseStatus::InvalidArgument
+            );
+            assert_eq!(
+                bitwise_parse_u64(core::ptr::null(), 10, &mut out),
+                BitwiseParseStatus::InvalidArgument
+            );
+            assert_eq!(out, 0xDEAD_BEEF);
+
+            let mut signed = 0i64;
+            assert_eq!(
+                bitwise_parse_i64(c"-128i8".as_ptr(), 10, &mut signed),
+                BitwiseParseStatus::Ok
+            );
+            assert_eq!(signed, -128);
+        }
+    }
+
+    #[test]
+    fn checksums_match_library() {
+        let data = b"123456789";
+        let crc =
+            |preset| unsafe { bitwise_crc(bitwise_crc_preset(preset), data.as_ptr(), data.len()) };
+        assert_eq!(crc(BitwiseCrcPreset::Crc32), 0xCBF4_3926);
+        assert_eq!(
+            crc(BitwiseCrcPreset::Crc16Modbus),
+            Crc::CRC16_MODBUS.checksum(data)
+        );
+        assert_eq!(crc(BitwiseCrcPreset::Crc8), Crc::CRC8.checksum(data));
+
+        let invalid = BitwiseCrc {
+            width: 0,
+            ..bitwise_crc_preset(BitwiseCrcPreset::Crc8)
+        };
+        unsafe {
+            assert_eq!(bitwise_crc(invalid, data.as_ptr(), data.len()), 0);
+            assert_eq!(
+                bitwise_sum8(data.as_ptr(), data.len()),
+                checksum::sum8(data)
+            );
+            assert_eq!(
+                bitwise_xor8(data.as_ptr(), data.len()),
+                checksum::xor8(data)
+            );
+            assert_eq!(
+                bitwise_sum8_complement(data.as_ptr(), data.len()),
+                checksum::sum8_complement(data)
+            );
+            assert_eq!(bitwise_sum8(core::ptr::null(), 4), 0);
+        }
+    }
+
+    #[test]
+    fn decode_float_maps_format() {
+        let decoded = bitwise_decode_float(0x3F80_0000, 32);
+        assert_eq!(decoded.format, BitwiseFloatFormat::Single);
+        assert_eq!(decoded.kind, BitwiseFloatClass::Normalized);
+        assert_eq!(decoded.value, 1.0);
+        let decoded = bitwise_decode_float(0x7FF0_0000_0000_0000, 64);
+        assert_eq!(decoded.format, BitwiseFloatFormat::Double);
+        assert_eq!(decoded.kind, BitwiseFloatClass::PosInf);
+    }
+}
@@ -0,0 +1,267 @@
+//! Python bindings for `bit_operations`
+//!
+//! Exposes the same bit manipulation, float decoding and checksum logic the
+//! viewer uses as a `bitwise` Python module, so scripts and notebooks agree
+//! with the GUI.
+
+use bit_operations::checksum::{self, Crc};
+use bit_operations::ieee754::{IEEEDecoder, IEEEFormat};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Validates a bit position coming from Python.
+fn check_pos(pos: u8) -> PyResult<u8> {
+    if pos < 64 {
+        Ok(pos)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "bit position {} out of range 0..64",
+            pos
+        )))
+    }
+}
+
+/// Python wrapper around `bit_operations::BitArray`.
+#[pyclass(name = "BitArray", from_py_object)]
+#[derive(Clone)]
+struct PyBitArray(bit_operations::BitArray);
+
+#[pymethods]
+impl PyBitArray {
+    #[new]
+    #[pyo3(signature = (value = 0))]
+    fn new(value: u64) -> Self {
+        Self(bit_operations::BitArray(value))
+    }
+
+    fn set_bit(&mut self, pos: u8) -> PyResult<()> {
+        self.0.set_bit(check_pos(pos)?);
+        Ok(())
+    }
+
+    fn clear_bit(&mut self, pos: u8) -> PyResult<()> {
+        self.0.clear_bit(check_pos(pos)?);
+        Ok(())
+    }
+
+    fn toggle_bit(&mut self, pos: u8) -> PyResult<()> {
+        self.0.toggle_bit(check_pos(pos)?);
+        Ok(())
+    }
+
+    fn get_bit(&self, pos: u8) -> PyResult<bool> {
+        Ok(self.0.get_bit(check_pos(pos)?))
+    }
+
+    fn get_all_bits(&self) -> Vec<bool> {
+        self.0.get_all_bits()
+    }
+
+    fn get_raw(&self) -> u64 {
+        self.0.get_raw()
+    }
+
+    fn __int__(&self) -> u64 {
+        self.0.get_raw()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BitArray(0x{:016X})", self.0.get_raw())
+    }
+}
+
+/// Result of decoding a bit pattern as an IEEE 754 float.
+#[pyclass(name = "FloatDecoding", frozen, get_all)]
+struct PyFloatDecoding {
+    sign: u8,
+    exponent: i32,
+    exponent_bits: i32,
+    mantissa: u64,
+    value: f64,
+    special: String,
+    format: String,
+}
+
+#[pymethods]
+impl PyFloatDecoding {
+    fn __repr__(&self) -> String {
+        format!(
+            "FloatDecoding(format={}, sign={}, exponent={}, mantissa=0x{:X}, value={:e}, special={})",
+            self.format, self.sign, self.exponent, self.mantissa, self.value, self.special
+        )
+    }
+}
+
+impl From<IEEEDecoder> for PyFloatDecoding {
+    fn from(decoder: IEEEDecoder) -> Self {
+        Self {
+            sign: decoder.sign,
+            exponent: decoder.exponent,
+            exponent_bits: decoder.exponent_bits,
+            mantissa: decoder.mantissa,
+            value: decoder.value,
+            special: decoder.special,
+            format: format!("{:?}", decoder.format),
+        }
+    }
+}
+
+/// Format names accepted by `decode_float`.
+const FORMATS: [(&str, IEEEFormat); 6] = [
+    ("half", IEEEFormat::Half),
+    ("single", IEEEFormat::Single),
+    ("double", IEEEFormat::Double),
+    ("bfloat16", IEEEFormat::BFloat16),
+    ("e4m3", IEEEFormat::E4M3),
+    ("e5m2", IEEEFormat::E5M2),
+];
+
+/// Decodes `bits` as a float. Without `format`, the bit size picks FP8 E4M3
+/// (8), half (16), single (32) or double (64) precision; `format` names one
+/// of `half`, `single`, `double`, `bfloat16`, `e4m3` or `e5m2` explicitly.
+#[pyfunction]
+#[pyo3(signature = (bits, bit_size = 64, format = None))]
+fn decode_float(bits: u64, bit_size: u64, format: Option<&str>) -> PyResult<PyFloatDecoding> {
+    let Some(name) = format else {
+        return Ok(IEEEDecoder::new(bits, bit_size).into());
+    };
+    let (_, format) = FORMATS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = FORMATS.iter().map(|(n, _)| *n).collect();
+            PyValueError::new_err(format!(
+                "unknown float format {:?}, expected one of {}",
+                name,
+                names.join(", ")
+            ))
+        })?;
+    Ok(IEEEDecoder::with_format(bits, *format).into())
+}
+
+/// Python wrapper around `bit_operations::checksum::Crc`.
+#[pyclass(name = "Crc", frozen)]
+struct PyCrc(Crc);
+
+#[pymethods]
+impl PyCrc {
+    /// Creates a custom CRC from its Rocksoft parameters.
+    #[new]
+    #[pyo3(signature = (width, poly, init = 0, refin = false, refout = false, xorout = 0))]
+    fn new(
+        width: u32,
+        poly: u64,
+        init: u64,
+        refin: bool,
+        refout: bool,
+        xorout: u64,
+    ) -> PyResult<Self> {
+        if !(1..=64).contains(&width) {
+            return Err(PyValueError::new_err(format!(
+                "CRC width {} out of range 1..=64",
+                width
+            )));
+        }
+        Ok(Self(Crc {
+            name: "custom",
+            width,
+            poly,
+            init,
+            refin,
+            refout,
+            xorout,
+        }))
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CRC8() -> Self {
+        Self(Crc::CRC8)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CRC16_CCITT() -> Self {
+        Self(Crc::CRC16_CCITT)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CRC16_MODBUS() -> Self {
+        Self(Crc::CRC16_MODBUS)
+    }
+
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn CRC32() -> Self {
+        Self(Crc::CRC32)
+    }
+
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.0.width
+    }
+
+    /// Computes the CRC of `data`.
+    fn checksum(&self, data: &[u8]) -> u64 {
+        self.0.checksum(data)
+    }
+
+    fn __repr__(&self) -> String {
+        let c = &self.0;
+        format!(
+            "Crc(name={}, width={}, poly=0x{:X}, init=0x{:X}, refin={}, refout={}, xorout=0x{:X})",
+            c.name,
+            c.width,
+            c.poly,
+            c.init,
+            if c.refin { "True" } else { "False" },
+            if c.refout { "True" } else { "False" },
+            c.xorout
+        )
+    }
+}
+
+/// Sums the bytes modulo 256.
+#[pyfunction]
+fn sum8(data: &[u8]) -> u8 {
+    checksum::sum8(data)
+}
+
+/// Returns the byte that makes the byte sum zero modulo 256.
+#[pyfunction]
+fn sum8_complement(data: &[u8]) -> u8 {
+    checksum::sum8_complement(data)
+}
+
+/// XORs the bytes together.
+#[pyfunction]
+fn xor8(data: &[u8]) -> u8 {
+    checksum::xor8(data)
+}
+
+/// The `bitwise` Python module.
+#[pymodule]
+fn bitwise(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBitArray>()?;
+    m.add_class::<PyFloatDecoding>()?;
+    m.add_function(wrap_pyfunction!(decode_float, m)?)?;
+    m.add_class::<PyCrc>()?;
+    m.add_function(wrap_pyfunction!(sum8, m)?)?;
+    m.add_function(wrap_pyfunction!(sum8_complement, m)?)?;
+    m.add_function(wrap_pyfunction!(xor8, m)?)?;
+    Ok(())
+}